@@ -0,0 +1,131 @@
+//! Compute-unit consumption of every SOLPRISM instruction across the
+//! variable-length field sizes that drive (de)serialization cost.
+//!
+//! Needs the SBF build of the program:
+//!
+//! ```sh
+//! anchor build   # or: cargo build-sbf --manifest-path programs/axiom/Cargo.toml
+//! cargo bench -p axiom-bench
+//! ```
+//!
+//! Results are appended to `benches/compute_units.md` with the delta
+//! against the previous run; increases are printed as regressions.
+
+use anchor_lang::prelude::Pubkey;
+use axiom_bench::*;
+use mollusk_svm::{program::keyed_account_for_system_program, Mollusk};
+use mollusk_svm_bencher::MolluskComputeUnitBencher;
+use std::path::PathBuf;
+
+/// Field sizes to sweep (shortest, typical, maximum).
+const NAME_LENS: [usize; 3] = [1, 16, 64];
+const ACTION_TYPE_LENS: [usize; 3] = [1, 8, 32];
+const URI_LENS: [usize; 3] = [1, 64, 256];
+
+/// Activity bucket for the harness clock, which starts at the Unix epoch.
+const DAY: i64 = 0;
+
+fn main() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    if std::env::var_os("SBF_OUT_DIR").is_none() {
+        std::env::set_var("SBF_OUT_DIR", manifest_dir.join("../../target/deploy"));
+    }
+    let out_dir = manifest_dir.join("benches");
+    let results_path = out_dir.join("compute_units.md");
+
+    let authority = Pubkey::new_unique();
+    let system = keyed_account_for_system_program();
+    let mut benches = Vec::new();
+
+    for len in NAME_LENS {
+        let name = "n".repeat(len);
+        let (profile, _) = agent_address(&authority);
+        benches.push((
+            format!("register_agent/name_{len}"),
+            register_agent_ix(&authority, &name),
+            vec![
+                (profile, uninitialized()),
+                (authority, wallet(PAYER_LAMPORTS)),
+                protocol_config(&authority),
+                system.clone(),
+            ],
+        ));
+    }
+
+    for len in ACTION_TYPE_LENS {
+        let action_type = "a".repeat(len);
+        let profile = agent_profile(&authority, "bench");
+        let (commitment, _) = commitment_address(&profile.0, 0);
+        let (bucket, _) = activity_address(&profile.0, DAY);
+        let (stats, _) = action_stats_address(&profile.0, &action_type);
+        benches.push((
+            format!("commit_reasoning/action_type_{len}"),
+            commit_reasoning_ix(&authority, 0, &action_type, DAY),
+            vec![
+                (commitment, uninitialized()),
+                profile,
+                (bucket, uninitialized()),
+                (stats, uninitialized()),
+                (authority, wallet(PAYER_LAMPORTS)),
+                protocol_config(&authority),
+                system.clone(),
+            ],
+        ));
+    }
+
+    for len in URI_LENS {
+        let uri = "u".repeat(len);
+        let profile = agent_profile(&authority, "bench");
+        let (bucket, _) = activity_address(&profile.0, DAY);
+        benches.push((
+            format!("start_reveal/uri_{len}"),
+            start_reveal_ix(&authority, 0, &uri, DAY),
+            vec![
+                commitment(&authority, 0, "trade"),
+                profile,
+                (bucket, uninitialized()),
+                (authority, wallet(PAYER_LAMPORTS)),
+                protocol_config(&authority),
+                system.clone(),
+            ],
+        ));
+    }
+
+    let before = std::fs::read_to_string(&results_path).unwrap_or_default();
+
+    let mollusk = Mollusk::new(&axiom::ID, "axiom");
+    let mut bencher = MolluskComputeUnitBencher::new(mollusk)
+        .must_pass(true)
+        .out_dir(out_dir.to_str().expect("utf-8 path"));
+    for (name, ix, accounts) in &benches {
+        bencher = bencher.bench((name.as_str(), ix, accounts.as_slice()));
+    }
+    bencher.execute();
+
+    let after = std::fs::read_to_string(&results_path).unwrap_or_default();
+    report(&before, &after);
+}
+
+/// Print the newest results table and call out every CU increase.
+fn report(before: &str, after: &str) {
+    if before == after {
+        println!("compute units unchanged since the last run");
+        return;
+    }
+    let latest: Vec<&str> = after
+        .lines()
+        .skip(6)
+        .take_while(|line| line.starts_with('|'))
+        .collect();
+    let mut regressions = 0;
+    for row in &latest {
+        println!("{row}");
+        let delta = row.split('|').nth(3).map(str::trim).unwrap_or_default();
+        if delta.starts_with('+') {
+            regressions += 1;
+        }
+    }
+    if regressions > 0 {
+        println!("{regressions} instruction(s) regressed; see benches/compute_units.md");
+    }
+}
@@ -0,0 +1,350 @@
+//! Account and instruction builders for exercising the SOLPRISM program
+//! outside a validator (compute-unit benches, SVM harnesses).
+//!
+//! Accounts are produced fully formed — discriminator, Borsh body and
+//! rent-exempt lamports — so an instruction can be run against them
+//! without first replaying the instructions that would create them.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData};
+use axiom::{
+    AgentProfile, CommitArgs, CommitmentCategory, ConfidenceSource, CommitmentStatus, HashAlgorithm,
+    ProtocolConfig, ReasoningCommitment, AGENT_PROFILE_RESERVED, COMMITMENT_RESERVED,
+};
+use solana_account::Account;
+
+/// Lamports given to fee payers.
+pub const PAYER_LAMPORTS: u64 = 10_000_000_000;
+
+/// A pubkey + account pair as consumed by SVM harnesses.
+pub type KeyedAccount = (Pubkey, Account);
+
+/// Serialize an Anchor account into a rent-exempt, program-owned account
+/// of `space` bytes (including the discriminator).
+pub fn program_account<T: AccountSerialize>(value: &T, space: usize) -> Account {
+    owned_account(value, space, axiom::ID)
+}
+
+/// [`program_account`] for an account of another program, such as an
+/// integration calling into SOLPRISM.
+pub fn owned_account<T: AccountSerialize>(value: &T, space: usize, owner: Pubkey) -> Account {
+    let mut data = Vec::with_capacity(space);
+    value
+        .try_serialize(&mut data)
+        .expect("account serializes");
+    assert!(data.len() <= space, "account exceeds its allocated space");
+    data.resize(space, 0);
+    Account {
+        lamports: Rent::default().minimum_balance(space),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// A system-owned wallet holding `lamports`.
+pub fn wallet(lamports: u64) -> Account {
+    Account::new(lamports, 0, &system_program::ID)
+}
+
+/// The not-yet-created account an `init` constraint expects.
+pub fn uninitialized() -> Account {
+    Account::default()
+}
+
+/// The protocol config PDA.
+pub fn config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], &axiom::ID)
+}
+
+/// Agent profile PDA for `authority`.
+pub fn agent_address(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"agent", authority.as_ref()], &axiom::ID)
+}
+
+/// Commitment PDA for `agent_profile` and `nonce`.
+pub fn commitment_address(agent_profile: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"commitment", agent_profile.as_ref(), &nonce.to_le_bytes()],
+        &axiom::ID,
+    )
+}
+
+/// Activity bucket PDA for `agent_profile` on `day` (see [`axiom::activity_day`]).
+pub fn activity_address(agent_profile: &Pubkey, day: i64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"activity", agent_profile.as_ref(), &day.to_le_bytes()],
+        &axiom::ID,
+    )
+}
+
+/// Action type stats PDA for `agent_profile` and `action_type`.
+pub fn action_stats_address(agent_profile: &Pubkey, action_type: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"action_stats", agent_profile.as_ref(), action_type.as_bytes()],
+        &axiom::ID,
+    )
+}
+
+/// The protocol config as `initialize_config` leaves it, administered
+/// by `admin`.
+pub fn protocol_config(admin: &Pubkey) -> KeyedAccount {
+    let (address, bump) = config_address();
+    let config = ProtocolConfig {
+        admin: *admin,
+        score_floor: 0,
+        score_ceiling: 10000,
+        probation_threshold: axiom::DEFAULT_PROBATION_THRESHOLD,
+        probation_required_reveals: axiom::DEFAULT_PROBATION_REQUIRED_REVEALS,
+        probation_recovery_bps: axiom::DEFAULT_PROBATION_RECOVERY_BPS,
+        probation_reference_lamports: 0,
+        reveal_grace_period: axiom::DEFAULT_REVEAL_GRACE_PERIOD,
+        storage_verifiers: Vec::new(),
+        storage_quorum: 0,
+        min_retention_epochs: None,
+        unstake_delay: axiom::DEFAULT_UNSTAKE_DELAY,
+        slash_bps: 0,
+        slash_challenger_bps: 0,
+        treasury: *admin,
+        challenge_bond: axiom::DEFAULT_CHALLENGE_BOND,
+        challenge_period: axiom::DEFAULT_CHALLENGE_PERIOD,
+        challenge_penalty_bps: axiom::DEFAULT_CHALLENGE_PENALTY_BPS,
+        council: Vec::new(),
+        council_threshold: 0,
+        expiry_penalty_bps: axiom::DEFAULT_EXPIRY_PENALTY_BPS,
+        score_reveal_weight_bps: axiom::DEFAULT_SCORE_REVEAL_WEIGHT_BPS,
+        score_dispute_weight_bps: axiom::DEFAULT_SCORE_DISPUTE_WEIGHT_BPS,
+        challenge_window: axiom::DEFAULT_CHALLENGE_WINDOW,
+        reviewer_bond: axiom::DEFAULT_REVIEWER_BOND,
+        review_period: axiom::DEFAULT_REVIEW_PERIOD,
+        max_uri_len: axiom::MAX_URI_LEN,
+        guardian: *admin,
+        paused: false,
+        features: axiom::ALL_FEATURES,
+        commit_fee: 0,
+        reveal_rebate_bps: 0,
+        max_deadline_extension: axiom::DEFAULT_MAX_DEADLINE_EXTENSION,
+        extension_fee: 0,
+        outcome_dispute_window: axiom::DEFAULT_OUTCOME_DISPUTE_WINDOW,
+        outcome_score_bps: axiom::DEFAULT_OUTCOME_SCORE_BPS,
+        integrator_fee_share_bps: 0,
+        outcome_verifiers: Vec::new(),
+        bump,
+    };
+    (address, program_account(&config, 8 + ProtocolConfig::INIT_SPACE))
+}
+
+/// A freshly registered agent profile owned by `authority`.
+pub fn agent_profile(authority: &Pubkey, name: &str) -> KeyedAccount {
+    let (address, bump) = agent_address(authority);
+    let profile = AgentProfile {
+        authority: *authority,
+        name: name.to_string(),
+        total_commitments: 0,
+        total_verified: 0,
+        accountability_score: 10000,
+        created_at: 0,
+        probation: None,
+        verified_operator: false,
+        operator_attestation: None,
+        confidence_histogram: [0; axiom::CONFIDENCE_BUCKETS],
+        signing_key: None,
+        signing_key_count: 0,
+        total_reveal_latency: 0,
+        timed_reveals: 0,
+        max_reveal_latency: 0,
+        integrator: None,
+        operator: None,
+        agent_id: None,
+        framework: None,
+        staked_lamports: 0,
+        simulated_commitments: 0,
+        challenges_received: 0,
+        challenges_upheld: 0,
+        expired_commitments: 0,
+        score_adjustment: 0,
+        cancelled_commitments: 0,
+        event_sequence: 0,
+        deactivated_at: None,
+        frozen: false,
+        idempotent_commits: false,
+        imported_score: 0,
+        has_metrics: false,
+        has_summary: false,
+        follower_count: 0,
+        open_challenges: 0,
+        bump,
+        reserved: [0; AGENT_PROFILE_RESERVED],
+    };
+    (address, program_account(&profile, 8 + AgentProfile::INIT_SPACE))
+}
+
+/// An unrevealed commitment by `authority`'s agent at `nonce`.
+pub fn commitment(authority: &Pubkey, nonce: u64, action_type: &str) -> KeyedAccount {
+    let (agent, _) = agent_address(authority);
+    let (address, bump) = commitment_address(&agent, nonce);
+    let commitment = ReasoningCommitment {
+        agent,
+        authority: *authority,
+        commitment_hash: [7; 32],
+        action_type: action_type.to_string(),
+        confidence: 80,
+        timestamp: 0,
+        status: CommitmentStatus::Committed,
+        reasoning_uri: String::new(),
+        nonce,
+        category: CommitmentCategory::Financial,
+        trace_len: 0,
+        chunk_count: 0,
+        chunk_manifest: None,
+        revealed_at: 0,
+        upvotes: 0,
+        downvotes: 0,
+        trace_signer: None,
+        availability_attestations: 0,
+        template: None,
+        available_at: None,
+        counterparty: None,
+        acknowledged_at: None,
+        details_hash: None,
+        details_published_at: None,
+        risk: None,
+        slot: 0,
+        action_signature: None,
+        action_slot: None,
+        salt: None,
+        parent: None,
+        confidence_source: ConfidenceSource::Unspecified,
+        verified_onchain: false,
+        locked: false,
+        uri_expiry_epoch: None,
+        verifier_confirmations: 0,
+        verifier_refutations: 0,
+        redacted_chunks: 0,
+        simulation: false,
+        slashed: false,
+        reveal_deadline: None,
+        expired: false,
+        cancelled: false,
+        open_challenges: 0,
+        sequence: 0,
+        escrowed: false,
+        counterfactuals: 0,
+        embargoed: false,
+        hash_algorithm: HashAlgorithm::Sha256,
+        in_action_stats: false,
+        fee_paid: 0,
+        deadline_extended: false,
+        action_in_trace: false,
+        bump,
+        reserved: [0; COMMITMENT_RESERVED],
+    };
+    (
+        address,
+        program_account(&commitment, 8 + ReasoningCommitment::INIT_SPACE),
+    )
+}
+
+/// `register_agent` for `authority`.
+pub fn register_agent_ix(authority: &Pubkey, name: &str) -> Instruction {
+    let (agent_profile, _) = agent_address(authority);
+    Instruction {
+        program_id: axiom::ID,
+        accounts: axiom::accounts::RegisterAgent {
+            agent_profile,
+            authority: *authority,
+            config: config_address().0,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: axiom::instruction::RegisterAgent {
+            name: name.to_string(),
+        }
+        .data(),
+    }
+}
+
+/// `commit_reasoning` by `authority`'s agent at `nonce`, on activity `day`.
+pub fn commit_reasoning_ix(
+    authority: &Pubkey,
+    nonce: u64,
+    action_type: &str,
+    day: i64,
+) -> Instruction {
+    let (agent_profile, _) = agent_address(authority);
+    let (commitment, _) = commitment_address(&agent_profile, nonce);
+    let (activity_bucket, _) = activity_address(&agent_profile, day);
+    let (action_stats, _) = action_stats_address(&agent_profile, action_type);
+    Instruction {
+        program_id: axiom::ID,
+        accounts: axiom::accounts::CommitReasoning {
+            commitment,
+            agent_profile,
+            activity_bucket,
+            action_stats,
+            authority: *authority,
+            config: config_address().0,
+            system_program: system_program::ID,
+            integrator: None,
+            compliance_policy: None,
+            delegate: None,
+            parent_commitment: None,
+            fee_treasury: None,
+        }
+        .to_account_metas(None),
+        data: axiom::instruction::CommitReasoning {
+            args: CommitArgs {
+                commitment_hash: [7; 32],
+                action_type: action_type.to_string(),
+                confidence: 80,
+                nonce,
+                category: CommitmentCategory::Financial,
+                chunk_manifest: None,
+                counterparty: None,
+                risk: None,
+                confidence_source: ConfidenceSource::ModelLogprob,
+                simulation: false,
+                reveal_deadline: None,
+                hash_algorithm: HashAlgorithm::Sha256,
+            },
+        }
+        .data(),
+    }
+}
+
+/// `start_reveal` for `authority`'s commitment at `nonce`, on activity `day`.
+pub fn start_reveal_ix(
+    authority: &Pubkey,
+    nonce: u64,
+    reasoning_uri: &str,
+    day: i64,
+) -> Instruction {
+    let (agent_profile, _) = agent_address(authority);
+    let (commitment, _) = commitment_address(&agent_profile, nonce);
+    let (activity_bucket, _) = activity_address(&agent_profile, day);
+    Instruction {
+        program_id: axiom::ID,
+        accounts: axiom::accounts::StartReveal {
+            commitment,
+            agent_profile,
+            activity_bucket,
+            authority: *authority,
+            config: config_address().0,
+            system_program: system_program::ID,
+            delegate: None,
+            metrics: None,
+            summary: None,
+            action_stats: None,
+        }
+        .to_account_metas(None),
+        data: axiom::instruction::StartReveal {
+            reasoning_uri: reasoning_uri.to_string(),
+            trace_len: 4096,
+            chunk_count: 1,
+            salt: None,
+        }
+        .data(),
+    }
+}
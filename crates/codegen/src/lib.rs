@@ -0,0 +1,21 @@
+//! Bindings for non-Rust consumers, generated from the program's IDL.
+//!
+//! [`schema`] flattens the Anchor IDL into a versioned JSON description
+//! of the program's accounts, events and instructions that doesn't move
+//! when the IDL format does. [`python`] turns that description into a
+//! dependency-free Python module that decodes accounts and events and
+//! builds instructions, for agent frameworks written in Python.
+
+pub mod python;
+pub mod schema;
+
+/// Errors surfaced by the generator.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("unsupported IDL: {0}")]
+    Unsupported(String),
+}
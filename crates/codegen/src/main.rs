@@ -0,0 +1,39 @@
+//! `axiom-codegen`: generate bindings for non-Rust consumers.
+//!
+//! Reads the IDL `anchor build` writes (`target/idl/axiom.json` by
+//! default) and writes `<program>.schema.json` and `<program>_client.py`
+//! into the output directory.
+
+use std::path::PathBuf;
+
+use anchor_lang_idl_spec::Idl;
+use axiom_codegen::schema::Schema;
+use axiom_codegen::{python, Error};
+use clap::Parser;
+
+#[derive(Parser)]
+#[command(name = "axiom-codegen", about = "Generate bindings from the SOLPRISM IDL")]
+struct Cli {
+    /// IDL produced by `anchor build`
+    #[arg(long, default_value = "target/idl/axiom.json")]
+    idl: PathBuf,
+    /// Directory to write the bindings into
+    #[arg(long, default_value = "bindings")]
+    out: PathBuf,
+}
+
+fn main() -> Result<(), Error> {
+    let cli = Cli::parse();
+    let idl: Idl = serde_json::from_str(&std::fs::read_to_string(&cli.idl)?)?;
+    let schema = Schema::from_idl(&idl)?;
+    let name = &schema.program.name;
+
+    std::fs::create_dir_all(&cli.out)?;
+    let schema_path = cli.out.join(format!("{name}.schema.json"));
+    std::fs::write(&schema_path, serde_json::to_string_pretty(&schema)? + "\n")?;
+    let python_path = cli.out.join(format!("{name}_client.py"));
+    std::fs::write(&python_path, python::generate(&schema)?)?;
+
+    println!("wrote {} and {}", schema_path.display(), python_path.display());
+    Ok(())
+}
@@ -0,0 +1,238 @@
+import base64
+import struct
+from dataclasses import dataclass
+from typing import Any, ClassVar, Dict, List, Optional, Tuple
+
+# ─── Runtime ──────────────────────────────────────────────────────────────
+
+_B58 = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+
+def b58encode(data: bytes) -> str:
+    n = int.from_bytes(data, "big")
+    out = ""
+    while n:
+        n, rem = divmod(n, 58)
+        out = _B58[rem] + out
+    pad = len(data) - len(data.lstrip(b"\0"))
+    return "1" * pad + out
+
+
+def b58decode(text: str) -> bytes:
+    n = 0
+    for char in text:
+        n = n * 58 + _B58.index(char)
+    body = n.to_bytes((n.bit_length() + 7) // 8, "big")
+    pad = len(text) - len(text.lstrip("1"))
+    return b"\0" * pad + body
+
+
+_STRUCT_FORMATS = {
+    "u8": "<B", "i8": "<b", "u16": "<H", "i16": "<h", "u32": "<I", "i32": "<i",
+    "u64": "<Q", "i64": "<q", "f32": "<f", "f64": "<d",
+}
+_WIDE_INTS = {"u128": (16, False), "i128": (16, True), "u256": (32, False), "i256": (32, True)}
+
+
+class DecodeError(ValueError):
+    """Raised when bytes don't match the expected layout."""
+
+
+class Reader:
+    """Borsh decoder over a byte string."""
+
+    def __init__(self, data: bytes):
+        self.data = data
+        self.offset = 0
+
+    def take(self, n: int) -> bytes:
+        if self.offset + n > len(self.data):
+            raise DecodeError("unexpected end of data")
+        chunk = self.data[self.offset:self.offset + n]
+        self.offset += n
+        return chunk
+
+    def read(self, ty: Any) -> Any:
+        if isinstance(ty, str):
+            if ty in _STRUCT_FORMATS:
+                fmt = _STRUCT_FORMATS[ty]
+                return struct.unpack(fmt, self.take(struct.calcsize(fmt)))[0]
+            if ty in _WIDE_INTS:
+                size, signed = _WIDE_INTS[ty]
+                return int.from_bytes(self.take(size), "little", signed=signed)
+            if ty == "bool":
+                return self.take(1) != b"\0"
+            if ty == "pubkey":
+                return b58encode(self.take(32))
+            if ty == "bytes":
+                return self.take(self.read("u32"))
+            if ty == "string":
+                return self.take(self.read("u32")).decode("utf-8")
+            raise DecodeError(f"unknown type {ty}")
+        kind = ty[0]
+        if kind == "option":
+            return self.read(ty[1]) if self.read("u8") else None
+        if kind == "vec":
+            n = self.read("u32")
+            return self.take(n) if ty[1] == "u8" else [self.read(ty[1]) for _ in range(n)]
+        if kind == "array":
+            return self.take(ty[2]) if ty[1] == "u8" else [self.read(ty[1]) for _ in range(ty[2])]
+        if kind == "defined":
+            return TYPES[ty[1]].decode_from(self)
+        raise DecodeError(f"unknown type {ty}")
+
+
+class Writer:
+    """Borsh encoder."""
+
+    def __init__(self):
+        self.parts: List[bytes] = []
+
+    def bytes(self) -> bytes:
+        return b"".join(self.parts)
+
+    def write(self, ty: Any, value: Any) -> None:
+        if isinstance(ty, str):
+            if ty in _STRUCT_FORMATS:
+                self.parts.append(struct.pack(_STRUCT_FORMATS[ty], value))
+            elif ty in _WIDE_INTS:
+                size, signed = _WIDE_INTS[ty]
+                self.parts.append(int(value).to_bytes(size, "little", signed=signed))
+            elif ty == "bool":
+                self.parts.append(b"\1" if value else b"\0")
+            elif ty == "pubkey":
+                key = b58decode(value) if isinstance(value, str) else bytes(value)
+                if len(key) != 32:
+                    raise ValueError(f"not a public key: {value!r}")
+                self.parts.append(key)
+            elif ty == "bytes":
+                self.write("u32", len(value))
+                self.parts.append(bytes(value))
+            elif ty == "string":
+                encoded = value.encode("utf-8")
+                self.write("u32", len(encoded))
+                self.parts.append(encoded)
+            else:
+                raise ValueError(f"unknown type {ty}")
+            return
+        kind = ty[0]
+        if kind == "option":
+            self.write("u8", 0 if value is None else 1)
+            if value is not None:
+                self.write(ty[1], value)
+        elif kind == "vec":
+            self.write("u32", len(value))
+            self._items(ty[1], value)
+        elif kind == "array":
+            if len(value) != ty[2]:
+                raise ValueError(f"expected {ty[2]} items, got {len(value)}")
+            self._items(ty[1], value)
+        elif kind == "defined":
+            value.encode_into(self)
+        else:
+            raise ValueError(f"unknown type {ty}")
+
+    def _items(self, ty: Any, values: Any) -> None:
+        if ty == "u8":
+            self.parts.append(bytes(values))
+        else:
+            for value in values:
+                self.write(ty, value)
+
+
+class Struct:
+    """Base for generated structs; `FIELDS` lists (name, type) in order."""
+
+    FIELDS: ClassVar[List[Tuple[str, Any]]] = []
+
+    @classmethod
+    def decode_from(cls, reader: Reader) -> Any:
+        return cls(*(reader.read(ty) for _, ty in cls.FIELDS))
+
+    def encode_into(self, writer: Writer) -> None:
+        for name, ty in self.FIELDS:
+            writer.write(ty, getattr(self, name))
+
+
+class Enum:
+    """Base for generated enums: the variant's name and, unless it is a
+    unit variant, a dict of its fields."""
+
+    VARIANTS: ClassVar[List[Tuple[str, List[Tuple[str, Any]]]]] = []
+
+    variant: str
+    value: Optional[Dict[str, Any]]
+
+    @classmethod
+    def decode_from(cls, reader: Reader) -> Any:
+        index = reader.read("u8")
+        if index >= len(cls.VARIANTS):
+            raise DecodeError(f"{cls.__name__} has no variant {index}")
+        name, variant_fields = cls.VARIANTS[index]
+        value = {field: reader.read(ty) for field, ty in variant_fields} if variant_fields else None
+        return cls(name, value)
+
+    def encode_into(self, writer: Writer) -> None:
+        for index, (name, variant_fields) in enumerate(self.VARIANTS):
+            if name == self.variant:
+                writer.write("u8", index)
+                for field, ty in variant_fields:
+                    writer.write(ty, (self.value or {})[field])
+                return
+        raise ValueError(f"{type(self).__name__} has no variant {self.variant}")
+
+
+class Layout(Struct):
+    """Base for accounts and events: a discriminator, then the struct."""
+
+    DISCRIMINATOR: ClassVar[bytes] = b""
+
+    @classmethod
+    def decode(cls, data: bytes) -> Any:
+        if data[:len(cls.DISCRIMINATOR)] != cls.DISCRIMINATOR:
+            raise DecodeError(f"not a {cls.__name__}")
+        reader = Reader(data)
+        reader.offset = len(cls.DISCRIMINATOR)
+        return cls.decode_from(reader)
+
+
+@dataclass
+class AccountMeta:
+    pubkey: str
+    is_signer: bool
+    is_writable: bool
+
+
+@dataclass
+class Instruction:
+    """An instruction in the shape Solana client libraries expect."""
+
+    program_id: str
+    accounts: List[AccountMeta]
+    data: bytes
+
+
+def _instruction(
+    program_id: str,
+    metas: List[Tuple[str, bool, bool, bool]],
+    accounts: Any,
+    discriminator: bytes,
+    args: List[Tuple[Any, Any]],
+) -> Instruction:
+    out = []
+    for name, writable, signer, optional in metas:
+        key = getattr(accounts, name)
+        if key is None:
+            if not optional:
+                raise ValueError(f"account {name} is required")
+            out.append(AccountMeta(program_id, False, False))
+        else:
+            out.append(AccountMeta(key, signer, writable))
+    writer = Writer()
+    writer.parts.append(discriminator)
+    for ty, value in args:
+        writer.write(ty, value)
+    return Instruction(program_id, out, writer.bytes())
+
+
+TYPES: Dict[str, Any] = {}
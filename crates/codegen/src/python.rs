@@ -0,0 +1,312 @@
+//! Python bindings.
+//!
+//! The generated module needs nothing beyond the standard library. It is
+//! a fixed runtime (Borsh reader and writer, base58) followed by one
+//! dataclass per type, account and event, a `<Instruction>Accounts`
+//! dataclass and a builder function per instruction, and log parsing for
+//! events. Public keys are base58 strings throughout.
+
+use std::fmt::Write;
+
+use crate::schema::{Field, Instruction, Layout, Schema, Type, TypeDef, TypeKind};
+use crate::Error;
+
+/// The runtime every generated module starts with.
+const PRELUDE: &str = include_str!("prelude.py");
+
+/// Python's reserved words; fields named after one get a trailing `_`.
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Names the runtime defines, which program types can't reuse.
+const RUNTIME_NAMES: &[&str] = &[
+    "AccountMeta", "DecodeError", "Enum", "Instruction", "Layout", "Reader", "Struct", "Writer",
+];
+
+/// Render the Python module for `schema`.
+pub fn generate(schema: &Schema) -> Result<String, Error> {
+    let names = schema
+        .types
+        .iter()
+        .map(|t| &t.name)
+        .chain(schema.accounts.iter().map(|a| &a.name))
+        .chain(schema.events.iter().map(|e| &e.name));
+    for name in names {
+        if RUNTIME_NAMES.contains(&name.as_str()) {
+            return Err(Error::Unsupported(format!("type {name} clashes with the Python runtime")));
+        }
+    }
+
+    let mut out = String::new();
+    let program = &schema.program;
+    let _ = writeln!(
+        out,
+        "\"\"\"Python bindings for the {} program, version {}.\n\nGenerated by axiom-codegen \
+         from its IDL; do not edit by hand.\n\"\"\"\n",
+        program.name, program.version
+    );
+    out.push_str("from __future__ import annotations\n\n");
+    out.push_str(PRELUDE);
+    let _ = writeln!(out, "\nPROGRAM_ID = {:?}", program.address);
+    let _ = writeln!(out, "SCHEMA_VERSION = {}", schema.schema_version);
+
+    out.push_str("\n# ─── Types ────────────────────────────────────────────────────────────────\n");
+    for def in &schema.types {
+        type_def(&mut out, def);
+    }
+    out.push_str("\n# ─── Accounts ─────────────────────────────────────────────────────────────\n");
+    for account in &schema.accounts {
+        layout(&mut out, account);
+    }
+    let _ = writeln!(out, "\nACCOUNTS = {{{}}}", registry(&schema.accounts));
+
+    out.push_str("\n# ─── Events ───────────────────────────────────────────────────────────────\n");
+    for event in &schema.events {
+        layout(&mut out, event);
+    }
+    let _ = writeln!(out, "\nEVENTS = {{{}}}", registry(&schema.events));
+    out.push_str(EVENT_PARSING);
+
+    out.push_str("\n# ─── Instructions ─────────────────────────────────────────────────────────\n");
+    for ix in &schema.instructions {
+        instruction(&mut out, ix);
+    }
+
+    out.push_str("\n# ─── Errors ───────────────────────────────────────────────────────────────\n\n");
+    out.push_str("ERRORS: Dict[int, Tuple[str, Optional[str]]] = {\n");
+    for e in &schema.errors {
+        let msg = e.msg.as_ref().map_or("None".into(), |m| format!("{m:?}"));
+        let _ = writeln!(out, "    {}: ({:?}, {msg}),", e.code, e.name);
+    }
+    out.push_str("}\n");
+    Ok(out)
+}
+
+const EVENT_PARSING: &str = r#"
+
+def parse_event(data: bytes) -> Optional[Any]:
+    """Decode an event from its discriminator-prefixed bytes, or None if
+    the discriminator is unknown."""
+    for event in EVENTS.values():
+        if data[:len(event.DISCRIMINATOR)] == event.DISCRIMINATOR:
+            return event.decode(data)
+    return None
+
+
+def parse_logs(logs: List[str]) -> List[Any]:
+    """Decode every event emitted in a transaction's log messages."""
+    events = []
+    for line in logs:
+        if line.startswith("Program data: "):
+            try:
+                event = parse_event(base64.b64decode(line[len("Program data: "):]))
+            except (ValueError, DecodeError):
+                continue
+            if event is not None:
+                events.append(event)
+    return events
+"#;
+
+fn type_def(out: &mut String, def: &TypeDef) {
+    match &def.kind {
+        TypeKind::Struct { fields } => {
+            class(out, &def.name, "Struct", &def.docs, fields, None);
+        }
+        TypeKind::Enum { variants } => {
+            let _ = writeln!(out, "\n\n@dataclass\nclass {}(Enum):", def.name);
+            docstring(out, &def.docs, "    ");
+            out.push_str("    VARIANTS = [\n");
+            for v in variants {
+                let _ = writeln!(out, "        ({:?}, [{}]),", v.name, field_types(&v.fields));
+            }
+            out.push_str("    ]\n\n    variant: str\n    value: Optional[Dict[str, Any]] = None\n");
+            let _ = writeln!(out, "\n\nTYPES[{:?}] = {}", def.name, def.name);
+        }
+    }
+}
+
+fn layout(out: &mut String, layout: &Layout) {
+    class(out, &layout.name, "Layout", &layout.docs, &layout.fields, Some(&layout.discriminator));
+}
+
+fn class(
+    out: &mut String,
+    name: &str,
+    base: &str,
+    docs: &[String],
+    fields: &[Field],
+    discriminator: Option<&[u8]>,
+) {
+    let _ = writeln!(out, "\n\n@dataclass\nclass {name}({base}):");
+    docstring(out, docs, "    ");
+    if let Some(discriminator) = discriminator {
+        let _ = writeln!(out, "    DISCRIMINATOR = bytes({discriminator:?})");
+    }
+    let _ = writeln!(out, "    FIELDS = [{}]\n", field_types(fields));
+    for f in fields {
+        let _ = writeln!(out, "    {}: {}", ident(&f.name), annotation(&f.ty));
+    }
+    if fields.is_empty() {
+        out.push_str("    pass\n");
+    }
+    let _ = writeln!(out, "\n\nTYPES[{name:?}] = {name}");
+}
+
+fn instruction(out: &mut String, ix: &Instruction) {
+    let accounts = format!("{}Accounts", pascal(&ix.name));
+    let _ = writeln!(out, "\n\n@dataclass\nclass {accounts}:");
+    // Fixed-address and optional accounts have defaults, so they go last
+    let (required, defaulted): (Vec<_>, Vec<_>) = ix
+        .accounts
+        .iter()
+        .partition(|a| a.address.is_none() && !a.optional);
+    for a in &required {
+        let _ = writeln!(out, "    {}: str", ident(&a.name));
+    }
+    for a in &defaulted {
+        match &a.address {
+            Some(address) => {
+                let _ = writeln!(out, "    {}: str = {address:?}", ident(&a.name));
+            }
+            None => {
+                let _ = writeln!(out, "    {}: Optional[str] = None", ident(&a.name));
+            }
+        }
+    }
+    if ix.accounts.is_empty() {
+        out.push_str("    pass\n");
+    }
+
+    let _ = writeln!(out, "\n\ndef {}(\n    accounts: {accounts},", ident(&ix.name));
+    for arg in &ix.args {
+        let _ = writeln!(out, "    {}: {},", ident(&arg.name), annotation(&arg.ty));
+    }
+    out.push_str("    program_id: str = PROGRAM_ID,\n) -> Instruction:\n");
+    docstring(out, &ix.docs, "    ");
+    out.push_str("    return _instruction(\n        program_id,\n        [\n");
+    for a in &ix.accounts {
+        let _ = writeln!(
+            out,
+            "            ({:?}, {}, {}, {}),",
+            ident(&a.name),
+            py_bool(a.writable),
+            py_bool(a.signer),
+            py_bool(a.optional)
+        );
+    }
+    out.push_str("        ],\n        accounts,\n");
+    let _ = writeln!(out, "        bytes({:?}),", ix.discriminator);
+    let args: Vec<String> = ix
+        .args
+        .iter()
+        .map(|arg| format!("({}, {})", descriptor(&arg.ty), ident(&arg.name)))
+        .collect();
+    let _ = writeln!(out, "        [{}],\n    )", args.join(", "));
+
+    if let Some(returns) = &ix.returns {
+        let _ = writeln!(
+            out,
+            "\n\ndef decode_{}_return(data: bytes) -> {}:\n    \"\"\"Decode the return data `{}` sets.\"\"\"\n    return Reader(data).read({})",
+            ix.name,
+            annotation(returns),
+            ix.name,
+            descriptor(returns)
+        );
+    }
+}
+
+fn docstring(out: &mut String, docs: &[String], indent: &str) {
+    if docs.is_empty() {
+        return;
+    }
+    let lines: Vec<String> = docs
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line.replace('\\', "\\\\").replace("\"\"\"", "'''");
+            if i == 0 || line.is_empty() {
+                line
+            } else {
+                format!("{indent}{line}")
+            }
+        })
+        .collect();
+    let _ = writeln!(out, "{indent}\"\"\"{}\"\"\"\n", lines.join("\n"));
+}
+
+/// `[("name", type), ...]` for a struct or variant's `FIELDS`.
+fn field_types(fields: &[Field]) -> String {
+    fields
+        .iter()
+        .map(|f| format!("({:?}, {})", ident(&f.name), descriptor(&f.ty)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn registry(layouts: &[Layout]) -> String {
+    layouts
+        .iter()
+        .map(|l| format!("{:?}: {}", l.name, l.name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The runtime's type descriptor: a string for primitives, a tuple
+/// otherwise.
+fn descriptor(ty: &Type) -> String {
+    match ty {
+        Type::Primitive(name) => format!("{name:?}"),
+        Type::Option { option } => format!("(\"option\", {})", descriptor(option)),
+        Type::Vec { vec } => format!("(\"vec\", {})", descriptor(vec)),
+        Type::Array { array: (inner, len) } => format!("(\"array\", {}, {len})", descriptor(inner)),
+        Type::Defined { defined } => format!("(\"defined\", {defined:?})"),
+    }
+}
+
+fn annotation(ty: &Type) -> String {
+    match ty {
+        Type::Primitive(name) => match name.as_str() {
+            "bool" => "bool".into(),
+            "f32" | "f64" => "float".into(),
+            "pubkey" | "string" => "str".into(),
+            "bytes" => "bytes".into(),
+            _ => "int".into(),
+        },
+        Type::Option { option } => format!("Optional[{}]", annotation(option)),
+        Type::Vec { vec } | Type::Array { array: (vec, _) } => match vec.as_ref() {
+            Type::Primitive(name) if name == "u8" => "bytes".into(),
+            inner => format!("List[{}]", annotation(inner)),
+        },
+        Type::Defined { defined } => format!("\"{defined}\""),
+    }
+}
+
+fn ident(name: &str) -> String {
+    match name {
+        _ if KEYWORDS.contains(&name) => format!("{name}_"),
+        _ if name.starts_with(|c: char| c.is_ascii_digit()) => format!("_{name}"),
+        _ => name.into(),
+    }
+}
+
+fn pascal(snake: &str) -> String {
+    snake
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |c| c.to_ascii_uppercase().to_string() + chars.as_str())
+        })
+        .collect()
+}
+
+fn py_bool(value: bool) -> &'static str {
+    if value {
+        "True"
+    } else {
+        "False"
+    }
+}
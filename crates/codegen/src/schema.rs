@@ -0,0 +1,356 @@
+//! The stable JSON schema.
+//!
+//! Anchor's IDL format has changed shape between releases and mixes in
+//! details consumers don't need (PDA seeds, generics, account relations).
+//! [`Schema`] keeps only what it takes to decode accounts and events and
+//! encode instructions: Borsh layouts, discriminators and account lists.
+//! Accounts and events carry their fields inline; `types` holds the
+//! remaining user-defined types they refer to. [`SCHEMA_VERSION`] is
+//! bumped whenever the shape of the schema itself changes.
+
+use std::collections::HashMap;
+
+use anchor_lang_idl_spec::{
+    Idl, IdlArrayLen, IdlDefinedFields, IdlField, IdlInstructionAccountItem, IdlSerialization,
+    IdlType, IdlTypeDef, IdlTypeDefTy,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// Version of the schema format, not of the program.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    pub schema_version: u32,
+    pub program: Program,
+    pub accounts: Vec<Layout>,
+    pub events: Vec<Layout>,
+    pub types: Vec<TypeDef>,
+    pub instructions: Vec<Instruction>,
+    pub errors: Vec<ErrorCode>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Program {
+    pub name: String,
+    pub address: String,
+    pub version: String,
+}
+
+/// An account or event: a discriminator followed by a Borsh struct.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Layout {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+    pub discriminator: Vec<u8>,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TypeDef {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+    #[serde(flatten)]
+    pub kind: TypeKind,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum TypeKind {
+    Struct { fields: Vec<Field> },
+    /// Borsh enum: a `u8` variant index, then that variant's fields
+    Enum { variants: Vec<Variant> },
+}
+
+/// A named field. Tuple fields are named by position (`"0"`, `"1"`, ...).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Type,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Variant {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<Field>,
+}
+
+/// A Borsh type. Primitives are plain strings (`"u64"`, `"pubkey"`,
+/// `"string"`, `"bytes"`, ...); composites are single-key objects.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Type {
+    Primitive(String),
+    Option { option: Box<Type> },
+    Vec { vec: Box<Type> },
+    Array { array: (Box<Type>, usize) },
+    Defined { defined: String },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Instruction {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+    pub discriminator: Vec<u8>,
+    /// In the order the program expects them
+    pub accounts: Vec<AccountMeta>,
+    pub args: Vec<Field>,
+    /// Type of the return data, if the instruction sets any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub returns: Option<Type>,
+}
+
+/// One account an instruction takes. Accounts of nested account structs
+/// are flattened and named `outer_inner`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AccountMeta {
+    pub name: String,
+    pub writable: bool,
+    pub signer: bool,
+    /// May be omitted; the program ID is passed in its place
+    pub optional: bool,
+    /// The only address the program accepts, when fixed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ErrorCode {
+    pub code: u32,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub msg: Option<String>,
+}
+
+impl Schema {
+    /// Flatten an Anchor IDL. Fails on what the schema can't express:
+    /// generic types and non-Borsh (zero-copy) layouts.
+    pub fn from_idl(idl: &Idl) -> Result<Self, Error> {
+        let aliases: HashMap<&str, &IdlType> = idl
+            .types
+            .iter()
+            .filter_map(|def| match &def.ty {
+                IdlTypeDefTy::Type { alias } => Some((def.name.as_str(), alias)),
+                _ => None,
+            })
+            .collect();
+        let cx = Context { aliases };
+        let find = |name: &str| {
+            idl.types
+                .iter()
+                .find(|def| def.name == name)
+                .ok_or_else(|| Error::Unsupported(format!("no type definition for {name}")))
+        };
+
+        let accounts = idl
+            .accounts
+            .iter()
+            .map(|a| cx.layout(find(&a.name)?, &a.discriminator))
+            .collect::<Result<Vec<_>, _>>()?;
+        let events = idl
+            .events
+            .iter()
+            .map(|e| cx.layout(find(&e.name)?, &e.discriminator))
+            .collect::<Result<Vec<_>, _>>()?;
+        let inline: Vec<&str> = idl
+            .accounts
+            .iter()
+            .map(|a| a.name.as_str())
+            .chain(idl.events.iter().map(|e| e.name.as_str()))
+            .collect();
+        let types = idl
+            .types
+            .iter()
+            .filter(|def| !inline.contains(&def.name.as_str()))
+            .filter(|def| !matches!(def.ty, IdlTypeDefTy::Type { .. }))
+            .map(|def| cx.type_def(def))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let instructions = idl
+            .instructions
+            .iter()
+            .map(|ix| {
+                let mut accounts = Vec::new();
+                flatten_accounts(&ix.accounts, "", &mut accounts);
+                Ok(Instruction {
+                    name: ix.name.clone(),
+                    docs: ix.docs.clone(),
+                    discriminator: ix.discriminator.clone(),
+                    accounts,
+                    args: cx.fields(&ix.args)?,
+                    returns: ix.returns.as_ref().map(|ty| cx.ty(ty)).transpose()?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Self {
+            schema_version: SCHEMA_VERSION,
+            program: Program {
+                name: idl.metadata.name.clone(),
+                address: idl.address.clone(),
+                version: idl.metadata.version.clone(),
+            },
+            accounts,
+            events,
+            types,
+            instructions,
+            errors: idl
+                .errors
+                .iter()
+                .map(|e| ErrorCode {
+                    code: e.code,
+                    name: e.name.clone(),
+                    msg: e.msg.clone(),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// What converting a type needs to know about the rest of the IDL.
+struct Context<'a> {
+    /// Type aliases, substituted wherever they are used
+    aliases: HashMap<&'a str, &'a IdlType>,
+}
+
+impl Context<'_> {
+    fn layout(&self, def: &IdlTypeDef, discriminator: &[u8]) -> Result<Layout, Error> {
+        let TypeKind::Struct { fields } = self.type_def(def)?.kind else {
+            return Err(Error::Unsupported(format!("{} is not a struct", def.name)));
+        };
+        Ok(Layout {
+            name: def.name.clone(),
+            docs: def.docs.clone(),
+            discriminator: discriminator.to_vec(),
+            fields,
+        })
+    }
+
+    fn type_def(&self, def: &IdlTypeDef) -> Result<TypeDef, Error> {
+        if !def.generics.is_empty() {
+            return Err(Error::Unsupported(format!("{} is generic", def.name)));
+        }
+        if def.serialization != IdlSerialization::Borsh {
+            return Err(Error::Unsupported(format!("{} is not Borsh-serialized", def.name)));
+        }
+        let kind = match &def.ty {
+            IdlTypeDefTy::Struct { fields } => TypeKind::Struct {
+                fields: self.defined_fields(fields.as_ref())?,
+            },
+            IdlTypeDefTy::Enum { variants } => TypeKind::Enum {
+                variants: variants
+                    .iter()
+                    .map(|v| {
+                        Ok(Variant {
+                            name: v.name.clone(),
+                            fields: self.defined_fields(v.fields.as_ref())?,
+                        })
+                    })
+                    .collect::<Result<_, Error>>()?,
+            },
+            IdlTypeDefTy::Type { alias } => {
+                return Err(Error::Unsupported(format!("{} aliases {alias:?}", def.name)))
+            }
+        };
+        Ok(TypeDef {
+            name: def.name.clone(),
+            docs: def.docs.clone(),
+            kind,
+        })
+    }
+
+    fn defined_fields(&self, fields: Option<&IdlDefinedFields>) -> Result<Vec<Field>, Error> {
+        match fields {
+            None => Ok(Vec::new()),
+            Some(IdlDefinedFields::Named(fields)) => self.fields(fields),
+            Some(IdlDefinedFields::Tuple(types)) => types
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| {
+                    Ok(Field {
+                        name: i.to_string(),
+                        ty: self.ty(ty)?,
+                        docs: Vec::new(),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    fn fields(&self, fields: &[IdlField]) -> Result<Vec<Field>, Error> {
+        fields
+            .iter()
+            .map(|f| {
+                Ok(Field {
+                    name: f.name.clone(),
+                    ty: self.ty(&f.ty)?,
+                    docs: f.docs.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn ty(&self, ty: &IdlType) -> Result<Type, Error> {
+        let primitive = |name: &str| Ok(Type::Primitive(name.into()));
+        match ty {
+            IdlType::Bool => primitive("bool"),
+            IdlType::U8 => primitive("u8"),
+            IdlType::I8 => primitive("i8"),
+            IdlType::U16 => primitive("u16"),
+            IdlType::I16 => primitive("i16"),
+            IdlType::U32 => primitive("u32"),
+            IdlType::I32 => primitive("i32"),
+            IdlType::F32 => primitive("f32"),
+            IdlType::U64 => primitive("u64"),
+            IdlType::I64 => primitive("i64"),
+            IdlType::F64 => primitive("f64"),
+            IdlType::U128 => primitive("u128"),
+            IdlType::I128 => primitive("i128"),
+            IdlType::U256 => primitive("u256"),
+            IdlType::I256 => primitive("i256"),
+            IdlType::Bytes => primitive("bytes"),
+            IdlType::String => primitive("string"),
+            IdlType::Pubkey => primitive("pubkey"),
+            IdlType::Option(inner) => Ok(Type::Option { option: Box::new(self.ty(inner)?) }),
+            IdlType::Vec(inner) => Ok(Type::Vec { vec: Box::new(self.ty(inner)?) }),
+            IdlType::Array(inner, IdlArrayLen::Value(len)) => Ok(Type::Array {
+                array: (Box::new(self.ty(inner)?), *len),
+            }),
+            IdlType::Defined { name, generics } if generics.is_empty() => {
+                match self.aliases.get(name.as_str()) {
+                    Some(alias) => self.ty(alias),
+                    None => Ok(Type::Defined { defined: name.clone() }),
+                }
+            }
+            other => Err(Error::Unsupported(format!("type {other:?}"))),
+        }
+    }
+}
+
+/// Depth-first, so nested account structs keep their position.
+fn flatten_accounts(items: &[IdlInstructionAccountItem], prefix: &str, out: &mut Vec<AccountMeta>) {
+    for item in items {
+        match item {
+            IdlInstructionAccountItem::Single(account) => out.push(AccountMeta {
+                name: format!("{prefix}{}", account.name),
+                writable: account.writable,
+                signer: account.signer,
+                optional: account.optional,
+                address: account.address.clone(),
+            }),
+            IdlInstructionAccountItem::Composite(group) => {
+                flatten_accounts(&group.accounts, &format!("{prefix}{}_", group.name), out)
+            }
+        }
+    }
+}
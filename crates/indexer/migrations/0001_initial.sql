@@ -0,0 +1,60 @@
+-- Indexed SOLPRISM state.
+--
+-- Portable across SQLite and Postgres: integers are BIGINT and flags are
+-- stored as 0/1, since sqlx's Any driver cannot decode SQLite booleans.
+
+CREATE TABLE agents (
+    address                 TEXT PRIMARY KEY,
+    authority               TEXT NOT NULL,
+    name                    TEXT NOT NULL,
+    total_commitments       BIGINT NOT NULL,
+    total_verified          BIGINT NOT NULL,
+    accountability_score    BIGINT NOT NULL,
+    created_at              BIGINT NOT NULL,
+    verified_operator       BIGINT NOT NULL,
+    on_probation            BIGINT NOT NULL,
+    attestation_attestor    TEXT,
+    attestation_reference   TEXT,
+    attestation_attested_at BIGINT
+);
+
+CREATE INDEX agents_attestation_attestor ON agents (attestation_attestor);
+
+CREATE TABLE commitments (
+    address         TEXT PRIMARY KEY,
+    agent           TEXT NOT NULL,
+    authority       TEXT NOT NULL,
+    commitment_hash TEXT NOT NULL,
+    action_type     TEXT NOT NULL,
+    category        TEXT NOT NULL,
+    confidence      BIGINT NOT NULL,
+    timestamp       BIGINT NOT NULL,
+    status          TEXT NOT NULL,
+    reasoning_uri   TEXT,
+    revealed_at     BIGINT,
+    trace_len       BIGINT NOT NULL,
+    chunk_count     BIGINT NOT NULL,
+    upvotes         BIGINT NOT NULL,
+    downvotes       BIGINT NOT NULL
+);
+
+CREATE INDEX commitments_agent ON commitments (agent, timestamp);
+CREATE INDEX commitments_status ON commitments (status);
+
+CREATE TABLE attestors (
+    attestor            TEXT PRIMARY KEY,
+    address             TEXT NOT NULL,
+    name                TEXT NOT NULL,
+    active              BIGINT NOT NULL,
+    registered_at       BIGINT NOT NULL,
+    revoked_at          BIGINT,
+    attestations_issued BIGINT NOT NULL
+);
+
+CREATE TABLE score_history (
+    agent                TEXT NOT NULL,
+    observed_at          BIGINT NOT NULL,
+    accountability_score BIGINT NOT NULL
+);
+
+CREATE INDEX score_history_agent ON score_history (agent, observed_at);
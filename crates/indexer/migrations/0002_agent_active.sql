@@ -0,0 +1,4 @@
+-- Whether each agent is live or was retired by its authority with
+-- `set_agent_status`. Agents indexed before this were all live.
+
+ALTER TABLE agents ADD COLUMN active BIGINT NOT NULL DEFAULT 1;
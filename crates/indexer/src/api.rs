@@ -0,0 +1,148 @@
+//! Read-only HTTP API over the [`Store`].
+//!
+//! | Route                                 | Returns                          |
+//! |---------------------------------------|----------------------------------|
+//! | `GET /agents`                         | page of agents                   |
+//! | `GET /agents/{address}`               | one agent                        |
+//! | `GET /agents/{address}/score-history` | score changes, oldest first      |
+//! | `GET /commitments`                    | page of commitments              |
+//! | `GET /commitments/{address}`          | one commitment with its status   |
+//! | `POST /graphql`                       | see [`graphql`](crate::graphql)  |
+//! | `GET /metrics`                        | see [`metrics`](crate::metrics)  |
+//!
+//! Listings take `offset` and `limit` (default 50, at most 500). Agents
+//! filter on `verified_operator`, `min_score` and `active`; commitments
+//! on `agent`, `status`, `category` and `action_type`.
+
+use async_graphql::http::GraphiQLSource;
+use async_graphql_axum::GraphQL;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::graphql;
+use crate::metrics::{self, Metrics};
+use crate::store::{AgentFilter, CommitmentFilter, Store};
+use crate::Error;
+
+/// Page size when the request names none.
+pub const DEFAULT_LIMIT: usize = 50;
+/// Largest page a request may ask for.
+pub const MAX_LIMIT: usize = 500;
+
+pub fn router(store: Store, metrics: Metrics) -> Router {
+    let graphql = GraphQL::new(graphql::schema(store.clone()));
+    Router::new()
+        .route("/agents", get(list_agents))
+        .route("/agents/{address}", get(get_agent))
+        .route("/agents/{address}/score-history", get(score_history))
+        .route("/commitments", get(list_commitments))
+        .route("/commitments/{address}", get(get_commitment))
+        .route("/graphql", get(graphiql).post_service(graphql))
+        .with_state(store)
+        .merge(metrics::router(metrics))
+}
+
+async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+#[derive(Deserialize)]
+struct Paging {
+    #[serde(default)]
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl Paging {
+    fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT)
+    }
+}
+
+#[derive(Deserialize)]
+struct AgentQuery {
+    verified_operator: Option<bool>,
+    min_score: Option<u16>,
+    active: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct CommitmentQuery {
+    agent: Option<String>,
+    status: Option<String>,
+    category: Option<String>,
+    action_type: Option<String>,
+}
+
+fn not_found(what: &str) -> Response {
+    (StatusCode::NOT_FOUND, Json(json!({ "error": format!("{what} not found") }))).into_response()
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        tracing::error!("request failed: {self}");
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "internal error" }))).into_response()
+    }
+}
+
+async fn list_agents(
+    State(store): State<Store>,
+    Query(paging): Query<Paging>,
+    Query(query): Query<AgentQuery>,
+) -> Result<Response, Error> {
+    let filter = AgentFilter {
+        verified_operator: query.verified_operator,
+        min_score: query.min_score,
+        active: query.active,
+    };
+    Ok(Json(store.agents(&filter, paging.offset, paging.limit()).await?).into_response())
+}
+
+async fn get_agent(
+    State(store): State<Store>,
+    Path(address): Path<String>,
+) -> Result<Response, Error> {
+    Ok(match store.agent(&address).await? {
+        Some(agent) => Json(agent).into_response(),
+        None => not_found("agent"),
+    })
+}
+
+async fn score_history(
+    State(store): State<Store>,
+    Path(address): Path<String>,
+) -> Result<Response, Error> {
+    if store.agent(&address).await?.is_none() {
+        return Ok(not_found("agent"));
+    }
+    Ok(Json(store.score_history(&address).await?).into_response())
+}
+
+async fn list_commitments(
+    State(store): State<Store>,
+    Query(paging): Query<Paging>,
+    Query(query): Query<CommitmentQuery>,
+) -> Result<Response, Error> {
+    let filter = CommitmentFilter {
+        agent: query.agent,
+        status: query.status,
+        category: query.category,
+        action_type: query.action_type,
+    };
+    Ok(Json(store.commitments(&filter, paging.offset, paging.limit()).await?).into_response())
+}
+
+async fn get_commitment(
+    State(store): State<Store>,
+    Path(address): Path<String>,
+) -> Result<Response, Error> {
+    Ok(match store.commitment(&address).await? {
+        Some(commitment) => Json(commitment).into_response(),
+        None => not_found("commitment"),
+    })
+}
@@ -0,0 +1,529 @@
+//! Rebuild the index from the program's transaction history.
+//!
+//! The poller only sees accounts as they are now. Backfill instead walks
+//! every transaction that touched the program, oldest first, and replays
+//! the instructions themselves. That covers commitments made before the
+//! program emitted events.
+//!
+//! Replayed records are approximations where history is silent (scores,
+//! reactions); the next poll overwrites every record whose account still
+//! exists with its exact state.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use axiom::CommitmentCategory;
+use std::collections::BTreeMap;
+
+use crate::rpc::{ConfirmedTransaction, RpcClient};
+use crate::store::{category_name, AgentRecord, CommitmentRecord, Store};
+use crate::Error;
+
+/// Signatures fetched per `getSignaturesForAddress` page.
+const PAGE_SIZE: usize = 1000;
+
+/// A program instruction that changes indexed state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Replayed {
+    RegisterAgent {
+        agent: String,
+        authority: String,
+        name: String,
+    },
+    Commit {
+        commitment: String,
+        agent: String,
+        authority: String,
+        commitment_hash: [u8; 32],
+        action_type: String,
+        confidence: u8,
+        category: &'static str,
+    },
+    CreateTemplate {
+        template: String,
+        action_type: String,
+        category: &'static str,
+    },
+    /// A commit whose action type and category come from `template`.
+    CommitFromTemplate {
+        commitment: String,
+        agent: String,
+        authority: String,
+        commitment_hash: [u8; 32],
+        confidence: u8,
+        template: String,
+    },
+    /// A private commitment's action type, category and confidence.
+    PublishDetails {
+        commitment: String,
+        action_type: String,
+        category: &'static str,
+        confidence: u8,
+    },
+    /// Reveal start; `FinalizeReveal` completes it.
+    Reveal {
+        commitment: String,
+        reasoning_uri: String,
+        trace_len: u64,
+        chunk_count: u32,
+    },
+    FinalizeReveal {
+        commitment: String,
+    },
+}
+
+/// Decode one instruction addressed to the program, given its account
+/// keys in instruction order.
+pub fn decode(accounts: &[String], data: &[u8]) -> Option<Replayed> {
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, mut args) = data.split_at(8);
+    let account = |i: usize| accounts.get(i).cloned();
+
+    if discriminator == axiom::instruction::RegisterAgent::DISCRIMINATOR {
+        let name = String::deserialize(&mut args).ok()?;
+        return Some(Replayed::RegisterAgent {
+            agent: account(0)?,
+            authority: account(1)?,
+            name,
+        });
+    }
+    if discriminator == axiom::instruction::CommitReasoning::DISCRIMINATOR {
+        let ix = axiom::instruction::CommitReasoning::deserialize(&mut args).ok()?;
+        return Some(Replayed::Commit {
+            commitment: account(0)?,
+            agent: account(1)?,
+            authority: account(4)?,
+            commitment_hash: ix.args.commitment_hash,
+            action_type: ix.args.action_type,
+            confidence: ix.args.confidence,
+            category: category_name(ix.args.category),
+        });
+    }
+    if discriminator == axiom::instruction::CreateTemplate::DISCRIMINATOR {
+        let ix = axiom::instruction::CreateTemplate::deserialize(&mut args).ok()?;
+        return Some(Replayed::CreateTemplate {
+            template: account(0)?,
+            action_type: ix.action_type,
+            category: category_name(ix.category),
+        });
+    }
+    if discriminator == axiom::instruction::CommitFromTemplate::DISCRIMINATOR {
+        let ix = axiom::instruction::CommitFromTemplate::deserialize(&mut args).ok()?;
+        return Some(Replayed::CommitFromTemplate {
+            commitment: account(0)?,
+            agent: account(1)?,
+            template: account(3)?,
+            authority: account(4)?,
+            commitment_hash: ix.commitment_hash,
+            confidence: ix.confidence,
+        });
+    }
+    if discriminator == axiom::instruction::CommitPrivate::DISCRIMINATOR {
+        // Details stay blank until `publish_details`
+        let ix = axiom::instruction::CommitPrivate::deserialize(&mut args).ok()?;
+        return Some(Replayed::Commit {
+            commitment: account(0)?,
+            agent: account(1)?,
+            authority: account(3)?,
+            commitment_hash: ix.commitment_hash,
+            action_type: String::new(),
+            confidence: 0,
+            category: category_name(CommitmentCategory::Other),
+        });
+    }
+    if discriminator == axiom::instruction::PublishDetails::DISCRIMINATOR {
+        let ix = axiom::instruction::PublishDetails::deserialize(&mut args).ok()?;
+        return Some(Replayed::PublishDetails {
+            commitment: account(0)?,
+            action_type: ix.action_type,
+            category: category_name(ix.category),
+            confidence: ix.confidence,
+        });
+    }
+    if discriminator == axiom::instruction::StartReveal::DISCRIMINATOR {
+        let ix = axiom::instruction::StartReveal::deserialize(&mut args).ok()?;
+        return Some(Replayed::Reveal {
+            commitment: account(0)?,
+            reasoning_uri: ix.reasoning_uri,
+            trace_len: ix.trace_len,
+            chunk_count: ix.chunk_count,
+        });
+    }
+    if discriminator == axiom::instruction::FinalizeReveal::DISCRIMINATOR {
+        return Some(Replayed::FinalizeReveal {
+            commitment: account(0)?,
+        });
+    }
+    None
+}
+
+/// Every instruction for `program` in `tx`, inner (CPI) ones included,
+/// in execution order, as (account keys, data).
+pub fn program_instructions(
+    tx: &ConfirmedTransaction,
+    program: &Pubkey,
+) -> Vec<(Vec<String>, Vec<u8>)> {
+    let message = &tx.transaction.message;
+    let mut keys = message.account_keys.clone();
+    if let Some(loaded) = tx.meta.as_ref().and_then(|m| m.loaded_addresses.as_ref()) {
+        keys.extend(loaded.writable.iter().cloned());
+        keys.extend(loaded.readonly.iter().cloned());
+    }
+    let inner = tx
+        .meta
+        .as_ref()
+        .and_then(|m| m.inner_instructions.clone())
+        .unwrap_or_default();
+    let program = program.to_string();
+
+    let mut out = Vec::new();
+    for (index, top) in message.instructions.iter().enumerate() {
+        let nested = inner
+            .iter()
+            .filter(|i| i.index == index)
+            .flat_map(|i| i.instructions.iter());
+        for ix in std::iter::once(top).chain(nested) {
+            if keys.get(ix.program_id_index) != Some(&program) {
+                continue;
+            }
+            let Ok(data) = bs58::decode(&ix.data).into_vec() else {
+                continue;
+            };
+            let accounts = ix.accounts.iter().filter_map(|&i| keys.get(i).cloned()).collect();
+            out.push((accounts, data));
+        }
+    }
+    out
+}
+
+/// Records reconstructed so far.
+#[derive(Debug, Default)]
+pub struct History {
+    agents: BTreeMap<String, AgentRecord>,
+    commitments: BTreeMap<String, CommitmentRecord>,
+    /// Action type and category per template address
+    templates: BTreeMap<String, (String, &'static str)>,
+}
+
+impl History {
+    /// Apply one replayed instruction executed at `block_time`.
+    pub fn apply(&mut self, ix: Replayed, block_time: i64) {
+        match ix {
+            Replayed::RegisterAgent {
+                agent,
+                authority,
+                name,
+            } => {
+                self.agents.insert(
+                    agent.clone(),
+                    AgentRecord {
+                        address: agent,
+                        authority,
+                        name,
+                        total_commitments: 0,
+                        total_verified: 0,
+                        accountability_score: 10000,
+                        created_at: block_time,
+                        verified_operator: false,
+                        on_probation: false,
+                        operator_attestation: None,
+                        active: true,
+                    },
+                );
+            }
+            Replayed::Commit {
+                commitment,
+                agent,
+                authority,
+                commitment_hash,
+                action_type,
+                confidence,
+                category,
+            } => {
+                if let Some(a) = self.agents.get_mut(&agent) {
+                    a.total_commitments += 1;
+                }
+                self.commitments.insert(
+                    commitment.clone(),
+                    CommitmentRecord {
+                        address: commitment,
+                        agent,
+                        authority,
+                        commitment_hash: hex::encode(commitment_hash),
+                        action_type,
+                        category: category.to_string(),
+                        confidence,
+                        timestamp: block_time,
+                        status: "committed".to_string(),
+                        reasoning_uri: None,
+                        revealed_at: None,
+                        trace_len: 0,
+                        chunk_count: 0,
+                        upvotes: 0,
+                        downvotes: 0,
+                    },
+                );
+            }
+            Replayed::CreateTemplate {
+                template,
+                action_type,
+                category,
+            } => {
+                self.templates.insert(template, (action_type, category));
+            }
+            Replayed::CommitFromTemplate {
+                commitment,
+                agent,
+                authority,
+                commitment_hash,
+                confidence,
+                template,
+            } => {
+                // A template created outside the replayed window leaves
+                // the fields blank until the next poll fills them in
+                let (action_type, category) = self
+                    .templates
+                    .get(&template)
+                    .cloned()
+                    .unwrap_or_else(|| (String::new(), category_name(CommitmentCategory::Other)));
+                self.apply(
+                    Replayed::Commit {
+                        commitment,
+                        agent,
+                        authority,
+                        commitment_hash,
+                        action_type,
+                        confidence,
+                        category,
+                    },
+                    block_time,
+                );
+            }
+            Replayed::PublishDetails {
+                commitment,
+                action_type,
+                category,
+                confidence,
+            } => {
+                let Some(c) = self.commitments.get_mut(&commitment) else {
+                    return;
+                };
+                c.action_type = action_type;
+                c.category = category.to_string();
+                c.confidence = confidence;
+            }
+            Replayed::Reveal {
+                commitment,
+                reasoning_uri,
+                trace_len,
+                chunk_count,
+            } => {
+                let Some(c) = self.commitments.get_mut(&commitment) else {
+                    return;
+                };
+                c.status = "revealing".to_string();
+                c.reasoning_uri = Some(reasoning_uri);
+                c.revealed_at = Some(block_time);
+                c.trace_len = trace_len;
+                c.chunk_count = chunk_count;
+            }
+            Replayed::FinalizeReveal { commitment } => {
+                let Some(c) = self.commitments.get_mut(&commitment) else {
+                    return;
+                };
+                c.status = "revealed".to_string();
+                if let Some(a) = self.agents.get_mut(&c.agent) {
+                    a.total_verified += 1;
+                }
+            }
+        }
+    }
+}
+
+/// What a backfill run loaded.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Summary {
+    pub transactions: usize,
+    pub instructions: usize,
+    pub agents: usize,
+    pub commitments: usize,
+}
+
+/// Replay the program's history (optionally only transactions older
+/// than `before`) and load the result into `store`.
+pub async fn run(
+    rpc: &RpcClient,
+    store: &Store,
+    before: Option<String>,
+    now: i64,
+) -> Result<Summary, Error> {
+    let mut signatures = Vec::new();
+    let mut cursor = before;
+    loop {
+        let page = rpc
+            .signatures_for_address(&axiom::ID, cursor.as_deref(), PAGE_SIZE)
+            .await?;
+        let Some(last) = page.last() else { break };
+        cursor = Some(last.signature.clone());
+        signatures.extend(page.into_iter().filter(|s| s.err.is_none()));
+        tracing::info!(found = signatures.len(), "walking signatures");
+    }
+
+    let mut history = History::default();
+    let mut summary = Summary::default();
+    for info in signatures.iter().rev() {
+        let Some(tx) = rpc.transaction(&info.signature).await? else {
+            tracing::warn!(signature = %info.signature, "transaction unavailable, skipping");
+            continue;
+        };
+        if tx.meta.as_ref().is_some_and(|m| m.err.is_some()) {
+            continue;
+        }
+        let block_time = tx.block_time.or(info.block_time).unwrap_or_default();
+        for (accounts, data) in program_instructions(&tx, &axiom::ID) {
+            if let Some(ix) = decode(&accounts, &data) {
+                history.apply(ix, block_time);
+                summary.instructions += 1;
+            }
+        }
+        summary.transactions += 1;
+        if summary.transactions % 100 == 0 {
+            tracing::info!(done = summary.transactions, total = signatures.len(), "replaying");
+        }
+    }
+
+    let agents: Vec<_> = history.agents.into_values().collect();
+    let commitments: Vec<_> = history.commitments.into_values().collect();
+    store.put_agents(&agents, now).await?;
+    store.put_commitments(&commitments).await?;
+    summary.agents = agents.len();
+    summary.commitments = commitments.len();
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AnchorSerialize, InstructionData};
+    use axiom::{CommitArgs, ConfidenceSource, HashAlgorithm};
+
+    fn keys(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn commit_data() -> Vec<u8> {
+        axiom::instruction::CommitReasoning {
+            args: CommitArgs {
+                commitment_hash: [7; 32],
+                action_type: "trade".to_string(),
+                confidence: 80,
+                nonce: 3,
+                category: CommitmentCategory::Governance,
+                chunk_manifest: None,
+                counterparty: None,
+                risk: None,
+                confidence_source: ConfidenceSource::Unspecified,
+                simulation: false,
+                reveal_deadline: None,
+                hash_algorithm: HashAlgorithm::Sha256,
+            },
+        }
+        .data()
+    }
+
+    fn start_reveal_data() -> Vec<u8> {
+        axiom::instruction::StartReveal {
+            reasoning_uri: "ipfs://trace".to_string(),
+            trace_len: 1200,
+            chunk_count: 2,
+            salt: None,
+        }
+        .data()
+    }
+
+    #[test]
+    fn decodes_commit_reasoning() {
+        let accounts = keys(&["commitment", "agent", "bucket", "stats", "authority", "config"]);
+        assert_eq!(
+            decode(&accounts, &commit_data()),
+            Some(Replayed::Commit {
+                commitment: "commitment".to_string(),
+                agent: "agent".to_string(),
+                authority: "authority".to_string(),
+                commitment_hash: [7; 32],
+                action_type: "trade".to_string(),
+                confidence: 80,
+                category: "governance",
+            })
+        );
+    }
+
+    #[test]
+    fn decodes_start_and_finalize_reveal() {
+        let accounts = keys(&["commitment", "agent", "bucket", "authority"]);
+        assert_eq!(
+            decode(&accounts, &start_reveal_data()),
+            Some(Replayed::Reveal {
+                commitment: "commitment".to_string(),
+                reasoning_uri: "ipfs://trace".to_string(),
+                trace_len: 1200,
+                chunk_count: 2,
+            })
+        );
+        assert_eq!(
+            decode(&accounts, &axiom::instruction::FinalizeReveal {}.data()),
+            Some(Replayed::FinalizeReveal {
+                commitment: "commitment".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn skips_what_it_cannot_decode() {
+        let accounts = keys(&["commitment", "agent", "bucket", "stats", "authority"]);
+        // Too short for a discriminator, unknown discriminator
+        assert_eq!(decode(&accounts, &[1, 2, 3]), None);
+        assert_eq!(decode(&accounts, &[0; 40]), None);
+        // Truncated arguments
+        let data = commit_data();
+        assert_eq!(decode(&accounts, &data[..data.len() - 1]), None);
+        // Missing accounts
+        assert_eq!(decode(&accounts[..4], &data), None);
+        // The original deployment's `reveal_reasoning`
+        let mut legacy = vec![76, 215, 6, 241, 209, 207, 84, 96];
+        "ipfs://trace".to_string().serialize(&mut legacy).unwrap();
+        assert_eq!(decode(&accounts, &legacy), None);
+    }
+
+    #[test]
+    fn replays_a_commitment_through_its_reveal() {
+        let mut history = History::default();
+        let register = axiom::instruction::RegisterAgent {
+            name: "agent".to_string(),
+        }
+        .data();
+        let steps = [
+            (keys(&["agent", "authority", "config"]), register),
+            (
+                keys(&["commitment", "agent", "bucket", "stats", "authority"]),
+                commit_data(),
+            ),
+            (keys(&["commitment"]), start_reveal_data()),
+        ];
+        for (time, (accounts, data)) in steps.into_iter().enumerate() {
+            history.apply(decode(&accounts, &data).unwrap(), 100 + time as i64);
+        }
+        let c = &history.commitments["commitment"];
+        assert_eq!(c.status, "revealing");
+        assert_eq!(c.authority, "authority");
+        assert_eq!(c.revealed_at, Some(102));
+        assert_eq!(history.agents["agent"].total_commitments, 1);
+        assert_eq!(history.agents["agent"].total_verified, 0);
+
+        let finalize = axiom::instruction::FinalizeReveal {}.data();
+        history.apply(decode(&keys(&["commitment"]), &finalize).unwrap(), 103);
+        assert_eq!(history.commitments["commitment"].status, "revealed");
+        assert_eq!(history.agents["agent"].total_verified, 1);
+    }
+}
@@ -0,0 +1,33 @@
+//! `axiom-api`: serve the indexed program state over HTTP.
+//!
+//! Runs its own indexer loop in the background, so it needs only an RPC
+//! endpoint. See [`axiom_indexer::config`] for the environment it reads
+//! and [`axiom_indexer::api`] for the routes.
+
+use axiom_indexer::config::Config;
+use axiom_indexer::metrics::Metrics;
+use axiom_indexer::service::Indexer;
+use axiom_indexer::store;
+use axiom_indexer::{api, Error};
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .init();
+
+    let config = Config::from_env()?;
+    let store = store::open(config.database_url.as_deref()).await?;
+    let metrics = Metrics::new();
+    tokio::spawn(Indexer::new(&config, store.clone(), metrics.clone()).run(config.poll_interval));
+
+    let listener = tokio::net::TcpListener::bind(config.api_addr).await?;
+    tracing::info!(addr = %config.api_addr, rpc = %config.rpc_url, "api listening");
+    axum::serve(listener, api::router(store, metrics))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! `axiom-keeper`: run the SOLPRISM program's permissionless cranks.
+//!
+//! Signs with `AXIOM_KEEPER_KEYPAIR`, which pays the fees, and polls
+//! every `AXIOM_POLL_SECS`. See [`axiom_indexer::config`] for the rest of
+//! the environment it reads and [`axiom_indexer::keeper`] for the cranks.
+
+use axiom_indexer::config::Config;
+use axiom_indexer::keeper::Keeper;
+use axiom_indexer::metrics::{self, Metrics};
+use axiom_indexer::rpc::RpcClient;
+use axiom_indexer::tx::{FeePolicy, Keypair, Sender};
+use axiom_indexer::webhook::RetryPolicy;
+use axiom_indexer::Error;
+use std::future::IntoFuture;
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .init();
+
+    let config = Config::from_env()?;
+    let Some(keypair) = config.keeper_keypair.as_deref() else {
+        return Err(Error::Config("AXIOM_KEEPER_KEYPAIR is required for axiom-keeper".into()));
+    };
+    let payer = Keypair::read(keypair)?;
+    let rpc = RpcClient::new(&config.rpc_url);
+    let fees = FeePolicy {
+        max_micro_lamports: config.keeper_max_priority_fee,
+        ..FeePolicy::default()
+    };
+    let sender = Sender::new(rpc.clone(), payer, fees, RetryPolicy::default());
+
+    let metrics = Metrics::new();
+    let listener = tokio::net::TcpListener::bind(config.metrics_addr).await?;
+    tokio::spawn(axum::serve(listener, metrics::router(metrics.clone())).into_future());
+
+    tracing::info!(
+        rpc = %config.rpc_url,
+        payer = %sender.payer(),
+        metrics = %config.metrics_addr,
+        "keeper started"
+    );
+    Keeper::new(rpc, sender, metrics, config.keeper_horizon)
+        .run(config.poll_interval)
+        .await;
+    tracing::info!("keeper stopped");
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! `axiom-watchtower`: guard a set of SOLPRISM agents.
+//!
+//! Watches the agent profiles in `AXIOM_WATCH_AGENTS` and posts an alert
+//! to `AXIOM_WEBHOOK_URLS` for each missed reveal deadline, commitment
+//! outside `AXIOM_WATCH_ACTION_TYPES` and score drop of
+//! `AXIOM_WATCH_SCORE_DROP_BPS` or more. With `AXIOM_KEEPER_KEYPAIR` it
+//! also expires the missed commitments, paying the fees. See
+//! [`axiom_indexer::config`] for the rest of the environment it reads and
+//! [`axiom_indexer::watchtower`] for the checks.
+
+use anchor_lang::prelude::Pubkey;
+use axiom_indexer::config::Config;
+use axiom_indexer::metrics::{self, Metrics};
+use axiom_indexer::rpc::RpcClient;
+use axiom_indexer::tx::{FeePolicy, Keypair, Sender};
+use axiom_indexer::watchtower::{Policy, Watchtower};
+use axiom_indexer::webhook::{Dispatcher, RetryPolicy};
+use axiom_indexer::Error;
+use std::future::IntoFuture;
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .init();
+
+    let config = Config::from_env()?;
+    if config.watch_agents.is_empty() {
+        return Err(Error::Config(
+            "AXIOM_WATCH_AGENTS is required for axiom-watchtower".into(),
+        ));
+    }
+    let agents = config
+        .watch_agents
+        .iter()
+        .map(|agent| {
+            agent
+                .parse::<Pubkey>()
+                .map_err(|_| Error::Config(format!("AXIOM_WATCH_AGENTS: invalid agent {agent}")))
+        })
+        .collect::<Result<_, _>>()?;
+    let policy = Policy {
+        agents,
+        action_types: config
+            .watch_action_types
+            .clone()
+            .map(|types| types.into_iter().collect()),
+        max_score_drop_bps: config.watch_score_drop_bps,
+    };
+
+    let rpc = RpcClient::new(&config.rpc_url);
+    let metrics = Metrics::new();
+    let sender = config
+        .keeper_keypair
+        .as_deref()
+        .map(|keypair| {
+            let fees = FeePolicy {
+                max_micro_lamports: config.keeper_max_priority_fee,
+                ..FeePolicy::default()
+            };
+            Ok::<_, Error>(Sender::new(
+                rpc.clone(),
+                Keypair::read(keypair)?,
+                fees,
+                RetryPolicy::default(),
+            ))
+        })
+        .transpose()?;
+    let dispatcher = config.webhook_secret.as_ref().map(|secret| {
+        Dispatcher::new(
+            config.webhook_urls.clone(),
+            secret.as_bytes(),
+            RetryPolicy::default(),
+            metrics.clone(),
+        )
+    });
+
+    let listener = tokio::net::TcpListener::bind(config.metrics_addr).await?;
+    tokio::spawn(axum::serve(listener, metrics::router(metrics.clone())).into_future());
+
+    tracing::info!(
+        rpc = %config.rpc_url,
+        agents = policy.agents.len(),
+        enforcing = sender.is_some(),
+        metrics = %config.metrics_addr,
+        "watchtower started"
+    );
+    Watchtower::new(rpc, policy, sender, dispatcher, metrics)
+        .run(config.poll_interval)
+        .await;
+    tracing::info!("watchtower stopped");
+    Ok(())
+}
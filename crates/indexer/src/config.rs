@@ -0,0 +1,84 @@
+//! Service configuration, read from the environment.
+//!
+//! | Variable                        | Default                                            |
+//! |---------------------------------|----------------------------------------------------|
+//! | `AXIOM_RPC_URL`                 | `http://127.0.0.1:8899`                            |
+//! | `AXIOM_POLL_SECS`               | `10`                                               |
+//! | `AXIOM_DATABASE_URL`            | none (in-memory); `sqlite:` or `postgres:`         |
+//! | `AXIOM_WEBHOOK_URLS`            | none (whitespace-separated URLs)                   |
+//! | `AXIOM_WEBHOOK_SECRET`          | required when webhooks are configured              |
+//! | `AXIOM_API_ADDR`                | `127.0.0.1:8080`                                   |
+//! | `AXIOM_METRICS_ADDR`            | `127.0.0.1:9100` (all but `axiom-api`)             |
+//! | `AXIOM_KEEPER_KEYPAIR`          | required for `axiom-keeper`                        |
+//! | `AXIOM_KEEPER_HORIZON_SECS`     | `600`                                              |
+//! | `AXIOM_KEEPER_MAX_PRIORITY_FEE` | `100000` (micro-lamports per CU)                   |
+//! | `AXIOM_WATCH_AGENTS`            | required for `axiom-watchtower`                    |
+//! | `AXIOM_WATCH_ACTION_TYPES`      | none (any action type)                             |
+//! | `AXIOM_WATCH_SCORE_DROP_BPS`    | `500`                                              |
+//! | `RUST_LOG`                      | `info`                                             |
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::Error;
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub rpc_url: String,
+    pub poll_interval: Duration,
+    pub database_url: Option<String>,
+    pub webhook_urls: Vec<String>,
+    pub webhook_secret: Option<String>,
+    pub api_addr: SocketAddr,
+    pub metrics_addr: SocketAddr,
+    pub keeper_keypair: Option<PathBuf>,
+    pub keeper_horizon: Duration,
+    pub keeper_max_priority_fee: u64,
+    pub watch_agents: Vec<String>,
+    pub watch_action_types: Option<Vec<String>>,
+    pub watch_score_drop_bps: u16,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, Error> {
+        let config = Self {
+            rpc_url: var("AXIOM_RPC_URL").unwrap_or_else(|| "http://127.0.0.1:8899".into()),
+            poll_interval: Duration::from_secs(parse("AXIOM_POLL_SECS")?.unwrap_or(10)),
+            database_url: var("AXIOM_DATABASE_URL"),
+            webhook_urls: var("AXIOM_WEBHOOK_URLS")
+                .map(|v| v.split_whitespace().map(String::from).collect())
+                .unwrap_or_default(),
+            webhook_secret: var("AXIOM_WEBHOOK_SECRET"),
+            api_addr: parse("AXIOM_API_ADDR")?.unwrap_or(([127, 0, 0, 1], 8080).into()),
+            metrics_addr: parse("AXIOM_METRICS_ADDR")?.unwrap_or(([127, 0, 0, 1], 9100).into()),
+            keeper_keypair: var("AXIOM_KEEPER_KEYPAIR").map(PathBuf::from),
+            keeper_horizon: Duration::from_secs(
+                parse("AXIOM_KEEPER_HORIZON_SECS")?.unwrap_or(600),
+            ),
+            keeper_max_priority_fee: parse("AXIOM_KEEPER_MAX_PRIORITY_FEE")?.unwrap_or(100_000),
+            watch_agents: var("AXIOM_WATCH_AGENTS")
+                .map(|v| v.split_whitespace().map(String::from).collect())
+                .unwrap_or_default(),
+            watch_action_types: var("AXIOM_WATCH_ACTION_TYPES")
+                .map(|v| v.split_whitespace().map(String::from).collect()),
+            watch_score_drop_bps: parse("AXIOM_WATCH_SCORE_DROP_BPS")?.unwrap_or(500),
+        };
+        if !config.webhook_urls.is_empty() && config.webhook_secret.is_none() {
+            return Err(Error::Config(
+                "AXIOM_WEBHOOK_SECRET is required when AXIOM_WEBHOOK_URLS is set".into(),
+            ));
+        }
+        Ok(config)
+    }
+}
+
+fn var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+fn parse<T: std::str::FromStr>(name: &str) -> Result<Option<T>, Error> {
+    var(name)
+        .map(|v| v.parse().map_err(|_| Error::Config(format!("{name}: invalid value {v}"))))
+        .transpose()
+}
@@ -0,0 +1,301 @@
+//! Protocol events, derived by diffing successive snapshots of program
+//! accounts.
+//!
+//! Polling account state rather than parsing logs means a missed poll
+//! rarely loses an event: the next snapshot still shows the transition.
+//! It can't show a commitment that was closed in between, nor every step
+//! of one that moved more than once. Each agent's events are numbered,
+//! though, and its profile holds the latest number, so the [`Watcher`]
+//! knows when a diff falls short. It reports such agents as [`Gap`]s,
+//! which [`crate::repair`] fills from the program's logs instead.
+
+use anchor_lang::prelude::Pubkey;
+use axiom::{AgentProfile, CommitmentStatus, ReasoningCommitment};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Something that happened on-chain that subscribers may care about.
+///
+/// `sequence` is the event's number in its agent's sequence; 0 for
+/// agents and commitments that predate sequencing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Event {
+    /// A new reasoning commitment was published.
+    Committed {
+        agent: String,
+        commitment: String,
+        action_type: String,
+        confidence: u8,
+        timestamp: i64,
+        sequence: u64,
+    },
+    /// The agent posted the reasoning URI for a commitment.
+    RevealStarted {
+        agent: String,
+        commitment: String,
+        reasoning_uri: String,
+        timestamp: i64,
+        sequence: u64,
+    },
+    /// A reveal completed and now counts towards the agent's record.
+    RevealFinalized {
+        agent: String,
+        commitment: String,
+        sequence: u64,
+    },
+    /// An unrevealed commitment passed its reveal deadline.
+    Expired {
+        agent: String,
+        commitment: String,
+        sequence: u64,
+    },
+    /// The authority called off an unrevealed commitment.
+    Cancelled {
+        agent: String,
+        commitment: String,
+        sequence: u64,
+    },
+    /// A revealed commitment was closed and its rent reclaimed.
+    Closed {
+        agent: String,
+        commitment: String,
+        sequence: u64,
+    },
+    /// A checkpoint of the agent's record, from the program's `resequence`.
+    Resequenced {
+        agent: String,
+        total_commitments: u64,
+        total_verified: u64,
+        accountability_score: u16,
+        sequence: u64,
+    },
+}
+
+impl Event {
+    /// Short name used in payloads and metrics (`committed`, ...).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::Committed { .. } => "committed",
+            Event::RevealStarted { .. } => "reveal_started",
+            Event::RevealFinalized { .. } => "reveal_finalized",
+            Event::Expired { .. } => "expired",
+            Event::Cancelled { .. } => "cancelled",
+            Event::Closed { .. } => "closed",
+            Event::Resequenced { .. } => "resequenced",
+        }
+    }
+
+    /// Address of the agent the event belongs to.
+    pub fn agent(&self) -> &str {
+        match self {
+            Event::Committed { agent, .. }
+            | Event::RevealStarted { agent, .. }
+            | Event::RevealFinalized { agent, .. }
+            | Event::Expired { agent, .. }
+            | Event::Cancelled { agent, .. }
+            | Event::Closed { agent, .. }
+            | Event::Resequenced { agent, .. } => agent,
+        }
+    }
+
+    pub fn sequence(&self) -> u64 {
+        match self {
+            Event::Committed { sequence, .. }
+            | Event::RevealStarted { sequence, .. }
+            | Event::RevealFinalized { sequence, .. }
+            | Event::Expired { sequence, .. }
+            | Event::Cancelled { sequence, .. }
+            | Event::Closed { sequence, .. }
+            | Event::Resequenced { sequence, .. } => *sequence,
+        }
+    }
+}
+
+/// An agent whose events `after + 1` through `through` a snapshot diff
+/// couldn't account for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Gap {
+    pub agent: Pubkey,
+    /// Last sequence number delivered before the gap
+    pub after: u64,
+    /// The agent's sequence number in the snapshot that found the gap
+    pub through: u64,
+}
+
+impl Gap {
+    pub fn len(&self) -> u64 {
+        self.through.saturating_sub(self.after)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, sequence: u64) -> bool {
+        sequence > self.after && sequence <= self.through
+    }
+}
+
+/// What one snapshot changed: each agent's events in sequence order, and
+/// the agents whose events have to be recovered some other way.
+#[derive(Debug, Default)]
+pub struct Observation {
+    pub events: Vec<Event>,
+    pub gaps: Vec<Gap>,
+}
+
+/// The parts of a commitment the watcher compares between snapshots.
+#[derive(Clone, Copy, Debug)]
+struct Seen {
+    status: CommitmentStatus,
+    expired: bool,
+    cancelled: bool,
+}
+
+impl Seen {
+    fn of(c: &ReasoningCommitment) -> Self {
+        Self {
+            status: c.status,
+            expired: c.expired,
+            cancelled: c.cancelled,
+        }
+    }
+}
+
+/// Tracks the last seen state of every commitment and every agent's
+/// event sequence, and turns changes into [`Event`]s.
+#[derive(Debug, Default)]
+pub struct Watcher {
+    seen: HashMap<Pubkey, Seen>,
+    /// Each agent's event sequence number as of the last snapshot
+    sequences: HashMap<Pubkey, u64>,
+    primed: bool,
+}
+
+impl Watcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one full snapshot of agents and commitments and get back what
+    /// changed since the previous one.
+    ///
+    /// An agent's events are only reported if they are exactly the
+    /// numbers its sequence advanced by, each changed commitment having
+    /// moved once; otherwise the agent is reported as a [`Gap`] instead.
+    /// Agents that predate sequencing get their events unnumbered.
+    ///
+    /// The first snapshot only primes the watcher, so restarting the
+    /// service does not replay the whole history to subscribers.
+    pub fn observe(
+        &mut self,
+        agents: &[(Pubkey, AgentProfile)],
+        commitments: &[(Pubkey, ReasoningCommitment)],
+    ) -> Observation {
+        let mut changed: HashMap<Pubkey, Vec<Vec<Event>>> = HashMap::new();
+        let mut seen = HashMap::with_capacity(commitments.len());
+        for (address, commitment) in commitments {
+            let previous = self.seen.get(address).copied();
+            let mut events = Vec::new();
+            transitions(*address, commitment, previous, &mut events);
+            if !events.is_empty() {
+                changed.entry(commitment.agent).or_default().push(events);
+            }
+            seen.insert(*address, Seen::of(commitment));
+        }
+        // Closed commitments drop out here; their events surface as gaps
+        self.seen = seen;
+
+        let mut observation = Observation::default();
+        for (address, profile) in agents {
+            let through = profile.event_sequence;
+            let after = self.sequences.insert(*address, through).unwrap_or(0);
+            let changes = changed.remove(address).unwrap_or_default();
+            if !self.primed {
+                continue;
+            }
+            if through == 0 {
+                observation.events.extend(changes.into_iter().flatten());
+                continue;
+            }
+            let mut events: Vec<Event> = changes
+                .into_iter()
+                .filter(|events| events.len() == 1)
+                .flatten()
+                .collect();
+            events.sort_by_key(Event::sequence);
+            let complete = events.len() as u64 == through.saturating_sub(after)
+                && events.iter().zip(after + 1..).all(|(e, n)| e.sequence() == n);
+            if complete {
+                observation.events.extend(events);
+            } else {
+                observation.gaps.push(Gap { agent: *address, after, through });
+            }
+        }
+        // Commitments whose agent is missing from the snapshot
+        if self.primed {
+            observation.events.extend(changed.into_values().flatten().flatten());
+        }
+        self.primed = true;
+        observation
+    }
+}
+
+fn transitions(
+    address: Pubkey,
+    c: &ReasoningCommitment,
+    previous: Option<Seen>,
+    events: &mut Vec<Event>,
+) {
+    let agent = c.agent.to_string();
+    let commitment = address.to_string();
+    let sequence = c.sequence;
+    let from = previous.map_or(0, |seen| rank(seen.status));
+    let to = rank(c.status);
+    if previous.is_none() {
+        events.push(Event::Committed {
+            agent: agent.clone(),
+            commitment: commitment.clone(),
+            action_type: c.action_type.clone(),
+            confidence: c.confidence,
+            timestamp: c.timestamp,
+            sequence,
+        });
+    }
+    if from < 2 && to >= 2 {
+        events.push(Event::RevealStarted {
+            agent: agent.clone(),
+            commitment: commitment.clone(),
+            reasoning_uri: c.reasoning_uri.clone(),
+            timestamp: c.revealed_at,
+            sequence,
+        });
+    }
+    if from < 3 && to >= 3 {
+        events.push(Event::RevealFinalized {
+            agent: agent.clone(),
+            commitment: commitment.clone(),
+            sequence,
+        });
+    }
+    if c.expired && !previous.is_some_and(|seen| seen.expired) {
+        events.push(Event::Expired {
+            agent: agent.clone(),
+            commitment: commitment.clone(),
+            sequence,
+        });
+    }
+    if c.cancelled && !previous.is_some_and(|seen| seen.cancelled) {
+        events.push(Event::Cancelled { agent, commitment, sequence });
+    }
+}
+
+/// Position in the lifecycle; 0 means "not seen yet".
+fn rank(status: CommitmentStatus) -> u8 {
+    match status {
+        CommitmentStatus::Committed => 1,
+        CommitmentStatus::Revealing => 2,
+        CommitmentStatus::Revealed => 3,
+    }
+}
@@ -0,0 +1,289 @@
+//! Point-in-time snapshots of the [`Store`] as flat files, for analysis
+//! outside the live database.
+//!
+//! Each export writes one file per table into the output directory:
+//!
+//! | Table         | One row per                                         |
+//! |---------------|-----------------------------------------------------|
+//! | `agents`      | agent, with its operator attestation inlined        |
+//! | `commitments` | commitment                                          |
+//! | `latencies`   | commitment whose reveal has started                 |
+//! | `scores`      | recorded accountability score change                |
+//!
+//! CSV is always available; Parquet needs the `parquet` feature.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use crate::api::MAX_LIMIT;
+use crate::store::{AgentFilter, AgentRecord, CommitmentFilter, CommitmentRecord, Store};
+use crate::Error;
+
+/// Output file format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Csv,
+    Parquet,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Parquet => "parquet",
+        }
+    }
+}
+
+/// One column of a table, typed so Parquet output keeps its schema.
+#[derive(Clone, Debug)]
+pub enum Values {
+    Int(Vec<Option<i64>>),
+    Bool(Vec<bool>),
+    Text(Vec<Option<String>>),
+}
+
+impl Values {
+    fn len(&self) -> usize {
+        match self {
+            Values::Int(v) => v.len(),
+            Values::Bool(v) => v.len(),
+            Values::Text(v) => v.len(),
+        }
+    }
+
+    fn cell(&self, row: usize) -> String {
+        match self {
+            Values::Int(v) => v[row].map(|n| n.to_string()).unwrap_or_default(),
+            Values::Bool(v) => v[row].to_string(),
+            Values::Text(v) => v[row].clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// A named table in column-major form.
+#[derive(Clone, Debug)]
+pub struct Table {
+    pub name: &'static str,
+    pub columns: Vec<(&'static str, Values)>,
+}
+
+impl Table {
+    pub fn rows(&self) -> usize {
+        self.columns.first().map_or(0, |(_, v)| v.len())
+    }
+}
+
+fn ints<T>(rows: &[T], f: impl Fn(&T) -> Option<i64>) -> Values {
+    Values::Int(rows.iter().map(f).collect())
+}
+
+fn bools<T>(rows: &[T], f: impl Fn(&T) -> bool) -> Values {
+    Values::Bool(rows.iter().map(f).collect())
+}
+
+fn texts<T>(rows: &[T], f: impl Fn(&T) -> Option<String>) -> Values {
+    Values::Text(rows.iter().map(f).collect())
+}
+
+pub fn agents_table(agents: &[AgentRecord]) -> Table {
+    Table {
+        name: "agents",
+        columns: vec![
+            ("address", texts(agents, |a| Some(a.address.clone()))),
+            ("authority", texts(agents, |a| Some(a.authority.clone()))),
+            ("name", texts(agents, |a| Some(a.name.clone()))),
+            ("total_commitments", ints(agents, |a| Some(a.total_commitments as i64))),
+            ("total_verified", ints(agents, |a| Some(a.total_verified as i64))),
+            ("accountability_score", ints(agents, |a| Some(a.accountability_score.into()))),
+            ("created_at", ints(agents, |a| Some(a.created_at))),
+            ("verified_operator", bools(agents, |a| a.verified_operator)),
+            ("on_probation", bools(agents, |a| a.on_probation)),
+            ("active", bools(agents, |a| a.active)),
+            (
+                "attestor",
+                texts(agents, |a| a.operator_attestation.as_ref().map(|x| x.attestor.clone())),
+            ),
+            (
+                "attested_at",
+                ints(agents, |a| a.operator_attestation.as_ref().map(|x| x.attested_at)),
+            ),
+        ],
+    }
+}
+
+pub fn commitments_table(commitments: &[CommitmentRecord]) -> Table {
+    Table {
+        name: "commitments",
+        columns: vec![
+            ("address", texts(commitments, |c| Some(c.address.clone()))),
+            ("agent", texts(commitments, |c| Some(c.agent.clone()))),
+            ("authority", texts(commitments, |c| Some(c.authority.clone()))),
+            ("commitment_hash", texts(commitments, |c| Some(c.commitment_hash.clone()))),
+            ("action_type", texts(commitments, |c| Some(c.action_type.clone()))),
+            ("category", texts(commitments, |c| Some(c.category.clone()))),
+            ("confidence", ints(commitments, |c| Some(c.confidence.into()))),
+            ("timestamp", ints(commitments, |c| Some(c.timestamp))),
+            ("status", texts(commitments, |c| Some(c.status.clone()))),
+            ("reasoning_uri", texts(commitments, |c| c.reasoning_uri.clone())),
+            ("revealed_at", ints(commitments, |c| c.revealed_at)),
+            ("trace_len", ints(commitments, |c| Some(c.trace_len as i64))),
+            ("chunk_count", ints(commitments, |c| Some(c.chunk_count.into()))),
+            ("upvotes", ints(commitments, |c| Some(c.upvotes.into()))),
+            ("downvotes", ints(commitments, |c| Some(c.downvotes.into()))),
+        ],
+    }
+}
+
+/// Commit-to-reveal latency of every commitment whose reveal has started.
+pub fn latencies_table(commitments: &[CommitmentRecord]) -> Table {
+    let revealed: Vec<_> = commitments
+        .iter()
+        .filter_map(|c| c.revealed_at.map(|at| (c, at)))
+        .collect();
+    Table {
+        name: "latencies",
+        columns: vec![
+            ("commitment", texts(&revealed, |(c, _)| Some(c.address.clone()))),
+            ("agent", texts(&revealed, |(c, _)| Some(c.agent.clone()))),
+            ("category", texts(&revealed, |(c, _)| Some(c.category.clone()))),
+            ("committed_at", ints(&revealed, |(c, _)| Some(c.timestamp))),
+            ("revealed_at", ints(&revealed, |(_, at)| Some(*at))),
+            ("latency_secs", ints(&revealed, |(c, at)| Some(at - c.timestamp))),
+        ],
+    }
+}
+
+/// Every agent's recorded score changes, agents in address order.
+pub async fn scores_table(store: &Store, agents: &[AgentRecord]) -> Result<Table, Error> {
+    let mut points = Vec::new();
+    for agent in agents {
+        for point in store.score_history(&agent.address).await? {
+            points.push((agent.address.clone(), point));
+        }
+    }
+    Ok(Table {
+        name: "scores",
+        columns: vec![
+            ("agent", texts(&points, |(a, _)| Some(a.clone()))),
+            ("observed_at", ints(&points, |(_, p)| Some(p.observed_at))),
+            ("accountability_score", ints(&points, |(_, p)| Some(p.accountability_score.into()))),
+        ],
+    })
+}
+
+async fn all_agents(store: &Store) -> Result<Vec<AgentRecord>, Error> {
+    let mut all = Vec::new();
+    loop {
+        let page = store.agents(&AgentFilter::default(), all.len(), MAX_LIMIT).await?;
+        let done = page.items.len() < MAX_LIMIT;
+        all.extend(page.items);
+        if done {
+            return Ok(all);
+        }
+    }
+}
+
+async fn all_commitments(store: &Store) -> Result<Vec<CommitmentRecord>, Error> {
+    let mut all = Vec::new();
+    loop {
+        let page = store
+            .commitments(&CommitmentFilter::default(), all.len(), MAX_LIMIT)
+            .await?;
+        let done = page.items.len() < MAX_LIMIT;
+        all.extend(page.items);
+        if done {
+            return Ok(all);
+        }
+    }
+}
+
+/// Snapshot every table into `dir` as `format`, returning the files
+/// written with their row counts.
+pub async fn run(store: &Store, dir: &Path, format: Format) -> Result<Vec<(PathBuf, usize)>, Error> {
+    if format == Format::Parquet && !cfg!(feature = "parquet") {
+        return Err(parquet_disabled());
+    }
+    let agents = all_agents(store).await?;
+    let commitments = all_commitments(store).await?;
+    let tables = [
+        agents_table(&agents),
+        commitments_table(&commitments),
+        latencies_table(&commitments),
+        scores_table(store, &agents).await?,
+    ];
+
+    std::fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    for table in &tables {
+        let path = dir.join(format!("{}.{}", table.name, format.extension()));
+        let file = File::create(&path)?;
+        match format {
+            Format::Csv => write_csv(table, file)?,
+            Format::Parquet => write_parquet(table, file)?,
+        }
+        written.push((path, table.rows()));
+    }
+    Ok(written)
+}
+
+pub fn write_csv(table: &Table, out: impl std::io::Write) -> Result<(), Error> {
+    let export = |e: csv::Error| Error::Export(e.to_string());
+    let mut writer = csv::Writer::from_writer(out);
+    writer
+        .write_record(table.columns.iter().map(|(name, _)| name))
+        .map_err(export)?;
+    for row in 0..table.rows() {
+        writer
+            .write_record(table.columns.iter().map(|(_, values)| values.cell(row)))
+            .map_err(export)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+pub fn write_parquet(table: &Table, out: File) -> Result<(), Error> {
+    use arrow_array::{ArrayRef, BooleanArray, Int64Array, RecordBatch, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+    use parquet::arrow::ArrowWriter;
+    use std::sync::Arc;
+
+    let fields: Vec<Field> = table
+        .columns
+        .iter()
+        .map(|(name, values)| match values {
+            Values::Int(_) => Field::new(*name, DataType::Int64, true),
+            Values::Bool(_) => Field::new(*name, DataType::Boolean, false),
+            Values::Text(_) => Field::new(*name, DataType::Utf8, true),
+        })
+        .collect();
+    let arrays: Vec<ArrayRef> = table
+        .columns
+        .iter()
+        .map(|(_, values)| -> ArrayRef {
+            match values {
+                Values::Int(v) => Arc::new(Int64Array::from(v.clone())),
+                Values::Bool(v) => Arc::new(BooleanArray::from(v.clone())),
+                Values::Text(v) => Arc::new(StringArray::from(v.clone())),
+            }
+        })
+        .collect();
+
+    let export = |e: &dyn std::fmt::Display| Error::Export(e.to_string());
+    let schema = Arc::new(Schema::new(fields));
+    let batch = RecordBatch::try_new(schema.clone(), arrays).map_err(|e| export(&e))?;
+    let mut writer = ArrowWriter::try_new(out, schema, None).map_err(|e| export(&e))?;
+    writer.write(&batch).map_err(|e| export(&e))?;
+    writer.close().map_err(|e| export(&e))?;
+    Ok(())
+}
+
+#[cfg(not(feature = "parquet"))]
+pub fn write_parquet(_table: &Table, _out: File) -> Result<(), Error> {
+    Err(parquet_disabled())
+}
+
+fn parquet_disabled() -> Error {
+    Error::Export("parquet output needs axiom-indexer built with the `parquet` feature".into())
+}
@@ -0,0 +1,297 @@
+//! GraphQL view of the [`Store`], for queries that follow relations
+//! (agent → commitments, agent → attestation → attestor → attested
+//! agents) which the flat REST listings can't express in one request.
+//!
+//! Served at `/graphql` by the [`api`](crate::api) router; `GET` returns
+//! the GraphiQL playground.
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Result, Schema};
+
+use crate::api::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::store::{
+    AgentFilter, AgentRecord, AttestationRecord, AttestorRecord, CommitmentFilter,
+    CommitmentRecord, ScorePoint, Store,
+};
+
+pub type AxiomSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+/// Deepest selection a query may nest, to bound resolver fan-out.
+pub const MAX_DEPTH: usize = 10;
+
+pub fn schema(store: Store) -> AxiomSchema {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(store)
+        .limit_depth(MAX_DEPTH)
+        .finish()
+}
+
+fn store<'a>(ctx: &Context<'a>) -> &'a Store {
+    ctx.data_unchecked::<Store>()
+}
+
+fn limit(limit: Option<usize>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT)
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    async fn agent(&self, ctx: &Context<'_>, address: String) -> Result<Option<Agent>> {
+        Ok(store(ctx).agent(&address).await?.map(Agent))
+    }
+
+    async fn agents(
+        &self,
+        ctx: &Context<'_>,
+        verified_operator: Option<bool>,
+        min_score: Option<u16>,
+        active: Option<bool>,
+        #[graphql(default)] offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<Agent>> {
+        let filter = AgentFilter {
+            verified_operator,
+            min_score,
+            active,
+        };
+        let page = store(ctx).agents(&filter, offset, self::limit(limit)).await?;
+        Ok(page.items.into_iter().map(Agent).collect())
+    }
+
+    async fn commitment(&self, ctx: &Context<'_>, address: String) -> Result<Option<Commitment>> {
+        Ok(store(ctx).commitment(&address).await?.map(Commitment))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn commitments(
+        &self,
+        ctx: &Context<'_>,
+        agent: Option<String>,
+        status: Option<String>,
+        category: Option<String>,
+        action_type: Option<String>,
+        #[graphql(default)] offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<Commitment>> {
+        let filter = CommitmentFilter {
+            agent,
+            status,
+            category,
+            action_type,
+        };
+        commitments(store(ctx), &filter, offset, limit).await
+    }
+
+    async fn attestors(&self, ctx: &Context<'_>) -> Result<Vec<Attestor>> {
+        Ok(store(ctx).attestors().await?.into_iter().map(Attestor).collect())
+    }
+}
+
+async fn commitments(
+    store: &Store,
+    filter: &CommitmentFilter,
+    offset: usize,
+    limit: Option<usize>,
+) -> Result<Vec<Commitment>> {
+    let page = store.commitments(filter, offset, self::limit(limit)).await?;
+    Ok(page.items.into_iter().map(Commitment).collect())
+}
+
+pub struct Agent(AgentRecord);
+
+#[Object]
+impl Agent {
+    async fn address(&self) -> &str {
+        &self.0.address
+    }
+
+    async fn authority(&self) -> &str {
+        &self.0.authority
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn total_commitments(&self) -> u64 {
+        self.0.total_commitments
+    }
+
+    async fn total_verified(&self) -> u64 {
+        self.0.total_verified
+    }
+
+    async fn accountability_score(&self) -> u16 {
+        self.0.accountability_score
+    }
+
+    async fn created_at(&self) -> i64 {
+        self.0.created_at
+    }
+
+    async fn verified_operator(&self) -> bool {
+        self.0.verified_operator
+    }
+
+    async fn on_probation(&self) -> bool {
+        self.0.on_probation
+    }
+
+    async fn active(&self) -> bool {
+        self.0.active
+    }
+
+    async fn operator_attestation(&self) -> Option<Attestation> {
+        self.0.operator_attestation.clone().map(Attestation)
+    }
+
+    async fn commitments(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<String>,
+        #[graphql(default)] offset: usize,
+        limit: Option<usize>,
+    ) -> Result<Vec<Commitment>> {
+        let filter = CommitmentFilter {
+            agent: Some(self.0.address.clone()),
+            status,
+            ..Default::default()
+        };
+        commitments(store(ctx), &filter, offset, limit).await
+    }
+
+    async fn score_history(&self, ctx: &Context<'_>) -> Result<Vec<Score>> {
+        let history = store(ctx).score_history(&self.0.address).await?;
+        Ok(history.into_iter().map(Score).collect())
+    }
+}
+
+pub struct Commitment(CommitmentRecord);
+
+#[Object]
+impl Commitment {
+    async fn address(&self) -> &str {
+        &self.0.address
+    }
+
+    async fn agent(&self, ctx: &Context<'_>) -> Result<Option<Agent>> {
+        Ok(store(ctx).agent(&self.0.agent).await?.map(Agent))
+    }
+
+    async fn commitment_hash(&self) -> &str {
+        &self.0.commitment_hash
+    }
+
+    async fn action_type(&self) -> &str {
+        &self.0.action_type
+    }
+
+    async fn category(&self) -> &str {
+        &self.0.category
+    }
+
+    async fn confidence(&self) -> u8 {
+        self.0.confidence
+    }
+
+    async fn timestamp(&self) -> i64 {
+        self.0.timestamp
+    }
+
+    async fn status(&self) -> &str {
+        &self.0.status
+    }
+
+    async fn reasoning_uri(&self) -> Option<&str> {
+        self.0.reasoning_uri.as_deref()
+    }
+
+    async fn revealed_at(&self) -> Option<i64> {
+        self.0.revealed_at
+    }
+
+    async fn trace_len(&self) -> u64 {
+        self.0.trace_len
+    }
+
+    async fn chunk_count(&self) -> u32 {
+        self.0.chunk_count
+    }
+
+    async fn upvotes(&self) -> u32 {
+        self.0.upvotes
+    }
+
+    async fn downvotes(&self) -> u32 {
+        self.0.downvotes
+    }
+}
+
+pub struct Attestation(AttestationRecord);
+
+#[Object]
+impl Attestation {
+    async fn attestor(&self, ctx: &Context<'_>) -> Result<Option<Attestor>> {
+        Ok(store(ctx).attestor(&self.0.attestor).await?.map(Attestor))
+    }
+
+    async fn reference(&self) -> &str {
+        &self.0.reference
+    }
+
+    async fn attested_at(&self) -> i64 {
+        self.0.attested_at
+    }
+}
+
+pub struct Attestor(AttestorRecord);
+
+#[Object]
+impl Attestor {
+    async fn address(&self) -> &str {
+        &self.0.address
+    }
+
+    async fn attestor(&self) -> &str {
+        &self.0.attestor
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn active(&self) -> bool {
+        self.0.active
+    }
+
+    async fn registered_at(&self) -> i64 {
+        self.0.registered_at
+    }
+
+    async fn revoked_at(&self) -> Option<i64> {
+        self.0.revoked_at
+    }
+
+    async fn attestations_issued(&self) -> u64 {
+        self.0.attestations_issued
+    }
+
+    /// Agents currently holding an attestation from this attestor.
+    async fn attested_agents(&self, ctx: &Context<'_>) -> Result<Vec<Agent>> {
+        Ok(store(ctx).attested_by(&self.0.attestor).await?.into_iter().map(Agent).collect())
+    }
+}
+
+pub struct Score(ScorePoint);
+
+#[Object]
+impl Score {
+    async fn observed_at(&self) -> i64 {
+        self.0.observed_at
+    }
+
+    async fn accountability_score(&self) -> u16 {
+        self.0.accountability_score
+    }
+}
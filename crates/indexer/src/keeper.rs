@@ -0,0 +1,364 @@
+//! Permissionless maintenance cranks and the loop that runs them.
+//!
+//! Several program instructions only take effect when someone calls
+//! them after a deadline. The keeper finds the accounts whose deadline
+//! has passed, sends the crank for each, and reports on the ones coming
+//! due within its horizon so operators can see a backlog building.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use axiom::{
+    AgentProfile, Challenge, CommitmentStatus, EpochSnapshot, ProtocolConfig, ReasoningCommitment,
+};
+
+use crate::metrics::Metrics;
+use crate::rpc::RpcClient;
+use crate::snapshot;
+use crate::tx::Sender;
+use crate::Error;
+
+/// A maintenance instruction the keeper runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Crank {
+    /// `expire_commitment` once an unrevealed commitment's deadline has passed
+    ExpireCommitment,
+    /// `finalize_reveal` once the reveal grace period is over
+    FinalizeReveal,
+    /// `lapse_challenge` once the council's deadline has passed
+    LapseChallenge,
+    /// `expire_reveal` once a reveal's retention has run out
+    ExpireReveal,
+    /// `recompute_score` for scores left stale by a config change
+    RecomputeScore,
+    /// `snapshot_epoch` for agents not yet snapshotted this epoch
+    SnapshotEpoch,
+}
+
+impl Crank {
+    pub const ALL: [Crank; 6] = [
+        Crank::ExpireCommitment,
+        Crank::FinalizeReveal,
+        Crank::LapseChallenge,
+        Crank::ExpireReveal,
+        Crank::RecomputeScore,
+        Crank::SnapshotEpoch,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Crank::ExpireCommitment => "expire_commitment",
+            Crank::FinalizeReveal => "finalize_reveal",
+            Crank::LapseChallenge => "lapse_challenge",
+            Crank::ExpireReveal => "expire_reveal",
+            Crank::RecomputeScore => "recompute_score",
+            Crank::SnapshotEpoch => "snapshot_epoch",
+        }
+    }
+}
+
+/// When a crank becomes callable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Due {
+    /// From this Unix timestamp
+    At(i64),
+    /// From the start of this epoch
+    AtEpoch(u64),
+}
+
+/// One crank for one account.
+#[derive(Clone, Debug)]
+pub struct Task {
+    pub crank: Crank,
+    /// The account the crank acts on
+    pub target: Pubkey,
+    pub due: Due,
+    pub instruction: Instruction,
+}
+
+impl Task {
+    pub fn is_due(&self, now: i64, epoch: u64) -> bool {
+        match self.due {
+            Due::At(at) => now >= at,
+            Due::AtEpoch(at) => epoch >= at,
+        }
+    }
+
+    /// Due now, or within `horizon` seconds (epoch deadlines count from
+    /// the epoch before).
+    pub fn is_approaching(&self, now: i64, epoch: u64, horizon: i64) -> bool {
+        match self.due {
+            Due::At(at) => now.saturating_add(horizon) >= at,
+            Due::AtEpoch(at) => epoch.saturating_add(1) >= at,
+        }
+    }
+}
+
+/// The agent's account at `[seed, agent]`, if `keeps` says its profile
+/// has one.
+fn agent_account(
+    agents: &[(Pubkey, AgentProfile)],
+    agent: &Pubkey,
+    seed: &[u8],
+    keeps: fn(&AgentProfile) -> bool,
+) -> Option<Pubkey> {
+    agents
+        .iter()
+        .any(|(address, profile)| address == agent && keeps(profile))
+        .then(|| Pubkey::find_program_address(&[seed, agent.as_ref()], &axiom::ID).0)
+}
+
+/// Every crank the program state calls for, due or not. `payer` funds
+/// the cranks that create accounts.
+pub fn tasks(
+    config: &ProtocolConfig,
+    agents: &[(Pubkey, AgentProfile)],
+    commitments: &[(Pubkey, ReasoningCommitment)],
+    challenges: &[(Pubkey, Challenge)],
+    snapshots: &[(Pubkey, EpochSnapshot)],
+    epoch: u64,
+    payer: Pubkey,
+) -> Vec<Task> {
+    let (config_address, _) = Pubkey::find_program_address(&[b"config"], &axiom::ID);
+    let mut tasks = Vec::new();
+    for (address, profile) in agents {
+        let mut recomputed = profile.clone();
+        recomputed.recompute_score(config);
+        if recomputed.accountability_score != profile.accountability_score {
+            tasks.push(Task {
+                crank: Crank::RecomputeScore,
+                target: *address,
+                due: Due::At(0),
+                instruction: Instruction {
+                    program_id: axiom::ID,
+                    accounts: axiom::accounts::RecomputeScore {
+                        agent_profile: *address,
+                        config: config_address,
+                    }
+                    .to_account_metas(None),
+                    data: axiom::instruction::RecomputeScore {}.data(),
+                },
+            });
+        }
+        let snapshotted = snapshots
+            .iter()
+            .any(|(_, snapshot)| snapshot.agent == *address && snapshot.epoch == epoch);
+        if !snapshotted {
+            let (snapshot, _) = Pubkey::find_program_address(
+                &[b"epoch_snapshot", address.as_ref(), &epoch.to_le_bytes()],
+                &axiom::ID,
+            );
+            tasks.push(Task {
+                crank: Crank::SnapshotEpoch,
+                target: *address,
+                due: Due::AtEpoch(epoch),
+                instruction: Instruction {
+                    program_id: axiom::ID,
+                    accounts: axiom::accounts::SnapshotEpoch {
+                        snapshot,
+                        agent_profile: *address,
+                        payer,
+                        config: config_address,
+                        system_program: anchor_lang::system_program::ID,
+                    }
+                    .to_account_metas(None),
+                    data: axiom::instruction::SnapshotEpoch {}.data(),
+                },
+            });
+        }
+    }
+    for (address, commitment) in commitments {
+        if let Some(deadline) = commitment.reveal_deadline {
+            if commitment.status == CommitmentStatus::Committed
+                && !commitment.expired
+                && !commitment.cancelled
+            {
+                tasks.push(Task {
+                    crank: Crank::ExpireCommitment,
+                    target: *address,
+                    due: Due::At(deadline.saturating_add(1)),
+                    instruction: Instruction {
+                        program_id: axiom::ID,
+                        accounts: axiom::accounts::ExpireCommitment {
+                            commitment: *address,
+                            agent_profile: commitment.agent,
+                            config: config_address,
+                            metrics: agent_account(
+                                agents,
+                                &commitment.agent,
+                                b"metrics",
+                                |profile| profile.has_metrics,
+                            ),
+                            summary: agent_account(
+                                agents,
+                                &commitment.agent,
+                                b"summary",
+                                |profile| profile.has_summary,
+                            ),
+                            action_stats: commitment.in_action_stats.then(|| {
+                                Pubkey::find_program_address(
+                                    &[
+                                        b"action_stats",
+                                        commitment.agent.as_ref(),
+                                        commitment.action_type.as_bytes(),
+                                    ],
+                                    &axiom::ID,
+                                )
+                                .0
+                            }),
+                        }
+                        .to_account_metas(None),
+                        data: axiom::instruction::ExpireCommitment {}.data(),
+                    },
+                });
+            }
+        }
+        if commitment.status == CommitmentStatus::Revealing {
+            tasks.push(Task {
+                crank: Crank::FinalizeReveal,
+                target: *address,
+                due: Due::At(commitment.revealed_at.saturating_add(config.reveal_grace_period)),
+                instruction: Instruction {
+                    program_id: axiom::ID,
+                    accounts: axiom::accounts::FinalizeReveal {
+                        commitment: *address,
+                        agent_profile: commitment.agent,
+                        config: config_address,
+                        summary: agent_account(
+                            agents,
+                            &commitment.agent,
+                            b"summary",
+                            |profile| profile.has_summary,
+                        ),
+                        // Any fee rebate goes back to the commitment's authority
+                        fee_treasury: (commitment.fee_paid > 0).then(|| {
+                            Pubkey::find_program_address(&[b"fee_treasury"], &axiom::ID).0
+                        }),
+                        authority: (commitment.fee_paid > 0).then_some(commitment.authority),
+                    }
+                    .to_account_metas(None),
+                    data: axiom::instruction::FinalizeReveal {}.data(),
+                },
+            });
+        }
+        if let Some(epoch) = commitment.uri_expiry_epoch {
+            if !commitment.reasoning_uri.is_empty() {
+                tasks.push(Task {
+                    crank: Crank::ExpireReveal,
+                    target: *address,
+                    due: Due::AtEpoch(epoch),
+                    instruction: Instruction {
+                        program_id: axiom::ID,
+                        accounts: axiom::accounts::ExpireReveal {
+                            commitment: *address,
+                            config: config_address,
+                        }
+                        .to_account_metas(None),
+                        data: axiom::instruction::ExpireReveal {}.data(),
+                    },
+                });
+            }
+        }
+    }
+    for (address, challenge) in challenges {
+        tasks.push(Task {
+            crank: Crank::LapseChallenge,
+            target: *address,
+            due: Due::At(challenge.deadline),
+            instruction: Instruction {
+                program_id: axiom::ID,
+                accounts: axiom::accounts::LapseChallenge {
+                    challenge: *address,
+                    commitment: challenge.commitment,
+                    agent: challenge.agent,
+                    challenger: challenge.challenger,
+                    config: config_address,
+                }
+                .to_account_metas(None),
+                data: axiom::instruction::LapseChallenge {}.data(),
+            },
+        });
+    }
+    tasks
+}
+
+/// Finds due cranks and sends them.
+pub struct Keeper {
+    rpc: RpcClient,
+    sender: Sender,
+    metrics: Metrics,
+    horizon: Duration,
+}
+
+impl Keeper {
+    pub fn new(rpc: RpcClient, sender: Sender, metrics: Metrics, horizon: Duration) -> Self {
+        Self { rpc, sender, metrics, horizon }
+    }
+
+    /// Send every due crank once. A failed crank is retried on the next
+    /// tick, when it is still due.
+    pub async fn tick(&self) -> Result<(), Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let Some(config) = snapshot::config(&self.rpc).await? else {
+            tracing::debug!("protocol config not initialized; nothing to crank");
+            return Ok(());
+        };
+        let epoch = self.rpc.epoch().await?;
+        let agents = snapshot::agents(&self.rpc).await?;
+        let commitments = snapshot::commitments(&self.rpc).await?;
+        let challenges = snapshot::challenges(&self.rpc).await?;
+        let snapshots = snapshot::epoch_snapshots(&self.rpc).await?;
+        let tasks = tasks(
+            &config,
+            &agents,
+            &commitments,
+            &challenges,
+            &snapshots,
+            epoch,
+            self.sender.payer(),
+        );
+
+        let horizon = self.horizon.as_secs() as i64;
+        for crank in Crank::ALL {
+            let pending = tasks
+                .iter()
+                .filter(|t| t.crank == crank && t.is_approaching(now, epoch, horizon))
+                .count();
+            self.metrics.set_pending_cranks(crank.name(), pending);
+        }
+
+        for task in tasks.iter().filter(|t| t.is_due(now, epoch)) {
+            let crank = task.crank.name();
+            match self.sender.send(std::slice::from_ref(&task.instruction)).await {
+                Ok(signature) => {
+                    tracing::info!(crank, target = %task.target, %signature, "crank sent");
+                    self.metrics.crank(crank, true);
+                }
+                Err(e) => {
+                    tracing::warn!(crank, target = %task.target, "crank failed: {e}");
+                    self.metrics.crank(crank, false);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Tick every `interval` until the process is interrupted.
+    pub async fn run(self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+            if let Err(e) = self.tick().await {
+                tracing::warn!("keeper tick failed: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Off-chain indexer for the SOLPRISM program.
+//!
+//! Polls program accounts over JSON-RPC, derives protocol [`events`] from
+//! the changes between snapshots, [`repair`]s any the snapshots missed
+//! from transaction logs, and fans them out to subscribers such as
+//! [`webhook`]s. The indexed state is kept in a [`store`] and served over
+//! HTTP by the [`api`], as REST and [`graphql`]. The [`keeper`] reuses
+//! the same RPC plumbing to send the program's maintenance cranks, and
+//! the [`watchtower`] to guard a chosen set of agents.
+
+pub mod api;
+pub mod backfill;
+pub mod config;
+pub mod events;
+pub mod export;
+pub mod graphql;
+pub mod keeper;
+pub mod metrics;
+pub mod repair;
+pub mod rpc;
+pub mod service;
+pub mod snapshot;
+pub mod store;
+pub mod tx;
+pub mod watchtower;
+pub mod webhook;
+
+/// Errors surfaced by the indexer.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("http: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("rpc: {0}")]
+    Rpc(String),
+    #[error("webhook: {0}")]
+    Webhook(String),
+    #[error("export: {0}")]
+    Export(String),
+    #[error("config: {0}")]
+    Config(String),
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("storage: {0}")]
+    Storage(#[from] sqlx::Error),
+    #[error("migration: {0}")]
+    Migrate(#[from] sqlx::migrate::MigrateError),
+}
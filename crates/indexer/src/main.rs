@@ -0,0 +1,118 @@
+//! `axiom-indexer`: watch the SOLPRISM program and notify subscribers.
+//!
+//! With no subcommand it runs the poller. `axiom-indexer backfill` instead
+//! replays the program's transaction history into `AXIOM_DATABASE_URL`
+//! once and exits, so a new database starts with the complete record, and
+//! `axiom-indexer export` snapshots that database to CSV or Parquet files.
+//!
+//! See [`axiom_indexer::config`] for the environment it reads.
+
+use axiom_indexer::backfill;
+use axiom_indexer::config::Config;
+use axiom_indexer::export::{self, Format};
+use axiom_indexer::metrics::{self, Metrics};
+use axiom_indexer::rpc::RpcClient;
+use axiom_indexer::service::Indexer;
+use axiom_indexer::store;
+use axiom_indexer::Error;
+use clap::{Parser, Subcommand};
+use std::future::IntoFuture;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser)]
+#[command(name = "axiom-indexer", about = "Index the SOLPRISM program")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Poll the program and dispatch webhooks (the default)
+    Run,
+    /// Replay historical transactions into the database, then exit
+    Backfill {
+        /// Only replay transactions older than this signature
+        #[arg(long)]
+        before: Option<String>,
+    },
+    /// Write a snapshot of the database to flat files, then exit
+    Export {
+        /// Directory to write one file per table into
+        #[arg(long, default_value = "export")]
+        out: PathBuf,
+        #[arg(long, value_enum, default_value_t = Format::Csv)]
+        format: Format,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .init();
+
+    let cli = Cli::parse();
+    let config = Config::from_env()?;
+    match cli.command.unwrap_or(Command::Run) {
+        Command::Run => run(config).await,
+        Command::Backfill { before } => run_backfill(config, before).await,
+        Command::Export { out, format } => run_export(config, out, format).await,
+    }
+}
+
+async fn run(config: Config) -> Result<(), Error> {
+    let store = store::open(config.database_url.as_deref()).await?;
+    let metrics = Metrics::new();
+    let listener = tokio::net::TcpListener::bind(config.metrics_addr).await?;
+    tokio::spawn(axum::serve(listener, metrics::router(metrics.clone())).into_future());
+
+    tracing::info!(
+        rpc = %config.rpc_url,
+        webhooks = config.webhook_urls.len(),
+        metrics = %config.metrics_addr,
+        "indexer started"
+    );
+    Indexer::new(&config, store, metrics).run(config.poll_interval).await;
+    tracing::info!("indexer stopped");
+    Ok(())
+}
+
+async fn run_backfill(config: Config, before: Option<String>) -> Result<(), Error> {
+    let store = open_database(&config, "backfill").await?;
+    let rpc = RpcClient::new(&config.rpc_url);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+
+    tracing::info!(rpc = %config.rpc_url, "backfill started");
+    let summary = backfill::run(&rpc, &store, before, now).await?;
+    tracing::info!(
+        transactions = summary.transactions,
+        instructions = summary.instructions,
+        agents = summary.agents,
+        commitments = summary.commitments,
+        "backfill finished"
+    );
+    Ok(())
+}
+
+async fn run_export(config: Config, out: PathBuf, format: Format) -> Result<(), Error> {
+    let store = open_database(&config, "export").await?;
+    for (path, rows) in export::run(&store, &out, format).await? {
+        tracing::info!(path = %path.display(), rows, "exported");
+    }
+    Ok(())
+}
+
+/// The configured database; `command` is meaningless against the
+/// in-memory store, which starts empty and is discarded on exit.
+async fn open_database(config: &Config, command: &str) -> Result<store::Store, Error> {
+    let Some(database_url) = config.database_url.as_deref() else {
+        return Err(Error::Config(format!("AXIOM_DATABASE_URL is required for {command}")));
+    };
+    store::open(Some(database_url)).await
+}
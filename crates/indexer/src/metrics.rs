@@ -0,0 +1,192 @@
+//! Prometheus metrics for the indexer services, the keeper and the
+//! watchtower, served at `/metrics`.
+//!
+//! | Metric                                       | Type    | Labels              |
+//! |----------------------------------------------|---------|---------------------|
+//! | `axiom_indexer_polls_total`                  | counter | `result`            |
+//! | `axiom_indexer_events_total`                 | counter | `kind`              |
+//! | `axiom_indexer_webhook_deliveries_total`     | counter | `result`            |
+//! | `axiom_indexer_lag_slots`                    | gauge   |                     |
+//! | `axiom_indexer_indexed_accounts`             | gauge   | `account`           |
+//! | `axiom_indexer_cranks_total`                 | counter | `crank`, `result`   |
+//! | `axiom_indexer_pending_cranks`               | gauge   | `crank`             |
+//! | `axiom_indexer_sequence_gaps_total`          | counter | `result`            |
+//! | `axiom_indexer_alerts_total`                 | counter | `kind`              |
+
+use axum::http::header::CONTENT_TYPE;
+use axum::routing::get;
+use axum::Router;
+use prometheus_client::encoding::{text::encode, EncodeLabelSet};
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::registry::Registry;
+use std::sync::Arc;
+
+const CONTENT_TYPE_OPENMETRICS: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct ResultLabel {
+    pub result: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct KindLabel {
+    pub kind: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct AccountLabel {
+    pub account: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct CrankLabel {
+    pub crank: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct CrankResultLabel {
+    pub crank: &'static str,
+    pub result: &'static str,
+}
+
+/// Handles to every metric; cheap to clone.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Arc<Registry>,
+    polls: Family<ResultLabel, Counter>,
+    events: Family<KindLabel, Counter>,
+    webhook_deliveries: Family<ResultLabel, Counter>,
+    lag_slots: Gauge,
+    indexed_accounts: Family<AccountLabel, Gauge>,
+    cranks: Family<CrankResultLabel, Counter>,
+    pending_cranks: Family<CrankLabel, Gauge>,
+    sequence_gaps: Family<ResultLabel, Counter>,
+    alerts: Family<KindLabel, Counter>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let mut registry = Registry::with_prefix("axiom_indexer");
+        let polls = Family::default();
+        let events = Family::default();
+        let webhook_deliveries = Family::default();
+        let lag_slots = Gauge::default();
+        let indexed_accounts = Family::default();
+        let cranks = Family::default();
+        let pending_cranks = Family::default();
+        let sequence_gaps = Family::default();
+        let alerts = Family::default();
+        registry.register("polls", "Snapshot polls by outcome", polls.clone());
+        registry.register("events", "Protocol events derived, by kind", events.clone());
+        registry.register(
+            "webhook_deliveries",
+            "Webhook deliveries by final outcome",
+            webhook_deliveries.clone(),
+        );
+        registry.register(
+            "lag_slots",
+            "Slots elapsed since the last successful snapshot",
+            lag_slots.clone(),
+        );
+        registry.register(
+            "indexed_accounts",
+            "Accounts in the latest snapshot, by account type",
+            indexed_accounts.clone(),
+        );
+        registry.register("cranks", "Keeper cranks sent, by crank and outcome", cranks.clone());
+        registry.register(
+            "pending_cranks",
+            "Cranks due or coming due within the keeper's horizon",
+            pending_cranks.clone(),
+        );
+        registry.register(
+            "sequence_gaps",
+            "Agent event sequence gaps found, by repair outcome",
+            sequence_gaps.clone(),
+        );
+        registry.register("alerts", "Watchtower policy violations, by kind", alerts.clone());
+        Self {
+            registry: Arc::new(registry),
+            polls,
+            events,
+            webhook_deliveries,
+            lag_slots,
+            indexed_accounts,
+            cranks,
+            pending_cranks,
+            sequence_gaps,
+            alerts,
+        }
+    }
+
+    pub fn poll(&self, ok: bool) {
+        let result = if ok { "ok" } else { "error" };
+        self.polls.get_or_create(&ResultLabel { result }).inc();
+    }
+
+    pub fn event(&self, kind: &'static str) {
+        self.events.get_or_create(&KindLabel { kind }).inc();
+    }
+
+    pub fn webhook_delivery(&self, ok: bool) {
+        let result = if ok { "ok" } else { "failed" };
+        self.webhook_deliveries.get_or_create(&ResultLabel { result }).inc();
+    }
+
+    pub fn set_lag(&self, slots: u64) {
+        self.lag_slots.set(i64::try_from(slots).unwrap_or(i64::MAX));
+    }
+
+    pub fn set_indexed(&self, account: &'static str, count: usize) {
+        self.indexed_accounts
+            .get_or_create(&AccountLabel { account })
+            .set(i64::try_from(count).unwrap_or(i64::MAX));
+    }
+
+    pub fn crank(&self, crank: &'static str, ok: bool) {
+        let result = if ok { "ok" } else { "failed" };
+        self.cranks.get_or_create(&CrankResultLabel { crank, result }).inc();
+    }
+
+    pub fn set_pending_cranks(&self, crank: &'static str, count: usize) {
+        self.pending_cranks
+            .get_or_create(&CrankLabel { crank })
+            .set(i64::try_from(count).unwrap_or(i64::MAX));
+    }
+
+    /// `result` is `repaired`, `partial` (some events were no longer in
+    /// the node's history) or `failed` (retried on the next poll).
+    pub fn sequence_gap(&self, result: &'static str) {
+        self.sequence_gaps.get_or_create(&ResultLabel { result }).inc();
+    }
+
+    pub fn alert(&self, kind: &'static str) {
+        self.alerts.get_or_create(&KindLabel { kind }).inc();
+    }
+
+    /// Text exposition of every metric.
+    pub fn encode(&self) -> String {
+        let mut out = String::new();
+        encode(&mut out, &self.registry).expect("writing to a String cannot fail");
+        out
+    }
+}
+
+/// Router serving `GET /metrics`.
+pub fn router(metrics: Metrics) -> Router {
+    Router::new().route(
+        "/metrics",
+        get(move || {
+            let metrics = metrics.clone();
+            async move { ([(CONTENT_TYPE, CONTENT_TYPE_OPENMETRICS)], metrics.encode()) }
+        }),
+    )
+}
@@ -0,0 +1,157 @@
+//! Recovering events a snapshot diff missed, from transaction logs.
+//!
+//! Every instruction that numbers an agent event writes the agent's
+//! profile, so the profile address's transaction history holds all of
+//! them. [`fill`] walks it newest first, decoding the program's events
+//! from each transaction's logs, until it has every event in the
+//! [`Gap`] or has walked past its start.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::Engine;
+
+use crate::events::{Event, Gap};
+use crate::rpc::RpcClient;
+use crate::Error;
+
+/// Signatures fetched per `getSignaturesForAddress` page. Gaps are
+/// usually recent, so pages are kept small.
+const PAGE_SIZE: usize = 100;
+
+/// The events of `gap` still in the node's transaction history, in
+/// sequence order. Fewer than [`Gap::len`] means the rest are gone; the
+/// agent's next `resequence` checkpoint stands in for them.
+pub async fn fill(rpc: &RpcClient, gap: &Gap) -> Result<Vec<Event>, Error> {
+    let agent = gap.agent.to_string();
+    let mut found = BTreeMap::new();
+    let mut cursor: Option<String> = None;
+    'walk: loop {
+        let page = rpc
+            .signatures_for_address(&gap.agent, cursor.as_deref(), PAGE_SIZE)
+            .await?;
+        let Some(last) = page.last() else { break };
+        cursor = Some(last.signature.clone());
+        for info in page.iter().filter(|s| s.err.is_none()) {
+            let Some(tx) = rpc.transaction(&info.signature).await? else {
+                continue;
+            };
+            let logs = tx.meta.and_then(|m| m.log_messages).unwrap_or_default();
+            let mut passed_start = false;
+            for event in program_events(&logs, &axiom::ID.to_string()) {
+                if event.agent() != agent {
+                    continue;
+                }
+                let sequence = event.sequence();
+                if gap.contains(sequence) {
+                    found.insert(sequence, event);
+                } else if sequence != 0 && sequence <= gap.after {
+                    passed_start = true;
+                }
+            }
+            if passed_start || found.len() as u64 == gap.len() {
+                break 'walk;
+            }
+        }
+    }
+    Ok(found.into_values().collect())
+}
+
+/// Numbered events `program` emitted in a transaction's log messages,
+/// skipping any logged by other programs it invoked.
+pub fn program_events(logs: &[String], program: &str) -> Vec<Event> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for line in logs {
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        if let Some(data) = rest.strip_prefix("data: ") {
+            if stack.last() == Some(&program) {
+                if let Some(event) = engine.decode(data).ok().and_then(|d| decode(&d)) {
+                    events.push(event);
+                }
+            }
+            continue;
+        }
+        let mut words = rest.split(' ');
+        match (words.next(), words.next()) {
+            (Some(id), Some("invoke")) => stack.push(id),
+            (Some(_), Some("success" | "failed:")) => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+/// Decode one of the program's numbered events from its
+/// discriminator-prefixed bytes.
+pub fn decode(data: &[u8]) -> Option<Event> {
+    if let Some(e) = parse::<axiom::ReasoningCommitted>(data) {
+        return Some(Event::Committed {
+            agent: e.agent.to_string(),
+            commitment: e.commitment.to_string(),
+            action_type: e.action_type,
+            confidence: e.confidence,
+            timestamp: e.timestamp,
+            sequence: e.sequence,
+        });
+    }
+    if let Some(e) = parse::<axiom::ReasoningRevealed>(data) {
+        return Some(Event::RevealStarted {
+            agent: e.agent.to_string(),
+            commitment: e.commitment.to_string(),
+            reasoning_uri: e.reasoning_uri,
+            timestamp: e.timestamp,
+            sequence: e.sequence,
+        });
+    }
+    if let Some(e) = parse::<axiom::RevealFinalized>(data) {
+        return Some(Event::RevealFinalized {
+            agent: e.agent.to_string(),
+            commitment: e.commitment.to_string(),
+            sequence: e.sequence,
+        });
+    }
+    if let Some(e) = parse::<axiom::CommitmentExpired>(data) {
+        return Some(Event::Expired {
+            agent: e.agent.to_string(),
+            commitment: e.commitment.to_string(),
+            sequence: e.sequence,
+        });
+    }
+    if let Some(e) = parse::<axiom::CommitmentCancelled>(data) {
+        return Some(Event::Cancelled {
+            agent: e.agent.to_string(),
+            commitment: e.commitment.to_string(),
+            sequence: e.sequence,
+        });
+    }
+    if let Some(e) = parse::<axiom::CommitmentClosed>(data) {
+        return Some(Event::Closed {
+            agent: e.agent.to_string(),
+            commitment: e.commitment.to_string(),
+            sequence: e.sequence,
+        });
+    }
+    if let Some(e) = parse::<axiom::AgentResequenced>(data) {
+        return Some(Event::Resequenced {
+            agent: e.agent.to_string(),
+            total_commitments: e.total_commitments,
+            total_verified: e.total_verified,
+            accountability_score: e.accountability_score,
+            sequence: e.sequence,
+        });
+    }
+    None
+}
+
+/// `T` if `data` starts with its discriminator and the rest decodes.
+/// Events logged before sequencing are too short and don't.
+fn parse<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    let mut body = data.strip_prefix(T::DISCRIMINATOR)?;
+    T::deserialize(&mut body).ok()
+}
@@ -0,0 +1,348 @@
+//! Minimal JSON-RPC client for the handful of calls the indexer and the
+//! keeper need.
+//!
+//! Kept hand-rolled rather than pulling in `solana-client`: the indexer
+//! only reads program accounts and transaction history, the keeper only
+//! adds sending, and the full client roughly triples the build.
+
+use anchor_lang::prelude::Pubkey;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+use crate::Error;
+
+/// A program-owned account as returned by `getProgramAccounts`.
+#[derive(Clone, Debug)]
+pub struct RawAccount {
+    pub address: Pubkey,
+    pub data: Vec<u8>,
+}
+
+/// One entry of `getSignaturesForAddress`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub err: Option<Value>,
+    pub block_time: Option<i64>,
+}
+
+/// A transaction as returned by `getTransaction` with `json` encoding.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmedTransaction {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub meta: Option<TransactionMeta>,
+    pub transaction: EncodedTransaction,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionMeta {
+    pub err: Option<Value>,
+    #[serde(default)]
+    pub inner_instructions: Option<Vec<InnerInstructions>>,
+    #[serde(default)]
+    pub loaded_addresses: Option<LoadedAddresses>,
+    #[serde(default)]
+    pub log_messages: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct InnerInstructions {
+    pub index: usize,
+    pub instructions: Vec<CompiledInstruction>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct LoadedAddresses {
+    pub writable: Vec<String>,
+    pub readonly: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct EncodedTransaction {
+    pub message: Message,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+    pub account_keys: Vec<String>,
+    pub instructions: Vec<CompiledInstruction>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompiledInstruction {
+    pub program_id_index: usize,
+    pub accounts: Vec<usize>,
+    /// Base58-encoded instruction data
+    pub data: String,
+}
+
+/// Status of a sent transaction, from `getSignatureStatuses`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureStatus {
+    pub err: Option<Value>,
+    pub confirmation_status: Option<String>,
+}
+
+impl SignatureStatus {
+    /// Whether the transaction reached at least `confirmed`.
+    pub fn is_confirmed(&self) -> bool {
+        matches!(self.confirmation_status.as_deref(), Some("confirmed" | "finalized"))
+    }
+}
+
+/// JSON-RPC client for a single Solana endpoint.
+#[derive(Clone, Debug)]
+pub struct RpcClient {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into(),
+        }
+    }
+
+    /// All accounts owned by `program` whose data starts with `discriminator`.
+    pub async fn program_accounts(
+        &self,
+        program: &Pubkey,
+        discriminator: &[u8],
+    ) -> Result<Vec<RawAccount>, Error> {
+        #[derive(Deserialize)]
+        struct Keyed {
+            pubkey: String,
+            account: Encoded,
+        }
+        #[derive(Deserialize)]
+        struct Encoded {
+            data: (String, String),
+        }
+
+        let engine = base64::engine::general_purpose::STANDARD;
+        let params = json!([
+            program.to_string(),
+            {
+                "encoding": "base64",
+                "commitment": "confirmed",
+                "filters": [{
+                    "memcmp": {
+                        "offset": 0,
+                        "bytes": engine.encode(discriminator),
+                        "encoding": "base64",
+                    }
+                }],
+            }
+        ]);
+        let keyed: Vec<Keyed> = self.call("getProgramAccounts", params).await?;
+        keyed
+            .into_iter()
+            .map(|k| {
+                Ok(RawAccount {
+                    address: Pubkey::from_str(&k.pubkey)
+                        .map_err(|_| Error::Rpc(format!("bad pubkey {}", k.pubkey)))?,
+                    data: engine
+                        .decode(&k.account.data.0)
+                        .map_err(|e| Error::Rpc(e.to_string()))?,
+                })
+            })
+            .collect()
+    }
+
+    /// Data of the account at `address`, if it exists.
+    pub async fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, Error> {
+        #[derive(Deserialize)]
+        struct Response {
+            value: Option<Encoded>,
+        }
+        #[derive(Deserialize)]
+        struct Encoded {
+            data: (String, String),
+        }
+
+        let params = json!([
+            address.to_string(),
+            { "encoding": "base64", "commitment": "confirmed" }
+        ]);
+        let response: Response = self.call("getAccountInfo", params).await?;
+        response
+            .value
+            .map(|account| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(&account.data.0)
+                    .map_err(|e| Error::Rpc(e.to_string()))
+            })
+            .transpose()
+    }
+
+    /// The current epoch at `confirmed` commitment.
+    pub async fn epoch(&self) -> Result<u64, Error> {
+        #[derive(Deserialize)]
+        struct EpochInfo {
+            epoch: u64,
+        }
+
+        let info: EpochInfo = self
+            .call("getEpochInfo", json!([{ "commitment": "confirmed" }]))
+            .await?;
+        Ok(info.epoch)
+    }
+
+    /// A recent blockhash and the last block height at which
+    /// transactions using it can land.
+    pub async fn latest_blockhash(&self) -> Result<([u8; 32], u64), Error> {
+        #[derive(Deserialize)]
+        struct Response {
+            value: Latest,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Latest {
+            blockhash: String,
+            last_valid_block_height: u64,
+        }
+
+        let response: Response = self
+            .call("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))
+            .await?;
+        let blockhash = bs58::decode(&response.value.blockhash)
+            .into_vec()
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or_else(|| Error::Rpc(format!("bad blockhash {}", response.value.blockhash)))?;
+        Ok((blockhash, response.value.last_valid_block_height))
+    }
+
+    /// The current block height at `confirmed` commitment.
+    pub async fn block_height(&self) -> Result<u64, Error> {
+        self.call("getBlockHeight", json!([{ "commitment": "confirmed" }]))
+            .await
+    }
+
+    /// Priority fees (micro-lamports per compute unit) paid in recent
+    /// slots by transactions writing any of `writable`.
+    pub async fn recent_prioritization_fees(&self, writable: &[Pubkey]) -> Result<Vec<u64>, Error> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Fee {
+            prioritization_fee: u64,
+        }
+
+        let accounts: Vec<String> = writable.iter().map(Pubkey::to_string).collect();
+        let fees: Vec<Fee> = self
+            .call("getRecentPrioritizationFees", json!([accounts]))
+            .await?;
+        Ok(fees.into_iter().map(|f| f.prioritization_fee).collect())
+    }
+
+    /// Submit a signed wire-format transaction, returning its signature.
+    /// Preflight runs, so a transaction that would fail is rejected here.
+    pub async fn send_transaction(&self, transaction: &[u8]) -> Result<String, Error> {
+        let params = json!([
+            base64::engine::general_purpose::STANDARD.encode(transaction),
+            {
+                "encoding": "base64",
+                "preflightCommitment": "confirmed",
+                "maxRetries": 0,
+            }
+        ]);
+        self.call("sendTransaction", params).await
+    }
+
+    /// Status of `signature`, once the node has seen it land.
+    pub async fn signature_status(&self, signature: &str) -> Result<Option<SignatureStatus>, Error> {
+        #[derive(Deserialize)]
+        struct Response {
+            value: Vec<Option<SignatureStatus>>,
+        }
+
+        let response: Response = self
+            .call("getSignatureStatuses", json!([[signature]]))
+            .await?;
+        Ok(response.value.into_iter().next().flatten())
+    }
+
+    /// The current slot at `confirmed` commitment.
+    pub async fn slot(&self) -> Result<u64, Error> {
+        self.call("getSlot", json!([{ "commitment": "confirmed" }]))
+            .await
+    }
+
+    /// Up to `limit` (at most 1000) signatures involving `address`,
+    /// newest first, starting before `before` when given.
+    pub async fn signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SignatureInfo>, Error> {
+        let mut options = json!({ "limit": limit, "commitment": "confirmed" });
+        if let Some(before) = before {
+            options["before"] = json!(before);
+        }
+        self.call("getSignaturesForAddress", json!([address.to_string(), options]))
+            .await
+    }
+
+    /// The confirmed transaction with `signature`, if the node still has it.
+    pub async fn transaction(&self, signature: &str) -> Result<Option<ConfirmedTransaction>, Error> {
+        let params = json!([
+            signature,
+            {
+                "encoding": "json",
+                "commitment": "confirmed",
+                "maxSupportedTransactionVersion": 0,
+            }
+        ]);
+        self.call_nullable("getTransaction", params).await
+    }
+
+    async fn call<T: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<T, Error> {
+        self.call_nullable(method, params)
+            .await?
+            .ok_or_else(|| Error::Rpc(format!("{method}: empty response")))
+    }
+
+    /// Like `call`, for methods that answer `null` when nothing matches.
+    async fn call_nullable<T: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<Option<T>, Error> {
+        #[derive(Deserialize)]
+        struct Response<T> {
+            result: Option<T>,
+            error: Option<Value>,
+        }
+
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Response<T> = self
+            .http
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        match response.error {
+            Some(error) => Err(Error::Rpc(format!("{method}: {error}"))),
+            None => Ok(response.result),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! The polling loop shared by the indexer binaries.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+use crate::events::{Event, Gap, Watcher};
+use crate::metrics::Metrics;
+use crate::repair;
+use crate::rpc::RpcClient;
+use crate::snapshot;
+use crate::store::{AgentRecord, AttestorRecord, CommitmentRecord, Store};
+use crate::webhook::{Dispatcher, RetryPolicy};
+use crate::Error;
+
+/// Keeps a [`Store`] in sync with the chain and notifies subscribers.
+pub struct Indexer {
+    rpc: RpcClient,
+    store: Store,
+    watcher: Watcher,
+    dispatcher: Option<Dispatcher>,
+    metrics: Metrics,
+    /// Slot of the last successful snapshot
+    indexed_slot: Option<u64>,
+    /// Sequence gaps whose repair failed, retried on the next poll
+    gaps: Vec<Gap>,
+}
+
+impl Indexer {
+    pub fn new(config: &Config, store: Store, metrics: Metrics) -> Self {
+        let dispatcher = config.webhook_secret.as_ref().map(|secret| {
+            Dispatcher::new(
+                config.webhook_urls.clone(),
+                secret.as_bytes(),
+                RetryPolicy::default(),
+                metrics.clone(),
+            )
+        });
+        Self {
+            rpc: RpcClient::new(&config.rpc_url),
+            store,
+            watcher: Watcher::new(),
+            dispatcher,
+            metrics,
+            indexed_slot: None,
+            gaps: Vec::new(),
+        }
+    }
+
+    /// Fetch one snapshot, update the store and dispatch what changed.
+    pub async fn poll(&mut self) -> Result<(), Error> {
+        let result = self.snapshot().await;
+        self.metrics.poll(result.is_ok());
+        result
+    }
+
+    async fn snapshot(&mut self) -> Result<(), Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let slot = self.rpc.slot().await?;
+        if let Some(indexed) = self.indexed_slot {
+            self.metrics.set_lag(slot.saturating_sub(indexed));
+        }
+        let agents = snapshot::agents(&self.rpc).await?;
+        let commitments = snapshot::commitments(&self.rpc).await?;
+        let attestors = snapshot::attestors(&self.rpc).await?;
+        let agent_records: Vec<_> = agents.iter().map(|(a, p)| AgentRecord::new(*a, p)).collect();
+        let commitment_records: Vec<_> =
+            commitments.iter().map(|(a, c)| CommitmentRecord::new(*a, c)).collect();
+        let attestor_records: Vec<_> =
+            attestors.iter().map(|(a, r)| AttestorRecord::new(*a, r)).collect();
+        self.store.put_agents(&agent_records, now).await?;
+        self.store.put_attestors(&attestor_records).await?;
+        self.store.put_commitments(&commitment_records).await?;
+        self.metrics.set_indexed("agent", agents.len());
+        self.metrics.set_indexed("commitment", commitments.len());
+        self.metrics.set_indexed("attestor", attestors.len());
+
+        let observation = self.watcher.observe(&agents, &commitments);
+        for event in observation.events {
+            self.publish(event);
+        }
+        self.gaps.extend(observation.gaps);
+        self.repair().await;
+        self.indexed_slot = Some(slot);
+        self.metrics.set_lag(0);
+        Ok(())
+    }
+
+    /// Fill sequence gaps from transaction history. A gap the RPC node
+    /// fails on is kept for the next poll; its events then arrive after
+    /// later ones, but still carry their sequence numbers.
+    async fn repair(&mut self) {
+        for gap in std::mem::take(&mut self.gaps) {
+            match repair::fill(&self.rpc, &gap).await {
+                Ok(events) => {
+                    let missing = gap.len().saturating_sub(events.len() as u64);
+                    if missing == 0 {
+                        self.metrics.sequence_gap("repaired");
+                    } else {
+                        tracing::warn!(
+                            agent = %gap.agent,
+                            after = gap.after,
+                            through = gap.through,
+                            missing,
+                            "events no longer in transaction history"
+                        );
+                        self.metrics.sequence_gap("partial");
+                    }
+                    for event in events {
+                        self.publish(event);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(agent = %gap.agent, "sequence gap repair failed: {e}");
+                    self.metrics.sequence_gap("failed");
+                    self.gaps.push(gap);
+                }
+            }
+        }
+    }
+
+    fn publish(&self, event: Event) {
+        tracing::info!(kind = event.kind(), sequence = event.sequence(), "event");
+        self.metrics.event(event.kind());
+        if let Some(dispatcher) = &self.dispatcher {
+            dispatcher.dispatch(event);
+        }
+    }
+
+    /// Poll every `interval` until the process is interrupted.
+    pub async fn run(mut self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+            if let Err(e) = self.poll().await {
+                tracing::warn!("poll failed: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Decoding program accounts fetched over RPC.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use axiom::{
+    AgentProfile, AttestorRecord, Challenge, EpochSnapshot, ProtocolConfig, ReasoningCommitment,
+};
+
+use crate::rpc::{RawAccount, RpcClient};
+use crate::Error;
+
+/// Every reasoning commitment currently on-chain.
+pub async fn commitments(rpc: &RpcClient) -> Result<Vec<(Pubkey, ReasoningCommitment)>, Error> {
+    fetch(rpc).await
+}
+
+/// Every agent profile currently on-chain.
+pub async fn agents(rpc: &RpcClient) -> Result<Vec<(Pubkey, AgentProfile)>, Error> {
+    fetch(rpc).await
+}
+
+/// Every registered attestor, active or revoked.
+pub async fn attestors(rpc: &RpcClient) -> Result<Vec<(Pubkey, AttestorRecord)>, Error> {
+    fetch(rpc).await
+}
+
+/// Every open challenge.
+pub async fn challenges(rpc: &RpcClient) -> Result<Vec<(Pubkey, Challenge)>, Error> {
+    fetch(rpc).await
+}
+
+/// Every epoch snapshot taken so far.
+pub async fn epoch_snapshots(rpc: &RpcClient) -> Result<Vec<(Pubkey, EpochSnapshot)>, Error> {
+    fetch(rpc).await
+}
+
+/// The protocol config, once initialized.
+pub async fn config(rpc: &RpcClient) -> Result<Option<ProtocolConfig>, Error> {
+    let (address, _) = Pubkey::find_program_address(&[b"config"], &axiom::ID);
+    let Some(data) = rpc.account_data(&address).await? else {
+        return Ok(None);
+    };
+    ProtocolConfig::try_deserialize(&mut data.as_slice())
+        .map(Some)
+        .map_err(|e| Error::Rpc(format!("config: {e}")))
+}
+
+async fn fetch<T: AccountDeserialize + Discriminator>(
+    rpc: &RpcClient,
+) -> Result<Vec<(Pubkey, T)>, Error> {
+    let raw = rpc.program_accounts(&axiom::ID, T::DISCRIMINATOR).await?;
+    Ok(decode(raw))
+}
+
+/// Decode `raw` accounts, skipping (and logging) any written by an older
+/// layout of the program.
+pub fn decode<T: AccountDeserialize>(raw: Vec<RawAccount>) -> Vec<(Pubkey, T)> {
+    raw.into_iter()
+        .filter_map(|account| match T::try_deserialize(&mut account.data.as_slice()) {
+            Ok(value) => Some((account.address, value)),
+            Err(e) => {
+                tracing::debug!(address = %account.address, "skipping undecodable account: {e}");
+                None
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,399 @@
+//! Indexed view of program state, shaped for API consumers, and the
+//! [`Storage`] backends that persist it.
+//!
+//! Records carry addresses as base58 strings and enums as lowercase names
+//! so they serialize straight into JSON responses and map onto plain SQL
+//! columns.
+
+use anchor_lang::prelude::Pubkey;
+use axiom::{
+    AgentProfile, AttestorRecord as AttestorAccount, CommitmentCategory, CommitmentStatus,
+    OperatorAttestation, ReasoningCommitment,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::Error;
+
+mod memory;
+mod sql;
+
+pub use memory::MemoryStorage;
+pub use sql::SqlStorage;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AgentRecord {
+    pub address: String,
+    pub authority: String,
+    pub name: String,
+    pub total_commitments: u64,
+    pub total_verified: u64,
+    pub accountability_score: u16,
+    pub created_at: i64,
+    pub verified_operator: bool,
+    pub on_probation: bool,
+    pub operator_attestation: Option<AttestationRecord>,
+    /// False once the authority retired the agent
+    pub active: bool,
+}
+
+impl AgentRecord {
+    pub fn new(address: Pubkey, profile: &AgentProfile) -> Self {
+        Self {
+            address: address.to_string(),
+            authority: profile.authority.to_string(),
+            name: profile.name.clone(),
+            total_commitments: profile.total_commitments,
+            total_verified: profile.total_verified,
+            accountability_score: profile.accountability_score,
+            created_at: profile.created_at,
+            verified_operator: profile.verified_operator,
+            on_probation: profile.probation.is_some(),
+            operator_attestation: profile.operator_attestation.map(AttestationRecord::new),
+            active: profile.is_active(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AttestationRecord {
+    pub attestor: String,
+    pub reference: String,
+    pub attested_at: i64,
+}
+
+impl AttestationRecord {
+    pub fn new(attestation: OperatorAttestation) -> Self {
+        Self {
+            attestor: attestation.attestor.to_string(),
+            reference: hex::encode(attestation.reference),
+            attested_at: attestation.attested_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AttestorRecord {
+    pub address: String,
+    pub attestor: String,
+    pub name: String,
+    pub active: bool,
+    pub registered_at: i64,
+    pub revoked_at: Option<i64>,
+    pub attestations_issued: u64,
+}
+
+impl AttestorRecord {
+    pub fn new(address: Pubkey, record: &AttestorAccount) -> Self {
+        Self {
+            address: address.to_string(),
+            attestor: record.attestor.to_string(),
+            name: record.name.clone(),
+            active: record.active,
+            registered_at: record.registered_at,
+            revoked_at: record.revoked_at,
+            attestations_issued: record.attestations_issued,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CommitmentRecord {
+    pub address: String,
+    pub agent: String,
+    pub authority: String,
+    pub commitment_hash: String,
+    pub action_type: String,
+    pub category: String,
+    pub confidence: u8,
+    pub timestamp: i64,
+    pub status: String,
+    pub reasoning_uri: Option<String>,
+    pub revealed_at: Option<i64>,
+    pub trace_len: u64,
+    pub chunk_count: u32,
+    pub upvotes: u32,
+    pub downvotes: u32,
+}
+
+impl CommitmentRecord {
+    pub fn new(address: Pubkey, c: &ReasoningCommitment) -> Self {
+        let revealed = c.status != CommitmentStatus::Committed;
+        Self {
+            address: address.to_string(),
+            agent: c.agent.to_string(),
+            authority: c.authority.to_string(),
+            commitment_hash: hex::encode(c.commitment_hash),
+            action_type: c.action_type.clone(),
+            category: category_name(c.category).to_string(),
+            confidence: c.confidence,
+            timestamp: c.timestamp,
+            status: commitment_status(c).to_string(),
+            reasoning_uri: revealed.then(|| c.reasoning_uri.clone()),
+            revealed_at: revealed.then_some(c.revealed_at),
+            trace_len: c.trace_len,
+            chunk_count: c.chunk_count,
+            upvotes: c.upvotes,
+            downvotes: c.downvotes,
+        }
+    }
+}
+
+/// An accountability score as first observed by the indexer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct ScorePoint {
+    pub observed_at: i64,
+    pub accountability_score: u16,
+}
+
+/// Like [`status_name`], but reports commitments voided before their
+/// reveal as `cancelled` or `expired`.
+pub fn commitment_status(c: &ReasoningCommitment) -> &'static str {
+    if c.cancelled {
+        "cancelled"
+    } else if c.expired {
+        "expired"
+    } else {
+        status_name(c.status)
+    }
+}
+
+pub fn status_name(status: CommitmentStatus) -> &'static str {
+    match status {
+        CommitmentStatus::Committed => "committed",
+        CommitmentStatus::Revealing => "revealing",
+        CommitmentStatus::Revealed => "revealed",
+    }
+}
+
+pub fn category_name(category: CommitmentCategory) -> &'static str {
+    match category {
+        CommitmentCategory::Financial => "financial",
+        CommitmentCategory::Governance => "governance",
+        CommitmentCategory::ContentModeration => "content_moderation",
+        CommitmentCategory::Research => "research",
+        CommitmentCategory::Infrastructure => "infrastructure",
+        CommitmentCategory::Other => "other",
+    }
+}
+
+/// Which agents a query selects.
+#[derive(Clone, Debug, Default)]
+pub struct AgentFilter {
+    pub verified_operator: Option<bool>,
+    pub min_score: Option<u16>,
+    pub active: Option<bool>,
+}
+
+impl AgentFilter {
+    pub fn matches(&self, a: &AgentRecord) -> bool {
+        self.verified_operator.is_none_or(|v| v == a.verified_operator)
+            && self.min_score.is_none_or(|m| a.accountability_score >= m)
+            && self.active.is_none_or(|v| v == a.active)
+    }
+}
+
+/// Which commitments a query selects.
+#[derive(Clone, Debug, Default)]
+pub struct CommitmentFilter {
+    pub agent: Option<String>,
+    pub status: Option<String>,
+    pub category: Option<String>,
+    pub action_type: Option<String>,
+}
+
+impl CommitmentFilter {
+    pub fn matches(&self, c: &CommitmentRecord) -> bool {
+        self.agent.as_ref().is_none_or(|a| *a == c.agent)
+            && self.status.as_ref().is_none_or(|s| *s == c.status)
+            && self.category.as_ref().is_none_or(|k| *k == c.category)
+            && self.action_type.as_ref().is_none_or(|t| *t == c.action_type)
+    }
+}
+
+/// One page of a listing.
+#[derive(Clone, Debug, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl<T: Clone> Page<T> {
+    pub(crate) fn of<'a>(matching: impl Iterator<Item = &'a T>, offset: usize, limit: usize) -> Self
+    where
+        T: 'a,
+    {
+        let matching: Vec<&T> = matching.collect();
+        Self {
+            total: matching.len(),
+            items: matching.into_iter().skip(offset).take(limit).cloned().collect(),
+            offset,
+            limit,
+        }
+    }
+}
+
+/// Where the indexer keeps what it has seen.
+///
+/// Writers upsert whole snapshots; readers never see a partially applied
+/// record, though a listing may mix two consecutive snapshots.
+#[async_trait::async_trait]
+pub trait Storage: Send + Sync {
+    /// Upsert agents, appending to score history where the score changed
+    /// since it was last observed (at `observed_at`).
+    async fn put_agents(&self, agents: &[AgentRecord], observed_at: i64) -> Result<(), Error>;
+    async fn put_commitments(&self, commitments: &[CommitmentRecord]) -> Result<(), Error>;
+    async fn put_attestors(&self, attestors: &[AttestorRecord]) -> Result<(), Error>;
+
+    async fn agent(&self, address: &str) -> Result<Option<AgentRecord>, Error>;
+    async fn agents(
+        &self,
+        filter: &AgentFilter,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Page<AgentRecord>, Error>;
+    async fn commitment(&self, address: &str) -> Result<Option<CommitmentRecord>, Error>;
+    async fn commitments(
+        &self,
+        filter: &CommitmentFilter,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Page<CommitmentRecord>, Error>;
+    /// The attestor that signs with `attestor`.
+    async fn attestor(&self, attestor: &str) -> Result<Option<AttestorRecord>, Error>;
+    async fn attestors(&self) -> Result<Vec<AttestorRecord>, Error>;
+    /// Agents whose current operator attestation was issued by `attestor`.
+    async fn attested_by(&self, attestor: &str) -> Result<Vec<AgentRecord>, Error>;
+    /// Score changes for `agent`, oldest first.
+    async fn score_history(&self, agent: &str) -> Result<Vec<ScorePoint>, Error>;
+}
+
+/// Shared handle to whichever backend is configured.
+pub type Store = Arc<dyn Storage>;
+
+/// Open the backend named by `database_url`: in-memory when `None`,
+/// otherwise `sqlite:` or `postgres:` with migrations applied.
+pub async fn open(database_url: Option<&str>) -> Result<Store, Error> {
+    Ok(match database_url {
+        None => Arc::new(MemoryStorage::new()),
+        Some(url) => Arc::new(SqlStorage::connect(url).await?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountDeserialize, Space};
+
+    fn agent(address: &str, score: u16, active: bool) -> AgentRecord {
+        AgentRecord {
+            address: address.to_string(),
+            authority: "authority".to_string(),
+            name: address.to_string(),
+            total_commitments: 0,
+            total_verified: 0,
+            accountability_score: score,
+            created_at: 0,
+            verified_operator: false,
+            on_probation: false,
+            operator_attestation: None,
+            active,
+        }
+    }
+
+    fn commitment(address: &str, agent: &str, status: &str) -> CommitmentRecord {
+        CommitmentRecord {
+            address: address.to_string(),
+            agent: agent.to_string(),
+            authority: "authority".to_string(),
+            commitment_hash: String::new(),
+            action_type: "trade".to_string(),
+            category: "financial".to_string(),
+            confidence: 80,
+            timestamp: 0,
+            status: status.to_string(),
+            reasoning_uri: None,
+            revealed_at: None,
+            trace_len: 0,
+            chunk_count: 0,
+            upvotes: 0,
+            downvotes: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn score_history_records_only_changes() {
+        let store = open(None).await.unwrap();
+        for (observed_at, score) in [(10, 10000), (20, 10000), (30, 9000), (40, 9000), (50, 9500)] {
+            store.put_agents(&[agent("a", score, true)], observed_at).await.unwrap();
+        }
+        let history: Vec<(i64, u16)> = store
+            .score_history("a")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| (p.observed_at, p.accountability_score))
+            .collect();
+        assert_eq!(history, [(10, 10000), (30, 9000), (50, 9500)]);
+        assert_eq!(store.agent("a").await.unwrap().unwrap().accountability_score, 9500);
+        assert!(store.score_history("b").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn listings_filter_then_page() {
+        let store = open(None).await.unwrap();
+        store
+            .put_agents(
+                &[agent("a", 9000, true), agent("b", 4000, true), agent("c", 9500, false)],
+                0,
+            )
+            .await
+            .unwrap();
+        let filter = AgentFilter {
+            min_score: Some(5000),
+            ..Default::default()
+        };
+        let page = store.agents(&filter, 0, 10).await.unwrap();
+        assert_eq!(page.total, 2);
+        let names: Vec<_> = page.items.iter().map(|a| a.address.as_str()).collect();
+        assert_eq!(names, ["a", "c"]);
+        let filter = AgentFilter {
+            min_score: Some(5000),
+            active: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(store.agents(&filter, 0, 10).await.unwrap().total, 1);
+
+        let commitments: Vec<_> = (0..5)
+            .map(|i| {
+                let status = if i % 2 == 0 { "revealed" } else { "committed" };
+                commitment(&format!("c{i}"), "a", status)
+            })
+            .collect();
+        store.put_commitments(&commitments).await.unwrap();
+        let filter = CommitmentFilter {
+            agent: Some("a".to_string()),
+            status: Some("revealed".to_string()),
+            ..Default::default()
+        };
+        let page = store.commitments(&filter, 1, 1).await.unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.items, [commitments[2].clone()]);
+        assert!(store.commitments(&filter, 3, 10).await.unwrap().items.is_empty());
+    }
+
+    #[test]
+    fn commitment_status_reports_voided_commitments() {
+        let data = vec![0u8; 8 + ReasoningCommitment::INIT_SPACE];
+        let mut c = ReasoningCommitment::try_deserialize_unchecked(&mut &data[..]).unwrap();
+        assert_eq!(commitment_status(&c), "committed");
+        c.status = CommitmentStatus::Revealing;
+        assert_eq!(commitment_status(&c), "revealing");
+        c.expired = true;
+        assert_eq!(commitment_status(&c), "expired");
+        c.cancelled = true;
+        assert_eq!(commitment_status(&c), "cancelled");
+    }
+}
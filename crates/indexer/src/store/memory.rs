@@ -0,0 +1,126 @@
+//! Process-local storage; the index is rebuilt from chain on restart.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::{
+    AgentFilter, AgentRecord, AttestorRecord, CommitmentFilter, CommitmentRecord, Page,
+    ScorePoint, Storage,
+};
+use crate::Error;
+
+#[derive(Debug, Default)]
+struct State {
+    agents: BTreeMap<String, AgentRecord>,
+    commitments: BTreeMap<String, CommitmentRecord>,
+    /// Keyed by the attestor's signing key, which attestations refer to
+    attestors: BTreeMap<String, AttestorRecord>,
+    scores: HashMap<String, Vec<ScorePoint>>,
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    state: RwLock<State>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, State> {
+        self.state.read().expect("store lock poisoned")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, State> {
+        self.state.write().expect("store lock poisoned")
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for MemoryStorage {
+    async fn put_agents(&self, agents: &[AgentRecord], observed_at: i64) -> Result<(), Error> {
+        let mut state = self.write();
+        for record in agents {
+            let history = state.scores.entry(record.address.clone()).or_default();
+            if history.last().map(|p| p.accountability_score) != Some(record.accountability_score) {
+                history.push(ScorePoint {
+                    observed_at,
+                    accountability_score: record.accountability_score,
+                });
+            }
+            state.agents.insert(record.address.clone(), record.clone());
+        }
+        Ok(())
+    }
+
+    async fn put_commitments(&self, commitments: &[CommitmentRecord]) -> Result<(), Error> {
+        let mut state = self.write();
+        for record in commitments {
+            state.commitments.insert(record.address.clone(), record.clone());
+        }
+        Ok(())
+    }
+
+    async fn put_attestors(&self, attestors: &[AttestorRecord]) -> Result<(), Error> {
+        let mut state = self.write();
+        for record in attestors {
+            state.attestors.insert(record.attestor.clone(), record.clone());
+        }
+        Ok(())
+    }
+
+    async fn agent(&self, address: &str) -> Result<Option<AgentRecord>, Error> {
+        Ok(self.read().agents.get(address).cloned())
+    }
+
+    async fn agents(
+        &self,
+        filter: &AgentFilter,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Page<AgentRecord>, Error> {
+        let state = self.read();
+        Ok(Page::of(state.agents.values().filter(|a| filter.matches(a)), offset, limit))
+    }
+
+    async fn commitment(&self, address: &str) -> Result<Option<CommitmentRecord>, Error> {
+        Ok(self.read().commitments.get(address).cloned())
+    }
+
+    async fn commitments(
+        &self,
+        filter: &CommitmentFilter,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Page<CommitmentRecord>, Error> {
+        let state = self.read();
+        Ok(Page::of(
+            state.commitments.values().filter(|c| filter.matches(c)),
+            offset,
+            limit,
+        ))
+    }
+
+    async fn attestor(&self, attestor: &str) -> Result<Option<AttestorRecord>, Error> {
+        Ok(self.read().attestors.get(attestor).cloned())
+    }
+
+    async fn attestors(&self) -> Result<Vec<AttestorRecord>, Error> {
+        Ok(self.read().attestors.values().cloned().collect())
+    }
+
+    async fn attested_by(&self, attestor: &str) -> Result<Vec<AgentRecord>, Error> {
+        Ok(self
+            .read()
+            .agents
+            .values()
+            .filter(|a| a.operator_attestation.as_ref().is_some_and(|o| o.attestor == attestor))
+            .cloned()
+            .collect())
+    }
+
+    async fn score_history(&self, agent: &str) -> Result<Vec<ScorePoint>, Error> {
+        Ok(self.read().scores.get(agent).cloned().unwrap_or_default())
+    }
+}
@@ -0,0 +1,406 @@
+//! SQLite (small deployments) and Postgres (production) storage.
+//!
+//! Both go through sqlx's `Any` driver with one portable schema, so the
+//! backend is picked purely by URL scheme. Migrations in
+//! `crates/indexer/migrations` are embedded and applied on connect.
+
+use sqlx::any::{AnyPoolOptions, AnyRow};
+use sqlx::{AnyPool, Row};
+
+use super::{
+    AgentFilter, AgentRecord, AttestationRecord, AttestorRecord, CommitmentFilter,
+    CommitmentRecord, Page, ScorePoint, Storage,
+};
+use crate::Error;
+
+const AGENT_COLUMNS: &str = "address, authority, name, total_commitments, total_verified, \
+    accountability_score, created_at, verified_operator, on_probation, attestation_attestor, \
+    attestation_reference, attestation_attested_at, active";
+
+const COMMITMENT_COLUMNS: &str = "address, agent, authority, commitment_hash, action_type, \
+    category, confidence, timestamp, status, reasoning_uri, revealed_at, trace_len, \
+    chunk_count, upvotes, downvotes";
+
+const ATTESTOR_COLUMNS: &str =
+    "attestor, address, name, active, registered_at, revoked_at, attestations_issued";
+
+pub struct SqlStorage {
+    pool: AnyPool,
+}
+
+impl SqlStorage {
+    /// Connect to `url` (`sqlite://...` or `postgres://...`) and bring the
+    /// schema up to date.
+    pub async fn connect(url: &str) -> Result<Self, Error> {
+        sqlx::any::install_default_drivers();
+        // An in-memory SQLite database exists per connection
+        let max_connections = if url.contains(":memory:") { 1 } else { 8 };
+        let pool = AnyPoolOptions::new()
+            .max_connections(max_connections)
+            .connect(url)
+            .await?;
+        sqlx::migrate!().run(&pool).await?;
+        Ok(Self { pool })
+    }
+}
+
+/// A SQL `WHERE` clause under construction, with `$n` placeholders.
+#[derive(Default)]
+struct Where {
+    clauses: Vec<String>,
+    binds: Vec<Bind>,
+}
+
+enum Bind {
+    Text(String),
+    Int(i64),
+}
+
+impl Where {
+    fn push(&mut self, column: &str, op: &str, value: Bind) {
+        self.binds.push(value);
+        self.clauses.push(format!("{column} {op} ${}", self.binds.len()));
+    }
+
+    fn sql(&self) -> String {
+        if self.clauses.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", self.clauses.join(" AND "))
+        }
+    }
+
+    fn query<'q>(&self, sql: &'q str) -> sqlx::query::Query<'q, sqlx::Any, sqlx::any::AnyArguments> {
+        let mut query = sqlx::query(sqlx::AssertSqlSafe(sql));
+        for bind in &self.binds {
+            query = match bind {
+                Bind::Text(v) => query.bind(v.clone()),
+                Bind::Int(v) => query.bind(*v),
+            };
+        }
+        query
+    }
+}
+
+fn int(v: u64) -> i64 {
+    i64::try_from(v).unwrap_or(i64::MAX)
+}
+
+fn flag(row: &AnyRow, column: &str) -> Result<bool, sqlx::Error> {
+    Ok(row.try_get::<i64, _>(column)? != 0)
+}
+
+fn agent(row: &AnyRow) -> Result<AgentRecord, sqlx::Error> {
+    let attestor: Option<String> = row.try_get("attestation_attestor")?;
+    Ok(AgentRecord {
+        address: row.try_get("address")?,
+        authority: row.try_get("authority")?,
+        name: row.try_get("name")?,
+        total_commitments: row.try_get::<i64, _>("total_commitments")? as u64,
+        total_verified: row.try_get::<i64, _>("total_verified")? as u64,
+        accountability_score: row.try_get::<i64, _>("accountability_score")? as u16,
+        created_at: row.try_get("created_at")?,
+        verified_operator: flag(row, "verified_operator")?,
+        on_probation: flag(row, "on_probation")?,
+        operator_attestation: match attestor {
+            Some(attestor) => Some(AttestationRecord {
+                attestor,
+                reference: row.try_get("attestation_reference")?,
+                attested_at: row.try_get("attestation_attested_at")?,
+            }),
+            None => None,
+        },
+        active: flag(row, "active")?,
+    })
+}
+
+fn commitment(row: &AnyRow) -> Result<CommitmentRecord, sqlx::Error> {
+    Ok(CommitmentRecord {
+        address: row.try_get("address")?,
+        agent: row.try_get("agent")?,
+        authority: row.try_get("authority")?,
+        commitment_hash: row.try_get("commitment_hash")?,
+        action_type: row.try_get("action_type")?,
+        category: row.try_get("category")?,
+        confidence: row.try_get::<i64, _>("confidence")? as u8,
+        timestamp: row.try_get("timestamp")?,
+        status: row.try_get("status")?,
+        reasoning_uri: row.try_get("reasoning_uri")?,
+        revealed_at: row.try_get("revealed_at")?,
+        trace_len: row.try_get::<i64, _>("trace_len")? as u64,
+        chunk_count: row.try_get::<i64, _>("chunk_count")? as u32,
+        upvotes: row.try_get::<i64, _>("upvotes")? as u32,
+        downvotes: row.try_get::<i64, _>("downvotes")? as u32,
+    })
+}
+
+fn attestor(row: &AnyRow) -> Result<AttestorRecord, sqlx::Error> {
+    Ok(AttestorRecord {
+        address: row.try_get("address")?,
+        attestor: row.try_get("attestor")?,
+        name: row.try_get("name")?,
+        active: flag(row, "active")?,
+        registered_at: row.try_get("registered_at")?,
+        revoked_at: row.try_get("revoked_at")?,
+        attestations_issued: row.try_get::<i64, _>("attestations_issued")? as u64,
+    })
+}
+
+impl SqlStorage {
+    async fn page<T>(
+        &self,
+        table: &str,
+        columns: &str,
+        filter: &Where,
+        offset: usize,
+        limit: usize,
+        decode: fn(&AnyRow) -> Result<T, sqlx::Error>,
+    ) -> Result<Page<T>, Error> {
+        let count_sql = format!("SELECT COUNT(*) AS n FROM {table}{}", filter.sql());
+        let total: i64 = filter.query(&count_sql).fetch_one(&self.pool).await?.try_get("n")?;
+        let sql = format!(
+            "SELECT {columns} FROM {table}{} ORDER BY address LIMIT {limit} OFFSET {offset}",
+            filter.sql()
+        );
+        let items = filter
+            .query(&sql)
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .map(decode)
+            .collect::<Result<_, _>>()?;
+        Ok(Page {
+            items,
+            total: total as usize,
+            offset,
+            limit,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for SqlStorage {
+    async fn put_agents(&self, agents: &[AgentRecord], observed_at: i64) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
+        for a in agents {
+            let last: Option<i64> = sqlx::query(
+                "SELECT accountability_score FROM score_history WHERE agent = $1 \
+                 ORDER BY observed_at DESC LIMIT 1",
+            )
+            .bind(a.address.clone())
+            .fetch_optional(&mut *tx)
+            .await?
+            .map(|row| row.try_get(0))
+            .transpose()?;
+            if last != Some(i64::from(a.accountability_score)) {
+                sqlx::query(
+                    "INSERT INTO score_history (agent, observed_at, accountability_score) \
+                     VALUES ($1, $2, $3)",
+                )
+                .bind(a.address.clone())
+                .bind(observed_at)
+                .bind(i64::from(a.accountability_score))
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            let attestation = a.operator_attestation.as_ref();
+            sqlx::query(
+                "INSERT INTO agents (address, authority, name, total_commitments, total_verified, \
+                 accountability_score, created_at, verified_operator, on_probation, \
+                 attestation_attestor, attestation_reference, attestation_attested_at, active) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13) \
+                 ON CONFLICT (address) DO UPDATE SET \
+                 name = excluded.name, total_commitments = excluded.total_commitments, \
+                 total_verified = excluded.total_verified, \
+                 accountability_score = excluded.accountability_score, \
+                 verified_operator = excluded.verified_operator, \
+                 on_probation = excluded.on_probation, \
+                 attestation_attestor = excluded.attestation_attestor, \
+                 attestation_reference = excluded.attestation_reference, \
+                 attestation_attested_at = excluded.attestation_attested_at, \
+                 active = excluded.active",
+            )
+            .bind(a.address.clone())
+            .bind(a.authority.clone())
+            .bind(a.name.clone())
+            .bind(int(a.total_commitments))
+            .bind(int(a.total_verified))
+            .bind(i64::from(a.accountability_score))
+            .bind(a.created_at)
+            .bind(i64::from(a.verified_operator))
+            .bind(i64::from(a.on_probation))
+            .bind(attestation.map(|o| o.attestor.clone()))
+            .bind(attestation.map(|o| o.reference.clone()))
+            .bind(attestation.map(|o| o.attested_at))
+            .bind(i64::from(a.active))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn put_commitments(&self, commitments: &[CommitmentRecord]) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
+        for c in commitments {
+            sqlx::query(
+                "INSERT INTO commitments (address, agent, authority, commitment_hash, action_type, \
+                 category, confidence, timestamp, status, reasoning_uri, revealed_at, trace_len, \
+                 chunk_count, upvotes, downvotes) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15) \
+                 ON CONFLICT (address) DO UPDATE SET \
+                 timestamp = excluded.timestamp, status = excluded.status, \
+                 reasoning_uri = excluded.reasoning_uri, revealed_at = excluded.revealed_at, \
+                 trace_len = excluded.trace_len, chunk_count = excluded.chunk_count, \
+                 upvotes = excluded.upvotes, downvotes = excluded.downvotes",
+            )
+            .bind(c.address.clone())
+            .bind(c.agent.clone())
+            .bind(c.authority.clone())
+            .bind(c.commitment_hash.clone())
+            .bind(c.action_type.clone())
+            .bind(c.category.clone())
+            .bind(i64::from(c.confidence))
+            .bind(c.timestamp)
+            .bind(c.status.clone())
+            .bind(c.reasoning_uri.clone())
+            .bind(c.revealed_at)
+            .bind(int(c.trace_len))
+            .bind(i64::from(c.chunk_count))
+            .bind(i64::from(c.upvotes))
+            .bind(i64::from(c.downvotes))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn put_attestors(&self, attestors: &[AttestorRecord]) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
+        for a in attestors {
+            sqlx::query(
+                "INSERT INTO attestors (attestor, address, name, active, registered_at, \
+                 revoked_at, attestations_issued) VALUES ($1, $2, $3, $4, $5, $6, $7) \
+                 ON CONFLICT (attestor) DO UPDATE SET \
+                 name = excluded.name, active = excluded.active, \
+                 revoked_at = excluded.revoked_at, \
+                 attestations_issued = excluded.attestations_issued",
+            )
+            .bind(a.attestor.clone())
+            .bind(a.address.clone())
+            .bind(a.name.clone())
+            .bind(i64::from(a.active))
+            .bind(a.registered_at)
+            .bind(a.revoked_at)
+            .bind(int(a.attestations_issued))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn agent(&self, address: &str) -> Result<Option<AgentRecord>, Error> {
+        let sql = format!("SELECT {AGENT_COLUMNS} FROM agents WHERE address = $1");
+        let row = sqlx::query(sqlx::AssertSqlSafe(sql))
+            .bind(address.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(agent).transpose()?)
+    }
+
+    async fn agents(
+        &self,
+        filter: &AgentFilter,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Page<AgentRecord>, Error> {
+        let mut w = Where::default();
+        if let Some(v) = filter.verified_operator {
+            w.push("verified_operator", "=", Bind::Int(i64::from(v)));
+        }
+        if let Some(m) = filter.min_score {
+            w.push("accountability_score", ">=", Bind::Int(i64::from(m)));
+        }
+        if let Some(v) = filter.active {
+            w.push("active", "=", Bind::Int(i64::from(v)));
+        }
+        self.page("agents", AGENT_COLUMNS, &w, offset, limit, agent).await
+    }
+
+    async fn commitment(&self, address: &str) -> Result<Option<CommitmentRecord>, Error> {
+        let sql = format!("SELECT {COMMITMENT_COLUMNS} FROM commitments WHERE address = $1");
+        let row = sqlx::query(sqlx::AssertSqlSafe(sql))
+            .bind(address.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(commitment).transpose()?)
+    }
+
+    async fn commitments(
+        &self,
+        filter: &CommitmentFilter,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Page<CommitmentRecord>, Error> {
+        let mut w = Where::default();
+        for (column, value) in [
+            ("agent", &filter.agent),
+            ("status", &filter.status),
+            ("category", &filter.category),
+            ("action_type", &filter.action_type),
+        ] {
+            if let Some(value) = value {
+                w.push(column, "=", Bind::Text(value.clone()));
+            }
+        }
+        self.page("commitments", COMMITMENT_COLUMNS, &w, offset, limit, commitment)
+            .await
+    }
+
+    async fn attestor(&self, key: &str) -> Result<Option<AttestorRecord>, Error> {
+        let sql = format!("SELECT {ATTESTOR_COLUMNS} FROM attestors WHERE attestor = $1");
+        let row = sqlx::query(sqlx::AssertSqlSafe(sql))
+            .bind(key.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(attestor).transpose()?)
+    }
+
+    async fn attestors(&self) -> Result<Vec<AttestorRecord>, Error> {
+        let sql = format!("SELECT {ATTESTOR_COLUMNS} FROM attestors ORDER BY attestor");
+        let rows = sqlx::query(sqlx::AssertSqlSafe(sql)).fetch_all(&self.pool).await?;
+        Ok(rows.iter().map(attestor).collect::<Result<_, _>>()?)
+    }
+
+    async fn attested_by(&self, key: &str) -> Result<Vec<AgentRecord>, Error> {
+        let sql = format!(
+            "SELECT {AGENT_COLUMNS} FROM agents WHERE attestation_attestor = $1 ORDER BY address"
+        );
+        let rows = sqlx::query(sqlx::AssertSqlSafe(sql))
+            .bind(key.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().map(agent).collect::<Result<_, _>>()?)
+    }
+
+    async fn score_history(&self, agent: &str) -> Result<Vec<ScorePoint>, Error> {
+        let rows = sqlx::query(
+            "SELECT observed_at, accountability_score FROM score_history \
+             WHERE agent = $1 ORDER BY observed_at",
+        )
+        .bind(agent.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter()
+            .map(|row| {
+                Ok(ScorePoint {
+                    observed_at: row.try_get("observed_at")?,
+                    accountability_score: row.try_get::<i64, _>("accountability_score")? as u16,
+                })
+            })
+            .collect()
+    }
+}
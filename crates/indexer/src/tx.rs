@@ -0,0 +1,210 @@
+//! Signing and sending the keeper's transactions.
+//!
+//! Messages are compiled with `solana-message` and put on the wire by
+//! hand, which is all the keeper needs from the transaction types.
+//! Every transaction is prefixed with compute-budget instructions
+//! setting a priority fee taken from recent fees on the accounts it
+//! writes.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use ed25519_dalek::Signer;
+use solana_hash::Hash;
+use solana_message::Message;
+
+use crate::rpc::RpcClient;
+use crate::webhook::RetryPolicy;
+use crate::Error;
+
+/// The compute budget program.
+const COMPUTE_BUDGET_ID: Pubkey = Pubkey::from_str_const("ComputeBudget111111111111111111111111111111");
+/// Interval between signature status checks.
+const CONFIRM_POLL: Duration = Duration::from_millis(500);
+
+/// The keeper's fee payer and signer.
+pub struct Keypair(ed25519_dalek::Keypair);
+
+impl Keypair {
+    /// Read a keypair file as written by `solana-keygen` (a JSON array
+    /// of the 64 secret and public key bytes).
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let bytes: Vec<u8> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        ed25519_dalek::Keypair::from_bytes(&bytes)
+            .map(Self)
+            .map_err(|e| Error::Config(format!("{}: {e}", path.display())))
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        Pubkey::new_from_array(self.0.public.to_bytes())
+    }
+}
+
+/// How much the keeper pays for priority.
+#[derive(Clone, Copy, Debug)]
+pub struct FeePolicy {
+    /// Compute units requested per transaction
+    pub compute_units: u32,
+    /// Highest priority fee paid, in micro-lamports per compute unit
+    pub max_micro_lamports: u64,
+}
+
+impl Default for FeePolicy {
+    fn default() -> Self {
+        Self {
+            compute_units: 200_000,
+            max_micro_lamports: 100_000,
+        }
+    }
+}
+
+/// Sends transactions signed by one keypair, retrying until they land.
+pub struct Sender {
+    rpc: RpcClient,
+    payer: Keypair,
+    fees: FeePolicy,
+    retry: RetryPolicy,
+}
+
+impl Sender {
+    pub fn new(rpc: RpcClient, payer: Keypair, fees: FeePolicy, retry: RetryPolicy) -> Self {
+        Self { rpc, payer, fees, retry }
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Send `instructions` in one transaction and wait for `confirmed`.
+    ///
+    /// A transaction whose blockhash expires before it lands is re-sent
+    /// with a fresh blockhash and double the priority fee (up to the
+    /// policy's maximum). A transaction the node rejects in preflight or
+    /// that lands with an error is not retried: it would fail the same
+    /// way again.
+    pub async fn send(&self, instructions: &[Instruction]) -> Result<String, Error> {
+        let writable: Vec<Pubkey> = instructions
+            .iter()
+            .flat_map(|ix| &ix.accounts)
+            .filter(|meta| meta.is_writable)
+            .map(|meta| meta.pubkey)
+            .collect();
+        let mut fee = self.priority_fee(&writable).await?;
+        for attempt in 1..=self.retry.max_attempts {
+            tokio::time::sleep(self.retry.backoff(attempt)).await;
+            match self.send_once(instructions, fee).await {
+                Ok(Some(signature)) => return Ok(signature),
+                Ok(None) => tracing::debug!(attempt, fee, "transaction expired"),
+                Err(Error::Http(e)) => tracing::debug!(attempt, "rpc unreachable: {e}"),
+                Err(e) => return Err(e),
+            }
+            fee = fee.saturating_mul(2).clamp(1, self.fees.max_micro_lamports);
+        }
+        Err(Error::Rpc(format!(
+            "transaction not confirmed after {} attempts",
+            self.retry.max_attempts
+        )))
+    }
+
+    /// The 75th percentile of recent fees on `writable`, capped.
+    async fn priority_fee(&self, writable: &[Pubkey]) -> Result<u64, Error> {
+        let mut fees = self.rpc.recent_prioritization_fees(writable).await?;
+        fees.sort_unstable();
+        let fee = fees.get(fees.len() * 3 / 4).copied().unwrap_or(0);
+        Ok(fee.min(self.fees.max_micro_lamports))
+    }
+
+    /// Send once; `None` if the blockhash expired before it landed.
+    async fn send_once(&self, instructions: &[Instruction], fee: u64) -> Result<Option<String>, Error> {
+        let (blockhash, last_valid_block_height) = self.rpc.latest_blockhash().await?;
+        let transaction = self.sign(instructions, fee, blockhash);
+        let signature = self.rpc.send_transaction(&transaction).await?;
+        loop {
+            if let Some(status) = self.rpc.signature_status(&signature).await? {
+                if let Some(err) = status.err {
+                    return Err(Error::Rpc(format!("{signature} failed: {err}")));
+                }
+                if status.is_confirmed() {
+                    return Ok(Some(signature));
+                }
+            }
+            if self.rpc.block_height().await? > last_valid_block_height {
+                return Ok(None);
+            }
+            tokio::time::sleep(CONFIRM_POLL).await;
+        }
+    }
+
+    /// Wire-format transaction running `instructions` at `fee`.
+    fn sign(&self, instructions: &[Instruction], fee: u64, blockhash: [u8; 32]) -> Vec<u8> {
+        let mut all = vec![
+            compute_budget_ix(2, &self.fees.compute_units.to_le_bytes()),
+            compute_budget_ix(3, &fee.to_le_bytes()),
+        ];
+        all.extend_from_slice(instructions);
+        let message = Message::new_with_blockhash(
+            &all,
+            Some(&self.payer()),
+            &Hash::new_from_array(blockhash),
+        );
+        let message = serialize_message(&message);
+        let signature = self.payer.0.sign(&message);
+
+        let mut transaction = Vec::with_capacity(1 + 64 + message.len());
+        short_vec_len(&mut transaction, 1);
+        transaction.extend_from_slice(&signature.to_bytes());
+        transaction.extend_from_slice(&message);
+        transaction
+    }
+}
+
+/// A compute budget instruction: `tag` 2 sets the unit limit, 3 the
+/// unit price.
+fn compute_budget_ix(tag: u8, value: &[u8]) -> Instruction {
+    let mut data = vec![tag];
+    data.extend_from_slice(value);
+    Instruction {
+        program_id: COMPUTE_BUDGET_ID,
+        accounts: Vec::new(),
+        data,
+    }
+}
+
+/// A legacy message in its wire format.
+fn serialize_message(message: &Message) -> Vec<u8> {
+    let header = &message.header;
+    let mut out = vec![
+        header.num_required_signatures,
+        header.num_readonly_signed_accounts,
+        header.num_readonly_unsigned_accounts,
+    ];
+    short_vec_len(&mut out, message.account_keys.len());
+    for key in &message.account_keys {
+        out.extend_from_slice(key.as_ref());
+    }
+    out.extend_from_slice(message.recent_blockhash.as_ref());
+    short_vec_len(&mut out, message.instructions.len());
+    for ix in &message.instructions {
+        out.push(ix.program_id_index);
+        short_vec_len(&mut out, ix.accounts.len());
+        out.extend_from_slice(&ix.accounts);
+        short_vec_len(&mut out, ix.data.len());
+        out.extend_from_slice(&ix.data);
+    }
+    out
+}
+
+/// Append `len` as a compact-u16, the length prefix of wire vectors.
+fn short_vec_len(out: &mut Vec<u8>, mut len: usize) {
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
@@ -0,0 +1,238 @@
+//! Guarding a chosen set of agents for their operators or third-party
+//! guardians.
+//!
+//! Each tick the [`Watchtower`] checks the watched agents against its
+//! [`Policy`] and raises an [`Alert`] for every new violation: a reveal
+//! deadline missed, a commitment outside the agent's expected action
+//! types, or a score drop past the threshold. Alerts go out as signed
+//! webhooks (see [`crate::webhook`]). Given a keypair, it also enforces
+//! missed deadlines by sending `expire_commitment`, the one sanction the
+//! program lets anyone apply; the other violations are alert-only.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_lang::prelude::Pubkey;
+use axiom::{AgentProfile, CommitmentStatus, ReasoningCommitment};
+use serde::Serialize;
+
+use crate::keeper::{self, Crank};
+use crate::metrics::Metrics;
+use crate::rpc::RpcClient;
+use crate::snapshot;
+use crate::tx::Sender;
+use crate::webhook::Dispatcher;
+use crate::Error;
+
+/// What the watched agents are held to.
+#[derive(Clone, Debug)]
+pub struct Policy {
+    /// Agent profiles to watch
+    pub agents: HashSet<Pubkey>,
+    /// Action types the agents are expected to commit to; any if `None`
+    pub action_types: Option<HashSet<String>>,
+    /// Fall (basis points) from an agent's recent high score that raises
+    /// an alert
+    pub max_score_drop_bps: u16,
+}
+
+/// A policy violation by a watched agent.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Alert {
+    /// A commitment passed its reveal deadline unrevealed.
+    MissedDeadline {
+        agent: String,
+        commitment: String,
+        deadline: i64,
+    },
+    /// A commitment's action type isn't one the policy expects.
+    UnusualActionType {
+        agent: String,
+        commitment: String,
+        action_type: String,
+    },
+    /// The agent's score fell from `from` to `to`.
+    ScoreDrop { agent: String, from: u16, to: u16 },
+}
+
+impl Alert {
+    /// Short name used in payloads and metrics (`missed_deadline`, ...).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Alert::MissedDeadline { .. } => "missed_deadline",
+            Alert::UnusualActionType { .. } => "unusual_action_type",
+            Alert::ScoreDrop { .. } => "score_drop",
+        }
+    }
+}
+
+/// Checks successive snapshots against a [`Policy`], raising each
+/// violation once.
+#[derive(Debug, Default)]
+pub struct Checker {
+    /// Commitment violations standing at the last check
+    standing: HashSet<Alert>,
+    /// Each agent's highest score since its last score alert
+    peaks: HashMap<Pubkey, u16>,
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Violations in `agents` and `commitments` (already limited to the
+    /// watched agents) not raised by an earlier check.
+    pub fn check(
+        &mut self,
+        policy: &Policy,
+        agents: &[(Pubkey, AgentProfile)],
+        commitments: &[(Pubkey, ReasoningCommitment)],
+        now: i64,
+    ) -> Vec<Alert> {
+        let mut standing = HashSet::new();
+        for (address, commitment) in commitments {
+            if commitment.cancelled || commitment.simulation {
+                continue;
+            }
+            if let Some(deadline) = commitment.reveal_deadline {
+                if commitment.status == CommitmentStatus::Committed && now > deadline {
+                    standing.insert(Alert::MissedDeadline {
+                        agent: commitment.agent.to_string(),
+                        commitment: address.to_string(),
+                        deadline,
+                    });
+                }
+            }
+            let unusual = policy.action_types.as_ref().is_some_and(|expected| {
+                !commitment.embargoed && !expected.contains(&commitment.action_type)
+            });
+            if unusual {
+                standing.insert(Alert::UnusualActionType {
+                    agent: commitment.agent.to_string(),
+                    commitment: address.to_string(),
+                    action_type: commitment.action_type.clone(),
+                });
+            }
+        }
+        let mut alerts: Vec<Alert> = standing.difference(&self.standing).cloned().collect();
+        self.standing = standing;
+
+        for (address, profile) in agents {
+            let score = profile.accountability_score;
+            let peak = self.peaks.entry(*address).or_insert(score);
+            if score > *peak {
+                *peak = score;
+            } else if *peak - score >= policy.max_score_drop_bps {
+                alerts.push(Alert::ScoreDrop {
+                    agent: address.to_string(),
+                    from: *peak,
+                    to: score,
+                });
+                *peak = score;
+            }
+        }
+        alerts
+    }
+}
+
+/// Watches agents, alerts on violations and expires missed deadlines.
+pub struct Watchtower {
+    rpc: RpcClient,
+    policy: Policy,
+    checker: Checker,
+    /// Sends `expire_commitment`; alert-only without one
+    sender: Option<Sender>,
+    dispatcher: Option<Dispatcher>,
+    metrics: Metrics,
+}
+
+impl Watchtower {
+    pub fn new(
+        rpc: RpcClient,
+        policy: Policy,
+        sender: Option<Sender>,
+        dispatcher: Option<Dispatcher>,
+        metrics: Metrics,
+    ) -> Self {
+        Self {
+            rpc,
+            policy,
+            checker: Checker::new(),
+            sender,
+            dispatcher,
+            metrics,
+        }
+    }
+
+    /// Check the watched agents once, alert on what's new and send due
+    /// expiries. A failed expiry is retried on the next tick.
+    pub async fn tick(&mut self) -> Result<(), Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let watched = |agent: &Pubkey| self.policy.agents.contains(agent);
+        let mut agents = snapshot::agents(&self.rpc).await?;
+        agents.retain(|(address, _)| watched(address));
+        let mut commitments = snapshot::commitments(&self.rpc).await?;
+        commitments.retain(|(_, commitment)| watched(&commitment.agent));
+
+        for alert in self.checker.check(&self.policy, &agents, &commitments, now) {
+            tracing::warn!(kind = alert.kind(), ?alert, "policy violation");
+            self.metrics.alert(alert.kind());
+            if let Some(dispatcher) = &self.dispatcher {
+                dispatcher.alert(alert);
+            }
+        }
+
+        let Some(sender) = &self.sender else {
+            return Ok(());
+        };
+        let Some(config) = snapshot::config(&self.rpc).await? else {
+            return Ok(());
+        };
+        let epoch = self.rpc.epoch().await?;
+        let tasks = keeper::tasks(
+            &config,
+            &agents,
+            &commitments,
+            &[],
+            &[],
+            epoch,
+            sender.payer(),
+        );
+        let expiries = tasks
+            .iter()
+            .filter(|t| t.crank == Crank::ExpireCommitment && t.is_due(now, epoch));
+        for task in expiries {
+            let crank = task.crank.name();
+            match sender.send(std::slice::from_ref(&task.instruction)).await {
+                Ok(signature) => {
+                    tracing::info!(crank, target = %task.target, %signature, "crank sent");
+                    self.metrics.crank(crank, true);
+                }
+                Err(e) => {
+                    tracing::warn!(crank, target = %task.target, "crank failed: {e}");
+                    self.metrics.crank(crank, false);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Tick every `interval` until the process is interrupted.
+    pub async fn run(mut self, interval: std::time::Duration) {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+            if let Err(e) = self.tick().await {
+                tracing::warn!("watchtower tick failed: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Webhook delivery of protocol [`Event`]s and watchtower [`Alert`]s.
+//!
+//! Payloads are `{"sent_at": ..., "event": {...}}` or
+//! `{"sent_at": ..., "alert": {...}}`.
+//!
+//! Every payload is signed so receivers can reject forgeries: the
+//! `X-Solprism-Signature` header carries `sha256=<hex>` where the MAC is
+//! HMAC-SHA256 under the shared secret over `"{timestamp}.{body}"`, and
+//! `timestamp` is the `X-Solprism-Timestamp` header. Receivers should also
+//! reject stale timestamps to stop replays.
+
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::events::Event;
+use crate::metrics::Metrics;
+use crate::watchtower::Alert;
+use crate::Error;
+
+/// Header carrying the payload signature.
+pub const SIGNATURE_HEADER: &str = "X-Solprism-Signature";
+/// Header carrying the Unix time the payload was signed at.
+pub const TIMESTAMP_HEADER: &str = "X-Solprism-Timestamp";
+
+/// How hard to try before giving up on a delivery.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after each failure
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before attempt `attempt` (1-based; the first attempt has none).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let doublings = attempt.saturating_sub(2).min(16);
+        self.initial_backoff
+            .saturating_mul(1 << doublings)
+            .min(self.max_backoff)
+    }
+}
+
+/// What a payload carries.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Notice {
+    Event(Event),
+    Alert(Alert),
+}
+
+impl Notice {
+    /// Short name used in logs (`committed`, `missed_deadline`, ...).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Notice::Event(event) => event.kind(),
+            Notice::Alert(alert) => alert.kind(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    sent_at: u64,
+    #[serde(flatten)]
+    notice: &'a Notice,
+}
+
+/// Posts signed event payloads to a fixed set of endpoints.
+#[derive(Clone)]
+pub struct Dispatcher {
+    http: reqwest::Client,
+    endpoints: Vec<String>,
+    secret: Vec<u8>,
+    retry: RetryPolicy,
+    metrics: Metrics,
+}
+
+impl Dispatcher {
+    pub fn new(
+        endpoints: Vec<String>,
+        secret: impl Into<Vec<u8>>,
+        retry: RetryPolicy,
+        metrics: Metrics,
+    ) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            endpoints,
+            secret: secret.into(),
+            retry,
+            metrics,
+        }
+    }
+
+    /// Deliver `event` to every endpoint in the background. Failures are
+    /// logged once retries are exhausted; they never stall indexing.
+    pub fn dispatch(&self, event: Event) {
+        self.spawn(Notice::Event(event));
+    }
+
+    /// Deliver `alert` to every endpoint in the background, as
+    /// [`dispatch`](Self::dispatch) does events.
+    pub fn alert(&self, alert: Alert) {
+        self.spawn(Notice::Alert(alert));
+    }
+
+    fn spawn(&self, notice: Notice) {
+        for endpoint in &self.endpoints {
+            let this = self.clone();
+            let endpoint = endpoint.clone();
+            let notice = notice.clone();
+            tokio::spawn(async move {
+                let result = this.deliver(&endpoint, &notice).await;
+                this.metrics.webhook_delivery(result.is_ok());
+                if let Err(e) = result {
+                    tracing::error!(%endpoint, kind = notice.kind(), "webhook delivery failed: {e}");
+                }
+            });
+        }
+    }
+
+    /// Deliver `notice` to `endpoint`, retrying transport errors, `429`
+    /// and `5xx` responses with exponential backoff.
+    pub async fn deliver(&self, endpoint: &str, notice: &Notice) -> Result<(), Error> {
+        let mut attempt = 1;
+        loop {
+            let sent_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let body = serde_json::to_string(&Payload { sent_at, notice })?;
+            let result = self
+                .http
+                .post(endpoint)
+                .header("Content-Type", "application/json")
+                .header(TIMESTAMP_HEADER, sent_at.to_string())
+                .header(SIGNATURE_HEADER, self.sign(sent_at, &body))
+                .body(body)
+                .send()
+                .await;
+
+            let retryable = match result {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    if !(status.is_server_error() || status.as_u16() == 429) {
+                        return Err(Error::Webhook(format!("rejected with {status}")));
+                    }
+                    format!("responded {status}")
+                }
+                Err(e) => e.to_string(),
+            };
+
+            if attempt >= self.retry.max_attempts {
+                return Err(Error::Webhook(format!(
+                    "gave up after {attempt} attempts: {retryable}"
+                )));
+            }
+            attempt += 1;
+            tracing::warn!(%endpoint, attempt, "webhook delivery failed, retrying: {retryable}");
+            tokio::time::sleep(self.retry.backoff(attempt)).await;
+        }
+    }
+
+    /// Signature header value for `body` sent at `timestamp`.
+    pub fn sign(&self, timestamp: u64, body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret)
+            .expect("HMAC accepts keys of any length");
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body.as_bytes());
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dispatcher(secret: &str) -> Dispatcher {
+        Dispatcher::new(vec![], secret, RetryPolicy::default(), Metrics::new())
+    }
+
+    #[test]
+    fn signs_timestamp_and_body() {
+        // HMAC-SHA256("whsec", "1700000000.{\"a\":1}")
+        assert_eq!(
+            dispatcher("whsec").sign(1_700_000_000, r#"{"a":1}"#),
+            "sha256=8ad37ba156048ae0e0a5533c75cdf26fee88b07f93cb57ee4c80adb053012032"
+        );
+    }
+
+    #[test]
+    fn signature_covers_secret_timestamp_and_body() {
+        let signature = dispatcher("whsec").sign(1_700_000_000, r#"{"a":1}"#);
+        assert_ne!(dispatcher("other").sign(1_700_000_000, r#"{"a":1}"#), signature);
+        assert_ne!(dispatcher("whsec").sign(1_700_000_001, r#"{"a":1}"#), signature);
+        assert_ne!(dispatcher("whsec").sign(1_700_000_000, r#"{"a":2}"#), signature);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let retry = RetryPolicy::default();
+        assert_eq!(retry.backoff(2), Duration::from_millis(500));
+        assert_eq!(retry.backoff(3), Duration::from_secs(1));
+        assert_eq!(retry.backoff(4), Duration::from_secs(2));
+        assert_eq!(retry.backoff(20), Duration::from_secs(30));
+        assert_eq!(retry.backoff(u32::MAX), Duration::from_secs(30));
+    }
+}
@@ -345,7 +345,8 @@ async function main() {
       const reveal = await client.revealReasoning(
         wallet,
         result.commitmentAddress,
-        dataUri
+        dataUri,
+        trace
       );
       console.log(`  ✅ Revealed: ${reveal.signature.slice(0, 16)}...`);
 
@@ -18,6 +18,8 @@ import {
   deriveCommitmentPDA,
   hashTrace,
   hashTraceHex,
+  traceByteLength,
+  feeTreasuryExists,
   createReasoningTrace,
 } from "../provider";
 import {
@@ -150,7 +152,8 @@ export const commitReasoningAction: Action = {
         hash,
         trace.action.type,
         trace.decision.confidence,
-        nonce
+        nonce,
+        await feeTreasuryExists()
       );
       const signature = await sendTransaction(ix);
 
@@ -176,10 +179,11 @@ export const commitReasoningAction: Action = {
         `**Commitment:** \`${result.commitmentAddress}\`\n` +
         `**Transaction:** ${signature}\n` +
         `**Nonce:** ${nonce} | **Slot:** ${slot}\n` +
+        `**Trace length:** ${traceByteLength(trace)} bytes\n` +
         `**Action:** ${trace.action.type} | **Confidence:** ${trace.decision.confidence}%\n\n` +
         `The reasoning hash is now immutably recorded. Execute your action, ` +
-        `then use REVEAL_REASONING with the commitment address to publish ` +
-        `the full trace.`
+        `then use REVEAL_REASONING with the commitment address and trace ` +
+        `length to publish the full trace.`
       );
     } catch (error) {
       const msg =
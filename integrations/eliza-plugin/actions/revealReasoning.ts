@@ -4,14 +4,16 @@
  * Reveals the full reasoning trace for a previously committed hash.
  * This is the second step of the commit-reveal protocol: after the
  * agent has executed its action, it reveals what it was thinking,
- * allowing anyone to verify the commitment.
+ * allowing anyone to verify the commitment. The reveal counts once it
+ * is finalized after the protocol's grace period.
  */
 
 import { Action, IAgentRuntime, Memory, State } from "@ai16z/eliza";
 import { PublicKey } from "@solana/web3.js";
 import {
   getWallet,
-  buildRevealReasoningIx,
+  buildStartRevealIx,
+  buildFinalizeRevealIx,
   sendTransaction,
   fetchCommitment,
 } from "../provider";
@@ -22,7 +24,8 @@ export const revealReasoningAction: Action = {
   description:
     "Reveal the full reasoning trace for a previously committed hash on SOLPRISM. " +
     "This links a URI (IPFS, Arweave, or HTTP) to the onchain commitment so anyone " +
-    "can fetch the reasoning and verify it matches the pre-committed hash.",
+    "can fetch the reasoning and verify it matches the pre-committed hash. " +
+    "Run it again to finalize the reveal once the grace period has passed.",
   similes: [
     "SOLPRISM_REVEAL",
     "REVEAL_COMMITMENT",
@@ -39,6 +42,7 @@ export const revealReasoningAction: Action = {
           text: "Reveal my reasoning for commitment 7KpX...4Xm2",
           commitmentAddress: "7KpX4Xm2nRtVqBcDeFgHiJkLmNoPqRsT4Xm2",
           reasoningUri: "ipfs://QmX7a9b2c3d4e5f6g7h8i9j0kLmNoPqRsT",
+          traceLength: 1234,
         } as RevealReasoningContent,
       },
       {
@@ -76,6 +80,7 @@ export const revealReasoningAction: Action = {
       const text = content.text?.toLowerCase() ?? "";
       return (
         text.includes("reveal") ||
+        text.includes("finalize") ||
         text.includes("disclose") ||
         text.includes("publish reasoning") ||
         (content.commitmentAddress != null && content.reasoningUri != null)
@@ -138,6 +143,18 @@ export const revealReasoningAction: Action = {
         );
       }
 
+      // A started reveal only needs finalizing
+      if (commitment.status === "revealing") {
+        const ix = await buildFinalizeRevealIx(signer.publicKey, commitmentPubkey);
+        const signature = await sendTransaction(ix);
+        return (
+          `✅ Reveal finalized!\n\n` +
+          `**Commitment:** \`${commitmentAddress}\`\n` +
+          `**Reasoning URI:** ${commitment.reasoningUri}\n` +
+          `**Transaction:** ${signature}`
+        );
+      }
+
       // Extract reasoning URI
       let reasoningUri = content.reasoningUri;
       if (!reasoningUri) {
@@ -155,11 +172,28 @@ export const revealReasoningAction: Action = {
         );
       }
 
+      // Extract the trace length reported by COMMIT_REASONING
+      let traceLength = content.traceLength;
+      if (!traceLength) {
+        const lengthMatch = content.text?.match(/(?:trace length|length)[:\s]+(\d+)/i);
+        traceLength = lengthMatch ? Number(lengthMatch[1]) : undefined;
+      }
+
+      if (!traceLength) {
+        return (
+          "❌ Missing trace length. Please provide the trace length from " +
+          "your COMMIT_REASONING result.\n\n" +
+          "Example: `Reveal reasoning for commitment <addr> with URI ipfs://Qm... trace length 1234`"
+        );
+      }
+
       // Build and send the reveal instruction
-      const ix = buildRevealReasoningIx(
+      const ix = await buildStartRevealIx(
         signer.publicKey,
         commitmentPubkey,
-        reasoningUri
+        commitment.actionType,
+        reasoningUri,
+        traceLength
       );
       const signature = await sendTransaction(ix);
 
@@ -171,7 +205,8 @@ export const revealReasoningAction: Action = {
         `**Action:** ${commitment.actionType} | **Confidence:** ${commitment.confidence}%\n\n` +
         `The full reasoning trace is now publicly linked to the commitment. ` +
         `Anyone can fetch the trace from the URI and use VERIFY_REASONING ` +
-        `to confirm the hash matches.`
+        `to confirm the hash matches. Run REVEAL_REASONING again once the ` +
+        `grace period has passed to finalize the reveal.`
       );
     } catch (error) {
       const msg =
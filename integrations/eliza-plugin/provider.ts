@@ -34,14 +34,24 @@ const SOLPRISM_PROGRAM_ID = new PublicKey(
 
 const SEED_AGENT = Buffer.from("agent");
 const SEED_COMMITMENT = Buffer.from("commitment");
+const SEED_CONFIG = Buffer.from("config");
+const SEED_ACTIVITY = Buffer.from("activity");
+const SEED_ACTION_STATS = Buffer.from("action_stats");
+const SEED_METRICS = Buffer.from("metrics");
+const SEED_SUMMARY = Buffer.from("summary");
+const SEED_FEE_TREASURY = Buffer.from("fee_treasury");
 
 /** Anchor 8-byte instruction discriminators */
 const DISCRIMINATORS = {
   registerAgent: Buffer.from([135, 157, 66, 195, 2, 113, 175, 30]),
   commitReasoning: Buffer.from([163, 80, 25, 135, 94, 49, 218, 44]),
-  revealReasoning: Buffer.from([76, 215, 6, 241, 209, 207, 84, 96]),
+  startReveal: Buffer.from([53, 233, 172, 216, 107, 91, 161, 239]),
+  finalizeReveal: Buffer.from([50, 41, 16, 48, 114, 165, 131, 206]),
 } as const;
 
+/** `CommitmentStatus` variants in the program's declaration order */
+const COMMITMENT_STATUSES = ["committed", "revealed", "revealing"] as const;
+
 /** Anchor 8-byte account discriminators for deserialization */
 const ACCOUNT_DISCRIMINATORS = {
   AgentProfile: Buffer.from([60, 227, 42, 24, 0, 87, 86, 205]),
@@ -132,6 +142,17 @@ export function deriveCommitmentPDA(
   );
 }
 
+function derivePDA(...seeds: Buffer[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
+/** The agent's activity bucket for today, as the program picks it */
+function deriveActivityPDA(agentProfile: PublicKey): PublicKey {
+  const day = Buffer.alloc(8);
+  day.writeBigInt64LE(BigInt(Math.floor(Date.now() / 1000 / 86_400)));
+  return derivePDA(SEED_ACTIVITY, agentProfile.toBuffer(), day);
+}
+
 // ─── Serialization Helpers ─────────────────────────────────────────────────
 
 function encodeString(s: string): Buffer {
@@ -152,6 +173,19 @@ function encodeU8(n: number): Buffer {
   return Buffer.from([n]);
 }
 
+/** Account meta for an optional account, `present` or not */
+function optionalAccount(pubkey: PublicKey, present: boolean) {
+  // Anchor reads the program ID in an optional account's slot as "none"
+  return present
+    ? { pubkey, isSigner: false, isWritable: true }
+    : { pubkey: programId, isSigner: false, isWritable: false };
+}
+
+/** Whether an account exists at `address` */
+async function accountExists(address: PublicKey): Promise<boolean> {
+  return (await getConnection().getAccountInfo(address)) !== null;
+}
+
 function readString(buf: Buffer, offset: number): [string, number] {
   const len = buf.readUInt32LE(offset);
   const str = buf.slice(offset + 4, offset + 4 + len).toString("utf-8");
@@ -180,6 +214,11 @@ export function hashTrace(trace: ReasoningTrace): Uint8Array {
   return new Uint8Array(hash);
 }
 
+/** Byte length of a trace as hashed, for `buildStartRevealIx` */
+export function traceByteLength(trace: ReasoningTrace): number {
+  return Buffer.byteLength(JSON.stringify(sortKeys(trace)), "utf-8");
+}
+
 /** Compute SHA-256 hash as hex string */
 export function hashTraceHex(trace: ReasoningTrace): string {
   return Buffer.from(hashTrace(trace)).toString("hex");
@@ -258,6 +297,7 @@ export function buildRegisterAgentIx(
     keys: [
       { pubkey: agentProfile, isSigner: false, isWritable: true },
       { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: derivePDA(SEED_CONFIG), isSigner: false, isWritable: false },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
     programId,
@@ -265,58 +305,137 @@ export function buildRegisterAgentIx(
   });
 }
 
+/**
+ * Build a commit_reasoning instruction. Pass `withFeeTreasury` while the
+ * protocol charges a commit fee.
+ */
 export function buildCommitReasoningIx(
   authority: PublicKey,
   commitmentHash: Uint8Array,
   actionType: string,
   confidence: number,
-  nonce: bigint | number
+  nonce: bigint | number,
+  withFeeTreasury = false
 ): TransactionInstruction {
   const [agentProfile] = deriveAgentPDA(authority);
   const [commitment] = deriveCommitmentPDA(agentProfile, nonce);
 
+  // CommitArgs, in field order
   const data = Buffer.concat([
     DISCRIMINATORS.commitReasoning,
-    Buffer.from(commitmentHash), // [u8; 32]
-    encodeString(actionType), // String (Borsh)
-    encodeU8(confidence), // u8
-    encodeU64(nonce), // u64
+    Buffer.from(commitmentHash), // commitment_hash: [u8; 32]
+    encodeString(actionType), // action_type: String (Borsh)
+    encodeU8(confidence), // confidence: u8
+    encodeU64(nonce), // nonce: u64
+    Buffer.from([
+      0, // category: Financial
+      0, // chunk_manifest: None
+      0, // counterparty: None
+      0, // risk: None
+      0, // confidence_source: Unspecified
+      0, // simulation: false
+      0, // reveal_deadline: None
+      0, // hash_algorithm: Sha256
+    ]),
   ]);
 
   return new TransactionInstruction({
     keys: [
       { pubkey: commitment, isSigner: false, isWritable: true },
       { pubkey: agentProfile, isSigner: false, isWritable: true },
+      { pubkey: deriveActivityPDA(agentProfile), isSigner: false, isWritable: true },
+      {
+        pubkey: derivePDA(SEED_ACTION_STATS, agentProfile.toBuffer(), Buffer.from(actionType, "utf-8")),
+        isSigner: false,
+        isWritable: true,
+      },
       { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: derivePDA(SEED_CONFIG), isSigner: false, isWritable: false },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      optionalAccount(programId, false), // integrator
+      optionalAccount(programId, false), // compliance_policy
+      optionalAccount(programId, false), // delegate
+      optionalAccount(programId, false), // parent_commitment
+      optionalAccount(derivePDA(SEED_FEE_TREASURY), withFeeTreasury),
     ],
     programId,
     data,
   });
 }
 
-export function buildRevealReasoningIx(
+/**
+ * Build a start_reveal instruction for an unchunked trace of
+ * `traceLength` bytes, passing the agent's optional accounts that exist.
+ */
+export async function buildStartRevealIx(
   authority: PublicKey,
   commitmentAddress: PublicKey,
-  reasoningUri: string
-): TransactionInstruction {
+  actionType: string,
+  reasoningUri: string,
+  traceLength: number
+): Promise<TransactionInstruction> {
   const [agentProfile] = deriveAgentPDA(authority);
+  const metrics = derivePDA(SEED_METRICS, agentProfile.toBuffer());
+  const summary = derivePDA(SEED_SUMMARY, agentProfile.toBuffer());
+  const actionStats = derivePDA(
+    SEED_ACTION_STATS,
+    agentProfile.toBuffer(),
+    Buffer.from(actionType, "utf-8")
+  );
   const data = Buffer.concat([
-    DISCRIMINATORS.revealReasoning,
-    encodeString(reasoningUri),
+    DISCRIMINATORS.startReveal,
+    encodeString(reasoningUri), // reasoning_uri: String
+    encodeU64(traceLength), // trace_len: u64
+    Buffer.from([1, 0, 0, 0]), // chunk_count: u32 (unchunked)
+    encodeU8(0), // salt: None
   ]);
 
   return new TransactionInstruction({
     keys: [
       { pubkey: commitmentAddress, isSigner: false, isWritable: true },
       { pubkey: agentProfile, isSigner: false, isWritable: true },
-      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: deriveActivityPDA(agentProfile), isSigner: false, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: derivePDA(SEED_CONFIG), isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      optionalAccount(programId, false), // delegate
+      optionalAccount(metrics, await accountExists(metrics)),
+      optionalAccount(summary, await accountExists(summary)),
+      optionalAccount(actionStats, await accountExists(actionStats)),
     ],
     programId,
     data,
   });
 }
 
+/**
+ * Build a finalize_reveal instruction, sendable once the reveal's grace
+ * period has passed.
+ */
+export async function buildFinalizeRevealIx(
+  authority: PublicKey,
+  commitmentAddress: PublicKey
+): Promise<TransactionInstruction> {
+  const [agentProfile] = deriveAgentPDA(authority);
+  const summary = derivePDA(SEED_SUMMARY, agentProfile.toBuffer());
+  const feeTreasury = derivePDA(SEED_FEE_TREASURY);
+  // The fee treasury and authority, so an earned fee rebate is paid
+  const withRebate = await accountExists(feeTreasury);
+
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: commitmentAddress, isSigner: false, isWritable: true },
+      { pubkey: agentProfile, isSigner: false, isWritable: true },
+      { pubkey: derivePDA(SEED_CONFIG), isSigner: false, isWritable: false },
+      optionalAccount(summary, await accountExists(summary)),
+      optionalAccount(feeTreasury, withRebate),
+      optionalAccount(authority, withRebate),
+    ],
+    programId,
+    data: DISCRIMINATORS.finalizeReveal,
+  });
+}
+
 // ─── Account Deserialization ───────────────────────────────────────────────
 
 export function deserializeAgentProfile(
@@ -392,7 +511,8 @@ export function deserializeCommitment(
   const timestamp = Number(data.readBigInt64LE(offset));
   offset += 8;
 
-  const revealed = data[offset] === 1;
+  // status: CommitmentStatus, where the old `revealed: bool` was
+  const status = COMMITMENT_STATUSES[data[offset]];
   offset += 1;
 
   const [reasoningUri, ruEnd] = readString(data, offset);
@@ -409,7 +529,8 @@ export function deserializeCommitment(
     actionType,
     confidence,
     timestamp,
-    revealed,
+    status,
+    revealed: status === "revealed",
     reasoningUri: reasoningUri || null,
     bump,
   };
@@ -438,6 +559,11 @@ export async function fetchCommitment(
   return deserializeCommitment(Buffer.from(info.data));
 }
 
+/** Whether the protocol's fee treasury exists, and so must be passed */
+export async function feeTreasuryExists(): Promise<boolean> {
+  return accountExists(derivePDA(SEED_FEE_TREASURY));
+}
+
 /** Send a transaction with the configured wallet */
 export async function sendTransaction(
   ix: TransactionInstruction
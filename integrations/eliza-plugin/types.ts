@@ -86,6 +86,8 @@ export interface OnChainCommitment {
   actionType: string;
   confidence: number;
   timestamp: number;
+  /** Reveal progress: started by REVEAL_REASONING, then finalized */
+  status: "committed" | "revealing" | "revealed";
   revealed: boolean;
   reasoningUri: string | null;
   bump: number;
@@ -119,6 +121,10 @@ export interface RevealReasoningContent extends Content {
   commitmentAddress?: string;
   /** URI where the full reasoning is stored (e.g., IPFS, Arweave) */
   reasoningUri?: string;
+  /** Byte length of the stored trace, from the COMMIT_REASONING result */
+  traceLength?: number;
+  /** Finalize a started reveal once its grace period has passed */
+  finalize?: boolean;
 }
 
 export interface VerifyReasoningContent extends Content {
@@ -8,7 +8,8 @@
  * Tools:
  *   - solprism_register_agent: Register an agent on SOLPRISM
  *   - solprism_commit_reasoning: Commit a reasoning hash onchain
- *   - solprism_reveal_reasoning: Reveal reasoning after action
+ *   - solprism_reveal_reasoning: Reveal reasoning after action, then
+ *     finalize the reveal once its grace period has passed
  *   - solprism_verify_reasoning: Verify a commitment matches reasoning
  *   - solprism_get_agent: Get agent profile and stats
  */
@@ -42,9 +43,13 @@ const RPC_URL =
 const DISC = {
   registerAgent: Buffer.from([135, 157, 66, 195, 2, 113, 175, 30]),
   commitReasoning: Buffer.from([163, 80, 25, 135, 94, 49, 218, 44]),
-  revealReasoning: Buffer.from([76, 215, 6, 241, 209, 207, 84, 96]),
+  startReveal: Buffer.from([53, 233, 172, 216, 107, 91, 161, 239]),
+  finalizeReveal: Buffer.from([50, 41, 16, 48, 114, 165, 131, 206]),
 };
 
+/** Offset of `action_type` in a ReasoningCommitment account */
+const ACTION_TYPE_OFFSET = 8 + 32 + 32 + 32;
+
 // Load wallet if available
 function loadWallet(): Keypair | null {
   const keypairPath =
@@ -67,28 +72,50 @@ function deriveAgentPda(authority: PublicKey): PublicKey {
   return pda;
 }
 
-function deriveCommitmentPda(
-  authority: PublicKey,
-  commitId: string
-): PublicKey {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from("commitment"),
-      authority.toBuffer(),
-      Buffer.from(commitId),
-    ],
-    PROGRAM_ID
-  );
+function derivePda(...seeds: Buffer[]): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(seeds, PROGRAM_ID);
   return pda;
 }
 
+function deriveCommitmentPda(agentPda: PublicKey, nonce: bigint): PublicKey {
+  return derivePda(Buffer.from("commitment"), agentPda.toBuffer(), encodeU64(nonce));
+}
+
+/** The agent's activity bucket for today, as the program picks it */
+function deriveActivityPda(agentPda: PublicKey): PublicKey {
+  const day = Buffer.alloc(8);
+  day.writeBigInt64LE(BigInt(Math.floor(Date.now() / 1000 / 86_400)));
+  return derivePda(Buffer.from("activity"), agentPda.toBuffer(), day);
+}
+
 function encodeString(s: string): Buffer {
-  const buf = Buffer.alloc(4 + s.length);
-  buf.writeUInt32LE(s.length, 0);
-  buf.write(s, 4);
+  const bytes = Buffer.from(s, "utf-8");
+  const buf = Buffer.alloc(4 + bytes.length);
+  buf.writeUInt32LE(bytes.length, 0);
+  bytes.copy(buf, 4);
   return buf;
 }
 
+function encodeU64(n: bigint | number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(BigInt(n));
+  return buf;
+}
+
+// Anchor reads the program ID in an optional account's slot as "none"
+const NONE = { pubkey: PROGRAM_ID, isSigner: false, isWritable: false };
+
+/** Account meta for an optional account, or `NONE` if it doesn't exist */
+async function optionalAccount(
+  connection: Connection,
+  pubkey: PublicKey,
+  isWritable = true
+) {
+  return (await connection.getAccountInfo(pubkey))
+    ? { pubkey, isSigner: false, isWritable }
+    : NONE;
+}
+
 const server = new Server(
   { name: "solprism", version: "0.1.0" },
   { capabilities: { tools: {} } }
@@ -124,33 +151,46 @@ server.setRequestHandler(ListToolsRequestSchema, async () => ({
             description:
               "JSON string of the reasoning trace to commit. Will be hashed with SHA-256.",
           },
-          commitId: {
+          actionType: {
             type: "string",
             description:
-              "Unique identifier for this commitment (e.g. 'trade-eth-2026-02-03')",
+              "Type of the action the reasoning is for (e.g. 'trade'; default 'decision')",
+          },
+          confidence: {
+            type: "number",
+            description: "Confidence in the decision, 0-100 (default 80)",
           },
         },
-        required: ["reasoning", "commitId"],
+        required: ["reasoning"],
       },
     },
     {
       name: "solprism_reveal_reasoning",
       description:
-        "Reveal the reasoning behind a previous commitment. Do this AFTER executing the action.",
+        "Reveal the reasoning behind a previous commitment. Do this AFTER executing the action, then call again with finalize once the protocol's grace period has passed.",
       inputSchema: {
         type: "object" as const,
         properties: {
-          commitId: {
+          commitment: {
             type: "string",
-            description: "The commit ID used when committing",
+            description: "Address of the commitment, as returned when committing",
           },
           storageUri: {
             type: "string",
             description:
               "URI where the full reasoning JSON is stored (e.g. IPFS, Arweave, or HTTPS URL)",
           },
+          traceLength: {
+            type: "number",
+            description: "Byte length of the stored reasoning, as returned when committing",
+          },
+          finalize: {
+            type: "boolean",
+            description:
+              "Finalize a started reveal instead; storageUri and traceLength are then unused",
+          },
         },
-        required: ["commitId", "storageUri"],
+        required: ["commitment"],
       },
     },
     {
@@ -235,6 +275,7 @@ server.setRequestHandler(CallToolRequestSchema, async (request) => {
           keys: [
             { pubkey: agentPda, isSigner: false, isWritable: true },
             { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
+            { pubkey: derivePda(Buffer.from("config")), isSigner: false, isWritable: false },
             {
               pubkey: SystemProgram.programId,
               isSigner: false,
@@ -279,28 +320,72 @@ server.setRequestHandler(CallToolRequestSchema, async (request) => {
           };
 
         const reasoning = args?.reasoning as string;
-        const commitId = args?.commitId as string;
+        const actionType = (args?.actionType as string) || "decision";
+        const confidence = Math.min(100, Math.max(0, (args?.confidence as number) ?? 80));
 
         const hash = createHash("sha256").update(reasoning).digest();
         const agentPda = deriveAgentPda(wallet.publicKey);
-        const commitPda = deriveCommitmentPda(wallet.publicKey, commitId);
+        const agent = await connection.getAccountInfo(agentPda);
+        if (!agent) {
+          return {
+            content: [
+              {
+                type: "text" as const,
+                text: "Error: Agent not registered. Call solprism_register_agent first.",
+              },
+            ],
+          };
+        }
+        // The next nonce is the profile's total_commitments
+        const nameLen = agent.data.readUInt32LE(40);
+        const nonce = agent.data.readBigUInt64LE(44 + nameLen);
+        const commitPda = deriveCommitmentPda(agentPda, nonce);
 
+        // CommitArgs, in field order
         const data = Buffer.concat([
           DISC.commitReasoning,
-          hash,
-          encodeString(commitId),
+          hash, // commitment_hash: [u8; 32]
+          encodeString(actionType), // action_type: String
+          Buffer.from([confidence]), // confidence: u8
+          encodeU64(nonce), // nonce: u64
+          Buffer.from([
+            0, // category: Financial
+            0, // chunk_manifest: None
+            0, // counterparty: None
+            0, // risk: None
+            0, // confidence_source: Unspecified
+            0, // simulation: false
+            0, // reveal_deadline: None
+            0, // hash_algorithm: Sha256
+          ]),
         ]);
 
         const ix = new TransactionInstruction({
           keys: [
             { pubkey: commitPda, isSigner: false, isWritable: true },
             { pubkey: agentPda, isSigner: false, isWritable: true },
+            { pubkey: deriveActivityPda(agentPda), isSigner: false, isWritable: true },
+            {
+              pubkey: derivePda(
+                Buffer.from("action_stats"),
+                agentPda.toBuffer(),
+                Buffer.from(actionType, "utf-8")
+              ),
+              isSigner: false,
+              isWritable: true,
+            },
             { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
+            { pubkey: derivePda(Buffer.from("config")), isSigner: false, isWritable: false },
             {
               pubkey: SystemProgram.programId,
               isSigner: false,
               isWritable: false,
             },
+            NONE, // integrator
+            NONE, // compliance_policy
+            NONE, // delegate
+            NONE, // parent_commitment
+            await optionalAccount(connection, derivePda(Buffer.from("fee_treasury"))),
           ],
           programId: PROGRAM_ID,
           data,
@@ -322,7 +407,7 @@ server.setRequestHandler(CallToolRequestSchema, async (request) => {
           content: [
             {
               type: "text" as const,
-              text: `Reasoning committed on SOLPRISM.\nCommit ID: ${commitId}\nHash: ${hash.toString("hex")}\nPDA: ${commitPda.toBase58()}\nTransaction: ${sig}\n\nNow execute your action, then call solprism_reveal_reasoning to reveal.`,
+              text: `Reasoning committed on SOLPRISM.\nHash: ${hash.toString("hex")}\nCommitment: ${commitPda.toBase58()}\nTrace length: ${Buffer.byteLength(reasoning, "utf-8")}\nTransaction: ${sig}\n\nNow execute your action, then call solprism_reveal_reasoning to reveal.`,
             },
           ],
         };
@@ -339,26 +424,98 @@ server.setRequestHandler(CallToolRequestSchema, async (request) => {
             ],
           };
 
-        const commitId = args?.commitId as string;
+        const commitPda = new PublicKey(args?.commitment as string);
         const storageUri = args?.storageUri as string;
+        const finalize = (args?.finalize as boolean) ?? false;
 
-        const agentPda = deriveAgentPda(wallet.publicKey);
-        const commitPda = deriveCommitmentPda(wallet.publicKey, commitId);
-
-        const data = Buffer.concat([
-          DISC.revealReasoning,
-          encodeString(storageUri),
-        ]);
+        if (!finalize && !(storageUri && (args?.traceLength as number) > 0)) {
+          return {
+            content: [
+              {
+                type: "text" as const,
+                text: "Error: storageUri and traceLength are required to start a reveal.",
+              },
+            ],
+          };
+        }
 
-        const ix = new TransactionInstruction({
-          keys: [
-            { pubkey: commitPda, isSigner: false, isWritable: true },
-            { pubkey: agentPda, isSigner: false, isWritable: true },
-            { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
-          ],
-          programId: PROGRAM_ID,
-          data,
-        });
+        const commitment = await connection.getAccountInfo(commitPda);
+        if (!commitment) {
+          return {
+            content: [
+              {
+                type: "text" as const,
+                text: `Error: Commitment ${commitPda.toBase58()} not found onchain.`,
+              },
+            ],
+          };
+        }
+        const agentPda = deriveAgentPda(wallet.publicKey);
+        const summary = await optionalAccount(
+          connection,
+          derivePda(Buffer.from("summary"), agentPda.toBuffer())
+        );
+
+        let ix: TransactionInstruction;
+        if (finalize) {
+          const feeTreasury = await optionalAccount(
+            connection,
+            derivePda(Buffer.from("fee_treasury"))
+          );
+          ix = new TransactionInstruction({
+            keys: [
+              { pubkey: commitPda, isSigner: false, isWritable: true },
+              { pubkey: agentPda, isSigner: false, isWritable: true },
+              { pubkey: derivePda(Buffer.from("config")), isSigner: false, isWritable: false },
+              summary,
+              // The fee treasury and authority, so an earned fee rebate is paid
+              feeTreasury,
+              feeTreasury.pubkey.equals(PROGRAM_ID)
+                ? NONE
+                : { pubkey: wallet.publicKey, isSigner: false, isWritable: true },
+            ],
+            programId: PROGRAM_ID,
+            data: DISC.finalizeReveal,
+          });
+        } else {
+          const actionTypeLen = commitment.data.readUInt32LE(ACTION_TYPE_OFFSET);
+          const actionType = commitment.data.subarray(
+            ACTION_TYPE_OFFSET + 4,
+            ACTION_TYPE_OFFSET + 4 + actionTypeLen
+          );
+          ix = new TransactionInstruction({
+            keys: [
+              { pubkey: commitPda, isSigner: false, isWritable: true },
+              { pubkey: agentPda, isSigner: false, isWritable: true },
+              { pubkey: deriveActivityPda(agentPda), isSigner: false, isWritable: true },
+              { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
+              { pubkey: derivePda(Buffer.from("config")), isSigner: false, isWritable: false },
+              {
+                pubkey: SystemProgram.programId,
+                isSigner: false,
+                isWritable: false,
+              },
+              NONE, // delegate
+              await optionalAccount(
+                connection,
+                derivePda(Buffer.from("metrics"), agentPda.toBuffer())
+              ),
+              summary,
+              await optionalAccount(
+                connection,
+                derivePda(Buffer.from("action_stats"), agentPda.toBuffer(), actionType)
+              ),
+            ],
+            programId: PROGRAM_ID,
+            data: Buffer.concat([
+              DISC.startReveal,
+              encodeString(storageUri), // reasoning_uri: String
+              encodeU64(args?.traceLength as number), // trace_len: u64
+              Buffer.from([1, 0, 0, 0]), // chunk_count: u32 (unchunked)
+              Buffer.from([0]), // salt: None
+            ]),
+          });
+        }
 
         const tx = new Transaction().add(ix);
         tx.feePayer = wallet.publicKey;
@@ -376,7 +533,9 @@ server.setRequestHandler(CallToolRequestSchema, async (request) => {
           content: [
             {
               type: "text" as const,
-              text: `Reasoning revealed.\nCommit ID: ${commitId}\nStorage: ${storageUri}\nTransaction: ${sig}`,
+              text: finalize
+                ? `Reveal finalized.\nCommitment: ${commitPda.toBase58()}\nTransaction: ${sig}`
+                : `Reveal started.\nCommitment: ${commitPda.toBase58()}\nStorage: ${storageUri}\nTransaction: ${sig}\n\nCall solprism_reveal_reasoning with finalize once the grace period has passed.`,
             },
           ],
         };
@@ -422,7 +581,7 @@ server.setRequestHandler(CallToolRequestSchema, async (request) => {
         const authorityBytes = data.subarray(8, 40);
         const nameLen = data.readUInt32LE(40);
         const agentName = data.subarray(44, 44 + nameLen).toString("utf-8");
-        const commitCount = data.readUInt32LE(44 + nameLen);
+        const commitCount = data.readBigUInt64LE(44 + nameLen);
 
         return {
           content: [
@@ -185,6 +185,7 @@ export function createSolprismActions(
           input: {
             commitmentAddress: "9abc...",
             reasoningUri: "ipfs://QmXyz...",
+            traceLength: 1234,
           },
           output: {
             signature: "3k8P...",
@@ -202,7 +203,16 @@ export function createSolprismActions(
         .describe("Base58 address of the commitment PDA"),
       reasoningUri: z
         .string()
+        .optional()
         .describe("URI where full reasoning is stored (ipfs://, ar://, https://)"),
+      traceLength: z
+        .number()
+        .optional()
+        .describe("Byte length of the stored trace, from the commit result"),
+      finalize: z
+        .boolean()
+        .optional()
+        .describe("Complete a started reveal once the grace period has passed"),
     }),
     handler: async (_agent: any, input: Record<string, any>) => {
       const result = await revealTool.invoke(JSON.stringify(input) as any);
@@ -272,7 +282,9 @@ export function createSolprismActions(
  *
  * // Now accessible via enhanced.methods
  * await enhanced.methods.commitReasoning("My reasoning...", "trade", 90);
- * await enhanced.methods.revealReasoning("9abc...", "ipfs://...");
+ * await enhanced.methods.revealReasoning("9abc...", "ipfs://...", 1234);
+ * // ...after the grace period
+ * await enhanced.methods.finalizeReveal("9abc...");
  * const result = await enhanced.methods.verifyReasoning("9abc...", "My reasoning...");
  * ```
  */
@@ -309,13 +321,25 @@ export function createSolprismPlugin(
 
       /**
        * Reveal reasoning by attaching a URI to a commitment.
+       * `traceLength` is the commit result's byte length of the trace.
        */
       revealReasoning: async (
         commitmentAddress: string,
         reasoningUri: string,
+        traceLength: number,
       ) => {
         const result = await revealTool.invoke(
-          JSON.stringify({ commitmentAddress, reasoningUri }) as any,
+          JSON.stringify({ commitmentAddress, reasoningUri, traceLength }) as any,
+        );
+        return JSON.parse(result as string);
+      },
+
+      /**
+       * Complete a reveal once the protocol's grace period has passed.
+       */
+      finalizeReveal: async (commitmentAddress: string) => {
+        const result = await revealTool.invoke(
+          JSON.stringify({ commitmentAddress, finalize: true }) as any,
         );
         return JSON.parse(result as string);
       },
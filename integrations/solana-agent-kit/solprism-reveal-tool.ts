@@ -7,7 +7,9 @@
  *
  * After committing and executing, the agent calls reveal to attach
  * a URI (e.g. IPFS, Arweave, or HTTPS) pointing to the full reasoning
- * trace. This makes it publicly auditable.
+ * trace. This makes it publicly auditable. The reveal counts once it is
+ * finalized after the protocol's grace period, by calling the tool
+ * again with `finalize: true`.
  *
  * @example
  * ```ts
@@ -15,6 +17,7 @@
  * const result = await tool.call(JSON.stringify({
  *   commitmentAddress: "9abc...",
  *   reasoningUri: "ipfs://Qm...",
+ *   traceLength: 1234,
  * }));
  * ```
  */
@@ -37,10 +40,20 @@ export const SOLPRISM_PROGRAM_ID = new PublicKey(
 );
 
 const DISCRIMINATORS = {
-  revealReasoning: Buffer.from([76, 215, 6, 241, 209, 207, 84, 96]),
+  startReveal: Buffer.from([53, 233, 172, 216, 107, 91, 161, 239]),
+  finalizeReveal: Buffer.from([50, 41, 16, 48, 114, 165, 131, 206]),
 } as const;
 
 const SEED_AGENT = Buffer.from("agent");
+const SEED_CONFIG = Buffer.from("config");
+const SEED_ACTIVITY = Buffer.from("activity");
+const SEED_ACTION_STATS = Buffer.from("action_stats");
+const SEED_METRICS = Buffer.from("metrics");
+const SEED_SUMMARY = Buffer.from("summary");
+const SEED_FEE_TREASURY = Buffer.from("fee_treasury");
+
+/** Offset of `action_type` in a ReasoningCommitment account */
+const ACTION_TYPE_OFFSET = 8 + 32 + 32 + 32;
 
 // ─── Types ──────────────────────────────────────────────────────────────────
 
@@ -52,6 +65,13 @@ export interface RevealReasoningInput {
    * Common schemes: ipfs://, ar://, https://
    */
   reasoningUri: string;
+  /** Byte length of the published trace (`traceLength` from the commit tool) */
+  traceLength: number;
+  /**
+   * Finalize an already started reveal instead; send once the grace
+   * period has passed. `reasoningUri` and `traceLength` are then unused.
+   */
+  finalize?: boolean;
 }
 
 export interface RevealReasoningOutput {
@@ -75,6 +95,10 @@ function deriveAgentPDA(
   );
 }
 
+function deriveSeededPDA(seeds: Buffer[], programId: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
 function encodeString(s: string): Buffer {
   const bytes = Buffer.from(s, "utf-8");
   const buf = Buffer.alloc(4 + bytes.length);
@@ -83,36 +107,113 @@ function encodeString(s: string): Buffer {
   return buf;
 }
 
-function buildRevealReasoningIx(
+function encodeU64(n: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(BigInt(n));
+  return buf;
+}
+
+/** Which of the agent's optional reveal accounts exist */
+interface RevealAccounts {
+  metrics: boolean;
+  summary: boolean;
+  actionStats: boolean;
+  feeTreasury: boolean;
+}
+
+function buildStartRevealIx(
   authority: PublicKey,
   commitmentAddress: PublicKey,
+  actionType: string,
   reasoningUri: string,
+  traceLength: number,
+  accounts: RevealAccounts,
   programId: PublicKey,
 ): TransactionInstruction {
   const [agentProfile] = deriveAgentPDA(authority, programId);
+  const day = Buffer.alloc(8);
+  day.writeBigInt64LE(BigInt(Math.floor(Date.now() / 1000 / 86_400)));
+  // Anchor reads the program ID in an optional account's slot as "none"
+  const optional = (pubkey: PublicKey, present: boolean) =>
+    present
+      ? { pubkey, isSigner: false, isWritable: true }
+      : { pubkey: programId, isSigner: false, isWritable: false };
 
   return new TransactionInstruction({
     keys: [
       { pubkey: commitmentAddress, isSigner: false, isWritable: true },
       { pubkey: agentProfile, isSigner: false, isWritable: true },
-      { pubkey: authority, isSigner: true, isWritable: false },
+      {
+        pubkey: deriveSeededPDA([SEED_ACTIVITY, agentProfile.toBuffer(), day], programId),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: deriveSeededPDA([SEED_CONFIG], programId), isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      optional(programId, false), // delegate
+      optional(deriveSeededPDA([SEED_METRICS, agentProfile.toBuffer()], programId), accounts.metrics),
+      optional(deriveSeededPDA([SEED_SUMMARY, agentProfile.toBuffer()], programId), accounts.summary),
+      optional(
+        deriveSeededPDA(
+          [SEED_ACTION_STATS, agentProfile.toBuffer(), Buffer.from(actionType, "utf-8")],
+          programId,
+        ),
+        accounts.actionStats,
+      ),
     ],
     programId,
     data: Buffer.concat([
-      DISCRIMINATORS.revealReasoning,
-      encodeString(reasoningUri),
+      DISCRIMINATORS.startReveal,
+      encodeString(reasoningUri), // reasoning_uri: String
+      encodeU64(traceLength), // trace_len: u64
+      Buffer.from([1, 0, 0, 0]), // chunk_count: u32 (unchunked)
+      Buffer.from([0]), // salt: None
     ]),
   });
 }
 
+function buildFinalizeRevealIx(
+  authority: PublicKey,
+  commitmentAddress: PublicKey,
+  accounts: RevealAccounts,
+  programId: PublicKey,
+): TransactionInstruction {
+  const [agentProfile] = deriveAgentPDA(authority, programId);
+  const none = { pubkey: programId, isSigner: false, isWritable: false };
+  const feeTreasury = deriveSeededPDA([SEED_FEE_TREASURY], programId);
+
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: commitmentAddress, isSigner: false, isWritable: true },
+      { pubkey: agentProfile, isSigner: false, isWritable: true },
+      { pubkey: deriveSeededPDA([SEED_CONFIG], programId), isSigner: false, isWritable: false },
+      accounts.summary
+        ? {
+            pubkey: deriveSeededPDA([SEED_SUMMARY, agentProfile.toBuffer()], programId),
+            isSigner: false,
+            isWritable: true,
+          }
+        : none,
+      // The fee treasury and authority, so an earned fee rebate is paid
+      accounts.feeTreasury ? { pubkey: feeTreasury, isSigner: false, isWritable: true } : none,
+      accounts.feeTreasury ? { pubkey: authority, isSigner: false, isWritable: true } : none,
+    ],
+    programId,
+    data: DISCRIMINATORS.finalizeReveal,
+  });
+}
+
 // ─── Tool ───────────────────────────────────────────────────────────────────
 
 /**
  * LangChain tool that reveals the full reasoning for an onchain
  * SOLPRISM commitment by attaching a URI.
  *
- * **Input:** JSON string with `commitmentAddress` (base58) and
- * `reasoningUri` (where the full trace is stored).
+ * **Input:** JSON string with `commitmentAddress` (base58),
+ * `reasoningUri` (where the full trace is stored) and `traceLength`
+ * (its size in bytes), or with `commitmentAddress` and `finalize: true`
+ * to complete the reveal after the grace period.
  *
  * **Output:** JSON string with the transaction signature and metadata.
  *
@@ -126,7 +227,9 @@ export class SolprismRevealReasoningTool extends Tool {
     "Reveal the full reasoning for a previously committed SOLPRISM hash. " +
     "Input is a JSON string with: commitmentAddress (base58 string of the " +
     "commitment PDA), reasoningUri (URI where full reasoning is stored, " +
-    "e.g. ipfs://..., ar://..., or https://...). " +
+    "e.g. ipfs://..., ar://..., or https://...), traceLength (byte length of " +
+    "the stored trace, returned by the commit tool). Call again with " +
+    "commitmentAddress and finalize: true once the grace period has passed. " +
     "Returns the transaction signature. Must be called by the original committer.";
 
   private connection: Connection;
@@ -157,7 +260,7 @@ export class SolprismRevealReasoningTool extends Tool {
         });
       }
 
-      if (!parsed.reasoningUri) {
+      if (!parsed.finalize && !parsed.reasoningUri) {
         return JSON.stringify({
           error:
             "Missing required field: reasoningUri " +
@@ -165,8 +268,16 @@ export class SolprismRevealReasoningTool extends Tool {
         });
       }
 
+      if (!parsed.finalize && !(parsed.traceLength > 0)) {
+        return JSON.stringify({
+          error:
+            "Missing required field: traceLength " +
+            "(byte length of the stored trace, from the commit tool)",
+        });
+      }
+
       // Validate the URI has a scheme
-      if (!/^[a-z][a-z0-9+.-]*:\/\//i.test(parsed.reasoningUri)) {
+      if (!parsed.finalize && !/^[a-z][a-z0-9+.-]*:\/\//i.test(parsed.reasoningUri)) {
         return JSON.stringify({
           error:
             `Invalid reasoningUri: "${parsed.reasoningUri}". ` +
@@ -186,15 +297,45 @@ export class SolprismRevealReasoningTool extends Tool {
         });
       }
 
-      // Check if already revealed (revealed flag is at a variable offset,
-      // but we can still try the tx — the program will reject with a clear error)
+      // The program rejects a commitment that is already revealed with a
+      // clear error, so there is no need to check its status here
+      const data = Buffer.from(accountInfo.data);
+      const actionTypeLen = data.readUInt32LE(ACTION_TYPE_OFFSET);
+      const actionType = data
+        .slice(ACTION_TYPE_OFFSET + 4, ACTION_TYPE_OFFSET + 4 + actionTypeLen)
+        .toString("utf-8");
+      const [agentProfile] = deriveAgentPDA(this.wallet.publicKey, this.programId);
+      const exists = async (seeds: Buffer[]) =>
+        (await this.connection.getAccountInfo(
+          deriveSeededPDA(seeds, this.programId),
+        )) !== null;
+      const accounts: RevealAccounts = {
+        metrics: await exists([SEED_METRICS, agentProfile.toBuffer()]),
+        summary: await exists([SEED_SUMMARY, agentProfile.toBuffer()]),
+        actionStats: await exists([
+          SEED_ACTION_STATS,
+          agentProfile.toBuffer(),
+          Buffer.from(actionType, "utf-8"),
+        ]),
+        feeTreasury: await exists([SEED_FEE_TREASURY]),
+      };
 
-      const ix = buildRevealReasoningIx(
-        this.wallet.publicKey,
-        commitPubkey,
-        parsed.reasoningUri,
-        this.programId,
-      );
+      const ix = parsed.finalize
+        ? buildFinalizeRevealIx(
+            this.wallet.publicKey,
+            commitPubkey,
+            accounts,
+            this.programId,
+          )
+        : buildStartRevealIx(
+            this.wallet.publicKey,
+            commitPubkey,
+            actionType,
+            parsed.reasoningUri,
+            parsed.traceLength,
+            accounts,
+            this.programId,
+          );
 
       const tx = new Transaction().add(ix);
       const signature = await sendAndConfirmTransaction(
@@ -50,6 +50,10 @@ const AGENT_PROFILE_DISCRIMINATOR = Buffer.from([
 
 const SEED_AGENT = Buffer.from("agent");
 const SEED_COMMITMENT = Buffer.from("commitment");
+const SEED_CONFIG = Buffer.from("config");
+const SEED_ACTIVITY = Buffer.from("activity");
+const SEED_ACTION_STATS = Buffer.from("action_stats");
+const SEED_FEE_TREASURY = Buffer.from("fee_treasury");
 
 // ─── Types ──────────────────────────────────────────────────────────────────
 
@@ -75,6 +79,8 @@ export interface CommitReasoningOutput {
   commitmentHash: string;
   /** Nonce used for this commitment */
   nonce: number;
+  /** Byte length of the hashed reasoning; pass it to the reveal tool */
+  traceLength: number;
 }
 
 // ─── Helpers ────────────────────────────────────────────────────────────────
@@ -102,6 +108,23 @@ function deriveCommitmentPDA(
   );
 }
 
+function deriveSeededPDA(
+  seeds: Buffer[],
+  programId: PublicKey = SOLPRISM_PROGRAM_ID,
+): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
+/** The agent's activity bucket for today, as the program picks it */
+function deriveActivityPDA(
+  agentProfile: PublicKey,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID,
+): PublicKey {
+  const day = Buffer.alloc(8);
+  day.writeBigInt64LE(BigInt(Math.floor(Date.now() / 1000 / 86_400)));
+  return deriveSeededPDA([SEED_ACTIVITY, agentProfile.toBuffer(), day], programId);
+}
+
 function encodeString(s: string): Buffer {
   const bytes = Buffer.from(s, "utf-8");
   const buf = Buffer.alloc(4 + bytes.length);
@@ -179,6 +202,7 @@ function buildRegisterAgentIx(
     keys: [
       { pubkey: agentProfile, isSigner: false, isWritable: true },
       { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: deriveSeededPDA([SEED_CONFIG], programId), isSigner: false, isWritable: false },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
     programId,
@@ -192,25 +216,53 @@ function buildCommitReasoningIx(
   actionType: string,
   confidence: number,
   nonce: number,
+  withFeeTreasury: boolean,
   programId: PublicKey,
 ): TransactionInstruction {
   const [agentProfile] = deriveAgentPDA(authority, programId);
   const [commitment] = deriveCommitmentPDA(agentProfile, nonce, programId);
+  const actionStats = deriveSeededPDA(
+    [SEED_ACTION_STATS, agentProfile.toBuffer(), Buffer.from(actionType, "utf-8")],
+    programId,
+  );
+  // Anchor reads the program ID in an optional account's slot as "none"
+  const none = { pubkey: programId, isSigner: false, isWritable: false };
 
   return new TransactionInstruction({
     keys: [
       { pubkey: commitment, isSigner: false, isWritable: true },
       { pubkey: agentProfile, isSigner: false, isWritable: true },
+      { pubkey: deriveActivityPDA(agentProfile, programId), isSigner: false, isWritable: true },
+      { pubkey: actionStats, isSigner: false, isWritable: true },
       { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: deriveSeededPDA([SEED_CONFIG], programId), isSigner: false, isWritable: false },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      none, // integrator
+      none, // compliance_policy
+      none, // delegate
+      none, // parent_commitment
+      withFeeTreasury
+        ? { pubkey: deriveSeededPDA([SEED_FEE_TREASURY], programId), isSigner: false, isWritable: true }
+        : none,
     ],
     programId,
+    // CommitArgs, in field order
     data: Buffer.concat([
       DISCRIMINATORS.commitReasoning,
-      commitmentHash, // [u8; 32]
-      encodeString(actionType), // String (Borsh)
-      Buffer.from([confidence & 0xff]), // u8
-      encodeU64(nonce), // u64
+      commitmentHash, // commitment_hash: [u8; 32]
+      encodeString(actionType), // action_type: String (Borsh)
+      Buffer.from([confidence & 0xff]), // confidence: u8
+      encodeU64(nonce), // nonce: u64
+      Buffer.from([
+        0, // category: Financial
+        0, // chunk_manifest: None
+        0, // counterparty: None
+        0, // risk: None
+        0, // confidence_source: Unspecified
+        0, // simulation: false
+        0, // reveal_deadline: None
+        0, // hash_algorithm: Sha256
+      ]),
     ]),
   });
 }
@@ -264,7 +316,7 @@ export class SolprismCommitReasoningTool extends Tool {
       const agentName = parsed.agentName ?? "solana-agent";
 
       // 1. Hash the reasoning deterministically
-      const { hash, hex } = hashReasoning(reasoning);
+      const { hash, hex, canonical } = hashReasoning(reasoning);
 
       // 2. Ensure agent is registered (auto-register on first use)
       let nonce = await readAgentNonce(
@@ -296,6 +348,9 @@ export class SolprismCommitReasoningTool extends Tool {
         actionType,
         confidence,
         nonce,
+        (await this.connection.getAccountInfo(
+          deriveSeededPDA([SEED_FEE_TREASURY], this.programId),
+        )) !== null,
         this.programId,
       );
 
@@ -323,6 +378,7 @@ export class SolprismCommitReasoningTool extends Tool {
         commitmentAddress: commitmentAddress.toBase58(),
         commitmentHash: hex,
         nonce,
+        traceLength: Buffer.byteLength(canonical, "utf8"),
       };
 
       return JSON.stringify(result);
@@ -126,7 +126,8 @@ function deserializeCommitment(data: Buffer): OnChainCommitmentData {
   const timestamp = Number(data.readBigInt64LE(offset));
   offset += 8;
 
-  // revealed: bool
+  // status: CommitmentStatus (1 = Revealed), where the old
+  // `revealed: bool` was
   const revealed = data[offset] === 1;
   offset += 1;
 
@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use axiom::cpi::accounts::CommitReasoning;
+use axiom::program::Axiom;
+use axiom::{
+    AgentProfile, CommitArgs, CommitmentCategory, ConfidenceSource, HashAlgorithm, RevealDeadline,
+};
+
+declare_id!("FgKtERAzZTv8oAKHADz2VTXVY5QjfZxLggGYFX8kQCWM");
+
+/// Action type of the commitments made for swaps.
+pub const SWAP_ACTION: &str = "swap";
+/// Seconds after a swap within which its reasoning must be revealed.
+pub const REVEAL_WINDOW: i64 = 86_400;
+
+/// Example integration — a trading agent on a toy DEX.
+///
+/// The pool is a constant-product market over two virtual reserves; no
+/// tokens move, so the example stays about the integration. Agents
+/// trade through `swap`, which commits the agent's reasoning to
+/// SOLPRISM by CPI in the same instruction as the trade. The commitment
+/// lands if and only if the trade does, and always before it, so every
+/// trade an agent makes here has a commitment it must reveal within
+/// `REVEAL_WINDOW` or lose score.
+#[program]
+pub mod axiom_example_dex {
+    use super::*;
+
+    /// Open a pool with the given reserves and fee.
+    pub fn create_pool(
+        ctx: Context<CreatePool>,
+        pool_id: u64,
+        reserve_a: u64,
+        reserve_b: u64,
+        fee_bps: u16,
+    ) -> Result<()> {
+        require!(reserve_a > 0 && reserve_b > 0, DexError::EmptyReserve);
+        require!(fee_bps < 10_000, DexError::InvalidFee);
+        let pool = &mut ctx.accounts.pool;
+        pool.pool_id = pool_id;
+        pool.creator = ctx.accounts.creator.key();
+        pool.reserve_a = reserve_a;
+        pool.reserve_b = reserve_b;
+        pool.fee_bps = fee_bps;
+        pool.bump = ctx.bumps.pool;
+        Ok(())
+    }
+
+    /// Commit the agent's reasoning for this trade, then swap
+    /// `amount_in` of one reserve for at least `min_out` of the other.
+    ///
+    /// `commitment_hash` is the hash of the reasoning trace the agent
+    /// produced before sending the transaction; `nonce` picks the
+    /// commitment's address. Signed by the agent's authority, which
+    /// pays for the commitment.
+    pub fn swap(
+        ctx: Context<Swap>,
+        a_to_b: bool,
+        amount_in: u64,
+        min_out: u64,
+        commitment_hash: [u8; 32],
+        confidence: u8,
+        nonce: u64,
+    ) -> Result<()> {
+        let amount_out = ctx.accounts.pool.quote(a_to_b, amount_in)?;
+        require!(amount_out >= min_out, DexError::SlippageExceeded);
+        
+        let accounts = &ctx.accounts;
+        axiom::cpi::commit_reasoning(
+            CpiContext::new(
+                accounts.axiom_program.to_account_info(),
+                CommitReasoning {
+                    commitment: accounts.commitment.to_account_info(),
+                    agent_profile: accounts.agent_profile.to_account_info(),
+                    activity_bucket: accounts.activity_bucket.to_account_info(),
+                    action_stats: accounts.action_stats.to_account_info(),
+                    authority: accounts.authority.to_account_info(),
+                    config: accounts.axiom_config.to_account_info(),
+                    system_program: accounts.system_program.to_account_info(),
+                    integrator: None,
+                    compliance_policy: None,
+                    delegate: None,
+                    parent_commitment: None,
+                    fee_treasury: accounts.fee_treasury.as_ref().map(|t| t.to_account_info()),
+                },
+            ),
+            CommitArgs {
+                commitment_hash,
+                action_type: SWAP_ACTION.to_string(),
+                confidence,
+                nonce,
+                category: CommitmentCategory::Financial,
+                chunk_manifest: None,
+                counterparty: Some(accounts.pool.key()),
+                risk: None,
+                confidence_source: ConfidenceSource::Unspecified,
+                simulation: false,
+                reveal_deadline: Some(RevealDeadline::After(REVEAL_WINDOW)),
+                hash_algorithm: HashAlgorithm::Sha256,
+            },
+        )?;
+        
+        let pool = &mut ctx.accounts.pool;
+        let Pool { reserve_a, reserve_b, .. } = &mut **pool;
+        let (reserve_in, reserve_out) = if a_to_b {
+            (reserve_a, reserve_b)
+        } else {
+            (reserve_b, reserve_a)
+        };
+        *reserve_in = reserve_in.checked_add(amount_in).ok_or(DexError::Overflow)?;
+        *reserve_out -= amount_out;
+        
+        emit!(Swapped {
+            pool: pool.key(),
+            agent: ctx.accounts.agent_profile.key(),
+            commitment: ctx.accounts.commitment.key(),
+            a_to_b,
+            amount_in,
+            amount_out,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+// ─── Account Structs ───────────────────────────────────────────────────────
+
+/// A constant-product pool over two virtual reserves
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    /// Distinguishes the creator's pools
+    pub pool_id: u64,
+    /// Who opened the pool
+    pub creator: Pubkey,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    /// Fee taken from each input (basis points)
+    pub fee_bps: u16,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Pool {
+    /// What `amount_in` buys at the current reserves, after the fee.
+    pub fn quote(&self, a_to_b: bool, amount_in: u64) -> Result<u64> {
+        let (reserve_in, reserve_out) = if a_to_b {
+            (self.reserve_a, self.reserve_b)
+        } else {
+            (self.reserve_b, self.reserve_a)
+        };
+        let in_after_fee = amount_in as u128 * (10_000 - self.fee_bps) as u128 / 10_000;
+        let out = reserve_out as u128 * in_after_fee / (reserve_in as u128 + in_after_fee);
+        require!(out > 0 && out < reserve_out as u128, DexError::EmptyReserve);
+        Ok(out as u64)
+    }
+}
+
+// ─── Instruction Contexts ──────────────────────────────────────────────────
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct CreatePool<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool", creator.key().as_ref(), &pool_id.to_le_bytes()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: created and checked by `commit_reasoning`
+    #[account(mut)]
+    pub commitment: UncheckedAccount<'info>,
+    
+    /// CHECK: created and checked by `commit_reasoning`
+    #[account(mut)]
+    pub activity_bucket: UncheckedAccount<'info>,
+    
+    /// CHECK: created and checked by `commit_reasoning`
+    #[account(mut)]
+    pub action_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: SOLPRISM's `ProtocolConfig`; checked by `commit_reasoning`
+    pub axiom_config: UncheckedAccount<'info>,
+    
+    /// CHECK: SOLPRISM's `FeeTreasury`, needed while it charges a commit
+    /// fee; checked by `commit_reasoning`
+    #[account(mut)]
+    pub fee_treasury: Option<UncheckedAccount<'info>>,
+    
+    pub axiom_program: Program<'info, Axiom>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// ─── Events ────────────────────────────────────────────────────────────────
+
+#[event]
+pub struct Swapped {
+    pub pool: Pubkey,
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub a_to_b: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub timestamp: i64,
+}
+
+// ─── Errors ────────────────────────────────────────────────────────────────
+
+#[error_code]
+pub enum DexError {
+    #[msg("Reserves can't cover the trade")]
+    EmptyReserve,
+    #[msg("Fee must be below 10000 basis points")]
+    InvalidFee,
+    #[msg("Trade would return less than the minimum")]
+    SlippageExceeded,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
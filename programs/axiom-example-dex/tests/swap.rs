@@ -0,0 +1,173 @@
+//! `swap` against the SBF builds of this program and SOLPRISM, checking
+//! that the commitment and the trade land together or not at all.
+//!
+//! Needs both programs built:
+//!
+//! ```sh
+//! anchor build
+//! cargo test -p axiom-example-dex -- --ignored
+//! ```
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData};
+use axiom::{AgentProfile, AxiomError, CommitmentStatus, ProtocolConfig, ReasoningCommitment};
+use axiom_bench::*;
+use axiom_example_dex::{Pool, REVEAL_WINDOW, SWAP_ACTION};
+use mollusk_svm::program::{
+    create_program_account_loader_v3, keyed_account_for_system_program, loader_keys,
+};
+use mollusk_svm::result::Check;
+use mollusk_svm::Mollusk;
+use solana_account::Account;
+
+/// Activity bucket for the harness clock, which starts at the Unix epoch.
+const DAY: i64 = 0;
+const RESERVE: u64 = 1_000_000;
+
+fn harness() -> Mollusk {
+    if std::env::var_os("SBF_OUT_DIR").is_none() {
+        let deploy = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy");
+        std::env::set_var("SBF_OUT_DIR", deploy);
+    }
+    let mut mollusk = Mollusk::new(&axiom_example_dex::ID, "axiom_example_dex");
+    mollusk.add_program(&axiom::ID, "axiom", &loader_keys::LOADER_V3);
+    mollusk
+}
+
+fn pool(creator: &Pubkey) -> KeyedAccount {
+    let (address, bump) = Pubkey::find_program_address(
+        &[b"pool", creator.as_ref(), &0u64.to_le_bytes()],
+        &axiom_example_dex::ID,
+    );
+    let pool = Pool {
+        pool_id: 0,
+        creator: *creator,
+        reserve_a: RESERVE,
+        reserve_b: RESERVE,
+        fee_bps: 30,
+        bump,
+    };
+    (address, owned_account(&pool, 8 + Pool::INIT_SPACE, axiom_example_dex::ID))
+}
+
+fn swap_ix(pool: &Pubkey, authority: &Pubkey, amount_in: u64, min_out: u64) -> Instruction {
+    let (agent_profile, _) = agent_address(authority);
+    Instruction {
+        program_id: axiom_example_dex::ID,
+        accounts: axiom_example_dex::accounts::Swap {
+            pool: *pool,
+            agent_profile,
+            authority: *authority,
+            commitment: commitment_address(&agent_profile, 0).0,
+            activity_bucket: activity_address(&agent_profile, DAY).0,
+            action_stats: action_stats_address(&agent_profile, SWAP_ACTION).0,
+            axiom_config: config_address().0,
+            fee_treasury: None,
+            axiom_program: axiom::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: axiom_example_dex::instruction::Swap {
+            a_to_b: true,
+            amount_in,
+            min_out,
+            commitment_hash: [7; 32],
+            confidence: 80,
+            nonce: 0,
+        }
+        .data(),
+    }
+}
+
+/// Everything `swap_ix` touches, with `commitment` at the nonce it uses.
+fn swap_accounts(
+    pool: KeyedAccount,
+    authority: &Pubkey,
+    commitment: Account,
+    config: KeyedAccount,
+) -> Vec<KeyedAccount> {
+    let profile = agent_profile(authority, "trader");
+    let commitment = (commitment_address(&profile.0, 0).0, commitment);
+    let bucket = activity_address(&profile.0, DAY).0;
+    let stats = action_stats_address(&profile.0, SWAP_ACTION).0;
+    vec![
+        pool,
+        profile,
+        (*authority, wallet(PAYER_LAMPORTS)),
+        commitment,
+        (bucket, uninitialized()),
+        (stats, uninitialized()),
+        config,
+        (axiom_example_dex::ID, create_program_account_loader_v3(&axiom_example_dex::ID)),
+        (axiom::ID, create_program_account_loader_v3(&axiom::ID)),
+        keyed_account_for_system_program(),
+    ]
+}
+
+fn decode<T: AccountDeserialize>(account: &Account) -> T {
+    T::try_deserialize(&mut account.data.as_slice()).expect("account decodes")
+}
+
+#[test]
+#[ignore = "needs the SBF builds (anchor build)"]
+fn swap_commits_before_trading() {
+    let mollusk = harness();
+    let authority = Pubkey::new_unique();
+    let pool = pool(&authority);
+    let quote = decode::<Pool>(&pool.1).quote(true, 10_000).unwrap();
+    let ix = swap_ix(&pool.0, &authority, 10_000, quote);
+    let accounts = swap_accounts(pool, &authority, uninitialized(), protocol_config(&authority));
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let traded: Pool = decode(result.get_account(&accounts[0].0).unwrap());
+    assert_eq!(traded.reserve_a, RESERVE + 10_000);
+    assert_eq!(traded.reserve_b, RESERVE - quote);
+
+    let profile: AgentProfile = decode(result.get_account(&accounts[1].0).unwrap());
+    assert_eq!(profile.total_commitments, 1);
+
+    let account = result.get_account(&accounts[3].0).unwrap();
+    assert_eq!(account.owner, axiom::ID);
+    let commitment: ReasoningCommitment = decode(account);
+    assert_eq!(commitment.agent, accounts[1].0);
+    assert_eq!(commitment.authority, authority);
+    assert_eq!(commitment.commitment_hash, [7; 32]);
+    assert_eq!(commitment.action_type, SWAP_ACTION);
+    assert_eq!(commitment.counterparty, Some(accounts[0].0));
+    assert_eq!(commitment.status, CommitmentStatus::Committed);
+    assert_eq!(commitment.reveal_deadline, Some(REVEAL_WINDOW));
+}
+
+#[test]
+#[ignore = "needs the SBF builds (anchor build)"]
+fn swap_fails_when_the_commit_does() {
+    let mollusk = harness();
+    let authority = Pubkey::new_unique();
+    let pool = pool(&authority);
+    let ix = swap_ix(&pool.0, &authority, 10_000, 1);
+
+    let (config, account) = protocol_config(&authority);
+    let mut paused: ProtocolConfig = decode(&account);
+    paused.paused = true;
+    let config = (config, program_account(&paused, 8 + ProtocolConfig::INIT_SPACE));
+    let accounts = swap_accounts(pool, &authority, uninitialized(), config);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::Custom(AxiomError::ProtocolPaused.into()))],
+    );
+}
+
+#[test]
+#[ignore = "needs the SBF builds (anchor build)"]
+fn swap_fails_on_a_taken_nonce() {
+    let mollusk = harness();
+    let authority = Pubkey::new_unique();
+    let pool = pool(&authority);
+    let ix = swap_ix(&pool.0, &authority, 10_000, 1);
+    let (_, taken) = commitment(&authority, 0, SWAP_ACTION);
+    let accounts = swap_accounts(pool, &authority, taken, protocol_config(&authority));
+    let result = mollusk.process_instruction(&ix, &accounts);
+    assert!(result.program_result.is_err());
+}
@@ -0,0 +1,415 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use axiom::cpi::accounts::{CommitReasoning, RegisterAgent, StartReveal};
+use axiom::program::Axiom;
+use axiom::{
+    AgentProfile, CommitArgs, CommitmentCategory, CommitmentStatus, ConfidenceSource,
+    HashAlgorithm, ReasoningCommitment,
+};
+
+declare_id!("7MPMRhykSXMMhRcfcHk5qDqRdHRxPpj8LzPBeSSr4rjS");
+
+/// Action type of the commitments made for payments.
+pub const PAYMENT_ACTION: &str = "treasury_payment";
+
+/// Example integration — a treasury run by a manager agent.
+///
+/// The treasury's lamports sit in a vault PDA that is also the agent's
+/// SOLPRISM authority, so only this program can commit or reveal for
+/// it, signing by CPI. A payment goes through three steps:
+/// `propose_payment` commits the manager's reasoning, `reveal_payment`
+/// publishes it, and once SOLPRISM has finalized the reveal (after its
+/// grace period, when verifiers have had time to check the trace)
+/// anyone can `execute_payment`. Funds never move on reasoning that
+/// wasn't made public first.
+#[program]
+pub mod axiom_example_treasury {
+    use super::*;
+
+    /// Open a treasury managed by the signer, move `deposit` lamports
+    /// into its vault and register the vault as a SOLPRISM agent.
+    ///
+    /// The deposit must cover the agent profile's rent and leave the
+    /// vault rent exempt.
+    pub fn create_treasury(ctx: Context<CreateTreasury>, name: String, deposit: u64) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.manager = ctx.accounts.manager.key();
+        treasury.agent_profile = ctx.accounts.agent_profile.key();
+        treasury.vault_bump = ctx.bumps.vault;
+        treasury.bump = ctx.bumps.treasury;
+        
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.manager.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+        
+        let treasury_key = treasury.key();
+        let seeds: &[&[u8]] = &[b"vault", treasury_key.as_ref(), &[treasury.vault_bump]];
+        axiom::cpi::register_agent(
+            CpiContext::new_with_signer(
+                ctx.accounts.axiom_program.to_account_info(),
+                RegisterAgent {
+                    agent_profile: ctx.accounts.agent_profile.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                    config: ctx.accounts.axiom_config.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                &[seeds],
+            ),
+            name,
+        )
+    }
+
+    /// Propose paying `amount` lamports to `recipient`, committing the
+    /// manager's reasoning for it. Manager only.
+    ///
+    /// The vault pays the commitment's rent; the manager pays for the
+    /// `Payment` record and gets it back on execution.
+    pub fn propose_payment(
+        ctx: Context<ProposePayment>,
+        recipient: Pubkey,
+        amount: u64,
+        commitment_hash: [u8; 32],
+        confidence: u8,
+        nonce: u64,
+    ) -> Result<()> {
+        require!(amount > 0, TreasuryError::InvalidAmount);
+        let accounts = &ctx.accounts;
+        let treasury_key = accounts.treasury.key();
+        let seeds: &[&[u8]] = &[b"vault", treasury_key.as_ref(), &[accounts.treasury.vault_bump]];
+        axiom::cpi::commit_reasoning(
+            CpiContext::new_with_signer(
+                accounts.axiom_program.to_account_info(),
+                CommitReasoning {
+                    commitment: accounts.commitment.to_account_info(),
+                    agent_profile: accounts.agent_profile.to_account_info(),
+                    activity_bucket: accounts.activity_bucket.to_account_info(),
+                    action_stats: accounts.action_stats.to_account_info(),
+                    authority: accounts.vault.to_account_info(),
+                    config: accounts.axiom_config.to_account_info(),
+                    system_program: accounts.system_program.to_account_info(),
+                    integrator: None,
+                    compliance_policy: None,
+                    delegate: None,
+                    parent_commitment: None,
+                    fee_treasury: accounts.fee_treasury.as_ref().map(|t| t.to_account_info()),
+                },
+                &[seeds],
+            ),
+            CommitArgs {
+                commitment_hash,
+                action_type: PAYMENT_ACTION.to_string(),
+                confidence,
+                nonce,
+                category: CommitmentCategory::Financial,
+                chunk_manifest: None,
+                counterparty: Some(recipient),
+                risk: None,
+                confidence_source: ConfidenceSource::Unspecified,
+                simulation: false,
+                reveal_deadline: None,
+                hash_algorithm: HashAlgorithm::Sha256,
+            },
+        )?;
+        
+        let payment = &mut ctx.accounts.payment;
+        payment.treasury = treasury_key;
+        payment.commitment = ctx.accounts.commitment.key();
+        payment.recipient = recipient;
+        payment.amount = amount;
+        payment.bump = ctx.bumps.payment;
+        
+        emit!(PaymentProposed {
+            treasury: treasury_key,
+            payment: payment.key(),
+            commitment: payment.commitment,
+            recipient,
+            amount,
+        });
+        
+        Ok(())
+    }
+
+    /// Publish the reasoning behind a proposed payment, as one trace of
+    /// `trace_len` bytes at `reasoning_uri`. Manager only.
+    pub fn reveal_payment(
+        ctx: Context<RevealPayment>,
+        reasoning_uri: String,
+        trace_len: u64,
+    ) -> Result<()> {
+        let accounts = &ctx.accounts;
+        let treasury_key = accounts.treasury.key();
+        let seeds: &[&[u8]] = &[b"vault", treasury_key.as_ref(), &[accounts.treasury.vault_bump]];
+        axiom::cpi::start_reveal(
+            CpiContext::new_with_signer(
+                accounts.axiom_program.to_account_info(),
+                StartReveal {
+                    commitment: accounts.commitment.to_account_info(),
+                    agent_profile: accounts.agent_profile.to_account_info(),
+                    activity_bucket: accounts.activity_bucket.to_account_info(),
+                    authority: accounts.vault.to_account_info(),
+                    config: accounts.axiom_config.to_account_info(),
+                    system_program: accounts.system_program.to_account_info(),
+                    delegate: None,
+                    metrics: None,
+                    summary: None,
+                    action_stats: Some(accounts.action_stats.to_account_info()),
+                },
+                &[seeds],
+            ),
+            reasoning_uri,
+            trace_len,
+            1,
+            None,
+        )
+    }
+
+    /// Pay out a proposal whose reasoning SOLPRISM has finalized as
+    /// revealed, closing the `Payment`. Permissionless.
+    pub fn execute_payment(ctx: Context<ExecutePayment>) -> Result<()> {
+        require!(
+            ctx.accounts.commitment.status == CommitmentStatus::Revealed,
+            TreasuryError::NotRevealed
+        );
+        let payment = &ctx.accounts.payment;
+        let treasury = &ctx.accounts.treasury;
+        let treasury_key = treasury.key();
+        let seeds: &[&[u8]] = &[b"vault", treasury_key.as_ref(), &[treasury.vault_bump]];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.recipient.to_account_info(),
+                },
+                &[seeds],
+            ),
+            payment.amount,
+        )?;
+        
+        emit!(PaymentExecuted {
+            treasury: treasury_key,
+            payment: payment.key(),
+            commitment: payment.commitment,
+            recipient: payment.recipient,
+            amount: payment.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+// ─── Account Structs ───────────────────────────────────────────────────────
+
+/// A treasury and the SOLPRISM agent managing it
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    /// Who proposes and reveals payments
+    pub manager: Pubkey,
+    /// The vault's agent profile
+    pub agent_profile: Pubkey,
+    /// Bump of the vault PDA holding the funds
+    pub vault_bump: u8,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A payment waiting on its reasoning to be revealed
+#[account]
+#[derive(InitSpace)]
+pub struct Payment {
+    pub treasury: Pubkey,
+    /// The commitment to the manager's reasoning for the payment
+    pub commitment: Pubkey,
+    pub recipient: Pubkey,
+    /// Lamports to pay
+    pub amount: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+// ─── Instruction Contexts ──────────────────────────────────────────────────
+
+#[derive(Accounts)]
+pub struct CreateTreasury<'info> {
+    #[account(
+        init,
+        payer = manager,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [b"treasury", manager.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    /// Holds the funds; the agent's SOLPRISM authority
+    #[account(mut, seeds = [b"vault", treasury.key().as_ref()], bump)]
+    pub vault: SystemAccount<'info>,
+    
+    /// CHECK: created and checked by `register_agent`
+    #[account(mut)]
+    pub agent_profile: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    
+    /// CHECK: SOLPRISM's `ProtocolConfig`; checked by `register_agent`
+    pub axiom_config: UncheckedAccount<'info>,
+    
+    pub axiom_program: Program<'info, Axiom>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposePayment<'info> {
+    #[account(has_one = manager, has_one = agent_profile)]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(mut, seeds = [b"vault", treasury.key().as_ref()], bump = treasury.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    #[account(
+        init,
+        payer = manager,
+        space = 8 + Payment::INIT_SPACE,
+        seeds = [b"payment", commitment.key().as_ref()],
+        bump
+    )]
+    pub payment: Account<'info, Payment>,
+    
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    /// CHECK: created and checked by `commit_reasoning`
+    #[account(mut)]
+    pub commitment: UncheckedAccount<'info>,
+    
+    /// CHECK: created and checked by `commit_reasoning`
+    #[account(mut)]
+    pub activity_bucket: UncheckedAccount<'info>,
+    
+    /// CHECK: created and checked by `commit_reasoning`
+    #[account(mut)]
+    pub action_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: SOLPRISM's `ProtocolConfig`; checked by `commit_reasoning`
+    pub axiom_config: UncheckedAccount<'info>,
+    
+    /// CHECK: SOLPRISM's `FeeTreasury`, needed while it charges a commit
+    /// fee; checked by `commit_reasoning`
+    #[account(mut)]
+    pub fee_treasury: Option<UncheckedAccount<'info>>,
+    
+    #[account(mut)]
+    pub manager: Signer<'info>,
+    
+    pub axiom_program: Program<'info, Axiom>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealPayment<'info> {
+    #[account(has_one = manager, has_one = agent_profile)]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(mut, seeds = [b"vault", treasury.key().as_ref()], bump = treasury.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    #[account(has_one = treasury, has_one = commitment)]
+    pub payment: Account<'info, Payment>,
+    
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    /// CHECK: checked by `start_reveal`
+    #[account(mut)]
+    pub commitment: UncheckedAccount<'info>,
+    
+    /// CHECK: created and checked by `start_reveal`
+    #[account(mut)]
+    pub activity_bucket: UncheckedAccount<'info>,
+    
+    /// CHECK: checked by `start_reveal`
+    #[account(mut)]
+    pub action_stats: UncheckedAccount<'info>,
+    
+    /// CHECK: SOLPRISM's `ProtocolConfig`; checked by `start_reveal`
+    pub axiom_config: UncheckedAccount<'info>,
+    
+    pub manager: Signer<'info>,
+    
+    pub axiom_program: Program<'info, Axiom>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecutePayment<'info> {
+    #[account(has_one = manager)]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(mut, seeds = [b"vault", treasury.key().as_ref()], bump = treasury.vault_bump)]
+    pub vault: SystemAccount<'info>,
+    
+    #[account(
+        mut,
+        close = manager,
+        has_one = treasury,
+        has_one = commitment,
+        has_one = recipient
+    )]
+    pub payment: Account<'info, Payment>,
+    
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    /// CHECK: only receives lamports; checked against the payment
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    
+    /// CHECK: only receives the payment's rent; checked against the
+    /// treasury
+    #[account(mut)]
+    pub manager: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// ─── Events ────────────────────────────────────────────────────────────────
+
+#[event]
+pub struct PaymentProposed {
+    pub treasury: Pubkey,
+    pub payment: Pubkey,
+    pub commitment: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PaymentExecuted {
+    pub treasury: Pubkey,
+    pub payment: Pubkey,
+    pub commitment: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// ─── Errors ────────────────────────────────────────────────────────────────
+
+#[error_code]
+pub enum TreasuryError {
+    #[msg("Payment amount must be positive")]
+    InvalidAmount,
+    #[msg("The payment's reasoning has not been revealed and finalized")]
+    NotRevealed,
+}
@@ -0,0 +1,262 @@
+//! The treasury's payment flow against the SBF builds of this program
+//! and SOLPRISM: the vault registers and commits by signed CPI, and no
+//! lamports leave it before the commitment is revealed.
+//!
+//! Needs both programs built:
+//!
+//! ```sh
+//! anchor build
+//! cargo test -p axiom-example-treasury -- --ignored
+//! ```
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData};
+use axiom::{AgentProfile, CommitmentStatus, ReasoningCommitment};
+use axiom_bench::*;
+use axiom_example_treasury::{Payment, Treasury, TreasuryError, PAYMENT_ACTION};
+use mollusk_svm::program::{
+    create_program_account_loader_v3, keyed_account_for_system_program, loader_keys,
+};
+use mollusk_svm::result::Check;
+use mollusk_svm::Mollusk;
+use solana_account::Account;
+
+/// Activity bucket for the harness clock, which starts at the Unix epoch.
+const DAY: i64 = 0;
+const VAULT_LAMPORTS: u64 = 1_000_000_000;
+const AMOUNT: u64 = 250_000_000;
+
+fn harness() -> Mollusk {
+    if std::env::var_os("SBF_OUT_DIR").is_none() {
+        let deploy = concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy");
+        std::env::set_var("SBF_OUT_DIR", deploy);
+    }
+    let mut mollusk = Mollusk::new(&axiom_example_treasury::ID, "axiom_example_treasury");
+    mollusk.add_program(&axiom::ID, "axiom", &loader_keys::LOADER_V3);
+    mollusk
+}
+
+fn treasury_address(manager: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"treasury", manager.as_ref()], &axiom_example_treasury::ID)
+}
+
+fn vault_address(treasury: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", treasury.as_ref()], &axiom_example_treasury::ID)
+}
+
+fn payment_address(commitment: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"payment", commitment.as_ref()], &axiom_example_treasury::ID).0
+}
+
+/// An open treasury of `manager`, with its vault holding `VAULT_LAMPORTS`.
+fn treasury(manager: &Pubkey) -> (KeyedAccount, KeyedAccount) {
+    let (address, bump) = treasury_address(manager);
+    let (vault, vault_bump) = vault_address(&address);
+    let treasury = Treasury {
+        manager: *manager,
+        agent_profile: agent_address(&vault).0,
+        vault_bump,
+        bump,
+    };
+    (
+        (address, owned_account(&treasury, 8 + Treasury::INIT_SPACE, axiom_example_treasury::ID)),
+        (vault, wallet(VAULT_LAMPORTS)),
+    )
+}
+
+fn program_accounts() -> [KeyedAccount; 3] {
+    [
+        (axiom_example_treasury::ID, create_program_account_loader_v3(&axiom_example_treasury::ID)),
+        (axiom::ID, create_program_account_loader_v3(&axiom::ID)),
+        keyed_account_for_system_program(),
+    ]
+}
+
+fn decode<T: AccountDeserialize>(account: &Account) -> T {
+    T::try_deserialize(&mut account.data.as_slice()).expect("account decodes")
+}
+
+#[test]
+#[ignore = "needs the SBF builds (anchor build)"]
+fn create_treasury_registers_the_vault() {
+    let mollusk = harness();
+    let manager = Pubkey::new_unique();
+    let (treasury, _) = treasury_address(&manager);
+    let (vault, _) = vault_address(&treasury);
+    let (agent_profile, _) = agent_address(&vault);
+    let ix = Instruction {
+        program_id: axiom_example_treasury::ID,
+        accounts: axiom_example_treasury::accounts::CreateTreasury {
+            treasury,
+            vault,
+            agent_profile,
+            manager,
+            axiom_config: config_address().0,
+            axiom_program: axiom::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: axiom_example_treasury::instruction::CreateTreasury {
+            name: "treasury".to_string(),
+            deposit: VAULT_LAMPORTS,
+        }
+        .data(),
+    };
+    let mut accounts = vec![
+        (treasury, uninitialized()),
+        (vault, uninitialized()),
+        (agent_profile, uninitialized()),
+        (manager, wallet(PAYER_LAMPORTS)),
+        protocol_config(&manager),
+    ];
+    accounts.extend(program_accounts());
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let profile = result.get_account(&agent_profile).unwrap();
+    assert_eq!(profile.owner, axiom::ID);
+    assert_eq!(decode::<AgentProfile>(profile).authority, vault);
+    let opened: Treasury = decode(result.get_account(&treasury).unwrap());
+    assert_eq!(opened.agent_profile, agent_profile);
+}
+
+#[test]
+#[ignore = "needs the SBF builds (anchor build)"]
+fn propose_payment_commits_for_the_vault() {
+    let mollusk = harness();
+    let manager = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let (treasury, vault) = treasury(&manager);
+    let profile = agent_profile(&vault.0, "treasury");
+    let (commitment, _) = commitment_address(&profile.0, 0);
+    let (bucket, _) = activity_address(&profile.0, DAY);
+    let (stats, _) = action_stats_address(&profile.0, PAYMENT_ACTION);
+    let payment = payment_address(&commitment);
+    let ix = Instruction {
+        program_id: axiom_example_treasury::ID,
+        accounts: axiom_example_treasury::accounts::ProposePayment {
+            treasury: treasury.0,
+            vault: vault.0,
+            payment,
+            agent_profile: profile.0,
+            commitment,
+            activity_bucket: bucket,
+            action_stats: stats,
+            axiom_config: config_address().0,
+            fee_treasury: None,
+            manager,
+            axiom_program: axiom::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: axiom_example_treasury::instruction::ProposePayment {
+            recipient,
+            amount: AMOUNT,
+            commitment_hash: [7; 32],
+            confidence: 80,
+            nonce: 0,
+        }
+        .data(),
+    };
+    let vault_key = vault.0;
+    let mut accounts = vec![
+        treasury,
+        vault,
+        (payment, uninitialized()),
+        profile,
+        (commitment, uninitialized()),
+        (bucket, uninitialized()),
+        (stats, uninitialized()),
+        protocol_config(&manager),
+        (manager, wallet(PAYER_LAMPORTS)),
+    ];
+    accounts.extend(program_accounts());
+    let result = mollusk.process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+
+    let committed: ReasoningCommitment = decode(result.get_account(&commitment).unwrap());
+    assert_eq!(committed.authority, vault_key);
+    assert_eq!(committed.action_type, PAYMENT_ACTION);
+    assert_eq!(committed.counterparty, Some(recipient));
+    assert_eq!(committed.status, CommitmentStatus::Committed);
+
+    let proposed: Payment = decode(result.get_account(&payment).unwrap());
+    assert_eq!(proposed.commitment, commitment);
+    assert_eq!(proposed.recipient, recipient);
+    assert_eq!(proposed.amount, AMOUNT);
+    // The vault paid the commitment's rent and nothing went to the recipient
+    assert!(result.get_account(&vault_key).unwrap().lamports < VAULT_LAMPORTS);
+}
+
+/// `execute_payment` of a proposal whose commitment is in `status`.
+fn execute_payment(status: CommitmentStatus) -> (Instruction, Vec<KeyedAccount>) {
+    let manager = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let (treasury, vault) = treasury(&manager);
+    let (commitment, account) = commitment(&vault.0, 0, PAYMENT_ACTION);
+    let mut committed: ReasoningCommitment = decode(&account);
+    committed.status = status;
+    let payment = payment_address(&commitment);
+    let proposal = Payment {
+        treasury: treasury.0,
+        commitment,
+        recipient,
+        amount: AMOUNT,
+        bump: 0,
+    };
+    let ix = Instruction {
+        program_id: axiom_example_treasury::ID,
+        accounts: axiom_example_treasury::accounts::ExecutePayment {
+            treasury: treasury.0,
+            vault: vault.0,
+            payment,
+            commitment,
+            recipient,
+            manager,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: axiom_example_treasury::instruction::ExecutePayment {}.data(),
+    };
+    let mut accounts = vec![
+        treasury,
+        vault,
+        (payment, owned_account(&proposal, 8 + Payment::INIT_SPACE, axiom_example_treasury::ID)),
+        (commitment, program_account(&committed, 8 + ReasoningCommitment::INIT_SPACE)),
+        (recipient, wallet(0)),
+        (manager, wallet(PAYER_LAMPORTS)),
+    ];
+    accounts.extend(program_accounts());
+    (ix, accounts)
+}
+
+#[test]
+#[ignore = "needs the SBF builds (anchor build)"]
+fn execute_payment_waits_for_the_reveal() {
+    let mollusk = harness();
+    let (ix, accounts) = execute_payment(CommitmentStatus::Committed);
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::Custom(TreasuryError::NotRevealed.into()))],
+    );
+}
+
+#[test]
+#[ignore = "needs the SBF builds (anchor build)"]
+fn execute_payment_pays_once_revealed() {
+    let mollusk = harness();
+    let (ix, accounts) = execute_payment(CommitmentStatus::Revealed);
+    let vault = accounts[1].0;
+    let payment = accounts[2].0;
+    let recipient = accounts[4].0;
+    mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&recipient).lamports(AMOUNT).build(),
+            Check::account(&vault).lamports(VAULT_LAMPORTS - AMOUNT).build(),
+            Check::account(&payment).closed().build(),
+        ],
+    );
+}
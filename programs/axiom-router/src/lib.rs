@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use axiom::{AgentProfile, CommitmentStatus, ReasoningCommitment};
+
+declare_id!("HgxMGqtnfA3U3bEL7ZoofwfZLfLkRbMxgQCFe6Coj1iE");
+
+/// SOLPRISM router — accountability-gated CPI.
+///
+/// Protocols that only want to act on behalf of accountable agents call
+/// `route` (directly or by CPI) with their requirements and the
+/// instruction to run. The router checks the agent's SOLPRISM profile
+/// and the commitment the agent made for this action, and forwards the
+/// instruction only if both pass. The target program must not be the
+/// caller: Solana rejects reentrant CPIs.
+#[program]
+pub mod axiom_router {
+    use super::*;
+
+    /// Check `requirements` against the agent, then invoke
+    /// `target_program` with `data` and the remaining accounts.
+    ///
+    /// The commitment must be the agent's, still unrevealed (neither
+    /// cancelled nor expired), for `action_type`, and recent enough.
+    /// Signer and writable flags of the remaining accounts are passed
+    /// through as given, so the agent's authority signature reaches the
+    /// target.
+    pub fn route(
+        ctx: Context<Route>,
+        requirements: Requirements,
+        action_type: String,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let profile = &ctx.accounts.agent_profile;
+        let commitment = &ctx.accounts.commitment;
+        let now = Clock::get()?.unix_timestamp;
+        requirements.check(profile, commitment, &action_type, now)?;
+        
+        let target = &ctx.accounts.target_program;
+        let forwarded = ctx.remaining_accounts;
+        let ix = Instruction {
+            program_id: target.key(),
+            accounts: forwarded
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        invoke(&ix, forwarded)?;
+        
+        emit!(Routed {
+            agent: profile.key(),
+            commitment: commitment.key(),
+            target_program: target.key(),
+            action_type,
+            accountability_score: profile.accountability_score,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+// ─── Types ─────────────────────────────────────────────────────────────────
+
+/// What a caller demands of an agent before its action is forwarded.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Requirements {
+    /// Lowest acceptable accountability score (basis points)
+    pub min_score: u16,
+    /// Only agents whose operator an attestor vetted
+    pub require_verified_operator: bool,
+    /// Lowest acceptable stake, in lamports (0 = none needed)
+    pub min_stake_lamports: u64,
+    /// Oldest acceptable commitment, in seconds
+    pub max_commitment_age: i64,
+}
+
+impl Requirements {
+    pub fn check(
+        &self,
+        profile: &AgentProfile,
+        commitment: &ReasoningCommitment,
+        action_type: &str,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            profile.accountability_score >= self.min_score,
+            RouterError::ScoreTooLow
+        );
+        require!(
+            profile.verified_operator || !self.require_verified_operator,
+            RouterError::OperatorNotVerified
+        );
+        require!(
+            profile.staked_lamports >= self.min_stake_lamports,
+            RouterError::StakeTooLow
+        );
+        require!(
+            commitment.status == CommitmentStatus::Committed
+                && !commitment.simulation
+                && !commitment.expired
+                && !commitment.cancelled
+                && commitment.action_type == action_type,
+            RouterError::CommitmentMismatch
+        );
+        require!(
+            now.saturating_sub(commitment.timestamp) <= self.max_commitment_age,
+            RouterError::CommitmentTooOld
+        );
+        Ok(())
+    }
+}
+
+// ─── Instruction Contexts ──────────────────────────────────────────────────
+
+#[derive(Accounts)]
+pub struct Route<'info> {
+    #[account(has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        constraint = commitment.agent == agent_profile.key() @ RouterError::CommitmentMismatch
+    )]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    pub authority: Signer<'info>,
+    
+    /// CHECK: any executable program other than the caller
+    #[account(executable)]
+    pub target_program: UncheckedAccount<'info>,
+}
+
+// ─── Events ────────────────────────────────────────────────────────────────
+
+#[event]
+pub struct Routed {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub target_program: Pubkey,
+    pub action_type: String,
+    pub accountability_score: u16,
+    pub timestamp: i64,
+}
+
+// ─── Errors ────────────────────────────────────────────────────────────────
+
+#[error_code]
+pub enum RouterError {
+    #[msg("Agent's accountability score is below the minimum")]
+    ScoreTooLow,
+    #[msg("Agent's operator is not verified")]
+    OperatorNotVerified,
+    #[msg("Agent's stake is below the minimum")]
+    StakeTooLow,
+    #[msg("Commitment is not the agent's open commitment for this action")]
+    CommitmentMismatch,
+    #[msg("Commitment is too old")]
+    CommitmentTooOld,
+}
@@ -1,7 +1,66 @@
+// The generated CPI wrappers take every instruction argument, so they
+// can't carry the `allow` put on the long handlers themselves.
+#![cfg_attr(feature = "cpi", allow(clippy::too_many_arguments))]
+
 use anchor_lang::prelude::*;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+
+#[cfg(any(
+    all(feature = "mainnet", feature = "devnet"),
+    all(feature = "mainnet", feature = "testnet"),
+    all(feature = "devnet", feature = "testnet"),
+))]
+compile_error!("Enable at most one of the `mainnet`, `devnet` and `testnet` features");
+
+#[cfg(all(feature = "mainnet", feature = "test-instructions"))]
+compile_error!("`test-instructions` must never be enabled for a mainnet build");
+
+#[cfg(feature = "testnet")]
+compile_error!("SOLPRISM has no testnet deployment yet; build with `devnet` instead");
+
+#[cfg(feature = "mainnet")]
+declare_id!("CZcvoryaQNrtZ3qb3gC1h9opcYpzEP1D9Mu1RVwFQeBu");
+
+#[cfg(feature = "devnet")]
+declare_id!("CZcvoryaQNrtZ3qb3gC1h9opcYpzEP1D9Mu1RVwFQeBu");
 
+#[cfg(not(any(feature = "mainnet", feature = "devnet", feature = "testnet")))]
 declare_id!("CZcvoryaQNrtZ3qb3gC1h9opcYpzEP1D9Mu1RVwFQeBu");
 
+/// Cluster this build targets, selected by cargo feature.
+///
+/// Each cluster gets its own `declare_id!` above so a redeploy to one
+/// cluster never silently changes the ID compiled in for another.
+pub mod cluster {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum Cluster {
+        Mainnet,
+        Devnet,
+        Testnet,
+        Localnet,
+    }
+
+    #[cfg(feature = "mainnet")]
+    pub const CLUSTER: Cluster = Cluster::Mainnet;
+    #[cfg(feature = "devnet")]
+    pub const CLUSTER: Cluster = Cluster::Devnet;
+    #[cfg(feature = "testnet")]
+    pub const CLUSTER: Cluster = Cluster::Testnet;
+    #[cfg(not(any(feature = "mainnet", feature = "devnet", feature = "testnet")))]
+    pub const CLUSTER: Cluster = Cluster::Localnet;
+
+    impl Cluster {
+        pub const fn name(self) -> &'static str {
+            match self {
+                Cluster::Mainnet => "mainnet-beta",
+                Cluster::Devnet => "devnet",
+                Cluster::Testnet => "testnet",
+                Cluster::Localnet => "localnet",
+            }
+        }
+    }
+}
+
 /// SOLPRISM — Verifiable AI Reasoning on Solana
 /// 
 /// Agents commit hashes of their reasoning before executing actions.
@@ -16,17 +75,337 @@ pub mod axiom {
         ctx: Context<RegisterAgent>,
         name: String,
     ) -> Result<()> {
-        require!(name.len() <= 64, AxiomError::NameTooLong);
-        require!(!name.is_empty(), AxiomError::NameEmpty);
+        let profile = AgentProfile::new(
+            ctx.accounts.authority.key(),
+            name,
+            None,
+            Clock::get()?.unix_timestamp,
+            ctx.bumps.agent_profile,
+        )?;
+        ctx.accounts.agent_profile.set_inner(profile);
+        Ok(())
+    }
+
+    /// Register an additional agent profile under the same wallet.
+    ///
+    /// The profile lives at `[b"agent", authority, agent_id]` (LE bytes),
+    /// so one authority can run several distinct agents (products,
+    /// models). The id-less `register_agent` profile is unaffected.
+    pub fn register_agent_with_id(
+        ctx: Context<RegisterAgentWithId>,
+        agent_id: u64,
+        name: String,
+    ) -> Result<()> {
+        let mut profile = AgentProfile::new(
+            ctx.accounts.authority.key(),
+            name,
+            None,
+            Clock::get()?.unix_timestamp,
+            ctx.bumps.agent_profile,
+        )?;
+        profile.agent_id = Some(agent_id);
+        ctx.accounts.agent_profile.set_inner(profile);
+        Ok(())
+    }
+
+    /// Register a fleet of agent profiles in one transaction.
+    ///
+    /// `remaining_accounts` holds one (profile, authority) pair per name:
+    /// the profile PDA `[b"agent", authority]`, writable, then its
+    /// authority, which must sign so nobody can claim another wallet's
+    /// profile. The operator pays for every profile and is recorded on
+    /// each as its `operator`.
+    pub fn register_agents_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RegisterAgentsBatch<'info>>,
+        names: Vec<String>,
+    ) -> Result<()> {
+        require!(
+            !names.is_empty() && names.len() <= MAX_AGENT_BATCH,
+            AxiomError::InvalidBatch
+        );
+        require!(
+            ctx.remaining_accounts.len() == names.len() * 2,
+            AxiomError::InvalidBatch
+        );
+        
+        let operator = ctx.accounts.operator.key();
+        let now = Clock::get()?.unix_timestamp;
+        
+        for (name, pair) in names.into_iter().zip(ctx.remaining_accounts.chunks(2)) {
+            let (profile_info, authority) = (&pair[0], &pair[1]);
+            require!(authority.is_signer, AxiomError::Unauthorized);
+            let (address, bump) = Pubkey::find_program_address(
+                &[b"agent", authority.key.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(profile_info.key(), address, AxiomError::InvalidBatch);
+            
+            let profile = AgentProfile::new(authority.key(), name, Some(operator), now, bump)?;
+            create_pda(
+                profile_info,
+                &ctx.accounts.operator,
+                &ctx.accounts.system_program,
+                &[b"agent", authority.key.as_ref(), &[bump]],
+                8 + AgentProfile::INIT_SPACE,
+            )?;
+            profile.try_serialize(&mut &mut profile_info.try_borrow_mut_data()?[..])?;
+        }
+        
+        Ok(())
+    }
+
+    /// Register (or rotate to) a document-signing key.
+    ///
+    /// Lets an inference service sign reasoning traces without holding
+    /// the authority wallet. Each key gets its own `SigningKeyRecord`, and
+    /// rotating retires the previous one (passed as `previous_key`), so
+    /// traces stay attributable to whichever key was active when they
+    /// were committed.
+    pub fn set_signing_key(ctx: Context<SetSigningKey>, signing_key: Pubkey) -> Result<()> {
+        rotate_signing_key(
+            &mut ctx.accounts.agent_profile,
+            ctx.accounts.previous_key.as_mut(),
+            &mut ctx.accounts.signing_key_record,
+            signing_key,
+            ctx.bumps.signing_key_record,
+        )
+    }
+
+    /// Point the agent at its off-chain metadata (model card, operator
+    /// info, avatar), or clear it with `None`. Authority only.
+    ///
+    /// The URI lives in the agent's `AgentMetadata` account rather than
+    /// on the profile itself, whose remaining reserved bytes can't hold one.
+    pub fn update_agent_metadata(
+        ctx: Context<UpdateAgentMetadata>,
+        metadata_uri: Option<String>,
+    ) -> Result<()> {
+        if let Some(uri) = &metadata_uri {
+            require!(
+                !uri.is_empty() && uri.len() <= MAX_METADATA_URI_LEN,
+                AxiomError::InvalidMetadataUri
+            );
+        }
+        let now = Clock::get()?.unix_timestamp;
+        let metadata = &mut ctx.accounts.agent_metadata;
+        metadata.agent = ctx.accounts.agent_profile.key();
+        metadata.metadata_uri = metadata_uri.clone();
+        metadata.updated_at = now;
+        metadata.bump = ctx.bumps.agent_metadata;
+        
+        emit!(AgentMetadataUpdated {
+            agent: metadata.agent,
+            metadata_uri,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Retire the agent (`active = false`) or bring it back. Authority
+    /// only.
+    ///
+    /// An inactive agent can't make new commitments, but keeps its
+    /// history and score, and can still reveal and close what it
+    /// committed to before.
+    pub fn set_agent_status(ctx: Context<SetAgentStatus>, active: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let profile = &mut ctx.accounts.agent_profile;
+        require!(profile.is_active() != active, AxiomError::AgentStatusUnchanged);
+        profile.deactivated_at = (!active).then_some(now);
+        
+        emit!(AgentStatusChanged {
+            agent: profile.key(),
+            active,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Let the agent's retried `commit_reasoning` calls succeed instead of
+    /// failing on the commitment they already made. Authority only.
+    pub fn set_idempotent_commits(
+        ctx: Context<SetIdempotentCommits>,
+        enabled: bool,
+    ) -> Result<()> {
+        ctx.accounts.agent_profile.idempotent_commits = enabled;
+        Ok(())
+    }
+
+    /// Start keeping smoothed metrics for the agent, seeded from its
+    /// lifetime stats. Authority only.
+    ///
+    /// Each reveal, expiry and upheld challenge afterwards updates the
+    /// agent's `AgentMetrics` as it happens, so the account must then be
+    /// passed to `start_reveal`, `expire_commitment` and
+    /// `resolve_challenge`.
+    pub fn open_metrics(ctx: Context<OpenMetrics>) -> Result<()> {
+        let profile = &mut ctx.accounts.agent_profile;
+        let stats = profile.stats(profile.key());
+        let metrics = &mut ctx.accounts.metrics;
+        metrics.agent = stats.agent;
+        metrics.reveal_rate_bps = stats.reveal_rate_bps;
+        metrics.reveal_latency = stats.average_reveal_latency.unwrap_or_default();
+        metrics.dispute_rate_bps = stats.dispute_rate_bps;
+        metrics.updated_at = Clock::get()?.unix_timestamp;
+        metrics.bump = ctx.bumps.metrics;
+        profile.has_metrics = true;
+        Ok(())
+    }
+
+    /// Start keeping a compact `AgentSummary` of the agent for other
+    /// programs to read by CPI or account lookup. Authority only.
+    ///
+    /// Reveals, their finalization, expiries and challenge rulings update
+    /// it as they happen, so the account must then be passed to
+    /// `start_reveal`, `finalize_reveal`, `expire_commitment` and
+    /// `resolve_challenge`; `refresh_summary` catches up with any other
+    /// score change.
+    pub fn open_summary(ctx: Context<OpenSummary>) -> Result<()> {
+        let profile = &mut ctx.accounts.agent_profile;
+        let summary = &mut ctx.accounts.summary;
+        summary.agent = profile.key();
+        summary.reveal_latency = profile.average_reveal_latency().unwrap_or_default();
+        summary.bump = ctx.bumps.summary;
+        summary.sync(profile, Clock::get()?.unix_timestamp);
+        profile.has_summary = true;
+        Ok(())
+    }
+
+    /// Bring an agent's summary up to date with its profile's score.
+    /// Permissionless.
+    pub fn refresh_summary(ctx: Context<RefreshSummary>) -> Result<()> {
+        ctx.accounts.summary.sync(&ctx.accounts.agent_profile, Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+
+    /// Offer control of the agent profile to `new_authority`, e.g. to
+    /// rotate its key or hand it to another operator. Authority only.
+    ///
+    /// Nothing changes until `new_authority` signs
+    /// `accept_authority_transfer`, so a mistyped key can't lock the
+    /// profile. Proposing again replaces the pending offer;
+    /// `cancel_authority_transfer` withdraws it.
+    pub fn propose_authority_transfer(
+        ctx: Context<ProposeAuthorityTransfer>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        require!(
+            new_authority != ctx.accounts.authority.key(),
+            AxiomError::InvalidAuthority
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let transfer = &mut ctx.accounts.authority_transfer;
+        transfer.agent = ctx.accounts.agent_profile.key();
+        transfer.proposed_by = ctx.accounts.authority.key();
+        transfer.new_authority = new_authority;
+        transfer.proposed_at = now;
+        transfer.bump = ctx.bumps.authority_transfer;
+        
+        emit!(AuthorityTransferProposed {
+            agent: transfer.agent,
+            authority: transfer.proposed_by,
+            new_authority,
+            timestamp: now,
+        });
         
+        Ok(())
+    }
+
+    /// Take control of an agent profile offered by
+    /// `propose_authority_transfer`. The profile keeps its address and
+    /// full history; the offer is closed, its rent returned to the
+    /// previous authority.
+    ///
+    /// Commitments record the authority they were made under; open ones
+    /// are moved over with `sync_commitment_authority`.
+    pub fn accept_authority_transfer(ctx: Context<AcceptAuthorityTransfer>) -> Result<()> {
         let profile = &mut ctx.accounts.agent_profile;
-        profile.authority = ctx.accounts.authority.key();
-        profile.name = name;
-        profile.total_commitments = 0;
-        profile.total_verified = 0;
-        profile.accountability_score = 10000; // Start at 100.00% (basis points)
-        profile.created_at = Clock::get()?.unix_timestamp;
-        profile.bump = ctx.bumps.agent_profile;
+        let previous = profile.authority;
+        profile.authority = ctx.accounts.new_authority.key();
+        
+        emit!(AgentAuthorityTransferred {
+            agent: profile.key(),
+            previous_authority: previous,
+            new_authority: profile.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw a pending authority transfer. Authority only.
+    pub fn cancel_authority_transfer(_ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Point a commitment at its agent's current authority after a
+    /// transfer, so the new wallet can reveal, claim and amend it.
+    /// Permissionless: the profile is the source of truth.
+    pub fn sync_commitment_authority(ctx: Context<SyncCommitmentAuthority>) -> Result<()> {
+        let authority = ctx.accounts.agent_profile.authority;
+        let commitment = &mut ctx.accounts.commitment;
+        require!(commitment.authority != authority, AxiomError::InvalidAuthority);
+        commitment.authority = authority;
+        Ok(())
+    }
+
+    /// Let `delegate` commit and start reveals for the agent, e.g. a
+    /// session key held by a hot inference service, so the authority key
+    /// can stay offline. Authority only.
+    ///
+    /// `expires_at` bounds how long the key works; `action_types`, if not
+    /// empty, limits it to those action types. Calling again for the same
+    /// key replaces its terms. Commitments made by a delegate belong to
+    /// the authority as usual.
+    pub fn set_delegate(
+        ctx: Context<SetDelegate>,
+        delegate: Pubkey,
+        expires_at: Option<i64>,
+        action_types: Vec<String>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            delegate != ctx.accounts.authority.key(),
+            AxiomError::InvalidAuthority
+        );
+        require!(
+            expires_at.is_none_or(|at| at > now),
+            AxiomError::InvalidDuration
+        );
+        require!(
+            action_types.len() <= MAX_DELEGATE_ACTION_TYPES
+                && action_types.iter().all(|t| !t.is_empty() && t.len() <= 32),
+            AxiomError::InvalidDelegate
+        );
+        
+        let record = &mut ctx.accounts.delegate_record;
+        record.agent = ctx.accounts.agent_profile.key();
+        record.delegate = delegate;
+        record.expires_at = expires_at;
+        record.action_types = action_types.clone();
+        record.created_at = now;
+        record.bump = ctx.bumps.delegate_record;
+        
+        emit!(DelegateSet {
+            agent: record.agent,
+            delegate,
+            expires_at,
+            action_types,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Revoke a delegate key, closing its record. Authority only.
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        emit!(DelegateRevoked {
+            agent: ctx.accounts.delegate_record.agent,
+            delegate: ctx.accounts.delegate_record.delegate,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
         Ok(())
     }
@@ -36,43 +415,335 @@ pub mod axiom {
     /// The agent publishes the SHA-256 hash of their full reasoning trace.
     /// This must happen BEFORE the action is executed, creating a 
     /// tamper-proof record of the agent's decision-making process.
-    pub fn commit_reasoning(
-        ctx: Context<CommitReasoning>,
+    ///
+    /// For very large traces, pass a `chunk_manifest`: the trace is split
+    /// into fixed-size chunks and `commitment_hash` is the Merkle root
+    /// over them (see [`merkle`]), so each chunk can be checked on its own.
+    ///
+    /// Naming a `counterparty` (e.g. the user whose funds are at stake)
+    /// lets them sign, via `acknowledge_commitment`, that they saw the
+    /// commitment before the action.
+    ///
+    /// `risk` records the stakes the agent believed it was playing for.
+    /// It is self-declared and unchecked, but fixed before the action.
+    /// `confidence_source` says how `confidence` was produced, so
+    /// calibration analysis can keep differently derived numbers apart.
+    ///
+    /// Set `simulation` for paper trades and backtests. Such commitments
+    /// are recorded and revealed as usual but never touch the agent's
+    /// accountability stats; they are only counted in
+    /// `simulated_commitments`.
+    ///
+    /// With a `reveal_deadline`, the reveal must start by then; after it
+    /// anyone may call `expire_commitment`, which costs the agent score.
+    ///
+    /// `hash_algorithm` names the hash `commitment_hash` was made with,
+    /// so onchain checks of the trace use the same one. Chunked
+    /// commitments are always SHA-256 Merkle roots.
+    ///
+    /// A delegate key (see `set_delegate`) may sign in place of the
+    /// authority by passing its `Delegate` account; the commitment still
+    /// belongs to the authority.
+    ///
+    /// Once the agent turns on `set_idempotent_commits`, repeating a
+    /// commit with the same nonce and arguments succeeds without doing
+    /// anything, so clients can retry blindly; a relative deadline
+    /// counts as the same if it resolves the same from the original
+    /// commit time. Different arguments still fail.
+    ///
+    /// Steps of a multi-step plan can pass an earlier commitment of the
+    /// same agent as `parent_commitment`, linking the plan's decisions
+    /// into a tree onchain.
+    ///
+    /// Unless simulated, the commitment is counted in the agent's
+    /// `ActionTypeStats` for `action_type`, which its reveal or expiry
+    /// then updates too.
+    ///
+    /// While the protocol charges a `commit_fee`, the signer pays it into
+    /// the `FeeTreasury`, which must then be passed.
+    pub fn commit_reasoning(ctx: Context<CommitReasoning>, args: CommitArgs) -> Result<()> {
+        let CommitArgs {
+            commitment_hash,
+            action_type,
+            confidence,
+            nonce,
+            category,
+            chunk_manifest,
+            counterparty,
+            risk,
+            confidence_source,
+            simulation,
+            reveal_deadline,
+            hash_algorithm,
+        } = args;
+        let now = Clock::get()?.unix_timestamp;
+        let accounts = ctx.accounts;
+        if accounts.authority.key() != accounts.agent_profile.authority {
+            check_delegate(
+                accounts.delegate.as_deref(),
+                accounts.agent_profile.key(),
+                accounts.authority.key(),
+                &action_type,
+                now,
+            )?;
+        }
+        let existing = &accounts.commitment;
+        if existing.agent != Pubkey::default() {
+            require!(
+                accounts.agent_profile.idempotent_commits,
+                AxiomError::CommitmentExists
+            );
+            let deadline = match reveal_deadline {
+                Some(RevealDeadline::At(at)) => Some(at),
+                Some(RevealDeadline::After(seconds)) => existing.timestamp.checked_add(seconds),
+                None => None,
+            };
+            let same = existing.commitment_hash == commitment_hash
+                && existing.action_type == action_type
+                && existing.confidence == confidence
+                && existing.category == category
+                && existing.chunk_manifest == chunk_manifest
+                && existing.counterparty == counterparty
+                && existing.risk == risk
+                && existing.details_hash.is_none()
+                && !existing.embargoed
+                && existing.confidence_source == confidence_source
+                && existing.simulation == simulation
+                && existing.reveal_deadline == deadline
+                && existing.hash_algorithm == hash_algorithm
+                && existing.parent == accounts.parent_commitment.as_ref().map(|p| p.key());
+            require!(same, AxiomError::CommitmentExists);
+            return Ok(());
+        }
+        
+        let reveal_deadline = reveal_deadline
+            .map(|deadline| deadline.resolve(now))
+            .transpose()?;
+        let new = NewCommitment {
+            commitment_hash,
+            action_type,
+            category,
+            confidence,
+            confidence_source,
+            nonce,
+            chunk_manifest,
+            template: None,
+            counterparty,
+            risk,
+            details_hash: None,
+            simulation,
+            reveal_deadline,
+            embargoed: false,
+            hash_algorithm,
+            parent: accounts.parent_commitment.as_ref().map(|parent| parent.key()),
+        };
+        count_for_integrator(
+            &accounts.config,
+            accounts.integrator.as_mut(),
+            accounts.fee_treasury.as_mut(),
+            accounts.compliance_policy.as_deref(),
+            &accounts.agent_profile,
+            &new,
+        )?;
+        let authority = accounts.agent_profile.authority;
+        record_commitment(
+            &mut accounts.commitment,
+            &mut accounts.agent_profile,
+            &mut accounts.activity_bucket,
+            authority,
+            ctx.bumps.commitment,
+            ctx.bumps.activity_bucket,
+            new,
+        )?;
+        
+        accounts.commitment.fee_paid = charge_commit_fee(
+            &accounts.config,
+            accounts.fee_treasury.as_mut(),
+            &accounts.authority,
+            &accounts.system_program,
+            1,
+        )?;
+        
+        if !simulation {
+            let stats = &mut accounts.action_stats;
+            if stats.agent == Pubkey::default() {
+                stats.agent = accounts.agent_profile.key();
+                stats.action_type = accounts.commitment.action_type.clone();
+                stats.bump = ctx.bumps.action_stats;
+            }
+            stats.commitments = stats.commitments.checked_add(1)
+                .ok_or(AxiomError::Overflow)?;
+            stats.confidence_total = stats.confidence_total.checked_add(u64::from(confidence))
+                .ok_or(AxiomError::Overflow)?;
+            accounts.commitment.in_action_stats = true;
+        }
+        Ok(())
+    }
+
+    /// Share the cost of a new commitment among the parties running the
+    /// agent, e.g. its operator and a sponsor, recording who paid what.
+    ///
+    /// `remaining_accounts` holds one funder per entry of `shares_bps`,
+    /// each signing and writable; a funder pays that share (basis
+    /// points) of the commitment's rent to the authority, who fronted
+    /// it and keeps whatever share is left. Meant to go in the commit's
+    /// transaction; only while the commitment is unrevealed, and once.
+    pub fn split_commit_costs<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SplitCommitCosts<'info>>,
+        shares_bps: Vec<u16>,
+    ) -> Result<()> {
+        require!(
+            !shares_bps.is_empty()
+                && shares_bps.len() <= MAX_COST_FUNDERS
+                && shares_bps.len() == ctx.remaining_accounts.len()
+                && shares_bps.iter().map(|&bps| u32::from(bps)).sum::<u32>() <= 10000,
+            AxiomError::InvalidCostSplit
+        );
+        require!(
+            ctx.accounts.commitment.status == CommitmentStatus::Committed,
+            AxiomError::AlreadyRevealed
+        );
+        
+        let cost = ctx.accounts.commitment.to_account_info().lamports();
+        let mut shares = Vec::with_capacity(shares_bps.len());
+        for (&bps, funder) in shares_bps.iter().zip(ctx.remaining_accounts) {
+            require!(funder.is_signer, AxiomError::Unauthorized);
+            let lamports = (u128::from(cost) * u128::from(bps) / 10000) as u64;
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: funder.clone(),
+                        to: ctx.accounts.authority.to_account_info(),
+                    },
+                ),
+                lamports,
+            )?;
+            shares.push(CostShare { funder: funder.key(), lamports });
+        }
+        
+        let now = Clock::get()?.unix_timestamp;
+        let split = &mut ctx.accounts.cost_split;
+        split.commitment = ctx.accounts.commitment.key();
+        split.payer = ctx.accounts.authority.key();
+        split.cost = cost;
+        split.shares = shares.clone();
+        split.split_at = now;
+        split.bump = ctx.bumps.cost_split;
+        
+        emit!(CommitCostsSplit {
+            commitment: split.commitment,
+            payer: split.payer,
+            cost,
+            shares,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Commit without disclosing the action type, category or confidence.
+    ///
+    /// Only `details_hash` (see [`details_hash`]) is recorded; the fields
+    /// read as blank until `publish_details` opens them. For agents that
+    /// can't reveal even what kind of action they are taking in real time.
+    pub fn commit_private(
+        ctx: Context<CommitPrivate>,
         commitment_hash: [u8; 32],
+        details_hash: [u8; 32],
+        nonce: u64,
+        chunk_manifest: Option<ChunkManifest>,
+        counterparty: Option<Pubkey>,
+        risk: Option<RiskDisclosure>,
+    ) -> Result<()> {
+        let accounts = ctx.accounts;
+        let new = NewCommitment {
+            commitment_hash,
+            action_type: String::new(),
+            category: CommitmentCategory::Other,
+            confidence: 0,
+            confidence_source: ConfidenceSource::Unspecified,
+            nonce,
+            chunk_manifest,
+            template: None,
+            counterparty,
+            risk,
+            details_hash: Some(details_hash),
+            simulation: false,
+            reveal_deadline: None,
+            embargoed: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            parent: None,
+        };
+        count_for_integrator(
+            &accounts.config,
+            accounts.integrator.as_mut(),
+            accounts.fee_treasury.as_mut(),
+            accounts.compliance_policy.as_deref(),
+            &accounts.agent_profile,
+            &new,
+        )?;
+        record_commitment(
+            &mut accounts.commitment,
+            &mut accounts.agent_profile,
+            &mut accounts.activity_bucket,
+            accounts.authority.key(),
+            ctx.bumps.commitment,
+            ctx.bumps.activity_bucket,
+            new,
+        )?;
+        accounts.commitment.fee_paid = charge_commit_fee(
+            &accounts.config,
+            accounts.fee_treasury.as_mut(),
+            &accounts.authority,
+            &accounts.system_program,
+            1,
+        )?;
+        Ok(())
+    }
+
+    /// Open a private commitment's details. They must hash to the
+    /// committed `details_hash`; the confidence then counts towards the
+    /// agent's histogram like any other. `confidence_source` isn't part
+    /// of the hash and is recorded as given.
+    pub fn publish_details(
+        ctx: Context<PublishDetails>,
         action_type: String,
+        category: CommitmentCategory,
         confidence: u8,
-        nonce: u64,
+        salt: [u8; 32],
+        confidence_source: ConfidenceSource,
     ) -> Result<()> {
         require!(action_type.len() <= 32, AxiomError::ActionTypeTooLong);
         require!(confidence <= 100, AxiomError::InvalidConfidence);
         
-        // Capture keys before mutable borrows
-        let agent_key = ctx.accounts.agent_profile.key();
-        let commitment_key = ctx.accounts.commitment.key();
-        let authority_key = ctx.accounts.authority.key();
         let now = Clock::get()?.unix_timestamp;
-        
         let commitment = &mut ctx.accounts.commitment;
-        commitment.agent = agent_key;
-        commitment.authority = authority_key;
-        commitment.commitment_hash = commitment_hash;
+        require!(!commitment.locked, AxiomError::CommitmentLocked);
+        let Some(expected) = commitment.details_hash else {
+            return err!(AxiomError::NotPrivate);
+        };
+        require!(
+            commitment.details_published_at.is_none(),
+            AxiomError::DetailsAlreadyPublished
+        );
+        require!(
+            details_hash(&action_type, category, confidence, &salt) == expected,
+            AxiomError::DetailsMismatch
+        );
+        
         commitment.action_type = action_type.clone();
+        commitment.category = category;
         commitment.confidence = confidence;
-        commitment.timestamp = now;
-        commitment.revealed = false;
-        commitment.reasoning_uri = String::new();
-        commitment.nonce = nonce;
-        commitment.bump = ctx.bumps.commitment;
-        
-        // Increment agent's commitment count
-        let profile = &mut ctx.accounts.agent_profile;
-        profile.total_commitments = profile.total_commitments.checked_add(1)
-            .ok_or(AxiomError::Overflow)?;
+        commitment.confidence_source = confidence_source;
+        commitment.details_published_at = Some(now);
+        ctx.accounts.agent_profile.record_confidence(confidence);
         
-        emit!(ReasoningCommitted {
-            agent: agent_key,
-            commitment: commitment_key,
+        emit!(DetailsPublished {
+            agent: commitment.agent,
+            commitment: commitment.key(),
             action_type,
+            category,
             confidence,
             timestamp: now,
         });
@@ -80,179 +751,10093 @@ pub mod axiom {
         Ok(())
     }
 
-    /// Reveal the full reasoning by providing its storage URI.
-    /// 
-    /// After the action is executed, the agent publishes the full
-    /// reasoning trace (e.g., to IPFS) and records the URI onchain.
-    /// Anyone can then fetch the reasoning and verify it matches
-    /// the committed hash.
-    pub fn reveal_reasoning(
-        ctx: Context<RevealReasoning>,
-        reasoning_uri: String,
+    /// Commit with the action type and tags under embargo: only their
+    /// `metadata_hash` (see [`metadata_hash`]) is recorded, in the
+    /// commitment's `MetadataEmbargo`, and `action_type` reads as blank
+    /// until `disclose_metadata` opens them. Unlike `commit_private`, the
+    /// category and confidence stay public, so the commitment counts
+    /// towards the agent's calibration straight away.
+    ///
+    /// For agents whose competitors would trade on what they are doing.
+    /// The reveal can't start while the embargo holds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn commit_embargoed(
+        ctx: Context<CommitEmbargoed>,
+        commitment_hash: [u8; 32],
+        metadata_hash: [u8; 32],
+        confidence: u8,
+        nonce: u64,
+        category: CommitmentCategory,
+        chunk_manifest: Option<ChunkManifest>,
+        counterparty: Option<Pubkey>,
+        confidence_source: ConfidenceSource,
+        reveal_deadline: Option<RevealDeadline>,
     ) -> Result<()> {
-        require!(reasoning_uri.len() <= 256, AxiomError::UriTooLong);
-        require!(!reasoning_uri.is_empty(), AxiomError::UriEmpty);
+        let now = Clock::get()?.unix_timestamp;
+        let reveal_deadline = reveal_deadline
+            .map(|deadline| deadline.resolve(now))
+            .transpose()?;
+        let accounts = ctx.accounts;
+        let new = NewCommitment {
+            commitment_hash,
+            action_type: String::new(),
+            category,
+            confidence,
+            confidence_source,
+            nonce,
+            chunk_manifest,
+            template: None,
+            counterparty,
+            risk: None,
+            details_hash: None,
+            simulation: false,
+            reveal_deadline,
+            embargoed: true,
+            hash_algorithm: HashAlgorithm::Sha256,
+            parent: None,
+        };
+        count_for_integrator(
+            &accounts.config,
+            accounts.integrator.as_mut(),
+            accounts.fee_treasury.as_mut(),
+            accounts.compliance_policy.as_deref(),
+            &accounts.agent_profile,
+            &new,
+        )?;
+        record_commitment(
+            &mut accounts.commitment,
+            &mut accounts.agent_profile,
+            &mut accounts.activity_bucket,
+            accounts.authority.key(),
+            ctx.bumps.commitment,
+            ctx.bumps.activity_bucket,
+            new,
+        )?;
+        accounts.commitment.fee_paid = charge_commit_fee(
+            &accounts.config,
+            accounts.fee_treasury.as_mut(),
+            &accounts.authority,
+            &accounts.system_program,
+            1,
+        )?;
         
-        let commitment = &mut ctx.accounts.commitment;
-        require!(!commitment.revealed, AxiomError::AlreadyRevealed);
+        let embargo = &mut accounts.embargo;
+        embargo.commitment = accounts.commitment.key();
+        embargo.metadata_hash = metadata_hash;
+        embargo.tags = Vec::new();
+        embargo.disclosed_at = None;
+        embargo.bump = ctx.bumps.embargo;
         
-        commitment.revealed = true;
-        commitment.reasoning_uri = reasoning_uri.clone();
+        Ok(())
+    }
+
+    /// Lift an embargoed commitment's embargo by giving its action type
+    /// and tags, which must hash to the committed `metadata_hash`.
+    /// Authority only; needed before the reveal can start.
+    pub fn disclose_metadata(
+        ctx: Context<DiscloseMetadata>,
+        action_type: String,
+        tags: Vec<String>,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        require!(action_type.len() <= 32, AxiomError::ActionTypeTooLong);
+        require!(
+            tags.len() <= MAX_COMMITMENT_TAGS && tags.iter().all(|t| !t.is_empty() && t.len() <= 32),
+            AxiomError::InvalidTags
+        );
         
-        // Increment verified count
-        let profile = &mut ctx.accounts.agent_profile;
-        profile.total_verified = profile.total_verified.checked_add(1)
-            .ok_or(AxiomError::Overflow)?;
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &mut ctx.accounts.commitment;
+        require!(commitment.embargoed, AxiomError::NotEmbargoed);
+        let embargo = &mut ctx.accounts.embargo;
+        require!(
+            metadata_hash(&action_type, &tags, &salt) == embargo.metadata_hash,
+            AxiomError::MetadataMismatch
+        );
         
-        emit!(ReasoningRevealed {
-            agent: ctx.accounts.agent_profile.key(),
-            commitment: ctx.accounts.commitment.key(),
-            reasoning_uri,
-            timestamp: Clock::get()?.unix_timestamp,
+        commitment.action_type = action_type.clone();
+        commitment.embargoed = false;
+        embargo.tags = tags.clone();
+        embargo.disclosed_at = Some(now);
+        
+        emit!(MetadataDisclosed {
+            agent: commitment.agent,
+            commitment: commitment.key(),
+            action_type,
+            tags,
+            timestamp: now,
         });
         
         Ok(())
     }
-}
 
-// ─── Account Structs ───────────────────────────────────────────────────────
+    /// Store a set of future commitments the authority approved offline,
+    /// for `submitter` to make one by one with `commit_presigned`.
+    ///
+    /// For operators whose authority key never touches an online
+    /// machine. Must directly follow an Ed25519 program instruction in
+    /// which the authority signs [`presign_message`] over a
+    /// `PresignPermit` with this `batch_id`; the permit is read from that
+    /// instruction rather than passed twice. Signed by the submitter,
+    /// who pays for the batch.
+    pub fn open_presigned_batch(ctx: Context<OpenPresignedBatch>, batch_id: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let (signer, message) = preceding_signature(&ctx.accounts.instructions)?;
+        let profile = &ctx.accounts.agent_profile;
+        require_keys_eq!(signer, profile.authority, AxiomError::InvalidPresignPermit);
+        let permit = message
+            .strip_prefix(PRESIGN_DOMAIN)
+            .and_then(|rest| rest.strip_prefix(profile.key().as_ref()))
+            .and_then(|permit| PresignPermit::try_from_slice(permit).ok())
+            .ok_or(AxiomError::InvalidPresignPermit)?;
+        require!(
+            permit.batch_id == batch_id
+                && permit.submitter == ctx.accounts.submitter.key()
+                && permit.valid_from < permit.valid_until
+                && permit.valid_until > now
+                && !permit.entries.is_empty()
+                && permit.entries.len() <= MAX_PRESIGNED_COMMITMENTS,
+            AxiomError::InvalidPresignPermit
+        );
+        for entry in &permit.entries {
+            require!(entry.action_type.len() <= 32, AxiomError::ActionTypeTooLong);
+            require!(entry.confidence <= 100, AxiomError::InvalidConfidence);
+        }
+        
+        let batch = &mut ctx.accounts.batch;
+        batch.agent = profile.key();
+        batch.authority = profile.authority;
+        batch.batch_id = batch_id;
+        batch.submitter = permit.submitter;
+        batch.valid_from = permit.valid_from;
+        batch.valid_until = permit.valid_until;
+        batch.entries = permit.entries;
+        batch.redeemed = 0;
+        batch.bump = ctx.bumps.batch;
+        
+        emit!(PresignedBatchOpened {
+            agent: batch.agent,
+            batch: batch.key(),
+            submitter: batch.submitter,
+            entries: batch.entries.len() as u8,
+            valid_from: batch.valid_from,
+            valid_until: batch.valid_until,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
 
-#[account]
-#[derive(InitSpace)]
-pub struct AgentProfile {
-    /// The agent's authority (wallet that controls this profile)
-    pub authority: Pubkey,
-    /// Display name (max 64 chars)
-    #[max_len(64)]
-    pub name: String,
-    /// Total reasoning commitments published
-    pub total_commitments: u64,
-    /// Total commitments that have been revealed
-    pub total_verified: u64,
-    /// Accountability score in basis points (0-10000 = 0%-100%)
-    pub accountability_score: u16,
-    /// When the agent registered
-    pub created_at: i64,
-    /// PDA bump
-    pub bump: u8,
-}
+    /// Make entry `index` of a pre-signed batch as a commitment at
+    /// `nonce`. The batch's submitter only, within its validity window,
+    /// once per entry, and only while the key that signed the batch is
+    /// still the agent's authority.
+    pub fn commit_presigned(ctx: Context<CommitPresigned>, index: u8, nonce: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let accounts = ctx.accounts;
+        let batch = &mut accounts.batch;
+        require_keys_eq!(
+            batch.authority,
+            accounts.agent_profile.authority,
+            AxiomError::InvalidPresignPermit
+        );
+        require!(
+            now >= batch.valid_from && now <= batch.valid_until,
+            AxiomError::PresignWindowClosed
+        );
+        let entry = batch.entries.get(usize::from(index))
+            .cloned()
+            .ok_or(AxiomError::InvalidPresignPermit)?;
+        let bit = 1u8 << index;
+        require!(batch.redeemed & bit == 0, AxiomError::PresignedEntryRedeemed);
+        batch.redeemed |= bit;
+        
+        let new = NewCommitment {
+            commitment_hash: entry.commitment_hash,
+            action_type: entry.action_type,
+            category: entry.category,
+            confidence: entry.confidence,
+            confidence_source: ConfidenceSource::Unspecified,
+            nonce,
+            chunk_manifest: None,
+            template: None,
+            counterparty: None,
+            risk: None,
+            details_hash: None,
+            simulation: false,
+            reveal_deadline: None,
+            embargoed: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            parent: None,
+        };
+        count_for_integrator(
+            &accounts.config,
+            accounts.integrator.as_mut(),
+            accounts.fee_treasury.as_mut(),
+            accounts.compliance_policy.as_deref(),
+            &accounts.agent_profile,
+            &new,
+        )?;
+        let authority = accounts.agent_profile.authority;
+        record_commitment(
+            &mut accounts.commitment,
+            &mut accounts.agent_profile,
+            &mut accounts.activity_bucket,
+            authority,
+            ctx.bumps.commitment,
+            ctx.bumps.activity_bucket,
+            new,
+        )?;
+        accounts.commitment.fee_paid = charge_commit_fee(
+            &accounts.config,
+            accounts.fee_treasury.as_mut(),
+            &accounts.submitter,
+            &accounts.system_program,
+            1,
+        )?;
+        Ok(())
+    }
 
-#[account]
-#[derive(InitSpace)]
-pub struct ReasoningCommitment {
-    /// The agent profile this commitment belongs to
-    pub agent: Pubkey,
-    /// The authority that created this commitment
-    pub authority: Pubkey,
-    /// SHA-256 hash of the full reasoning trace
-    pub commitment_hash: [u8; 32],
-    /// Type of action (e.g., "trade", "audit", "rebalance")
-    #[max_len(32)]
-    pub action_type: String,
-    /// Confidence score (0-100)
-    pub confidence: u8,
-    /// Unix timestamp when committed
-    pub timestamp: i64,
-    /// Whether the full reasoning has been revealed
-    pub revealed: bool,
-    /// URI to the full reasoning (IPFS, Arweave, etc.)
-    #[max_len(256)]
-    pub reasoning_uri: String,
-    /// Nonce for unique PDA derivation (allows multiple commitments)
-    pub nonce: u64,
-    /// PDA bump
-    pub bump: u8,
-}
+    /// Close a pre-signed batch, giving up its unredeemed entries and
+    /// refunding its rent. Submitter only.
+    pub fn close_presigned_batch(_ctx: Context<ClosePresignedBatch>) -> Result<()> {
+        Ok(())
+    }
 
-// ─── Instruction Contexts ──────────────────────────────────────────────────
+    /// Let `reporter` record outcomes of the agent's commitments
+    /// alongside the authority, e.g. the executor service that sees
+    /// whether actions landed. Replaces any earlier reporter. Authority
+    /// only.
+    pub fn set_outcome_reporter(ctx: Context<SetOutcomeReporter>, reporter: Pubkey) -> Result<()> {
+        let record = &mut ctx.accounts.reporter_record;
+        record.agent = ctx.accounts.agent_profile.key();
+        record.reporter = reporter;
+        record.bump = ctx.bumps.reporter_record;
+        Ok(())
+    }
 
-#[derive(Accounts)]
-#[instruction(name: String)]
-pub struct RegisterAgent<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + AgentProfile::INIT_SPACE,
-        seeds = [b"agent", authority.key().as_ref()],
-        bump
-    )]
-    pub agent_profile: Account<'info, AgentProfile>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Remove the agent's outcome reporter. Authority only.
+    pub fn clear_outcome_reporter(_ctx: Context<ClearOutcomeReporter>) -> Result<()> {
+        Ok(())
+    }
 
-#[derive(Accounts)]
-#[instruction(commitment_hash: [u8; 32], action_type: String, confidence: u8, nonce: u64)]
+    /// Attach what happened when the committed action ran: whether it
+    /// succeeded, a hash of its result (e.g. of the transaction receipt
+    /// or an off-chain report), and when it was executed.
+    ///
+    /// The commitment's authority or the agent's outcome reporter (passing
+    /// its `reporter_record`) may record it, once per commitment, at any
+    /// point after the commit unless the commitment was cancelled or
+    /// expired; `executed_at` can't precede the commit or lie in the
+    /// future.
+    ///
+    /// A failed action must carry a `failure_reason`, and a successful one
+    /// none, so analytics can tell bad luck from bad reasoning.
+    ///
+    /// The outcome is self-reported, so it only counts toward the score
+    /// once `finalize_outcome` runs after `outcome_dispute_window`, and
+    /// not at all if a verifier disputed it in the meantime.
+    pub fn record_outcome(
+        ctx: Context<RecordOutcome>,
+        success: bool,
+        result_hash: [u8; 32],
+        executed_at: i64,
+        failure_reason: Option<FailureReason>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &ctx.accounts.commitment;
+        let reporter = ctx.accounts.reporter.key();
+        if reporter != commitment.authority {
+            let record = ctx.accounts.reporter_record.as_ref().ok_or(AxiomError::Unauthorized)?;
+            require_keys_eq!(record.reporter, reporter, AxiomError::Unauthorized);
+        }
+        require!(!commitment.cancelled, AxiomError::CommitmentCancelled);
+        require!(!commitment.expired, AxiomError::CommitmentExpired);
+        require!(
+            executed_at >= commitment.timestamp && executed_at <= now,
+            AxiomError::InvalidOutcomeTime
+        );
+        require!(success == failure_reason.is_none(), AxiomError::InvalidFailureReason);
+        
+        let outcome = &mut ctx.accounts.outcome;
+        outcome.commitment = commitment.key();
+        outcome.success = success;
+        outcome.failure_reason = failure_reason;
+        outcome.result_hash = result_hash;
+        outcome.executed_at = executed_at;
+        outcome.reporter = reporter;
+        outcome.recorded_at = now;
+        outcome.disputable_until = now.checked_add(ctx.accounts.config.outcome_dispute_window)
+            .ok_or(AxiomError::Overflow)?;
+        outcome.dispute = None;
+        outcome.finalized = false;
+        outcome.bump = ctx.bumps.outcome;
+        
+        emit!(OutcomeRecorded {
+            agent: commitment.agent,
+            commitment: outcome.commitment,
+            success,
+            failure_reason,
+            result_hash,
+            executed_at,
+            reporter,
+            disputable_until: outcome.disputable_until,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Dispute a recorded outcome before it counts toward the score.
+    ///
+    /// Only the config's `outcome_verifiers` may dispute, and only while
+    /// the outcome's dispute window is open. `evidence_hash`
+    /// commits to the verifier's off-chain evidence. A disputed outcome
+    /// never counts toward the score; one dispute is enough.
+    pub fn dispute_outcome(ctx: Context<DisputeOutcome>, evidence_hash: [u8; 32]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let verifier = ctx.accounts.verifier.key();
+        require!(
+            ctx.accounts.config.outcome_verifiers.contains(&verifier),
+            AxiomError::NotOutcomeVerifier
+        );
+        let outcome = &mut ctx.accounts.outcome;
+        require!(now < outcome.disputable_until, AxiomError::OutcomeDisputeWindowClosed);
+        require!(outcome.dispute.is_none(), AxiomError::OutcomeAlreadyDisputed);
+        outcome.dispute = Some(OutcomeDispute {
+            verifier,
+            evidence_hash,
+            disputed_at: now,
+        });
+        
+        emit!(OutcomeDisputed {
+            agent: ctx.accounts.commitment.agent,
+            commitment: outcome.commitment,
+            verifier,
+            evidence_hash,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Count an outcome toward the agent's score once its dispute window
+    /// has passed.
+    ///
+    /// Permissionless, once per outcome. An undisputed success adds
+    /// `outcome_score_bps` to the agent's score and an undisputed failure
+    /// takes it away; a disputed outcome, or one for a simulated
+    /// commitment, is finalized without counting.
+    pub fn finalize_outcome(ctx: Context<FinalizeOutcome>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let outcome = &mut ctx.accounts.outcome;
+        require!(!outcome.finalized, AxiomError::OutcomeAlreadyFinalized);
+        require!(now >= outcome.disputable_until, AxiomError::OutcomeDisputeWindowOpen);
+        outcome.finalized = true;
+        
+        let config = &ctx.accounts.config;
+        let profile = &mut ctx.accounts.agent_profile;
+        let counted = outcome.dispute.is_none() && !ctx.accounts.commitment.simulation;
+        if counted {
+            let delta = i32::from(config.outcome_score_bps);
+            profile.adjust_score(config, if outcome.success { delta } else { -delta })?;
+        }
+        
+        emit!(OutcomeFinalized {
+            agent: profile.key(),
+            commitment: outcome.commitment,
+            success: outcome.success,
+            counted,
+            accountability_score: profile.accountability_score,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Record the plans the agent considered for a commitment's action
+    /// and rejected, as hashes of each plan. Authority only.
+    ///
+    /// Only before the reveal starts, typically in the commit's own
+    /// transaction, so the alternatives are fixed before the outcome is
+    /// known. Each can then be revealed next to the chosen plan with
+    /// `reveal_counterfactual`, letting audits judge how the decision
+    /// was made and not only what was done.
+    pub fn record_counterfactuals(
+        ctx: Context<RecordCounterfactuals>,
+        plan_hashes: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require!(
+            !plan_hashes.is_empty()
+                && plan_hashes.len() <= MAX_COUNTERFACTUALS
+                && plan_hashes.iter().enumerate().all(|(i, h)| !plan_hashes[..i].contains(h)),
+            AxiomError::InvalidCounterfactuals
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &mut ctx.accounts.commitment;
+        require!(
+            commitment.status == CommitmentStatus::Committed
+                && !commitment.expired
+                && !commitment.cancelled,
+            AxiomError::AlreadyRevealed
+        );
+        commitment.counterfactuals = plan_hashes.len() as u8;
+        
+        let counterfactuals = &mut ctx.accounts.counterfactuals;
+        counterfactuals.commitment = commitment.key();
+        counterfactuals.alternatives = plan_hashes
+            .iter()
+            .map(|&plan_hash| Alternative { plan_hash, plan_uri: String::new() })
+            .collect();
+        counterfactuals.recorded_at = now;
+        counterfactuals.bump = ctx.bumps.counterfactuals;
+        
+        emit!(CounterfactualsRecorded {
+            agent: commitment.agent,
+            commitment: commitment.key(),
+            plan_hashes,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Publish where rejected alternative `index` of a commitment can be
+    /// read. Authority only, once the reveal of the chosen plan started;
+    /// anyone can then check the plan against its recorded hash.
+    pub fn reveal_counterfactual(
+        ctx: Context<RevealCounterfactual>,
+        index: u8,
+        plan_uri: String,
+    ) -> Result<()> {
+        require!(plan_uri.len() <= 256, AxiomError::UriTooLong);
+        require!(!plan_uri.is_empty(), AxiomError::UriEmpty);
+        let commitment = &ctx.accounts.commitment;
+        require!(
+            commitment.status != CommitmentStatus::Committed,
+            AxiomError::NotRevealed
+        );
+        
+        let counterfactuals = &mut ctx.accounts.counterfactuals;
+        let alternative = counterfactuals.alternatives
+            .get_mut(usize::from(index))
+            .ok_or(AxiomError::InvalidCounterfactuals)?;
+        require!(alternative.plan_uri.is_empty(), AxiomError::AlreadyRevealed);
+        alternative.plan_uri = plan_uri.clone();
+        
+        emit!(CounterfactualRevealed {
+            agent: commitment.agent,
+            commitment: commitment.key(),
+            index,
+            plan_hash: alternative.plan_hash,
+            plan_uri,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Commit using one of the authority's `Template`s for the action
+    /// type, category and reveal deadline.
+    ///
+    /// Same as `commit_reasoning`, minus the fields the template fixes;
+    /// the commitment records which template it was made from.
+    #[allow(clippy::too_many_arguments)]
+    pub fn commit_from_template(
+        ctx: Context<CommitFromTemplate>,
+        commitment_hash: [u8; 32],
+        confidence: u8,
+        nonce: u64,
+        chunk_manifest: Option<ChunkManifest>,
+        counterparty: Option<Pubkey>,
+        risk: Option<RiskDisclosure>,
+        confidence_source: ConfidenceSource,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let accounts = ctx.accounts;
+        let template = &mut accounts.template;
+        template.uses = template.uses.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        let reveal_deadline = template.reveal_after
+            .map(|seconds| RevealDeadline::After(seconds).resolve(now))
+            .transpose()?;
+        let new = NewCommitment {
+            commitment_hash,
+            action_type: template.action_type.clone(),
+            category: template.category,
+            confidence,
+            confidence_source,
+            nonce,
+            chunk_manifest,
+            template: Some(template.key()),
+            counterparty,
+            risk,
+            details_hash: None,
+            simulation: false,
+            reveal_deadline,
+            embargoed: false,
+            hash_algorithm: HashAlgorithm::Sha256,
+            parent: None,
+        };
+        count_for_integrator(
+            &accounts.config,
+            accounts.integrator.as_mut(),
+            accounts.fee_treasury.as_mut(),
+            accounts.compliance_policy.as_deref(),
+            &accounts.agent_profile,
+            &new,
+        )?;
+        record_commitment(
+            &mut accounts.commitment,
+            &mut accounts.agent_profile,
+            &mut accounts.activity_bucket,
+            accounts.authority.key(),
+            ctx.bumps.commitment,
+            ctx.bumps.activity_bucket,
+            new,
+        )?;
+        accounts.commitment.fee_paid = charge_commit_fee(
+            &accounts.config,
+            accounts.fee_treasury.as_mut(),
+            &accounts.authority,
+            &accounts.system_program,
+            1,
+        )?;
+        Ok(())
+    }
+
+    /// Create a reusable commitment template.
+    ///
+    /// `required_fields` names the trace metadata keys every commitment
+    /// made from the template must carry. The program can't see traces,
+    /// so it only publishes the list; verifiers check it on reveal.
+    ///
+    /// With `reveal_after`, every commitment made from the template is
+    /// due for reveal that many seconds after it is made.
+    pub fn create_template(
+        ctx: Context<CreateTemplate>,
+        name: String,
+        action_type: String,
+        category: CommitmentCategory,
+        required_fields: Vec<String>,
+        reveal_after: Option<i64>,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= 32,
+            AxiomError::InvalidTemplate
+        );
+        require!(action_type.len() <= 32, AxiomError::ActionTypeTooLong);
+        require!(
+            required_fields.len() <= MAX_TEMPLATE_FIELDS
+                && required_fields.iter().all(|f| !f.is_empty() && f.len() <= 32),
+            AxiomError::InvalidTemplate
+        );
+        require!(
+            reveal_after.is_none_or(|seconds| seconds > 0),
+            AxiomError::InvalidRevealDeadline
+        );
+        
+        let template = &mut ctx.accounts.template;
+        template.owner = ctx.accounts.owner.key();
+        template.name = name.clone();
+        template.action_type = action_type;
+        template.category = category;
+        template.required_fields = required_fields;
+        template.reveal_after = reveal_after;
+        template.uses = 0;
+        template.bump = ctx.bumps.template;
+        
+        emit!(TemplateCreated {
+            owner: template.owner,
+            template: template.key(),
+            name,
+        });
+        
+        Ok(())
+    }
+
+    /// Close a template, returning its rent to the owner. Commitments
+    /// already made from it keep their copied action type and category.
+    pub fn close_template(_ctx: Context<CloseTemplate>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Commit to up to `MAX_BATCH_SIZE` reasoning hashes in one account.
+    ///
+    /// `root` is the Merkle root (see [`merkle`]) over the hashes, each
+    /// hashed as a leaf, in the order they will be revealed by index.
+    /// Every hash counts as a commitment on the agent's record, and pays
+    /// the `commit_fee` like one. For agents deciding too often to pay
+    /// for one account per commitment; the batch shares one action type
+    /// and category.
+    pub fn commit_reasoning_batch(
+        ctx: Context<CommitReasoningBatch>,
+        root: [u8; 32],
+        leaf_count: u32,
+        action_type: String,
+        category: CommitmentCategory,
+        nonce: u64,
+    ) -> Result<()> {
+        require!(action_type.len() <= 32, AxiomError::ActionTypeTooLong);
+        require!(
+            leaf_count > 0 && leaf_count <= MAX_BATCH_SIZE,
+            AxiomError::InvalidBatchSize
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let accounts = ctx.accounts;
+        let profile = &mut accounts.agent_profile;
+        require!(profile.is_active(), AxiomError::AgentInactive);
+        require!(!profile.frozen, AxiomError::AgentFrozen);
+        require!(
+            accounts.integrator.as_ref().map(|i| i.key()) == profile.integrator,
+            AxiomError::IntegratorMismatch
+        );
+        if let Some(integrator) = accounts.integrator.as_mut() {
+            // Batched hashes can't name a counterparty
+            require!(
+                !integrator.policy.require_counterparty,
+                AxiomError::IntegratorPolicyViolation
+            );
+            let compliance = compliance_policy(
+                &integrator.key(),
+                accounts.compliance_policy.as_deref(),
+            )?;
+            if let Some(compliance) = compliance {
+                // ... nor carry a confidence or reveal deadline
+                require!(
+                    compliance.allows_action_type(&action_type, false)
+                        && compliance.min_confidence == 0
+                        && !compliance.require_reveal_deadline,
+                    AxiomError::CompliancePolicyViolation
+                );
+            }
+            integrator.commitments = integrator.commitments.checked_add(u64::from(leaf_count))
+                .ok_or(AxiomError::Overflow)?;
+            credit_integrator(
+                &accounts.config,
+                integrator,
+                accounts.fee_treasury.as_mut(),
+                u64::from(leaf_count),
+            )?;
+        }
+        profile.total_commitments = profile.total_commitments.checked_add(u64::from(leaf_count))
+            .ok_or(AxiomError::Overflow)?;
+        
+        let bucket = &mut accounts.activity_bucket;
+        bucket.open(profile.key(), activity_day(now), ctx.bumps.activity_bucket);
+        bucket.commits = bucket.commits.checked_add(leaf_count)
+            .ok_or(AxiomError::Overflow)?;
+        charge_commit_fee(
+            &accounts.config,
+            accounts.fee_treasury.as_mut(),
+            &accounts.authority,
+            &accounts.system_program,
+            u64::from(leaf_count),
+        )?;
+        
+        let batch = &mut accounts.batch;
+        batch.agent = profile.key();
+        batch.authority = accounts.authority.key();
+        batch.root = root;
+        batch.leaf_count = leaf_count;
+        batch.revealed_count = 0;
+        batch.revealed = [0; BATCH_BITMAP_LEN];
+        batch.action_type = action_type.clone();
+        batch.category = category;
+        batch.nonce = nonce;
+        batch.timestamp = now;
+        batch.bump = ctx.bumps.batch;
+        
+        emit!(BatchCommitted {
+            agent: batch.agent,
+            batch: batch.key(),
+            root,
+            leaf_count,
+            action_type,
+            category,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Reveal one reasoning hash of a batch. Authority only.
+    ///
+    /// `proof` shows `reasoning_hash` is leaf `index` under the batch
+    /// root; `reasoning_uri` is where its trace was published. The reveal
+    /// counts towards the agent's record at once: a batched hash has no
+    /// account of its own to hold a grace period.
+    pub fn reveal_from_batch(
+        ctx: Context<RevealFromBatch>,
+        index: u32,
+        reasoning_hash: [u8; 32],
+        proof: Vec<[u8; 32]>,
+        reasoning_uri: String,
+    ) -> Result<()> {
+        ctx.accounts.config.check_uri(&reasoning_uri)?;
+        let now = Clock::get()?.unix_timestamp;
+        let batch = &mut ctx.accounts.batch;
+        require!(
+            merkle::verify(
+                &batch.root,
+                &merkle::leaf(&reasoning_hash),
+                index,
+                batch.leaf_count,
+                &proof,
+            ),
+            AxiomError::InvalidBatchLeaf
+        );
+        require!(!batch.is_revealed(index), AxiomError::AlreadyRevealed);
+        batch.set_revealed(index);
+        batch.revealed_count = batch.revealed_count.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        
+        let profile = &mut ctx.accounts.agent_profile;
+        profile.total_verified = profile.total_verified.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        profile.recompute_score(&ctx.accounts.config);
+        
+        emit!(BatchLeafRevealed {
+            agent: batch.agent,
+            batch: batch.key(),
+            index,
+            reasoning_hash,
+            reasoning_uri,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Register a platform that embeds SOLPRISM as an integrator.
+    ///
+    /// Agents that join it are counted in its stats and held to its
+    /// `policy` on every commit.
+    pub fn register_integrator(
+        ctx: Context<RegisterIntegrator>,
+        name: String,
+        policy: IntegratorPolicy,
+    ) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= 32,
+            AxiomError::InvalidIntegrator
+        );
+        
+        let integrator = &mut ctx.accounts.integrator;
+        integrator.authority = ctx.accounts.authority.key();
+        integrator.name = name.clone();
+        integrator.policy = policy;
+        integrator.agents = 0;
+        integrator.commitments = 0;
+        integrator.claimable_fees = 0;
+        integrator.created_at = Clock::get()?.unix_timestamp;
+        integrator.bump = ctx.bumps.integrator;
+        
+        emit!(IntegratorRegistered {
+            integrator: integrator.key(),
+            authority: integrator.authority,
+            name,
+            timestamp: integrator.created_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Replace an integrator's policy. Applies to commits from then on.
+    /// The integrator's authority or an admin may call it.
+    pub fn set_integrator_policy(
+        ctx: Context<SetIntegratorPolicy>,
+        policy: IntegratorPolicy,
+    ) -> Result<()> {
+        check_org_permission(
+            &ctx.accounts.integrator,
+            ctx.accounts.authority.key(),
+            ctx.accounts.role.as_deref(),
+            OrgPermission::ManagePolicy,
+        )?;
+        ctx.accounts.integrator.policy = policy;
+        Ok(())
+    }
+
+    /// Set the compliance rules every member agent's commits are checked
+    /// against, on top of the integrator's `policy`: the action types
+    /// they may commit to (empty = any), a minimum confidence, and
+    /// whether each commitment must carry a reveal deadline so none can
+    /// stay unverified. The integrator's authority or an admin may call
+    /// it; applies to commits from then on and replaces any earlier
+    /// rules.
+    ///
+    /// Private commitments hide action type and confidence, so they are
+    /// rejected under rules restricting either; batches carry neither a
+    /// confidence nor a deadline.
+    pub fn set_compliance_policy(
+        ctx: Context<SetCompliancePolicy>,
+        allowed_action_types: Vec<String>,
+        min_confidence: u8,
+        require_reveal_deadline: bool,
+    ) -> Result<()> {
+        require!(
+            allowed_action_types.len() <= MAX_POLICY_ACTION_TYPES
+                && allowed_action_types.iter().all(|t| !t.is_empty() && t.len() <= 32),
+            AxiomError::InvalidCompliancePolicy
+        );
+        require!(min_confidence <= 100, AxiomError::InvalidConfidence);
+        check_org_permission(
+            &ctx.accounts.integrator,
+            ctx.accounts.authority.key(),
+            ctx.accounts.role.as_deref(),
+            OrgPermission::ManagePolicy,
+        )?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let compliance = &mut ctx.accounts.compliance_policy;
+        compliance.integrator = ctx.accounts.integrator.key();
+        compliance.allowed_action_types = allowed_action_types.clone();
+        compliance.min_confidence = min_confidence;
+        compliance.require_reveal_deadline = require_reveal_deadline;
+        compliance.updated_at = now;
+        compliance.bump = ctx.bumps.compliance_policy;
+        
+        emit!(CompliancePolicySet {
+            integrator: compliance.integrator,
+            allowed_action_types,
+            min_confidence,
+            require_reveal_deadline,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Drop the integrator's compliance rules. The integrator's
+    /// authority or an admin may call it.
+    pub fn clear_compliance_policy(ctx: Context<ClearCompliancePolicy>) -> Result<()> {
+        check_org_permission(
+            &ctx.accounts.integrator,
+            ctx.accounts.authority.key(),
+            ctx.accounts.role.as_deref(),
+            OrgPermission::ManagePolicy,
+        )
+    }
+
+    /// Count a rejection the agent ran into in its `RejectionStats`, so
+    /// operators can see how often policy blocks it without scraping
+    /// logs. Authority only.
+    ///
+    /// A rejected instruction rolls back every write, so it can't count
+    /// itself; the agent reports it here instead, in a transaction of its
+    /// own. The rejection is checked again against the rule behind it,
+    /// so only real ones count: a reveal of `commitment` past its
+    /// deadline, or a commit of an action type the integrator's
+    /// compliance policy (passed as `compliance_policy`) doesn't allow.
+    pub fn record_rejection(ctx: Context<RecordRejection>, rejection: Rejection) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let accounts = ctx.accounts;
+        let agent = accounts.agent_profile.key();
+        match &rejection {
+            Rejection::RevealDeadlinePassed => {
+                let commitment = accounts.commitment.as_ref().ok_or(AxiomError::NotRejected)?;
+                require_keys_eq!(commitment.agent, agent, AxiomError::AgentMismatch);
+                require!(
+                    commitment.status == CommitmentStatus::Committed
+                        && !commitment.cancelled
+                        && commitment.reveal_deadline.is_some_and(|deadline| now > deadline),
+                    AxiomError::NotRejected
+                );
+            }
+            Rejection::ActionTypeNotAllowed(action_type) => {
+                let integrator = accounts.agent_profile.integrator
+                    .ok_or(AxiomError::NotRejected)?;
+                let compliance = compliance_policy(
+                    &integrator,
+                    accounts.compliance_policy.as_deref(),
+                )?
+                .ok_or(AxiomError::NotRejected)?;
+                require!(
+                    !compliance.allows_action_type(action_type, false),
+                    AxiomError::NotRejected
+                );
+            }
+        }
+        
+        let stats = &mut accounts.rejection_stats;
+        if stats.agent == Pubkey::default() {
+            stats.agent = agent;
+            stats.bump = ctx.bumps.rejection_stats;
+        }
+        match rejection {
+            Rejection::RevealDeadlinePassed => {
+                stats.late_reveals = stats.late_reveals.checked_add(1)
+                    .ok_or(AxiomError::Overflow)?;
+            }
+            Rejection::ActionTypeNotAllowed(_) => {
+                stats.disallowed_action_types = stats.disallowed_action_types.checked_add(1)
+                    .ok_or(AxiomError::Overflow)?;
+            }
+        }
+        stats.last_rejected_at = now;
+        
+        emit!(RejectionRecorded {
+            agent,
+            late_reveals: stats.late_reveals,
+            disallowed_action_types: stats.disallowed_action_types,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Give `member` a role in the integrator, replacing any role it
+    /// held. The integrator's authority or an admin may call it.
+    ///
+    /// Admins manage roles and policies and everything operators can;
+    /// operators admit members, freeze them and rotate their signing
+    /// keys; auditors get no onchain powers, the role only marking them
+    /// for off-chain tooling as view-only.
+    pub fn grant_role(ctx: Context<GrantRole>, member: Pubkey, role: OrgRole) -> Result<()> {
+        check_org_permission(
+            &ctx.accounts.integrator,
+            ctx.accounts.authority.key(),
+            ctx.accounts.granter_role.as_deref(),
+            OrgPermission::ManageRoles,
+        )?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let record = &mut ctx.accounts.role;
+        record.integrator = ctx.accounts.integrator.key();
+        record.member = member;
+        record.role = role;
+        record.granted_by = ctx.accounts.authority.key();
+        record.granted_at = now;
+        record.bump = ctx.bumps.role;
+        
+        emit!(RoleGranted {
+            integrator: record.integrator,
+            member,
+            role,
+            granted_by: record.granted_by,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Take away a member's role. The integrator's authority or an admin
+    /// may call it.
+    pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+        check_org_permission(
+            &ctx.accounts.integrator,
+            ctx.accounts.authority.key(),
+            ctx.accounts.revoker_role.as_deref(),
+            OrgPermission::ManageRoles,
+        )?;
+        
+        emit!(RoleRevoked {
+            integrator: ctx.accounts.integrator.key(),
+            member: ctx.accounts.role.member,
+            revoked_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Freeze a member agent, stopping its new commitments, or thaw it.
+    /// The integrator's authority, an admin or an operator may call it.
+    ///
+    /// Like `set_agent_status`, it leaves reveals alone. Leaving the
+    /// integrator thaws the agent.
+    pub fn set_member_frozen(ctx: Context<SetMemberFrozen>, frozen: bool) -> Result<()> {
+        check_org_permission(
+            &ctx.accounts.integrator,
+            ctx.accounts.authority.key(),
+            ctx.accounts.role.as_deref(),
+            OrgPermission::Freeze,
+        )?;
+        let profile = &mut ctx.accounts.agent_profile;
+        profile.frozen = frozen;
+        
+        emit!(MemberFrozen {
+            integrator: ctx.accounts.integrator.key(),
+            agent: profile.key(),
+            frozen,
+            by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// `set_signing_key` for a member agent, e.g. when its inference
+    /// service's key leaks. The integrator's authority, an admin or an
+    /// operator may call it, and pays for the new record.
+    pub fn set_member_signing_key(
+        ctx: Context<SetMemberSigningKey>,
+        signing_key: Pubkey,
+    ) -> Result<()> {
+        check_org_permission(
+            &ctx.accounts.integrator,
+            ctx.accounts.authority.key(),
+            ctx.accounts.role.as_deref(),
+            OrgPermission::RotateKeys,
+        )?;
+        rotate_signing_key(
+            &mut ctx.accounts.agent_profile,
+            ctx.accounts.previous_key.as_mut(),
+            &mut ctx.accounts.signing_key_record,
+            signing_key,
+            ctx.bumps.signing_key_record,
+        )
+    }
+
+    /// Place an agent in an integrator's namespace. Both the agent and
+    /// the integrator sign; an agent belongs to at most one integrator.
+    ///
+    /// The integrator's side may be signed by its authority, an admin or
+    /// an operator (passing their `role`).
+    pub fn join_integrator(ctx: Context<JoinIntegrator>) -> Result<()> {
+        check_org_permission(
+            &ctx.accounts.integrator,
+            ctx.accounts.integrator_authority.key(),
+            ctx.accounts.role.as_deref(),
+            OrgPermission::Admit,
+        )?;
+        let profile = &mut ctx.accounts.agent_profile;
+        require!(profile.integrator.is_none(), AxiomError::AlreadyInIntegrator);
+        
+        let integrator = &mut ctx.accounts.integrator;
+        integrator.agents = integrator.agents.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        profile.integrator = Some(integrator.key());
+        
+        let member = &mut ctx.accounts.member;
+        member.integrator = integrator.key();
+        member.agent = profile.key();
+        member.joined_at = Clock::get()?.unix_timestamp;
+        member.bump = ctx.bumps.member;
+        
+        emit!(IntegratorJoined {
+            integrator: member.integrator,
+            agent: member.agent,
+            timestamp: member.joined_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Leave the agent's integrator. Needs only the agent's signature, so
+    /// an integrator can't hold an agent to its policy.
+    pub fn leave_integrator(ctx: Context<LeaveIntegrator>) -> Result<()> {
+        let integrator = &mut ctx.accounts.integrator;
+        integrator.agents = integrator.agents.saturating_sub(1);
+        let profile = &mut ctx.accounts.agent_profile;
+        profile.integrator = None;
+        profile.frozen = false;
+        Ok(())
+    }
+
+    /// Reveal the full reasoning by providing its storage URI.
+    /// 
+    /// After the action is executed, the agent publishes the full
+    /// reasoning trace (e.g., to IPFS) and records the URI onchain.
+    /// Anyone can then fetch the reasoning and verify it matches
+    /// the committed hash.
+    ///
+    /// This starts the reveal: the commitment moves to `Revealing` and
+    /// only becomes `Revealed` through `finalize_reveal`, so consumers can
+    /// tell a posted-but-unchecked URI from a completed reveal.
+    ///
+    /// `trace_len` (bytes) and `chunk_count` describe the stored trace so
+    /// verifiers can pre-allocate and detect truncated fetches.
+    ///
+    /// Agents that committed to `H(salt || trace)` disclose the 32-byte
+    /// `salt` here, so the trace can be checked against the hash.
+    /// Chunk-manifest commitments are Merkle roots and take no salt.
+    ///
+    /// Like `commit_reasoning`, a delegate key may sign in place of the
+    /// authority.
+    pub fn start_reveal(
+        ctx: Context<StartReveal>,
+        reasoning_uri: String,
+        trace_len: u64,
+        chunk_count: u32,
+        salt: Option<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.config.check_uri(&reasoning_uri)?;
+        require!(trace_len > 0, AxiomError::InvalidTraceSize);
+        require!(
+            chunk_count > 0 && u64::from(chunk_count) <= trace_len,
+            AxiomError::InvalidTraceSize
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &mut ctx.accounts.commitment;
+        if ctx.accounts.authority.key() != commitment.authority {
+            check_delegate(
+                ctx.accounts.delegate.as_deref(),
+                commitment.agent,
+                ctx.accounts.authority.key(),
+                &commitment.action_type,
+                now,
+            )?;
+        }
+        require!(
+            commitment.status == CommitmentStatus::Committed,
+            AxiomError::AlreadyRevealed
+        );
+        require!(!commitment.cancelled, AxiomError::CommitmentCancelled);
+        require!(!commitment.embargoed, AxiomError::MetadataEmbargoed);
+        require!(
+            commitment.reveal_deadline.is_none_or(|deadline| now <= deadline),
+            AxiomError::RevealDeadlinePassed
+        );
+        if let Some(manifest) = commitment.chunk_manifest {
+            require!(manifest.matches(trace_len, chunk_count), AxiomError::InvalidTraceSize);
+            require!(salt.is_none(), AxiomError::ChunkedCommitment);
+        }
+        
+        commitment.status = CommitmentStatus::Revealing;
+        commitment.revealed_at = now;
+        commitment.reasoning_uri = reasoning_uri.clone();
+        commitment.trace_len = trace_len;
+        commitment.chunk_count = chunk_count;
+        commitment.salt = salt;
+        
+        if !commitment.simulation {
+            let latency = now.saturating_sub(commitment.timestamp);
+            ctx.accounts.agent_profile.record_reveal_latency(latency)?;
+            record_metrics(
+                &ctx.accounts.agent_profile,
+                ctx.accounts.metrics.as_mut(),
+                now,
+                |metrics| metrics.record_reveal(u64::try_from(latency).unwrap_or(0)),
+            )?;
+            record_summary(
+                &ctx.accounts.agent_profile,
+                ctx.accounts.summary.as_mut(),
+                now,
+                |summary| summary.record_reveal(u64::try_from(latency).unwrap_or(0)),
+            )?;
+        }
+        record_action_stats(
+            &ctx.accounts.commitment,
+            ctx.accounts.action_stats.as_mut(),
+            |stats| stats.reveals = stats.reveals.saturating_add(1),
+        )?;
+        
+        let bucket = &mut ctx.accounts.activity_bucket;
+        bucket.open(ctx.accounts.agent_profile.key(), activity_day(now), ctx.bumps.activity_bucket);
+        bucket.reveals = bucket.reveals.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        
+        let sequence = ctx.accounts.agent_profile.next_event_sequence()?;
+        ctx.accounts.commitment.sequence = sequence;
+        
+        emit!(ReasoningRevealed {
+            agent: ctx.accounts.agent_profile.key(),
+            commitment: ctx.accounts.commitment.key(),
+            reasoning_uri,
+            trace_len,
+            chunk_count,
+            sequence,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Complete a reveal once its grace period has passed.
+    ///
+    /// Permissionless: the grace period gives verifiers time to check the
+    /// posted trace before the reveal counts towards the agent's record.
+    /// A reveal started by its deadline and verified onchain by then
+    /// refunds `reveal_rebate_bps` of its commit fee to the commitment's
+    /// authority.
+    pub fn finalize_reveal(ctx: Context<FinalizeReveal>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &ctx.accounts.commitment;
+        require!(
+            commitment.status == CommitmentStatus::Revealing,
+            AxiomError::NotRevealing
+        );
+        let ends_at = commitment.revealed_at
+            .checked_add(ctx.accounts.config.reveal_grace_period)
+            .ok_or(AxiomError::Overflow)?;
+        require!(now >= ends_at, AxiomError::GracePeriodActive);
+        
+        let agent_key = ctx.accounts.agent_profile.key();
+        let commitment_key = ctx.accounts.commitment.key();
+        complete_reveal(
+            &ctx.accounts.config,
+            &mut ctx.accounts.commitment,
+            &mut ctx.accounts.agent_profile,
+            ctx.accounts.fee_treasury.as_mut(),
+            ctx.accounts.authority.as_ref(),
+            agent_key,
+            commitment_key,
+            now,
+        )?;
+        record_summary(&ctx.accounts.agent_profile, ctx.accounts.summary.as_mut(), now, |_| {})
+    }
+
+    /// Expire a commitment that wasn't revealed by its deadline.
+    ///
+    /// Permissionless, so an agent can't dodge the penalty by going
+    /// quiet. Unless the commitment was a simulation, it counts against
+    /// the agent's reveal rate and costs a further `expiry_penalty_bps`
+    /// of accountability score.
+    pub fn expire_commitment(ctx: Context<ExpireCommitment>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &mut ctx.accounts.commitment;
+        let Some(deadline) = commitment.reveal_deadline else {
+            return err!(AxiomError::NoRevealDeadline);
+        };
+        require!(now > deadline, AxiomError::RevealDeadlineNotPassed);
+        require!(
+            commitment.status == CommitmentStatus::Committed && !commitment.expired,
+            AxiomError::AlreadyRevealed
+        );
+        require!(!commitment.cancelled, AxiomError::CommitmentCancelled);
+        commitment.expired = true;
+        
+        let config = &ctx.accounts.config;
+        let profile = &mut ctx.accounts.agent_profile;
+        if !commitment.simulation {
+            let expired = profile.expired_commitments.checked_add(1)
+                .ok_or(AxiomError::Overflow)?;
+            profile.expired_commitments = expired;
+            profile.adjust_score(config, -i32::from(config.expiry_penalty_bps))?;
+            record_metrics(profile, ctx.accounts.metrics.as_mut(), now, AgentMetrics::record_expiry)?;
+            record_summary(profile, ctx.accounts.summary.as_mut(), now, AgentSummary::record_expiry)?;
+        }
+        record_action_stats(commitment, ctx.accounts.action_stats.as_mut(), |stats| {
+            stats.expiries = stats.expiries.saturating_add(1)
+        })?;
+        let sequence = profile.next_event_sequence()?;
+        commitment.sequence = sequence;
+        
+        emit!(CommitmentExpired {
+            agent: profile.key(),
+            commitment: commitment.key(),
+            accountability_score: profile.accountability_score,
+            sequence,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Void a commitment whose action was called off. Authority only.
+    ///
+    /// Only an unrevealed commitment can be cancelled, only before its
+    /// reveal deadline, and not while it has an open escrow (the
+    /// counterparty's tranches would be voided with it). A cancelled commitment can no longer be
+    /// revealed or expired and doesn't count against the agent: it is
+    /// left out of the reveal rate and tallied in `cancelled_commitments`.
+    pub fn cancel_commitment(ctx: Context<CancelCommitment>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &mut ctx.accounts.commitment;
+        require!(
+            commitment.status == CommitmentStatus::Committed && !commitment.expired,
+            AxiomError::AlreadyRevealed
+        );
+        require!(!commitment.cancelled, AxiomError::CommitmentCancelled);
+        require!(!commitment.escrowed, AxiomError::EscrowActive);
+        require!(
+            commitment.reveal_deadline.is_none_or(|deadline| now <= deadline),
+            AxiomError::RevealDeadlinePassed
+        );
+        commitment.cancelled = true;
+        
+        let profile = &mut ctx.accounts.agent_profile;
+        if !commitment.simulation {
+            let cancelled = profile.cancelled_commitments.checked_add(1)
+                .ok_or(AxiomError::Overflow)?;
+            profile.cancelled_commitments = cancelled;
+        }
+        let sequence = profile.next_event_sequence()?;
+        commitment.sequence = sequence;
+        
+        emit!(CommitmentCancelled {
+            agent: profile.key(),
+            commitment: commitment.key(),
+            sequence,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Push an unrevealed commitment's reveal deadline back by
+    /// `extension` seconds, when the action legitimately takes longer
+    /// than planned. Authority only.
+    ///
+    /// Once per commitment, before the deadline passes, and by at most
+    /// the config's `max_deadline_extension`. The authority pays the
+    /// `extension_fee` into the `FeeTreasury`, which must then be passed.
+    pub fn request_deadline_extension(
+        ctx: Context<RequestDeadlineExtension>,
+        extension: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.config;
+        require!(
+            extension > 0 && extension <= config.max_deadline_extension,
+            AxiomError::InvalidDeadlineExtension
+        );
+        let commitment = &mut ctx.accounts.commitment;
+        require!(!commitment.locked, AxiomError::CommitmentLocked);
+        require!(
+            commitment.status == CommitmentStatus::Committed && !commitment.expired,
+            AxiomError::AlreadyRevealed
+        );
+        require!(!commitment.cancelled, AxiomError::CommitmentCancelled);
+        require!(!commitment.deadline_extended, AxiomError::DeadlineAlreadyExtended);
+        let Some(deadline) = commitment.reveal_deadline else {
+            return err!(AxiomError::NoRevealDeadline);
+        };
+        require!(now <= deadline, AxiomError::RevealDeadlinePassed);
+        let extended = deadline.checked_add(extension)
+            .ok_or(AxiomError::Overflow)?;
+        commitment.reveal_deadline = Some(extended);
+        commitment.deadline_extended = true;
+        
+        let fee = config.extension_fee;
+        collect_fee(
+            ctx.accounts.fee_treasury.as_mut(),
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            fee,
+        )?;
+        
+        emit!(DeadlineExtended {
+            agent: commitment.agent,
+            commitment: commitment.key(),
+            previous_deadline: deadline,
+            reveal_deadline: extended,
+            fee,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Close a revealed commitment and reclaim its rent. Authority only.
+    ///
+    /// Only once the reveal's challenge window has passed and no
+    /// challenge is pending, and never for a locked commitment or one
+    /// with an open escrow. The commitment's address and hash are folded
+    /// into the agent's `CommitmentHistory` accumulator first, so a
+    /// closed commitment can still be proven to have existed.
+    pub fn close_commitment(ctx: Context<CloseCommitment>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &ctx.accounts.commitment;
+        require!(
+            commitment.status == CommitmentStatus::Revealed,
+            AxiomError::NotRevealed
+        );
+        require!(!commitment.locked, AxiomError::CommitmentLocked);
+        require!(!commitment.escrowed, AxiomError::EscrowActive);
+        let window_ends = commitment.revealed_at
+            .checked_add(ctx.accounts.config.challenge_window)
+            .ok_or(AxiomError::Overflow)?;
+        require!(now >= window_ends, AxiomError::ChallengeWindowOpen);
+        require!(commitment.open_challenges == 0, AxiomError::ChallengeWindowOpen);
+        
+        let history = &mut ctx.accounts.history;
+        history.open(commitment.agent, ctx.bumps.history);
+        history.record(&commitment.key(), &commitment.commitment_hash, now)?;
+        let sequence = ctx.accounts.agent_profile.next_event_sequence()?;
+        
+        emit!(CommitmentClosed {
+            agent: commitment.agent,
+            commitment: commitment.key(),
+            commitment_hash: commitment.commitment_hash,
+            closed_commitments: history.closed_commitments,
+            accumulator: history.accumulator,
+            sequence,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Check one chunk of a chunked commitment against its Merkle root.
+    ///
+    /// Succeeds (and emits `ChunkVerified`) only if `chunk` is the
+    /// `index`-th chunk of the committed trace, so a corrupted chunk can
+    /// be pinpointed without re-hashing the whole trace.
+    pub fn verify_chunk(
+        ctx: Context<VerifyChunk>,
+        index: u32,
+        chunk: Vec<u8>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let commitment = &ctx.accounts.commitment;
+        check_chunk(commitment, index, &chunk, &proof)?;
+        
+        emit!(ChunkVerified {
+            commitment: commitment.key(),
+            index,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Escrow lamports for the commitment's counterparty, paid out step
+    /// by step as the action's trace is revealed. Authority only.
+    ///
+    /// Each chunk of a chunked commitment is one step and releases an
+    /// equal tranche (the last one takes the remainder) once it is
+    /// checked against the Merkle root with `release_tranche`. The escrow
+    /// is funded before the reveal and bounded by the action value the
+    /// commitment disclosed (`risk.affected_lamports`).
+    pub fn open_escrow(ctx: Context<OpenEscrow>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &mut ctx.accounts.commitment;
+        require!(
+            commitment.status == CommitmentStatus::Committed
+                && !commitment.expired
+                && !commitment.cancelled,
+            AxiomError::AlreadyRevealed
+        );
+        let counterparty = commitment.counterparty.ok_or(AxiomError::NoCounterparty)?;
+        let manifest = commitment.chunk_manifest.ok_or(AxiomError::NotChunked)?;
+        require!(
+            manifest.chunk_count <= MAX_ESCROW_TRANCHES,
+            AxiomError::TooManyTranches
+        );
+        let action_value = commitment.risk.map_or(0, |risk| risk.affected_lamports);
+        require!(
+            amount > 0 && amount <= action_value,
+            AxiomError::EscrowExceedsActionValue
+        );
+        commitment.escrowed = true;
+        
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.commitment = commitment.key();
+        escrow.depositor = ctx.accounts.authority.key();
+        escrow.counterparty = counterparty;
+        escrow.amount = amount;
+        escrow.released = 0;
+        escrow.tranches = manifest.chunk_count;
+        escrow.released_tranches = 0;
+        escrow.bump = ctx.bumps.escrow;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: escrow.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        emit!(EscrowOpened {
+            commitment: escrow.commitment,
+            counterparty,
+            amount,
+            tranches: escrow.tranches,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Pay the counterparty the tranche for chunk `index`. Permissionless.
+    ///
+    /// Needs the reveal to have started and `chunk` to check out against
+    /// the commitment's Merkle root, as in `verify_chunk`. Each tranche
+    /// is paid once; none are paid for a slashed commitment.
+    pub fn release_tranche(
+        ctx: Context<ReleaseTranche>,
+        index: u32,
+        chunk: Vec<u8>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &ctx.accounts.commitment;
+        require!(
+            commitment.status != CommitmentStatus::Committed,
+            AxiomError::NotRevealed
+        );
+        require!(!commitment.slashed, AxiomError::AlreadySlashed);
+        check_chunk(commitment, index, &chunk, &proof)?;
+        
+        let escrow = &mut ctx.accounts.escrow;
+        let bit = 1u64 << index;
+        require!(escrow.released_tranches & bit == 0, AxiomError::TrancheReleased);
+        let amount = escrow.tranche_amount(index);
+        escrow.released_tranches |= bit;
+        escrow.released = escrow.released.checked_add(amount)
+            .ok_or(AxiomError::Overflow)?;
+        escrow.sub_lamports(amount)?;
+        ctx.accounts.counterparty.add_lamports(amount)?;
+        
+        emit!(TrancheReleased {
+            commitment: escrow.commitment,
+            counterparty: escrow.counterparty,
+            index,
+            amount,
+            released: escrow.released,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Close an escrow and return what it still holds to the depositor.
+    ///
+    /// Once every tranche is paid, once the reveal's challenge window has
+    /// passed (chunks never published, e.g. redacted ones, stay unpaid),
+    /// or as soon as the commitment was cancelled, expired or slashed.
+    pub fn close_escrow(ctx: Context<CloseEscrow>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = &ctx.accounts.escrow;
+        let commitment = &mut ctx.accounts.commitment;
+        let window_ends = commitment.revealed_at
+            .checked_add(ctx.accounts.config.challenge_window)
+            .ok_or(AxiomError::Overflow)?;
+        let window_passed = commitment.status == CommitmentStatus::Revealed && now >= window_ends;
+        require!(
+            escrow.released == escrow.amount
+                || window_passed
+                || commitment.cancelled
+                || commitment.expired
+                || commitment.slashed,
+            AxiomError::EscrowActive
+        );
+        commitment.escrowed = false;
+        
+        emit!(EscrowClosed {
+            commitment: escrow.commitment,
+            depositor: escrow.depositor,
+            refunded: escrow.amount.saturating_sub(escrow.released),
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Record that the agent signed the committed trace.
+    ///
+    /// Must directly follow an Ed25519 program instruction verifying one
+    /// signature, with key and message inline, over `commitment_hash`.
+    /// The signer must be the agent's authority, its current signing key,
+    /// or the key of `signing_key_record` if that key was active when the
+    /// commitment was made. The runtime has already checked the signature
+    /// by then, so this only confirms who signed what. Anyone holding the
+    /// signed trace can submit it, which keeps the trace attributable to
+    /// the agent wherever it is rehosted.
+    pub fn verify_trace_signature(ctx: Context<VerifyTraceSignature>) -> Result<()> {
+        let (signer, message) = preceding_signature(&ctx.accounts.instructions)?;
+        let profile = &ctx.accounts.agent_profile;
+        let commitment = &mut ctx.accounts.commitment;
+        let historical = ctx.accounts.signing_key_record.as_ref().is_some_and(|record| {
+            record.key == signer && record.was_active_at(commitment.timestamp)
+        });
+        require!(
+            signer == profile.authority || profile.signing_key == Some(signer) || historical,
+            AxiomError::TraceSignatureMismatch
+        );
+        require!(
+            message == commitment.commitment_hash.as_ref(),
+            AxiomError::TraceSignatureMismatch
+        );
+        commitment.trace_signer = Some(signer);
+        
+        emit!(TraceSignatureVerified {
+            commitment: commitment.key(),
+            signer,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Prove onchain that `trace` is what the commitment committed to.
+    ///
+    /// Hashes the bytes with the SHA-256 syscall (as a Merkle root when
+    /// the commitment has a chunk manifest) and, on a match, marks the
+    /// commitment `verified_onchain`. Permissionless; the trace must fit
+    /// in one transaction, so roughly 900 bytes at most. Also sets
+    /// `action_in_trace` if the trace contains the claimed action
+    /// signature (in base58). Passing a `VerificationBounty` on the
+    /// commitment collects it.
+    pub fn verify_reveal_inline(ctx: Context<VerifyRevealInline>, trace: Vec<u8>) -> Result<()> {
+        let commitment = &mut ctx.accounts.commitment;
+        require!(
+            trace_digest(
+                &trace,
+                commitment.chunk_manifest,
+                commitment.hash_algorithm,
+                commitment.salt,
+            )
+                == Some(commitment.commitment_hash),
+            AxiomError::TraceHashMismatch
+        );
+        commitment.verified_onchain = true;
+        
+        let now = Clock::get()?.unix_timestamp;
+        emit!(TraceVerifiedOnchain {
+            commitment: commitment.key(),
+            verifier: ctx.accounts.verifier.key(),
+            trace_len: trace.len() as u64,
+            timestamp: now,
+        });
+        
+        if let Some(action_signature) = commitment.action_signature {
+            let encoded = base58(&action_signature);
+            if !commitment.action_in_trace && trace.windows(encoded.len()).any(|w| w == encoded) {
+                commitment.action_in_trace = true;
+                emit!(ActionFoundInTrace {
+                    commitment: commitment.key(),
+                    action_signature,
+                    timestamp: now,
+                });
+            }
+        }
+        
+        pay_bounty(
+            ctx.accounts.bounty.as_ref(),
+            ctx.accounts.verifier.to_account_info(),
+            now,
+        )
+    }
+
+    /// Start verifying a trace too large for `verify_reveal_inline`.
+    ///
+    /// Opens a `ReasoningBuffer` for the signer; `append_chunk` feeds it
+    /// the trace across as many transactions as needed, keeping only a
+    /// running SHA-256 state, and `finalize_verification` compares the
+    /// digest. Chunk-manifest commitments don't need this: each chunk
+    /// is provable on its own against the Merkle root. Only SHA-256
+    /// commitments can be streamed.
+    pub fn begin_verification(ctx: Context<BeginVerification>) -> Result<()> {
+        require!(
+            ctx.accounts.commitment.chunk_manifest.is_none(),
+            AxiomError::ChunkedCommitment
+        );
+        require!(
+            ctx.accounts.commitment.hash_algorithm == HashAlgorithm::Sha256,
+            AxiomError::UnsupportedHashAlgorithm
+        );
+        let buffer = &mut ctx.accounts.buffer;
+        buffer.commitment = ctx.accounts.commitment.key();
+        buffer.verifier = ctx.accounts.verifier.key();
+        buffer.hasher = sha256_stream::Sha256Stream::default();
+        if let Some(salt) = ctx.accounts.commitment.salt {
+            buffer.hasher.update(&salt);
+        }
+        buffer.started_at = Clock::get()?.unix_timestamp;
+        buffer.bump = ctx.bumps.buffer;
+        Ok(())
+    }
+
+    /// Hash the next piece of the trace into the buffer.
+    pub fn append_chunk(ctx: Context<AppendChunk>, data: Vec<u8>) -> Result<()> {
+        ctx.accounts.buffer.hasher.update(&data);
+        Ok(())
+    }
+
+    /// Finish a streamed verification, closing the buffer. On a match
+    /// the commitment is marked `verified_onchain` and any bounty passed
+    /// is paid, as with `verify_reveal_inline`.
+    pub fn finalize_verification(ctx: Context<FinalizeVerification>) -> Result<()> {
+        let buffer = &ctx.accounts.buffer;
+        let commitment = &mut ctx.accounts.commitment;
+        require!(
+            buffer.hasher.finalize() == commitment.commitment_hash,
+            AxiomError::TraceHashMismatch
+        );
+        commitment.verified_onchain = true;
+        
+        let now = Clock::get()?.unix_timestamp;
+        emit!(TraceVerifiedOnchain {
+            commitment: commitment.key(),
+            verifier: buffer.verifier,
+            trace_len: buffer.hasher.len - commitment.salt.map_or(0, |salt| salt.len() as u64),
+            timestamp: now,
+        });
+        
+        pay_bounty(
+            ctx.accounts.bounty.as_ref(),
+            ctx.accounts.verifier.to_account_info(),
+            now,
+        )
+    }
+
+    /// Abandon a streamed verification (e.g. after a mismatch), returning
+    /// the buffer's rent.
+    pub fn cancel_verification(_ctx: Context<CancelVerification>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Escrow `amount` lamports for whoever first checks the
+    /// commitment's reveal onchain: a matching `verify_reveal_inline` or
+    /// `finalize_verification`, or a proven `slash_mismatch`. Anyone may
+    /// post one bounty per commitment; the payout takes the bounty's
+    /// rent with it.
+    ///
+    /// Unclaimed by `expires_at`, it goes back to the funder through
+    /// `reclaim_bounty`.
+    pub fn post_bounty(ctx: Context<PostBounty>, amount: u64, expires_at: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &ctx.accounts.commitment;
+        require!(
+            amount > 0
+                && expires_at > now
+                && !commitment.verified_onchain
+                && !commitment.slashed,
+            AxiomError::InvalidBounty
+        );
+        
+        let bounty = &mut ctx.accounts.bounty;
+        bounty.commitment = commitment.key();
+        bounty.funder = ctx.accounts.funder.key();
+        bounty.amount = amount;
+        bounty.expires_at = expires_at;
+        bounty.bump = ctx.bumps.bounty;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: bounty.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        emit!(BountyPosted {
+            bounty: bounty.key(),
+            commitment: bounty.commitment,
+            funder: bounty.funder,
+            amount,
+            expires_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Take back an expired, unclaimed bounty. Funder only.
+    pub fn reclaim_bounty(ctx: Context<ReclaimBounty>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let bounty = &ctx.accounts.bounty;
+        require!(now >= bounty.expires_at, AxiomError::BountyActive);
+        
+        emit!(BountyReclaimed {
+            bounty: bounty.key(),
+            commitment: bounty.commitment,
+            funder: bounty.funder,
+            amount: bounty.amount,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Attest that a reveal's URI was fetchable at `checked_at` and its
+    /// content matched `commitment_hash`.
+    ///
+    /// Only members of the configured storage verifier committee may
+    /// attest, once each per commitment (enforced by the attestation
+    /// PDA). When the quorum is reached, the commitment records
+    /// `available_at`, closing the "reveal a dead link" loophole for
+    /// consumers that require it.
+    pub fn attest_availability(
+        ctx: Context<AttestAvailability>,
+        checked_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.config;
+        let verifier = ctx.accounts.verifier.key();
+        require!(
+            config.storage_verifiers.contains(&verifier),
+            AxiomError::NotStorageVerifier
+        );
+        
+        let commitment = &mut ctx.accounts.commitment;
+        require!(
+            commitment.status != CommitmentStatus::Committed,
+            AxiomError::NotRevealed
+        );
+        require!(
+            commitment.revealed_at <= checked_at && checked_at <= now,
+            AxiomError::InvalidCheckTime
+        );
+        commitment.availability_attestations = commitment.availability_attestations
+            .checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        if commitment.available_at.is_none()
+            && commitment.availability_attestations >= config.storage_quorum
+        {
+            commitment.available_at = Some(checked_at);
+        }
+        
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.commitment = commitment.key();
+        attestation.verifier = verifier;
+        attestation.checked_at = checked_at;
+        attestation.bump = ctx.bumps.attestation;
+        
+        emit!(AvailabilityAttested {
+            commitment: commitment.key(),
+            verifier,
+            checked_at,
+            attestations: commitment.availability_attestations,
+            available_at: commitment.available_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Record an independent check of a reveal: the verifier fetched the
+    /// reasoning URI at `checked_at` and found content hashing to
+    /// `content_hash`, which `passed` says matched `commitment_hash` or
+    /// not.
+    ///
+    /// Open to anyone, once per verifier per commitment. The commitment
+    /// keeps pass/fail tallies so consumers can see how well-checked a
+    /// reveal is; who the verifiers are is for them to weigh.
+    pub fn attest_commitment(
+        ctx: Context<AttestCommitment>,
+        passed: bool,
+        content_hash: [u8; 32],
+        checked_at: i64,
+    ) -> Result<()> {
+        record_attestation(
+            &mut ctx.accounts.commitment,
+            &mut ctx.accounts.attestation,
+            ctx.accounts.verifier.key(),
+            passed,
+            content_hash,
+            checked_at,
+            ctx.bumps.attestation,
+        )
+    }
+
+    /// Escrow `fee` for an independent review of a revealed commitment.
+    /// Only its authority or named counterparty may ask, once each.
+    ///
+    /// Any other key can claim the request by bonding `reviewer_bond`
+    /// and then has `review_period` to submit its attestation, which
+    /// pays it the fee and returns the bond. A request nobody claimed
+    /// can be withdrawn; one whose reviewer missed the deadline lapses,
+    /// forfeiting the bond to the requester.
+    pub fn request_review(ctx: Context<RequestReview>, fee: u64) -> Result<()> {
+        require!(fee > 0, AxiomError::InvalidReviewFee);
+        let commitment = &ctx.accounts.commitment;
+        let requester = ctx.accounts.requester.key();
+        require!(
+            commitment.status == CommitmentStatus::Revealed,
+            AxiomError::NotRevealed
+        );
+        require!(
+            requester == commitment.authority || commitment.counterparty == Some(requester),
+            AxiomError::Unauthorized
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let review = &mut ctx.accounts.review;
+        review.commitment = commitment.key();
+        review.requester = requester;
+        review.reviewer = None;
+        review.fee = fee;
+        review.bond = 0;
+        review.requested_at = now;
+        review.deadline = None;
+        review.bump = ctx.bumps.review;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.requester.to_account_info(),
+                    to: review.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+        
+        emit!(ReviewRequested {
+            review: review.key(),
+            commitment: review.commitment,
+            requester,
+            fee,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Take an open review request, bonding the configured
+    /// `reviewer_bond`. The commitment's own authority can't review it.
+    pub fn claim_review(ctx: Context<ClaimReview>) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let reviewer = ctx.accounts.reviewer.key();
+        require!(
+            reviewer != ctx.accounts.commitment.authority,
+            AxiomError::Unauthorized
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let review = &mut ctx.accounts.review;
+        require!(review.reviewer.is_none(), AxiomError::ReviewAlreadyClaimed);
+        review.reviewer = Some(reviewer);
+        review.bond = config.reviewer_bond;
+        let deadline = now.checked_add(config.review_period)
+            .ok_or(AxiomError::Overflow)?;
+        review.deadline = Some(deadline);
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.reviewer.to_account_info(),
+                    to: review.to_account_info(),
+                },
+            ),
+            review.bond,
+        )?;
+        
+        emit!(ReviewClaimed {
+            review: review.key(),
+            commitment: review.commitment,
+            reviewer,
+            bond: review.bond,
+            deadline,
+        });
+        
+        Ok(())
+    }
+
+    /// Complete a claimed review before its deadline with the same
+    /// attestation `attest_commitment` records, and collect the fee and
+    /// bond. The request is closed, its rent returned to the requester.
+    pub fn submit_review(
+        ctx: Context<SubmitReview>,
+        passed: bool,
+        content_hash: [u8; 32],
+        checked_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let review = &ctx.accounts.review;
+        let reviewer = ctx.accounts.reviewer.key();
+        require!(review.reviewer == Some(reviewer), AxiomError::Unauthorized);
+        require!(
+            review.deadline.is_some_and(|deadline| now < deadline),
+            AxiomError::ReviewDeadlinePassed
+        );
+        
+        record_attestation(
+            &mut ctx.accounts.commitment,
+            &mut ctx.accounts.attestation,
+            reviewer,
+            passed,
+            content_hash,
+            checked_at,
+            ctx.bumps.attestation,
+        )?;
+        
+        let paid = review.fee.checked_add(review.bond)
+            .ok_or(AxiomError::Overflow)?;
+        review.sub_lamports(paid)?;
+        ctx.accounts.reviewer.add_lamports(paid)?;
+        
+        emit!(ReviewCompleted {
+            review: review.key(),
+            commitment: review.commitment,
+            reviewer,
+            passed,
+            fee: review.fee,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw a review request nobody has claimed, refunding the fee.
+    /// Requester only.
+    pub fn cancel_review(ctx: Context<CancelReview>) -> Result<()> {
+        let review = &ctx.accounts.review;
+        require!(review.reviewer.is_none(), AxiomError::ReviewAlreadyClaimed);
+        
+        emit!(ReviewCancelled {
+            review: review.key(),
+            commitment: review.commitment,
+            requester: review.requester,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Close a claimed review whose reviewer missed the deadline. The
+    /// requester gets the fee back and the reviewer's bond with it.
+    /// Permissionless once the deadline has passed.
+    pub fn lapse_review(ctx: Context<LapseReview>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let review = &ctx.accounts.review;
+        require!(
+            review.deadline.is_some_and(|deadline| now >= deadline),
+            AxiomError::ReviewPeriodActive
+        );
+        
+        emit!(ReviewLapsed {
+            review: review.key(),
+            commitment: review.commitment,
+            reviewer: review.reviewer.unwrap_or_default(),
+            bond: review.bond,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Link an already-executed action transaction to its commitment,
+    /// for when the agent missed recording it at the time.
+    ///
+    /// The program can't look up past transactions, so on its own the
+    /// link is the agent's claim; it only checks that `action_slot`
+    /// falls after the commitment was made and isn't in the future.
+    /// Indexers confirm the signature landed in that slot. The claim is
+    /// bound to the reasoning when the agent signed the action before
+    /// committing and put its signature in the trace: a later
+    /// `verify_reveal_inline` then finds it there and sets
+    /// `action_in_trace`. One claim per commitment.
+    pub fn claim_action(
+        ctx: Context<ClaimAction>,
+        action_signature: [u8; 64],
+        action_slot: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let commitment = &mut ctx.accounts.commitment;
+        require!(!commitment.locked, AxiomError::CommitmentLocked);
+        require!(
+            commitment.action_signature.is_none(),
+            AxiomError::ActionAlreadyClaimed
+        );
+        require!(
+            action_slot > commitment.slot,
+            AxiomError::ActionPredatesCommitment
+        );
+        require!(action_slot <= clock.slot, AxiomError::InvalidActionSlot);
+        
+        commitment.action_signature = Some(action_signature);
+        commitment.action_slot = Some(action_slot);
+        
+        emit!(ActionClaimed {
+            agent: commitment.agent,
+            commitment: commitment.key(),
+            action_signature,
+            action_slot,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Permanently freeze a revealed commitment, e.g. once it is cited in
+    /// a legal or compliance record. The agent can no longer amend it
+    /// (claim an action, publish details) and it can never be closed.
+    /// Third-party records about it, like reactions and attestations,
+    /// are unaffected.
+    pub fn lock_commitment(ctx: Context<LockCommitment>) -> Result<()> {
+        let commitment = &mut ctx.accounts.commitment;
+        require!(
+            commitment.status == CommitmentStatus::Revealed,
+            AxiomError::NotRevealed
+        );
+        require!(!commitment.locked, AxiomError::CommitmentLocked);
+        require!(
+            commitment.uri_expiry_epoch.is_none(),
+            AxiomError::RetentionConflict
+        );
+        commitment.locked = true;
+        
+        emit!(CommitmentLocked {
+            agent: commitment.agent,
+            commitment: commitment.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Mark a reveal's URI to be cleared after `retention_epochs`, for
+    /// traces holding user data that may not be kept indefinitely.
+    ///
+    /// Only the pointer goes: the hash, status and every count stay, so
+    /// the record of the commitment and its reveal survives. Needs a
+    /// retention policy in the config; locked commitments can't expire.
+    pub fn set_reveal_retention(
+        ctx: Context<SetRevealRetention>,
+        retention_epochs: u64,
+    ) -> Result<()> {
+        let Some(min_retention_epochs) = ctx.accounts.config.min_retention_epochs else {
+            return err!(AxiomError::RetentionDisabled);
+        };
+        require!(
+            retention_epochs >= min_retention_epochs,
+            AxiomError::RetentionTooShort
+        );
+        let commitment = &mut ctx.accounts.commitment;
+        require!(
+            commitment.status != CommitmentStatus::Committed,
+            AxiomError::NotRevealed
+        );
+        require!(!commitment.locked, AxiomError::RetentionConflict);
+        require!(
+            commitment.uri_expiry_epoch.is_none(),
+            AxiomError::RetentionAlreadySet
+        );
+        
+        let expiry_epoch = Clock::get()?.epoch.checked_add(retention_epochs)
+            .ok_or(AxiomError::Overflow)?;
+        commitment.uri_expiry_epoch = Some(expiry_epoch);
+        Ok(())
+    }
+
+    /// Clear the URI of a reveal whose retention has run out.
+    /// Permissionless, so expiry doesn't depend on the agent.
+    pub fn expire_reveal(ctx: Context<ExpireReveal>) -> Result<()> {
+        let clock = Clock::get()?;
+        let commitment = &mut ctx.accounts.commitment;
+        let Some(expiry_epoch) = commitment.uri_expiry_epoch else {
+            return err!(AxiomError::RetentionNotExpired);
+        };
+        require!(clock.epoch >= expiry_epoch, AxiomError::RetentionNotExpired);
+        require!(!commitment.reasoning_uri.is_empty(), AxiomError::RetentionNotExpired);
+        commitment.reasoning_uri = String::new();
+        
+        emit!(RevealExpired {
+            commitment: commitment.key(),
+            epoch: clock.epoch,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Declare which chunks of a reveal were withheld, e.g. for PII.
+    ///
+    /// Only for chunk-manifest commitments. The published trace carries
+    /// each listed chunk as its Merkle leaf hash instead of its data,
+    /// which still reproduces `commitment_hash` (see
+    /// [`merkle::root_from_leaves`]), so the reveal stays verifiable.
+    /// A short chunk's leaf hash can be brute-forced; agents that may
+    /// redact should pad sensitive chunks with random bytes when
+    /// committing. `redacted_chunks` is strictly increasing, and can
+    /// only be declared once.
+    pub fn declare_redactions(
+        ctx: Context<DeclareRedactions>,
+        redacted_chunks: Vec<u32>,
+    ) -> Result<()> {
+        let commitment = &mut ctx.accounts.commitment;
+        require!(!commitment.locked, AxiomError::CommitmentLocked);
+        require!(
+            commitment.chunk_manifest.is_some(),
+            AxiomError::InvalidChunkManifest
+        );
+        require!(
+            commitment.status != CommitmentStatus::Committed,
+            AxiomError::NotRevealed
+        );
+        require!(
+            commitment.redacted_chunks == 0,
+            AxiomError::RedactionsAlreadyDeclared
+        );
+        require!(
+            !redacted_chunks.is_empty()
+                && redacted_chunks.windows(2).all(|w| w[0] < w[1])
+                && redacted_chunks.iter().all(|&i| i < commitment.chunk_count),
+            AxiomError::InvalidRedactions
+        );
+        commitment.redacted_chunks = redacted_chunks.len() as u32;
+        
+        emit!(RedactionsDeclared {
+            commitment: commitment.key(),
+            redacted_chunks,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Sign, as the commitment's named counterparty, that you have seen
+    /// it. Only possible once, and only before the reveal starts, so the
+    /// record shows the counterparty knew of it ahead of the action.
+    pub fn acknowledge_commitment(ctx: Context<AcknowledgeCommitment>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &mut ctx.accounts.commitment;
+        require!(
+            commitment.counterparty == Some(ctx.accounts.counterparty.key()),
+            AxiomError::Unauthorized
+        );
+        require!(
+            commitment.status == CommitmentStatus::Committed,
+            AxiomError::AlreadyRevealed
+        );
+        require!(
+            commitment.acknowledged_at.is_none(),
+            AxiomError::AlreadyAcknowledged
+        );
+        commitment.acknowledged_at = Some(now);
+        
+        emit!(CommitmentAcknowledged {
+            commitment: commitment.key(),
+            counterparty: ctx.accounts.counterparty.key(),
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Up- or downvote a revealed commitment. One reaction per wallet per
+    /// commitment, enforced by the reaction PDA.
+    pub fn react(ctx: Context<React>, upvote: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let commitment = &mut ctx.accounts.commitment;
+        require!(
+            commitment.status == CommitmentStatus::Revealed,
+            AxiomError::NotRevealed
+        );
+        if upvote {
+            commitment.upvotes = commitment.upvotes.checked_add(1)
+                .ok_or(AxiomError::Overflow)?;
+        } else {
+            commitment.downvotes = commitment.downvotes.checked_add(1)
+                .ok_or(AxiomError::Overflow)?;
+        }
+        
+        let reaction = &mut ctx.accounts.reaction;
+        reaction.commitment = commitment.key();
+        reaction.reactor = ctx.accounts.reactor.key();
+        reaction.upvote = upvote;
+        reaction.reacted_at = now;
+        reaction.bump = ctx.bumps.reaction;
+        
+        emit!(ReactionAdded {
+            commitment: commitment.key(),
+            reactor: ctx.accounts.reactor.key(),
+            upvote,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Follow an agent, e.g. to be notified of its commitments. One
+    /// `Follower` per wallet per agent, enforced by its PDA; the
+    /// profile's `follower_count` tallies them. Each follow costs the
+    /// follower rent, which keeps the count expensive to inflate, and
+    /// who follows stays visible onchain.
+    pub fn follow_agent(ctx: Context<FollowAgent>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let profile = &mut ctx.accounts.agent_profile;
+        let count = profile.follower_count.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        profile.follower_count = count;
+        
+        let follower = &mut ctx.accounts.follower;
+        follower.agent = profile.key();
+        follower.follower = ctx.accounts.wallet.key();
+        follower.followed_at = now;
+        follower.bump = ctx.bumps.follower;
+        
+        emit!(AgentFollowed {
+            agent: follower.agent,
+            follower: follower.follower,
+            follower_count: count,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Stop following an agent, returning the `Follower`'s rent.
+    pub fn unfollow_agent(ctx: Context<UnfollowAgent>) -> Result<()> {
+        let profile = &mut ctx.accounts.agent_profile;
+        let count = profile.follower_count.saturating_sub(1);
+        profile.follower_count = count;
+        
+        emit!(AgentUnfollowed {
+            agent: profile.key(),
+            follower: ctx.accounts.wallet.key(),
+            follower_count: count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Compare two agents' track records, for routing programs choosing
+    /// between them onchain.
+    ///
+    /// Read-only; the result is set as return data, so it can be read
+    /// by a CPI caller via `get_return_data` or by simulating the
+    /// instruction.
+    pub fn compare_agents(ctx: Context<CompareAgents>) -> Result<AgentComparison> {
+        let first = ctx.accounts.first.stats(ctx.accounts.first.key());
+        let second = ctx.accounts.second.stats(ctx.accounts.second.key());
+        let preferred = if second.outranks(&first) { second.agent } else { first.agent };
+        Ok(AgentComparison { first, second, preferred })
+    }
+
+    /// Report the protocol version and which optional subsystems this
+    /// deployment has enabled, so CPI callers can check before relying
+    /// on one.
+    ///
+    /// Read-only; the result is set as return data, like
+    /// `compare_agents`'s.
+    pub fn get_features(ctx: Context<GetFeatures>) -> Result<ProtocolFeatures> {
+        let config = &ctx.accounts.config;
+        Ok(ProtocolFeatures {
+            version: PROTOCOL_VERSION,
+            features: config.features,
+            paused: config.paused,
+        })
+    }
+
+    /// Create the protocol config. Only the program's upgrade authority
+    /// can call this, and only once; it becomes the initial admin.
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        score_floor: u16,
+        score_ceiling: u16,
+    ) -> Result<()> {
+        validate_score_bounds(score_floor, score_ceiling)?;
+        
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.score_floor = score_floor;
+        config.score_ceiling = score_ceiling;
+        config.probation_threshold = DEFAULT_PROBATION_THRESHOLD;
+        config.probation_required_reveals = DEFAULT_PROBATION_REQUIRED_REVEALS;
+        config.probation_recovery_bps = DEFAULT_PROBATION_RECOVERY_BPS;
+        config.probation_reference_lamports = 0;
+        config.reveal_grace_period = DEFAULT_REVEAL_GRACE_PERIOD;
+        config.storage_verifiers = Vec::new();
+        config.storage_quorum = 0;
+        config.min_retention_epochs = None;
+        config.unstake_delay = DEFAULT_UNSTAKE_DELAY;
+        config.slash_bps = 0;
+        config.slash_challenger_bps = 0;
+        config.treasury = ctx.accounts.admin.key();
+        config.challenge_bond = DEFAULT_CHALLENGE_BOND;
+        config.challenge_period = DEFAULT_CHALLENGE_PERIOD;
+        config.challenge_penalty_bps = DEFAULT_CHALLENGE_PENALTY_BPS;
+        config.council = Vec::new();
+        config.council_threshold = 0;
+        config.expiry_penalty_bps = DEFAULT_EXPIRY_PENALTY_BPS;
+        config.score_reveal_weight_bps = DEFAULT_SCORE_REVEAL_WEIGHT_BPS;
+        config.score_dispute_weight_bps = DEFAULT_SCORE_DISPUTE_WEIGHT_BPS;
+        config.challenge_window = DEFAULT_CHALLENGE_WINDOW;
+        config.reviewer_bond = DEFAULT_REVIEWER_BOND;
+        config.review_period = DEFAULT_REVIEW_PERIOD;
+        config.max_uri_len = MAX_URI_LEN;
+        config.guardian = ctx.accounts.admin.key();
+        config.paused = false;
+        config.features = ALL_FEATURES;
+        config.commit_fee = 0;
+        config.reveal_rebate_bps = 0;
+        config.max_deadline_extension = DEFAULT_MAX_DEADLINE_EXTENSION;
+        config.extension_fee = 0;
+        config.outcome_dispute_window = DEFAULT_OUTCOME_DISPUTE_WINDOW;
+        config.outcome_score_bps = DEFAULT_OUTCOME_SCORE_BPS;
+        config.integrator_fee_share_bps = 0;
+        config.outcome_verifiers = Vec::new();
+        config.bump = ctx.bumps.config;
+        
+        Ok(())
+    }
+
+    /// Update the score bounds. Admin only.
+    pub fn update_score_bounds(
+        ctx: Context<UpdateConfig>,
+        score_floor: u16,
+        score_ceiling: u16,
+    ) -> Result<()> {
+        validate_score_bounds(score_floor, score_ceiling)?;
+        
+        let config = &mut ctx.accounts.config;
+        config.score_floor = score_floor;
+        config.score_ceiling = score_ceiling;
+        
+        Ok(())
+    }
+
+    /// Update the probation terms offered to penalized agents. Admin only.
+    pub fn update_probation_terms(
+        ctx: Context<UpdateConfig>,
+        probation_threshold: u16,
+        probation_required_reveals: u32,
+        probation_recovery_bps: u16,
+    ) -> Result<()> {
+        require!(probation_threshold <= 10000, AxiomError::InvalidScore);
+        require!(probation_required_reveals > 0, AxiomError::InvalidProbationTerms);
+        require!(probation_recovery_bps <= 10000, AxiomError::InvalidProbationTerms);
+        
+        let config = &mut ctx.accounts.config;
+        config.probation_threshold = probation_threshold;
+        config.probation_required_reveals = probation_required_reveals;
+        config.probation_recovery_bps = probation_recovery_bps;
+        
+        Ok(())
+    }
+
+    /// Weight probation recovery by each revealed commitment's declared
+    /// value at risk. Admin only.
+    ///
+    /// A reveal whose `RiskDisclosure::affected_lamports` reaches
+    /// `reference_lamports` recovers the full `recovery_bps`; smaller
+    /// stakes recover proportionally less, and undeclared ones nothing,
+    /// so a serious failure can't be worked off with trivial actions.
+    /// 0 turns weighting off.
+    pub fn update_probation_value_weighting(
+        ctx: Context<UpdateConfig>,
+        reference_lamports: u64,
+    ) -> Result<()> {
+        ctx.accounts.config.probation_reference_lamports = reference_lamports;
+        Ok(())
+    }
+
+    /// Allow agents to mark reveals for expiry, no sooner than
+    /// `min_retention_epochs` after marking, or disallow it with `None`.
+    /// Admin only. Already-marked reveals keep their expiry.
+    pub fn update_retention_policy(
+        ctx: Context<UpdateConfig>,
+        min_retention_epochs: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.config.min_retention_epochs = min_retention_epochs;
+        Ok(())
+    }
+
+    /// Update how long unstaked collateral stays locked before it can be
+    /// withdrawn. Admin only. Pending requests keep their original time.
+    pub fn update_unstake_delay(ctx: Context<UpdateConfig>, unstake_delay: i64) -> Result<()> {
+        require!(unstake_delay >= 0, AxiomError::InvalidDuration);
+        ctx.accounts.config.unstake_delay = unstake_delay;
+        Ok(())
+    }
+
+    /// Update how long reveals stay open before finalization. Admin only.
+    pub fn update_reveal_grace_period(
+        ctx: Context<UpdateConfig>,
+        reveal_grace_period: i64,
+    ) -> Result<()> {
+        require!(reveal_grace_period >= 0, AxiomError::InvalidDuration);
+        ctx.accounts.config.reveal_grace_period = reveal_grace_period;
+        Ok(())
+    }
+
+    /// Set the storage verifier committee and how many of its members
+    /// must attest a reveal's availability. Admin only; an empty committee
+    /// with a quorum of 0 disables the flow.
+    pub fn update_storage_verifiers(
+        ctx: Context<UpdateConfig>,
+        storage_verifiers: Vec<Pubkey>,
+        storage_quorum: u8,
+    ) -> Result<()> {
+        require!(
+            storage_verifiers.len() <= MAX_STORAGE_VERIFIERS,
+            AxiomError::InvalidStorageQuorum
+        );
+        require!(
+            storage_quorum as usize <= storage_verifiers.len()
+                && (storage_quorum == 0) == storage_verifiers.is_empty(),
+            AxiomError::InvalidStorageQuorum
+        );
+        let config = &mut ctx.accounts.config;
+        config.storage_verifiers = storage_verifiers;
+        config.storage_quorum = storage_quorum;
+        Ok(())
+    }
+
+    /// Put a heavily penalized agent on probation.
+    ///
+    /// Only agents scoring below the configured threshold may enter. The
+    /// current terms are copied onto the profile, and every subsequent
+    /// reveal recovers `recovery_bps` of score until `required_reveals`
+    /// have been made, at which point probation ends.
+    pub fn enter_probation(ctx: Context<EnterProbation>) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let profile = &mut ctx.accounts.agent_profile;
+        require!(profile.probation.is_none(), AxiomError::AlreadyOnProbation);
+        require!(
+            profile.accountability_score < config.probation_threshold,
+            AxiomError::ScoreAboveProbationThreshold
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        profile.probation = Some(Probation {
+            started_at: now,
+            required_reveals: config.probation_required_reveals,
+            completed_reveals: 0,
+            recovery_bps: config.probation_recovery_bps,
+            max_score: config.score_ceiling,
+            reference_lamports: config.probation_reference_lamports,
+        });
+        
+        emit!(ProbationStarted {
+            agent: profile.key(),
+            accountability_score: profile.accountability_score,
+            required_reveals: config.probation_required_reveals,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Post SOL collateral behind the agent's reasoning.
+    ///
+    /// The lamports move into the agent's stake vault PDA and count
+    /// towards `staked_lamports` on the profile until withdrawn.
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        require!(amount > 0, AxiomError::InvalidStakeAmount);
+        
+        let vault = &mut ctx.accounts.stake_vault;
+        vault.agent = ctx.accounts.agent_profile.key();
+        vault.bump = ctx.bumps.stake_vault;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        let profile = &mut ctx.accounts.agent_profile;
+        let staked = profile.staked_lamports.checked_add(amount)
+            .ok_or(AxiomError::Overflow)?;
+        profile.staked_lamports = staked;
+        
+        emit!(StakeChanged {
+            agent: profile.key(),
+            staked_lamports: staked,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Start the unstake delay for `amount` of the agent's stake. Only
+    /// one request can be pending; a new one replaces it and restarts
+    /// the delay. The stake keeps counting until withdrawn, and can't
+    /// be requested while a challenge against the agent is open.
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+        require!(
+            ctx.accounts.agent_profile.open_challenges == 0,
+            AxiomError::ChallengesOpen
+        );
+        require!(
+            amount > 0 && amount <= ctx.accounts.agent_profile.staked_lamports,
+            AxiomError::InvalidStakeAmount
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let vault = &mut ctx.accounts.stake_vault;
+        vault.pending_unstake = amount;
+        vault.unstake_available_at = Some(
+            now.checked_add(ctx.accounts.config.unstake_delay)
+                .ok_or(AxiomError::Overflow)?,
+        );
+        Ok(())
+    }
+
+    /// Withdraw a pending unstake once its delay has passed and no
+    /// challenge against the agent is open.
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        require!(
+            ctx.accounts.agent_profile.open_challenges == 0,
+            AxiomError::ChallengesOpen
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let vault = &mut ctx.accounts.stake_vault;
+        let Some(available_at) = vault.unstake_available_at else {
+            return err!(AxiomError::NoPendingUnstake);
+        };
+        require!(now >= available_at, AxiomError::UnstakeDelayActive);
+        
+        let amount = vault.pending_unstake;
+        vault.pending_unstake = 0;
+        vault.unstake_available_at = None;
+        vault.sub_lamports(amount)?;
+        ctx.accounts.authority.add_lamports(amount)?;
+        
+        let profile = &mut ctx.accounts.agent_profile;
+        let staked = profile.staked_lamports.saturating_sub(amount);
+        profile.staked_lamports = staked;
+        
+        emit!(StakeChanged {
+            agent: profile.key(),
+            staked_lamports: staked,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Slash the agent's stake over a reveal whose content does not hash
+    /// to `commitment_hash`.
+    ///
+    /// The program can't fetch the reasoning URI, so the content is tied
+    /// to the reveal by a failing `attest_commitment` from a member of
+    /// the storage verifier committee: `trace` must hash to that
+    /// attestation's `content_hash` (as a Merkle root for chunk-manifest
+    /// commitments). Otherwise anyone could slash with made-up content.
+    /// `slash_bps` of the stake is taken, the challenger's share going
+    /// to the signer and the rest to the treasury. Once per commitment.
+    /// Passing a `VerificationBounty` on the commitment also collects it.
+    pub fn slash_mismatch(ctx: Context<SlashMismatch>, trace: Vec<u8>) -> Result<()> {
+        let commitment = &ctx.accounts.commitment;
+        let digest = trace_digest(
+            &trace,
+            commitment.chunk_manifest,
+            commitment.hash_algorithm,
+            commitment.salt,
+        );
+        ctx.accounts.slash(digest)
+    }
+
+    /// `slash_mismatch` for a trace streamed through a `ReasoningBuffer`
+    /// (see `begin_verification`). Closes the buffer.
+    pub fn slash_mismatch_streamed(ctx: Context<SlashMismatchStreamed>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let digest = accounts.buffer.hasher.finalize();
+        accounts.slash.slash(Some(digest))?;
+        accounts.buffer.close(accounts.slash.challenger.to_account_info())
+    }
+
+    /// Set the share of an agent's stake slashed per proven mismatch,
+    /// the challenger's share of it, and where the rest goes. Admin
+    /// only. A `slash_bps` of 0 turns slashing off.
+    pub fn update_slashing(
+        ctx: Context<UpdateConfig>,
+        slash_bps: u16,
+        challenger_bps: u16,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        require!(
+            slash_bps <= 10000 && challenger_bps <= 10000,
+            AxiomError::InvalidSlashingTerms
+        );
+        let config = &mut ctx.accounts.config;
+        config.slash_bps = slash_bps;
+        config.slash_challenger_bps = challenger_bps;
+        config.treasury = treasury;
+        Ok(())
+    }
+
+    /// Dispute a revealed commitment by posting `challenge_bond` lamports.
+    ///
+    /// Opens a `Challenge` for governance to rule on before its deadline.
+    /// `evidence_hash` commits to the challenger's off-chain evidence.
+    /// One open challenge per challenger per commitment.
+    pub fn open_challenge(
+        ctx: Context<OpenChallenge>,
+        reason: ChallengeReason,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let commitment = &mut ctx.accounts.commitment;
+        require!(
+            commitment.status != CommitmentStatus::Committed,
+            AxiomError::NotRevealed
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let window_ends = commitment.revealed_at
+            .checked_add(config.challenge_window)
+            .ok_or(AxiomError::Overflow)?;
+        require!(now < window_ends, AxiomError::ChallengeWindowClosed);
+        let open = commitment.open_challenges.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        commitment.open_challenges = open;
+        
+        let challenge = &mut ctx.accounts.challenge;
+        challenge.commitment = commitment.key();
+        challenge.agent = commitment.agent;
+        challenge.challenger = ctx.accounts.challenger.key();
+        challenge.reason = reason;
+        challenge.evidence_hash = evidence_hash;
+        challenge.bond = config.challenge_bond;
+        challenge.opened_at = now;
+        challenge.deadline = now.checked_add(config.challenge_period)
+            .ok_or(AxiomError::Overflow)?;
+        challenge.bump = ctx.bumps.challenge;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.challenger.to_account_info(),
+                    to: challenge.to_account_info(),
+                },
+            ),
+            challenge.bond,
+        )?;
+        
+        let profile = &mut ctx.accounts.agent;
+        let received = profile.challenges_received.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        profile.challenges_received = received;
+        let agent_open = profile.open_challenges.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        profile.open_challenges = agent_open;
+        
+        emit!(ChallengeOpened {
+            challenge: challenge.key(),
+            commitment: challenge.commitment,
+            agent: challenge.agent,
+            challenger: challenge.challenger,
+            reason,
+            bond: challenge.bond,
+            deadline: challenge.deadline,
+        });
+        
+        Ok(())
+    }
+
+    /// Rule on an open challenge, before its deadline.
+    ///
+    /// At least `council_threshold` distinct members of the arbitration
+    /// council must sign; pass them as remaining accounts. An upheld
+    /// challenge costs the agent `challenge_penalty_bps` of
+    /// accountability score, refunds the bond, and, if the agent has a
+    /// stake vault and the commitment wasn't slashed yet, slashes the
+    /// stake as `slash_mismatch` does. A rejected challenge forfeits the
+    /// bond to the treasury. Either way the challenge is closed and its
+    /// rent returned to the challenger.
+    pub fn resolve_challenge(ctx: Context<ResolveChallenge>, upheld: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let accounts = ctx.accounts;
+        let config = &accounts.config;
+        require!(
+            config.council_approves(ctx.remaining_accounts),
+            AxiomError::CouncilQuorumNotMet
+        );
+        let challenge = &accounts.challenge;
+        require!(now < challenge.deadline, AxiomError::ChallengeDeadlinePassed);
+        let open = accounts.commitment.open_challenges.saturating_sub(1);
+        accounts.commitment.open_challenges = open;
+        
+        let profile = &mut accounts.agent;
+        profile.open_challenges = profile.open_challenges.saturating_sub(1);
+        if upheld {
+            let upheld_count = profile.challenges_upheld.checked_add(1)
+                .ok_or(AxiomError::Overflow)?;
+            profile.challenges_upheld = upheld_count;
+            profile.adjust_score(config, -i32::from(config.challenge_penalty_bps))?;
+            record_metrics(
+                profile,
+                accounts.metrics.as_mut(),
+                now,
+                AgentMetrics::record_upheld_challenge,
+            )?;
+            record_summary(profile, accounts.summary.as_mut(), now, |_| {})?;
+            
+            if let Some(vault) = accounts.stake_vault.as_mut() {
+                if config.slash_bps > 0 && !accounts.commitment.slashed {
+                    slash_stake(
+                        config,
+                        &mut accounts.commitment,
+                        profile,
+                        vault,
+                        &accounts.challenger,
+                        &accounts.treasury,
+                    )?;
+                }
+            }
+        } else {
+            challenge.sub_lamports(challenge.bond)?;
+            accounts.treasury.add_lamports(challenge.bond)?;
+        }
+        
+        emit!(ChallengeResolved {
+            challenge: challenge.key(),
+            commitment: challenge.commitment,
+            agent: challenge.agent,
+            upheld,
+            accountability_score: profile.accountability_score,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Close a challenge the council didn't rule on in time, refunding
+    /// the bond. Permissionless once the deadline has passed.
+    pub fn lapse_challenge(ctx: Context<LapseChallenge>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let challenge = &ctx.accounts.challenge;
+        require!(now >= challenge.deadline, AxiomError::ChallengePeriodActive);
+        let commitment = &mut ctx.accounts.commitment;
+        let open = commitment.open_challenges.saturating_sub(1);
+        commitment.open_challenges = open;
+        let profile = &mut ctx.accounts.agent;
+        profile.open_challenges = profile.open_challenges.saturating_sub(1);
+        
+        emit!(ChallengeLapsed {
+            challenge: challenge.key(),
+            commitment: challenge.commitment,
+            agent: challenge.agent,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Update the score an agent loses per expired commitment. Admin only.
+    pub fn update_expiry_penalty(
+        ctx: Context<UpdateConfig>,
+        expiry_penalty_bps: u16,
+    ) -> Result<()> {
+        require!(expiry_penalty_bps <= 10000, AxiomError::InvalidScore);
+        ctx.accounts.config.expiry_penalty_bps = expiry_penalty_bps;
+        Ok(())
+    }
+
+    /// Update how long recorded outcomes stay open to disputes and the
+    /// score an undisputed one moves by. Admin only; applies to outcomes
+    /// recorded afterwards.
+    pub fn update_outcome_terms(
+        ctx: Context<UpdateConfig>,
+        outcome_dispute_window: i64,
+        outcome_score_bps: u16,
+    ) -> Result<()> {
+        require!(outcome_dispute_window > 0, AxiomError::InvalidDuration);
+        require!(outcome_score_bps <= 10000, AxiomError::InvalidScore);
+        let config = &mut ctx.accounts.config;
+        config.outcome_dispute_window = outcome_dispute_window;
+        config.outcome_score_bps = outcome_score_bps;
+        Ok(())
+    }
+
+    /// Set the keys allowed to dispute recorded outcomes. Admin only;
+    /// an empty list leaves every outcome undisputable.
+    pub fn update_outcome_verifiers(
+        ctx: Context<UpdateConfig>,
+        outcome_verifiers: Vec<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            outcome_verifiers.len() <= MAX_OUTCOME_VERIFIERS,
+            AxiomError::TooManyOutcomeVerifiers
+        );
+        ctx.accounts.config.outcome_verifiers = outcome_verifiers;
+        Ok(())
+    }
+
+    /// Update how the score weighs the on-time reveal rate against the
+    /// undisputed reveal rate. Admin only; the weights must sum to
+    /// 10000. Existing scores pick the new weights up on their next
+    /// recomputation.
+    pub fn update_score_weights(
+        ctx: Context<UpdateConfig>,
+        score_reveal_weight_bps: u16,
+        score_dispute_weight_bps: u16,
+    ) -> Result<()> {
+        require!(
+            u32::from(score_reveal_weight_bps) + u32::from(score_dispute_weight_bps) == 10000,
+            AxiomError::InvalidScoreWeights
+        );
+        let config = &mut ctx.accounts.config;
+        config.score_reveal_weight_bps = score_reveal_weight_bps;
+        config.score_dispute_weight_bps = score_dispute_weight_bps;
+        Ok(())
+    }
+
+    /// Record the agent's score, commitment count and reveal latency as
+    /// they stand in the current epoch, in an `EpochSnapshot` that never
+    /// changes afterwards, for leaderboards and reward programs.
+    /// Permissionless crank, once per agent and epoch; the signer pays.
+    pub fn snapshot_epoch(ctx: Context<SnapshotEpoch>) -> Result<()> {
+        let clock = Clock::get()?;
+        let profile = &ctx.accounts.agent_profile;
+        let snapshot = &mut ctx.accounts.snapshot;
+        snapshot.agent = profile.key();
+        snapshot.epoch = clock.epoch;
+        snapshot.accountability_score = profile.accountability_score;
+        snapshot.total_commitments = profile.total_commitments;
+        snapshot.average_reveal_latency = profile.average_reveal_latency();
+        snapshot.taken_at = clock.unix_timestamp;
+        snapshot.bump = ctx.bumps.snapshot;
+        
+        emit!(EpochSnapshotTaken {
+            agent: snapshot.agent,
+            epoch: snapshot.epoch,
+            accountability_score: snapshot.accountability_score,
+            total_commitments: snapshot.total_commitments,
+            average_reveal_latency: snapshot.average_reveal_latency,
+            timestamp: snapshot.taken_at,
+        });
+        
+        Ok(())
+    }
+
+    /// Recompute an agent's score from its record under the current
+    /// config. Permissionless; reveals, expiries and rulings already
+    /// recompute, so this only matters after the weights or bounds
+    /// change.
+    pub fn recompute_score(ctx: Context<RecomputeScore>) -> Result<()> {
+        let profile = &mut ctx.accounts.agent_profile;
+        let previous_score = profile.accountability_score;
+        profile.recompute_score(&ctx.accounts.config);
+        
+        emit!(ScoreRecomputed {
+            agent: profile.key(),
+            previous_score,
+            accountability_score: profile.accountability_score,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Emit a checkpoint of the agent's record into its event sequence.
+    ///
+    /// Permissionless. Every lifecycle event of an agent's commitments
+    /// carries the next number in the agent's sequence, so consumers can
+    /// tell when they missed one. A consumer that can't recover what it
+    /// missed (the RPC node no longer has the transactions) calls this and
+    /// resumes from the `AgentResequenced` event, whose totals stand in
+    /// for everything before it.
+    pub fn resequence(ctx: Context<Resequence>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let profile = &mut ctx.accounts.agent_profile;
+        let sequence = profile.next_event_sequence()?;
+        
+        emit!(AgentResequenced {
+            agent: profile.key(),
+            sequence,
+            total_commitments: profile.total_commitments,
+            total_verified: profile.total_verified,
+            accountability_score: profile.accountability_score,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Replace the arbitration council that rules on challenges. Admin
+    /// only. `council_threshold` members must sign each ruling.
+    pub fn update_council(
+        ctx: Context<UpdateConfig>,
+        council: Vec<Pubkey>,
+        council_threshold: u8,
+    ) -> Result<()> {
+        require!(
+            council.len() <= MAX_COUNCIL_SIZE
+                && council_threshold > 0
+                && council_threshold as usize <= council.len(),
+            AxiomError::InvalidCouncil
+        );
+        require!(
+            council.iter().enumerate().all(|(i, member)| !council[..i].contains(member)),
+            AxiomError::InvalidCouncil
+        );
+        let config = &mut ctx.accounts.config;
+        config.council = council;
+        config.council_threshold = council_threshold;
+        Ok(())
+    }
+
+    /// Update the challenge bond, how long the council has to rule on a
+    /// challenge, and the score penalty for an upheld one. Admin only.
+    pub fn update_challenge_terms(
+        ctx: Context<UpdateConfig>,
+        challenge_bond: u64,
+        challenge_period: i64,
+        challenge_penalty_bps: u16,
+    ) -> Result<()> {
+        require!(challenge_period > 0, AxiomError::InvalidDuration);
+        require!(challenge_penalty_bps <= 10000, AxiomError::InvalidScore);
+        let config = &mut ctx.accounts.config;
+        config.challenge_bond = challenge_bond;
+        config.challenge_period = challenge_period;
+        config.challenge_penalty_bps = challenge_penalty_bps;
+        Ok(())
+    }
+
+    /// Update how long a reveal stays open to challenges. Admin only.
+    /// Revealed commitments can be closed once their window has passed.
+    pub fn update_challenge_window(
+        ctx: Context<UpdateConfig>,
+        challenge_window: i64,
+    ) -> Result<()> {
+        require!(challenge_window > 0, AxiomError::InvalidDuration);
+        ctx.accounts.config.challenge_window = challenge_window;
+        Ok(())
+    }
+
+    /// Update the bond reviewers post to claim a review request and how
+    /// long they then have to submit it. Admin only; applies to requests
+    /// claimed afterwards.
+    pub fn update_review_terms(
+        ctx: Context<UpdateConfig>,
+        reviewer_bond: u64,
+        review_period: i64,
+    ) -> Result<()> {
+        require!(review_period > 0, AxiomError::InvalidDuration);
+        let config = &mut ctx.accounts.config;
+        config.reviewer_bond = reviewer_bond;
+        config.review_period = review_period;
+        Ok(())
+    }
+
+    /// Cap the length of reasoning URIs posted from now on, e.g. to
+    /// keep them to one storage scheme's format. At most `MAX_URI_LEN`,
+    /// the room a commitment has for one. Admin only.
+    pub fn update_max_uri_len(ctx: Context<UpdateConfig>, max_uri_len: u16) -> Result<()> {
+        require!(
+            max_uri_len > 0 && max_uri_len <= MAX_URI_LEN,
+            AxiomError::InvalidUriLimit
+        );
+        ctx.accounts.config.max_uri_len = max_uri_len;
+        Ok(())
+    }
+
+    /// Name the key that may pause the protocol alongside the admin, e.g.
+    /// an incident responder's hot wallet. Admin only.
+    pub fn set_guardian(ctx: Context<UpdateConfig>, guardian: Pubkey) -> Result<()> {
+        ctx.accounts.config.guardian = guardian;
+        Ok(())
+    }
+
+    /// Halt the protocol while an incident is investigated, or resume
+    /// it. The guardian or the admin may call it.
+    ///
+    /// While paused, every instruction agents, integrators, verifiers
+    /// and cranks send fails, expiring commitments included, so no agent
+    /// is penalized for a deadline it couldn't meet. Withdrawals of funds
+    /// never pause: `request_unstake` and `withdraw_stake`,
+    /// `reclaim_bounty`, `close_escrow`, `withdraw_treasury` and
+    /// `claim_integrator_fees` keep working, as do governance's own
+    /// instructions and account migrations.
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.paused = paused;
+        
+        emit!(ProtocolPauseChanged {
+            paused,
+            by: ctx.accounts.signer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Create the `FeeTreasury` that commit fees are paid into. Admin
+    /// only; needed before `update_commit_fee` can turn fees on.
+    pub fn open_fee_treasury(ctx: Context<OpenFeeTreasury>) -> Result<()> {
+        let treasury = &mut ctx.accounts.fee_treasury;
+        treasury.collected = 0;
+        treasury.withdrawn = 0;
+        treasury.rebated = 0;
+        treasury.integrator_credited = 0;
+        treasury.bump = ctx.bumps.fee_treasury;
+        Ok(())
+    }
+
+    /// Set the lamports each new commitment pays into the fee treasury
+    /// (0 = free); a batch pays it once per hash. Admin only.
+    pub fn update_commit_fee(ctx: Context<UpdateCommitFee>, commit_fee: u64) -> Result<()> {
+        ctx.accounts.config.commit_fee = commit_fee;
+        Ok(())
+    }
+
+    /// Set how far `request_deadline_extension` may push a reveal
+    /// deadline back (0 = no extensions) and the lamports it pays into
+    /// the fee treasury. Admin only.
+    pub fn update_deadline_extensions(
+        ctx: Context<UpdateCommitFee>,
+        max_extension: i64,
+        extension_fee: u64,
+    ) -> Result<()> {
+        require!(max_extension >= 0, AxiomError::InvalidDuration);
+        let config = &mut ctx.accounts.config;
+        config.max_deadline_extension = max_extension;
+        config.extension_fee = extension_fee;
+        Ok(())
+    }
+
+    /// Set the share of its commit fee (basis points) a commitment gets
+    /// back when its reveal was made by its deadline and verified
+    /// onchain (0 = no rebate). Admin only. Together with the integrator
+    /// fee share it can't exceed the whole fee.
+    pub fn update_reveal_rebate(ctx: Context<UpdateConfig>, reveal_rebate_bps: u16) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(
+            u32::from(reveal_rebate_bps) + u32::from(config.integrator_fee_share_bps) <= 10000,
+            AxiomError::InvalidRebate
+        );
+        config.reveal_rebate_bps = reveal_rebate_bps;
+        Ok(())
+    }
+
+    /// Set the share of its member agents' commit fees (basis points) an
+    /// integrator can claim (0 = none). Applies to commits from then on.
+    /// Admin only. Together with the reveal rebate it can't exceed the
+    /// whole fee.
+    pub fn update_integrator_fee_share(
+        ctx: Context<UpdateConfig>,
+        integrator_fee_share_bps: u16,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(
+            u32::from(integrator_fee_share_bps) + u32::from(config.reveal_rebate_bps) <= 10000,
+            AxiomError::InvalidFeeShare
+        );
+        config.integrator_fee_share_bps = integrator_fee_share_bps;
+        Ok(())
+    }
+
+    /// Pay `amount` of the collected fees out to `recipient`. Admin
+    /// only, so a multisig admin keeps the treasury under its threshold.
+    /// The treasury's rent stays put.
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        let treasury = &mut ctx.accounts.fee_treasury;
+        let available = treasury.available()?;
+        require!(amount > 0 && amount <= available, AxiomError::InsufficientTreasury);
+        treasury.withdrawn = treasury.withdrawn.checked_add(amount)
+            .ok_or(AxiomError::Overflow)?;
+        treasury.sub_lamports(amount)?;
+        ctx.accounts.recipient.add_lamports(amount)?;
+        
+        emit!(TreasuryWithdrawn {
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+            remaining: available.checked_sub(amount).ok_or(AxiomError::Overflow)?,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Pay an integrator its whole claimable fee share out of the fee
+    /// treasury. The integrator's authority only.
+    pub fn claim_integrator_fees(ctx: Context<ClaimIntegratorFees>) -> Result<()> {
+        let integrator = &mut ctx.accounts.integrator;
+        let amount = integrator.claimable_fees;
+        require!(amount > 0, AxiomError::NothingToClaim);
+        integrator.claimable_fees = 0;
+        ctx.accounts.fee_treasury.sub_lamports(amount)?;
+        ctx.accounts.authority.add_lamports(amount)?;
+        
+        emit!(IntegratorFeesClaimed {
+            integrator: integrator.key(),
+            authority: integrator.authority,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Enable or disable optional subsystems: `features` is the full set
+    /// of `FEATURE_*` bits to leave on. Disabling one stops new uses
+    /// (challenges, escrows, batches); those already open run their
+    /// course. Admin only.
+    pub fn set_features(ctx: Context<UpdateConfig>, features: u64) -> Result<()> {
+        require!(features & !ALL_FEATURES == 0, AxiomError::UnknownFeature);
+        ctx.accounts.config.features = features;
+        Ok(())
+    }
+
+    /// Register an attestor organization. Admin only.
+    pub fn register_attestor(
+        ctx: Context<RegisterAttestor>,
+        attestor: Pubkey,
+        name: String,
+    ) -> Result<()> {
+        require!(name.len() <= 64, AxiomError::NameTooLong);
+        require!(!name.is_empty(), AxiomError::NameEmpty);
+        
+        let now = Clock::get()?.unix_timestamp;
+        let record = &mut ctx.accounts.attestor_record;
+        record.attestor = attestor;
+        record.name = name.clone();
+        record.active = true;
+        record.registered_at = now;
+        record.revoked_at = None;
+        record.attestations_issued = 0;
+        record.bump = ctx.bumps.attestor_record;
+        
+        emit!(AttestorRegistered {
+            attestor,
+            name,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Revoke an attestor organization. Admin only.
+    ///
+    /// The record stays on chain so past attestations remain traceable;
+    /// consumers should treat attestations from inactive attestors as
+    /// void.
+    pub fn revoke_attestor(ctx: Context<RevokeAttestor>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let record = &mut ctx.accounts.attestor_record;
+        require!(record.active, AxiomError::AttestorInactive);
+        record.active = false;
+        record.revoked_at = Some(now);
+        
+        emit!(AttestorRevoked {
+            attestor: record.attestor,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Register another accountability system whose signed reputation
+    /// snapshots agents may import, under the key it signs them with.
+    /// Admin only.
+    pub fn register_reputation_source(
+        ctx: Context<RegisterReputationSource>,
+        source: Pubkey,
+        name: String,
+    ) -> Result<()> {
+        require!(name.len() <= 64, AxiomError::NameTooLong);
+        require!(!name.is_empty(), AxiomError::NameEmpty);
+        
+        let now = Clock::get()?.unix_timestamp;
+        let record = &mut ctx.accounts.source_record;
+        record.source = source;
+        record.name = name.clone();
+        record.active = true;
+        record.registered_at = now;
+        record.bump = ctx.bumps.source_record;
+        
+        emit!(ReputationSourceRegistered {
+            source,
+            name,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Stop accepting a source's snapshots. Imports already made stay,
+    /// naming the source, for consumers to weigh. Admin only.
+    pub fn revoke_reputation_source(ctx: Context<RevokeReputationSource>) -> Result<()> {
+        let record = &mut ctx.accounts.source_record;
+        require!(record.active, AxiomError::ReputationSourceInactive);
+        record.active = false;
+        
+        emit!(ReputationSourceRevoked {
+            source: record.source,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Import a reputation score the agent earned in another
+    /// accountability system, so it doesn't start from nothing here.
+    ///
+    /// Must directly follow an Ed25519 program instruction in which an
+    /// active registered source signs
+    /// [`reputation_snapshot_message`]`(agent, score, snapshot_at)`.
+    /// The score lands in the profile's separate `imported_score`; the
+    /// native `accountability_score` is never touched. A newer snapshot
+    /// (or one from another source) replaces the last import.
+    /// Authority only.
+    pub fn import_reputation(
+        ctx: Context<ImportReputation>,
+        score: u16,
+        snapshot_at: i64,
+    ) -> Result<()> {
+        require!(score <= 10000, AxiomError::InvalidScore);
+        let now = Clock::get()?.unix_timestamp;
+        require!(snapshot_at <= now, AxiomError::InvalidSnapshot);
+        
+        let (signer, message) = preceding_signature(&ctx.accounts.instructions)?;
+        let source = &ctx.accounts.source_record;
+        require!(source.active, AxiomError::ReputationSourceInactive);
+        let profile = &mut ctx.accounts.agent_profile;
+        require!(
+            signer == source.source
+                && message == reputation_snapshot_message(&profile.key(), score, snapshot_at),
+            AxiomError::SnapshotSignatureMismatch
+        );
+        
+        let import = &mut ctx.accounts.import;
+        require!(
+            import.source != source.source || snapshot_at > import.snapshot_at,
+            AxiomError::InvalidSnapshot
+        );
+        import.agent = profile.key();
+        import.source = source.source;
+        import.score = score;
+        import.snapshot_at = snapshot_at;
+        import.imported_at = now;
+        import.bump = ctx.bumps.import;
+        profile.imported_score = score;
+        
+        emit!(ReputationImported {
+            agent: import.agent,
+            source: import.source,
+            score,
+            snapshot_at,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Mark an agent's operator as verified after an off-chain KYC or
+    /// audit. Registered, active attestors only.
+    ///
+    /// `reference` is the hash of (or pointer to) the attestation
+    /// report, so consumers can look up what was checked.
+    pub fn attest_operator(
+        ctx: Context<AttestOperator>,
+        reference: [u8; 32],
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let record = &mut ctx.accounts.attestor_record;
+        require!(record.active, AxiomError::AttestorInactive);
+        record.attestations_issued = record.attestations_issued.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        
+        let profile = &mut ctx.accounts.agent_profile;
+        profile.verified_operator = true;
+        profile.operator_attestation = Some(OperatorAttestation {
+            attestor: ctx.accounts.attestor.key(),
+            reference,
+            attested_at: now,
+        });
+        
+        emit!(OperatorAttested {
+            agent: profile.key(),
+            attestor: ctx.accounts.attestor.key(),
+            reference,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Record which agent framework, and which version of it, an agent
+    /// runs. Registered, active attestors only.
+    ///
+    /// Meant to be called by framework programs via CPI: the admin
+    /// registers a PDA of the framework program as an attestor, and the
+    /// framework signs with it when an agent built on it registers or
+    /// upgrades. A later attestation replaces the earlier one.
+    pub fn attest_framework(
+        ctx: Context<AttestOperator>,
+        version: String,
+    ) -> Result<()> {
+        require!(
+            !version.is_empty() && version.len() <= MAX_FRAMEWORK_VERSION_LEN,
+            AxiomError::InvalidFrameworkVersion
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let record = &mut ctx.accounts.attestor_record;
+        require!(record.active, AxiomError::AttestorInactive);
+        record.attestations_issued = record.attestations_issued.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        
+        let profile = &mut ctx.accounts.agent_profile;
+        profile.framework = Some(FrameworkAttestation {
+            attestor: ctx.accounts.attestor.key(),
+            version: version.clone(),
+            attested_at: now,
+        });
+        
+        emit!(FrameworkAttested {
+            agent: profile.key(),
+            attestor: ctx.accounts.attestor.key(),
+            version,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    /// Withdraw an operator attestation. Only the attestor that issued
+    /// it may withdraw it.
+    pub fn revoke_operator_attestation(ctx: Context<RevokeOperatorAttestation>) -> Result<()> {
+        let profile = &mut ctx.accounts.agent_profile;
+        let issued_by_signer = profile.operator_attestation
+            .is_some_and(|a| a.attestor == ctx.accounts.attestor.key());
+        require!(issued_by_signer, AxiomError::NotAttested);
+        profile.verified_operator = false;
+        profile.operator_attestation = None;
+        
+        emit!(OperatorAttestationRevoked {
+            agent: profile.key(),
+            attestor: ctx.accounts.attestor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Overwrite an agent's accountability score. Admin only.
+    ///
+    /// Meant for correcting scores corrupted by bugs. The admin must
+    /// publish a justification document and pass its hash; the event
+    /// makes every correction auditable.
+    pub fn override_score(
+        ctx: Context<OverrideScore>,
+        new_score: u16,
+        justification_hash: [u8; 32],
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(
+            new_score >= config.score_floor && new_score <= config.score_ceiling,
+            AxiomError::ScoreOutOfBounds
+        );
+        require!(justification_hash != [0u8; 32], AxiomError::JustificationRequired);
+        
+        let profile = &mut ctx.accounts.agent_profile;
+        let previous_score = profile.accountability_score;
+        profile.set_score(config, new_score)?;
+        
+        emit!(ScoreOverridden {
+            agent: profile.key(),
+            admin: ctx.accounts.admin.key(),
+            previous_score,
+            new_score,
+            justification_hash,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    /// Grow a commitment made under an earlier, smaller layout to the
+    /// current size so it can be read again. Permissionless; `payer`
+    /// covers the extra rent.
+    ///
+    /// The original fields keep their offsets and every field added
+    /// since starts zeroed, which decodes as its default. Migrating a
+    /// commitment that is already full size changes nothing.
+    pub fn migrate_commitment(ctx: Context<MigrateCommitment>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let commitment = accounts.commitment.to_account_info();
+        grow_account(
+            &commitment,
+            &accounts.payer,
+            &accounts.system_program,
+            8 + ReasoningCommitment::INIT_SPACE,
+        )?;
+        let data = commitment.try_borrow_data()?;
+        ReasoningCommitment::try_deserialize(&mut &data[..])?;
+        Ok(())
+    }
+
+    /// Grow an agent profile registered under an earlier, smaller
+    /// layout to the current size so it can be read again, as
+    /// `migrate_commitment` does for commitments. Permissionless;
+    /// `payer` covers the extra rent.
+    pub fn migrate_agent_profile(ctx: Context<MigrateAgentProfile>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let profile = accounts.agent_profile.to_account_info();
+        grow_account(
+            &profile,
+            &accounts.payer,
+            &accounts.system_program,
+            8 + AgentProfile::INIT_SPACE,
+        )?;
+        let data = profile.try_borrow_data()?;
+        AgentProfile::try_deserialize(&mut &data[..])?;
+        Ok(())
+    }
+
+    /// [test-instructions] Force an agent's accountability score.
+    ///
+    /// Lets integration environments put an agent into any score band
+    /// without replaying the history that would normally produce it.
+    #[cfg(feature = "test-instructions")]
+    pub fn test_set_score(
+        ctx: Context<TestSetScore>,
+        accountability_score: u16,
+    ) -> Result<()> {
+        require!(accountability_score <= 10000, AxiomError::InvalidScore);
+        ctx.accounts.agent_profile.set_score(&ctx.accounts.config, accountability_score)
+    }
+
+    /// [test-instructions] Move a commitment's timestamps into the past.
+    ///
+    /// Simulates aged commitments so time-dependent behaviour can be
+    /// exercised without waiting in real time.
+    #[cfg(feature = "test-instructions")]
+    pub fn test_backdate_commitment(
+        ctx: Context<TestBackdateCommitment>,
+        seconds: i64,
+    ) -> Result<()> {
+        require!(seconds >= 0, AxiomError::InvalidDuration);
+        let commitment = &mut ctx.accounts.commitment;
+        commitment.timestamp = commitment.timestamp.checked_sub(seconds)
+            .ok_or(AxiomError::Overflow)?;
+        if let Some(deadline) = commitment.reveal_deadline {
+            let deadline = deadline.checked_sub(seconds).ok_or(AxiomError::Overflow)?;
+            commitment.reveal_deadline = Some(deadline);
+        }
+        if commitment.status != CommitmentStatus::Committed {
+            commitment.revealed_at = commitment.revealed_at.checked_sub(seconds)
+                .ok_or(AxiomError::Overflow)?;
+        }
+        Ok(())
+    }
+}
+
+/// Binary Merkle tree over trace chunks.
+///
+/// Leaves and inner nodes are domain-separated SHA-256 hashes
+/// (`0x00 || chunk`, `0x01 || left || right`). A node without a right
+/// sibling is promoted to the next level unchanged, so proofs only carry
+/// siblings that exist.
+pub mod merkle {
+    use solana_sha256_hasher::hashv;
+
+    /// Hash of a leaf (one chunk of data).
+    pub fn leaf(data: &[u8]) -> [u8; 32] {
+        hashv(&[&[0u8], data]).to_bytes()
+    }
+
+    /// Hash of an inner node.
+    pub fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        hashv(&[&[1u8], left.as_ref(), right.as_ref()]).to_bytes()
+    }
+
+    /// Root of the tree over `data` split into `chunk_size`-byte chunks.
+    pub fn root(data: &[u8], chunk_size: usize) -> [u8; 32] {
+        root_from_leaves(data.chunks(chunk_size).map(leaf).collect())
+    }
+
+    /// Root of the tree over already-hashed leaves. This is how a
+    /// redacted reveal is checked: withheld chunks are published as
+    /// their leaf hashes, the rest as data.
+    pub fn root_from_leaves(mut level: Vec<[u8; 32]>) -> [u8; 32] {
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+        level.first().copied().unwrap_or_else(|| leaf(&[]))
+    }
+
+    /// Verify that `leaf` sits at `index` of a `leaf_count`-leaf tree
+    /// with root `root`.
+    pub fn verify(
+        root: &[u8; 32],
+        leaf: &[u8; 32],
+        mut index: u32,
+        mut leaf_count: u32,
+        proof: &[[u8; 32]],
+    ) -> bool {
+        if index >= leaf_count {
+            return false;
+        }
+        let mut hash = *leaf;
+        let mut siblings = proof.iter();
+        while leaf_count > 1 {
+            if index % 2 == 1 {
+                let Some(sibling) = siblings.next() else { return false };
+                hash = node(sibling, &hash);
+            } else if index + 1 < leaf_count {
+                let Some(sibling) = siblings.next() else { return false };
+                hash = node(&hash, sibling);
+            }
+            index /= 2;
+            leaf_count = leaf_count.div_ceil(2);
+        }
+        siblings.next().is_none() && hash == *root
+    }
+}
+
+/// SHA-256 whose state survives between transactions.
+///
+/// The syscall only hashes data it is given in one call, so traces
+/// streamed over several transactions are hashed in software, block by
+/// block, with the unprocessed tail kept alongside the state.
+pub mod sha256_stream {
+    use anchor_lang::prelude::*;
+    use sha2::compress256;
+    use sha2::digest::generic_array::GenericArray;
+
+    const INITIAL_STATE: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+        0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ];
+
+    #[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Sha256Stream {
+        /// Chaining state after every complete block seen so far
+        pub state: [u32; 8],
+        /// Bytes of the current, incomplete block
+        pub pending: [u8; 64],
+        /// Total bytes hashed
+        pub len: u64,
+    }
+
+    impl Default for Sha256Stream {
+        fn default() -> Self {
+            Self {
+                state: INITIAL_STATE,
+                pending: [0; 64],
+                len: 0,
+            }
+        }
+    }
+
+    impl Sha256Stream {
+        pub fn update(&mut self, mut data: &[u8]) {
+            let mut filled = (self.len % 64) as usize;
+            self.len += data.len() as u64;
+            while !data.is_empty() {
+                let take = data.len().min(64 - filled);
+                self.pending[filled..filled + take].copy_from_slice(&data[..take]);
+                data = &data[take..];
+                filled += take;
+                if filled == 64 {
+                    compress256(&mut self.state, &[GenericArray::from(self.pending)]);
+                    filled = 0;
+                }
+            }
+        }
+
+        pub fn finalize(mut self) -> [u8; 32] {
+            let bit_len = self.len.wrapping_mul(8);
+            self.update(&[0x80]);
+            while self.len % 64 != 56 {
+                self.update(&[0]);
+            }
+            self.update(&bit_len.to_be_bytes());
+            let mut digest = [0u8; 32];
+            for (out, word) in digest.chunks_mut(4).zip(self.state) {
+                out.copy_from_slice(&word.to_be_bytes());
+            }
+            digest
+        }
+    }
+}
+
+/// Parsing of Ed25519 program instructions, as found through the
+/// instructions sysvar.
+///
+/// The instruction data is a count byte and a padding byte, then one
+/// 14-byte offsets record per signature (seven little-endian `u16`s:
+/// signature offset and instruction, public key offset and instruction,
+/// message offset, size and instruction), then the data they point at.
+pub mod ed25519 {
+    /// Instruction index meaning "this instruction's own data".
+    pub const INLINE: u16 = u16::MAX;
+    /// Bytes before the first offsets record.
+    const HEADER_LEN: usize = 2;
+    /// Bytes per offsets record.
+    const OFFSETS_LEN: usize = 14;
+    const PUBKEY_LEN: usize = 32;
+    const SIGNATURE_LEN: usize = 64;
+
+    /// Public key and message of an instruction verifying exactly one
+    /// signature with everything inline, or `None` for any other shape.
+    pub fn single_signature(data: &[u8]) -> Option<(&[u8], &[u8])> {
+        if data.len() < HEADER_LEN + OFFSETS_LEN || data[0] != 1 {
+            return None;
+        }
+        let field = |i: usize| {
+            let at = HEADER_LEN + 2 * i;
+            u16::from_le_bytes([data[at], data[at + 1]])
+        };
+        let (signature_offset, signature_ix) = (field(0), field(1));
+        let (pubkey_offset, pubkey_ix) = (field(2), field(3));
+        let (message_offset, message_size, message_ix) = (field(4), field(5), field(6));
+        if [signature_ix, pubkey_ix, message_ix] != [INLINE; 3] {
+            return None;
+        }
+        let slice = |offset: u16, len: usize| data.get(offset as usize..offset as usize + len);
+        slice(signature_offset, SIGNATURE_LEN)?;
+        Some((
+            slice(pubkey_offset, PUBKEY_LEN)?,
+            slice(message_offset, message_size as usize)?,
+        ))
+    }
+}
+
+/// Initial probation threshold: agents below 50.00% may enter probation.
+pub const DEFAULT_PROBATION_THRESHOLD: u16 = 5000;
+/// Initial number of reveals needed to complete probation.
+pub const DEFAULT_PROBATION_REQUIRED_REVEALS: u32 = 20;
+/// Initial score recovered per reveal on probation (1.00%).
+pub const DEFAULT_PROBATION_RECOVERY_BPS: u16 = 100;
+/// Version of the program's interface, reported by `get_features`.
+/// Bumped when instructions or accounts change incompatibly.
+pub const PROTOCOL_VERSION: u16 = 2;
+/// `ProtocolConfig::features` bit for challenges and their rulings.
+pub const FEATURE_DISPUTES: u64 = 1 << 0;
+/// `ProtocolConfig::features` bit for action escrows.
+pub const FEATURE_ESCROW: u64 = 1 << 1;
+/// `ProtocolConfig::features` bit for batched (Merkle-compressed)
+/// commitments.
+pub const FEATURE_COMPRESSION: u64 = 1 << 2;
+/// Every feature bit the program knows.
+pub const ALL_FEATURES: u64 = FEATURE_DISPUTES | FEATURE_ESCROW | FEATURE_COMPRESSION;
+/// Longest reasoning URI a commitment can store.
+pub const MAX_URI_LEN: u16 = 256;
+/// Initial reveal grace period (one hour).
+pub const DEFAULT_REVEAL_GRACE_PERIOD: i64 = 3600;
+/// Initial unstake delay (seven days).
+pub const DEFAULT_UNSTAKE_DELAY: i64 = 7 * SECONDS_PER_DAY;
+/// Initial challenge bond (0.1 SOL).
+pub const DEFAULT_CHALLENGE_BOND: u64 = 100_000_000;
+/// Initial time the council has to rule on a challenge (seven days).
+pub const DEFAULT_CHALLENGE_PERIOD: i64 = 7 * SECONDS_PER_DAY;
+/// Initial time a reveal stays open to challenges (seven days).
+pub const DEFAULT_CHALLENGE_WINDOW: i64 = 7 * SECONDS_PER_DAY;
+/// Default reviewer bond (0.1 SOL).
+pub const DEFAULT_REVIEWER_BOND: u64 = 100_000_000;
+/// Default time a reviewer has to submit a claimed review.
+pub const DEFAULT_REVIEW_PERIOD: i64 = 3 * SECONDS_PER_DAY;
+/// Initial longest reveal deadline extension (two days).
+pub const DEFAULT_MAX_DEADLINE_EXTENSION: i64 = 2 * SECONDS_PER_DAY;
+/// Initial window for disputing a recorded outcome (three days).
+pub const DEFAULT_OUTCOME_DISPUTE_WINDOW: i64 = 3 * SECONDS_PER_DAY;
+/// Most tranches an `ActionEscrow` can release in: one per chunk, each
+/// tracked by a bit of `released_tranches`.
+pub const MAX_ESCROW_TRANCHES: u32 = 64;
+/// Initial score penalty for an upheld challenge (5.00%).
+pub const DEFAULT_CHALLENGE_PENALTY_BPS: u16 = 500;
+/// Initial score penalty for an expired commitment (2.00%).
+pub const DEFAULT_EXPIRY_PENALTY_BPS: u16 = 200;
+/// Initial score an undisputed outcome moves by (0.50%).
+pub const DEFAULT_OUTCOME_SCORE_BPS: u16 = 50;
+/// Initial weight of the on-time reveal rate in the score (80.00%).
+pub const DEFAULT_SCORE_REVEAL_WEIGHT_BPS: u16 = 8000;
+/// Initial weight of the undisputed reveal rate in the score (20.00%).
+pub const DEFAULT_SCORE_DISPUTE_WEIGHT_BPS: u16 = 2000;
+/// Number of confidence buckets kept on each profile.
+pub const CONFIDENCE_BUCKETS: usize = 10;
+/// Length of one activity bucket.
+pub const SECONDS_PER_DAY: i64 = 86_400;
+/// Most required metadata fields a template can list.
+pub const MAX_TEMPLATE_FIELDS: usize = 8;
+/// Largest storage verifier committee the config can hold.
+pub const MAX_STORAGE_VERIFIERS: usize = 8;
+/// Most keys the config can allow to dispute outcomes.
+pub const MAX_OUTCOME_VERIFIERS: usize = 8;
+/// Most profiles `register_agents_batch` creates at once.
+pub const MAX_AGENT_BATCH: usize = 8;
+/// Bytes of a `BatchCommitment`'s revealed bitmap.
+pub const BATCH_BITMAP_LEN: usize = 128;
+/// Most reasoning hashes one `BatchCommitment` covers: a bit each.
+pub const MAX_BATCH_SIZE: u32 = BATCH_BITMAP_LEN as u32 * 8;
+/// Largest arbitration council the config can hold.
+pub const MAX_COUNCIL_SIZE: usize = 9;
+/// Longest framework version string an attestation can carry.
+pub const MAX_FRAMEWORK_VERSION_LEN: usize = 16;
+/// Longest metadata URI an agent can publish.
+pub const MAX_METADATA_URI_LEN: usize = 256;
+/// Most rejected alternatives one commitment can record.
+pub const MAX_COUNTERFACTUALS: usize = 4;
+/// Most action types a delegate key can be limited to.
+pub const MAX_DELEGATE_ACTION_TYPES: usize = 4;
+/// Most action types a compliance policy can allow.
+pub const MAX_POLICY_ACTION_TYPES: usize = 8;
+/// Most funders one commitment's costs can be split with.
+pub const MAX_COST_FUNDERS: usize = 4;
+/// Most tags an embargoed commitment can carry.
+pub const MAX_COMMITMENT_TAGS: usize = 8;
+/// Most commitments one pre-signed batch can hold (one bit each of
+/// `PresignedBatch::redeemed`).
+pub const MAX_PRESIGNED_COMMITMENTS: usize = 8;
+/// Prefix of [`presign_message`].
+pub const PRESIGN_DOMAIN: &[u8] = b"solprism:presign";
+/// Weight (basis points) each new sample gets in an `AgentMetrics`
+/// average; the rest stays with the history.
+pub const METRICS_SMOOTHING_BPS: u64 = 1000;
+/// Lowest score of an agent in `AgentTier::Medium`.
+pub const TIER_MEDIUM_SCORE: u16 = 5000;
+/// Lowest score of an agent in `AgentTier::High`.
+pub const TIER_HIGH_SCORE: u16 = 8000;
+/// Bytes still held back on each `AgentProfile` for future fields, out
+/// of the 128 it was created with.
+pub const AGENT_PROFILE_RESERVED: usize = 2;
+/// Bytes still held back on each `ReasoningCommitment` for future
+/// fields, out of the 64 it was created with.
+pub const COMMITMENT_RESERVED: usize = 2;
+
+// ─── Account Structs ───────────────────────────────────────────────────────
+
+#[account]
+#[derive(InitSpace)]
+pub struct AgentProfile {
+    /// The agent's authority (wallet that controls this profile)
+    pub authority: Pubkey,
+    /// Display name (max 64 chars)
+    #[max_len(64)]
+    pub name: String,
+    /// Total reasoning commitments published
+    pub total_commitments: u64,
+    /// Total commitments that have been revealed
+    pub total_verified: u64,
+    /// Accountability score in basis points (0-10000 = 0%-100%)
+    pub accountability_score: u16,
+    /// When the agent registered
+    pub created_at: i64,
+    /// PDA bump
+    pub bump: u8,
+    /// Active probation, if the agent is working its way back up
+    pub probation: Option<Probation>,
+    /// Whether an attestor has vetted the agent's operator
+    pub verified_operator: bool,
+    /// The attestation behind `verified_operator`
+    pub operator_attestation: Option<OperatorAttestation>,
+    /// Commitments per confidence decile (0-9, 10-19, ..., 90-100)
+    pub confidence_histogram: [u32; CONFIDENCE_BUCKETS],
+    /// Key currently allowed to sign reasoning documents for the agent
+    pub signing_key: Option<Pubkey>,
+    /// Signing keys registered so far (the next `SigningKeyRecord` index)
+    pub signing_key_count: u32,
+    /// Sum of commit-to-reveal latencies, in seconds
+    pub total_reveal_latency: u64,
+    /// Reveals counted in `total_reveal_latency`
+    pub timed_reveals: u64,
+    /// Longest commit-to-reveal latency, in seconds
+    pub max_reveal_latency: u64,
+    /// Integrator whose namespace the agent belongs to
+    pub integrator: Option<Pubkey>,
+    /// Fleet operator that registered the agent, if batch-registered
+    pub operator: Option<Pubkey>,
+    /// Distinguishes this profile among its authority's profiles (`None`
+    /// for the default one, see `register_agent_with_id`)
+    pub agent_id: Option<u64>,
+    /// The agent framework an attestor vouched the agent runs
+    pub framework: Option<FrameworkAttestation>,
+    /// SOL the agent has posted as collateral, in lamports
+    pub staked_lamports: u64,
+    /// Dry-run commitments, kept out of `total_commitments` and every
+    /// other accountability stat
+    pub simulated_commitments: u64,
+    /// Challenges opened against the agent's commitments
+    pub challenges_received: u32,
+    /// Challenges governance upheld against the agent
+    pub challenges_upheld: u32,
+    /// Commitments expired for missing their reveal deadline
+    pub expired_commitments: u32,
+    /// Basis points added to (or taken from) the score the record
+    /// implies: penalties, probation recovery and overrides
+    pub score_adjustment: i32,
+    /// Commitments the agent voided before revealing them
+    pub cancelled_commitments: u32,
+    /// Sequence number of the agent's latest lifecycle event; 0 before
+    /// the first
+    pub event_sequence: u64,
+    /// When the authority retired the agent, if it is inactive
+    pub deactivated_at: Option<i64>,
+    /// Whether the agent's integrator froze it
+    pub frozen: bool,
+    /// Whether repeating an identical `commit_reasoning` succeeds
+    pub idempotent_commits: bool,
+    /// Score imported from another accountability system (basis points;
+    /// 0 if none), kept apart from `accountability_score`
+    pub imported_score: u16,
+    /// Whether the agent keeps an `AgentMetrics` account
+    pub has_metrics: bool,
+    /// Whether the agent keeps an `AgentSummary` account
+    pub has_summary: bool,
+    /// Wallets following the agent (see `follow_agent`)
+    pub follower_count: u32,
+    /// Challenges against the agent still awaiting a ruling or lapse;
+    /// the stake can't be unstaked or withdrawn while any are open
+    pub open_challenges: u16,
+    /// Zeroed space for future fields
+    pub reserved: [u8; AGENT_PROFILE_RESERVED],
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReasoningCommitment {
+    /// The agent profile this commitment belongs to
+    pub agent: Pubkey,
+    /// The authority that created this commitment, or the agent's
+    /// current one once synced after a transfer
+    pub authority: Pubkey,
+    /// SHA-256 hash of the full reasoning trace
+    pub commitment_hash: [u8; 32],
+    /// Type of action (e.g., "trade", "audit", "rebalance")
+    #[max_len(32)]
+    pub action_type: String,
+    /// Confidence score (0-100)
+    pub confidence: u8,
+    /// Unix timestamp when committed
+    pub timestamp: i64,
+    /// Where the commitment is in the commit → reveal lifecycle (in
+    /// place of the original `revealed` flag, which it still decodes)
+    pub status: CommitmentStatus,
+    /// URI to the full reasoning (IPFS, Arweave, etc.)
+    #[max_len(256)]
+    pub reasoning_uri: String,
+    /// Nonce for unique PDA derivation (allows multiple commitments)
+    pub nonce: u64,
+    /// PDA bump
+    pub bump: u8,
+    /// Protocol-defined category, for aggregation across action types
+    pub category: CommitmentCategory,
+    /// Byte length of the revealed trace (0 until revealed)
+    pub trace_len: u64,
+    /// Number of chunks the revealed trace is stored in (0 until revealed)
+    pub chunk_count: u32,
+    /// Set when `commitment_hash` is a Merkle root over fixed-size chunks
+    pub chunk_manifest: Option<ChunkManifest>,
+    /// When the reveal was started (0 until revealed)
+    pub revealed_at: i64,
+    /// Reactions in favour
+    pub upvotes: u32,
+    /// Reactions against
+    pub downvotes: u32,
+    /// Key proven (by `verify_trace_signature`) to have signed the trace
+    pub trace_signer: Option<Pubkey>,
+    /// Storage verifiers that have attested the revealed trace
+    pub availability_attestations: u8,
+    /// When the verifier quorum found the trace retrievable and matching
+    pub available_at: Option<i64>,
+    /// The template the commitment was made from, if any
+    pub template: Option<Pubkey>,
+    /// Party the action affects, who may acknowledge the commitment
+    pub counterparty: Option<Pubkey>,
+    /// When the counterparty acknowledged it
+    pub acknowledged_at: Option<i64>,
+    /// For private commitments, the hash hiding action type, category and
+    /// confidence (see [`details_hash`])
+    pub details_hash: Option<[u8; 32]>,
+    /// When a private commitment's details were published
+    pub details_published_at: Option<i64>,
+    /// Stakes the agent declared at commit time
+    pub risk: Option<RiskDisclosure>,
+    /// Slot the commitment was made in
+    pub slot: u64,
+    /// Transaction that carried out the action, if claimed
+    pub action_signature: Option<[u8; 64]>,
+    /// Slot of `action_signature`
+    pub action_slot: Option<u64>,
+    /// Salt disclosed at reveal, when `commitment_hash` is
+    /// `H(salt || trace)` so a guessable trace can't be brute-forced
+    /// from the hash before then
+    pub salt: Option<[u8; 32]>,
+    /// Earlier commitment of the same agent this one follows from, as
+    /// a step of a multi-step plan
+    pub parent: Option<Pubkey>,
+    /// How `confidence` was produced
+    pub confidence_source: ConfidenceSource,
+    /// Whether the program itself has hashed the trace and found it
+    /// matching `commitment_hash`
+    pub verified_onchain: bool,
+    /// Whether the commitment was frozen by `lock_commitment`; anything
+    /// that lets the agent change or close a commitment must check this
+    pub locked: bool,
+    /// Epoch from which `expire_reveal` may clear the reveal's URI
+    pub uri_expiry_epoch: Option<u64>,
+    /// Verifier attestations that confirmed the reveal
+    pub verifier_confirmations: u32,
+    /// Verifier attestations that refuted the reveal
+    pub verifier_refutations: u32,
+    /// Chunks withheld from the public reveal (listed in the
+    /// `RedactionsDeclared` event)
+    pub redacted_chunks: u32,
+    /// Whether this commitment is about a simulated (paper) action
+    pub simulation: bool,
+    /// Whether the agent's stake was slashed over this reveal
+    pub slashed: bool,
+    /// Latest time the reveal may start
+    pub reveal_deadline: Option<i64>,
+    /// Whether the commitment missed its reveal deadline and was expired
+    /// (its status stays `Committed`)
+    pub expired: bool,
+    /// Whether the authority voided the commitment before revealing it
+    pub cancelled: bool,
+    /// Challenges against the commitment awaiting a ruling or lapse
+    pub open_challenges: u16,
+    /// The agent's event sequence number of the commitment's latest
+    /// lifecycle event; 0 if it predates sequencing
+    pub sequence: u64,
+    /// Whether an `ActionEscrow` for the commitment is open
+    pub escrowed: bool,
+    /// Number of rejected alternatives recorded in the commitment's
+    /// `Counterfactuals` account; 0 if it has none
+    pub counterfactuals: u8,
+    /// Whether the action type is still hidden under an embargo
+    pub embargoed: bool,
+    /// The hash `commitment_hash` was made with
+    pub hash_algorithm: HashAlgorithm,
+    /// Whether the commitment counts towards its action type's
+    /// `ActionTypeStats`, which its reveal or expiry must then update
+    pub in_action_stats: bool,
+    /// Commit fee paid for the commitment, in lamports
+    pub fee_paid: u64,
+    /// Whether `request_deadline_extension` pushed the reveal deadline
+    /// back, which it does at most once
+    pub deadline_extended: bool,
+    /// Whether `action_signature` appears in the trace as verified by
+    /// `verify_reveal_inline`, i.e. the committed reasoning named the
+    /// transaction before it ran
+    pub action_in_trace: bool,
+    /// Zeroed space for future fields
+    pub reserved: [u8; COMMITMENT_RESERVED],
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolConfig {
+    /// Governance key allowed to change parameters and override scores
+    pub admin: Pubkey,
+    /// Lowest accountability score an agent can hold (basis points)
+    pub score_floor: u16,
+    /// Highest accountability score an agent can hold (basis points)
+    pub score_ceiling: u16,
+    /// Agents scoring below this may enter probation (basis points)
+    pub probation_threshold: u16,
+    /// Reveals needed to complete probation
+    pub probation_required_reveals: u32,
+    /// Score recovered per reveal while on probation (basis points)
+    pub probation_recovery_bps: u16,
+    /// Declared value at risk earning a reveal full probation recovery
+    /// (lamports; 0 = every reveal recovers in full)
+    pub probation_reference_lamports: u64,
+    /// Seconds a reveal stays `Revealing` before anyone may finalize it
+    pub reveal_grace_period: i64,
+    /// Keys allowed to attest that revealed traces are retrievable
+    #[max_len(MAX_STORAGE_VERIFIERS)]
+    pub storage_verifiers: Vec<Pubkey>,
+    /// Attestations needed before a reveal counts as available (0 = off)
+    pub storage_quorum: u8,
+    /// Shortest retention an agent may put on a reveal's URI, in epochs
+    /// (`None` = reveals can't be marked for expiry)
+    pub min_retention_epochs: Option<u64>,
+    /// Seconds between requesting an unstake and being able to withdraw
+    pub unstake_delay: i64,
+    /// Share of an agent's stake slashed per proven mismatch (basis
+    /// points; 0 = slashing off)
+    pub slash_bps: u16,
+    /// Share of a slash paid to the challenger (basis points); the rest
+    /// goes to `treasury`
+    pub slash_challenger_bps: u16,
+    /// Receives the protocol's share of slashed stake and forfeited bonds
+    pub treasury: Pubkey,
+    /// Lamports a challenger bonds to open a challenge
+    pub challenge_bond: u64,
+    /// Seconds the council has to rule on a challenge
+    pub challenge_period: i64,
+    /// Score an upheld challenge costs the agent (basis points)
+    pub challenge_penalty_bps: u16,
+    /// Arbitration council ruling on challenges
+    #[max_len(MAX_COUNCIL_SIZE)]
+    pub council: Vec<Pubkey>,
+    /// Council signatures a ruling needs (0 = no council yet)
+    pub council_threshold: u8,
+    /// Score an expired commitment costs the agent (basis points)
+    pub expiry_penalty_bps: u16,
+    /// Weight of the on-time reveal rate in the score (basis points)
+    pub score_reveal_weight_bps: u16,
+    /// Weight of the undisputed reveal rate in the score (basis points)
+    pub score_dispute_weight_bps: u16,
+    /// Seconds after a reveal during which it may be challenged
+    pub challenge_window: i64,
+    /// Lamports a reviewer bonds to claim a review request
+    pub reviewer_bond: u64,
+    /// Seconds a reviewer has to submit a claimed review
+    pub review_period: i64,
+    /// Longest reasoning URI a reveal may post, up to `MAX_URI_LEN`
+    pub max_uri_len: u16,
+    /// Key besides the admin allowed to pause the protocol
+    pub guardian: Pubkey,
+    /// Commits, reveals and other user actions are halted (see
+    /// `set_paused`)
+    pub paused: bool,
+    /// Optional subsystems enabled on this deployment (`FEATURE_*` bits)
+    pub features: u64,
+    /// Lamports each new commitment pays into the `FeeTreasury` (0 = free)
+    pub commit_fee: u64,
+    /// Share of the commit fee (basis points) refunded for a reveal made
+    /// by its deadline and verified onchain
+    pub reveal_rebate_bps: u16,
+    /// Longest a reveal deadline can be pushed back, in seconds (0 = no
+    /// extensions)
+    pub max_deadline_extension: i64,
+    /// Lamports an extension pays into the `FeeTreasury`
+    pub extension_fee: u64,
+    /// Seconds after `record_outcome` during which verifiers may dispute
+    /// the outcome
+    pub outcome_dispute_window: i64,
+    /// Score an undisputed outcome adds on success, or costs on failure
+    /// (basis points)
+    pub outcome_score_bps: u16,
+    /// Share of a member agent's commit fee (basis points) credited to
+    /// its integrator
+    pub integrator_fee_share_bps: u16,
+    /// Keys allowed to dispute recorded outcomes
+    #[max_len(MAX_OUTCOME_VERIFIERS)]
+    pub outcome_verifiers: Vec<Pubkey>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Another accountability system whose reputation snapshots agents may
+/// import
+#[account]
+#[derive(InitSpace)]
+pub struct ReputationSource {
+    /// Key the system signs snapshots with
+    pub source: Pubkey,
+    /// System name (max 64 chars)
+    #[max_len(64)]
+    pub name: String,
+    /// Whether its snapshots are currently accepted
+    pub active: bool,
+    /// When governance registered it
+    pub registered_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// An agent's latest imported reputation snapshot
+#[account]
+#[derive(InitSpace)]
+pub struct ReputationImport {
+    /// The agent profile
+    pub agent: Pubkey,
+    /// Signing key of the `ReputationSource` it came from
+    pub source: Pubkey,
+    /// Score the source gave the agent (basis points)
+    pub score: u16,
+    /// When the source took the snapshot
+    pub snapshot_at: i64,
+    /// When it was imported
+    pub imported_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AttestorRecord {
+    /// Key the attestor signs with
+    pub attestor: Pubkey,
+    /// Organization name (max 64 chars)
+    #[max_len(64)]
+    pub name: String,
+    /// Whether the attestor may currently issue attestations
+    pub active: bool,
+    /// When governance registered the attestor
+    pub registered_at: i64,
+    /// When governance revoked the attestor, if it has
+    pub revoked_at: Option<i64>,
+    /// Operator attestations issued so far
+    pub attestations_issued: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Many reasoning commitments in one account: a Merkle root over their
+/// hashes
+#[account]
+#[derive(InitSpace)]
+pub struct BatchCommitment {
+    /// The agent profile that made the batch
+    pub agent: Pubkey,
+    /// Wallet that committed, and the only one that can reveal
+    pub authority: Pubkey,
+    /// Merkle root over the batch's reasoning hashes
+    pub root: [u8; 32],
+    /// Number of reasoning hashes under `root`
+    pub leaf_count: u32,
+    /// Hashes revealed so far
+    pub revealed_count: u32,
+    /// Bit `i % 8` of byte `i / 8` is set once hash `i` is revealed
+    pub revealed: [u8; BATCH_BITMAP_LEN],
+    /// Action type shared by the batch
+    #[max_len(32)]
+    pub action_type: String,
+    /// Category shared by the batch
+    pub category: CommitmentCategory,
+    /// Nonce the batch address was derived from
+    pub nonce: u64,
+    /// When the batch was committed
+    pub timestamp: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Reusable commitment shape, e.g. shared across an operator's fleet
+#[account]
+#[derive(InitSpace)]
+pub struct Template {
+    /// Key that created the template and may close it
+    pub owner: Pubkey,
+    /// Name, unique per owner (max 32 chars)
+    #[max_len(32)]
+    pub name: String,
+    /// Action type given to every commitment made from the template
+    #[max_len(32)]
+    pub action_type: String,
+    /// Category given to every commitment made from the template
+    pub category: CommitmentCategory,
+    /// Trace metadata keys every commitment must carry (checked off-chain)
+    #[max_len(MAX_TEMPLATE_FIELDS, 32)]
+    pub required_fields: Vec<String>,
+    /// Seconds after committing by which commitments made from the
+    /// template must be revealed, if they have a deadline
+    pub reveal_after: Option<i64>,
+    /// Commitments made from the template
+    pub uses: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A platform embedding SOLPRISM, namespacing the agents that join it
+#[account]
+#[derive(InitSpace)]
+pub struct Integrator {
+    /// Key that registered the integrator and manages its policy
+    pub authority: Pubkey,
+    /// Display name (max 32 chars)
+    #[max_len(32)]
+    pub name: String,
+    /// Rules every member agent's commitments must follow
+    pub policy: IntegratorPolicy,
+    /// Current member agents
+    pub agents: u64,
+    /// Commitments made by member agents
+    pub commitments: u64,
+    /// Its share of member agents' commit fees, held in the
+    /// `FeeTreasury` until `claim_integrator_fees`
+    pub claimable_fees: u64,
+    /// When the integrator registered
+    pub created_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Compliance rules an integrator holds its member agents' commits to,
+/// beyond its `IntegratorPolicy`
+#[account]
+#[derive(InitSpace)]
+pub struct CompliancePolicy {
+    /// The integrator the rules belong to
+    pub integrator: Pubkey,
+    /// Action types members may commit to (empty = any)
+    #[max_len(MAX_POLICY_ACTION_TYPES, 32)]
+    pub allowed_action_types: Vec<String>,
+    /// Lowest confidence a member may commit with
+    pub min_confidence: u8,
+    /// Every commitment must carry a reveal deadline
+    pub require_reveal_deadline: bool,
+    /// When the rules were last set
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A key's role within an integrator
+#[account]
+#[derive(InitSpace)]
+pub struct IntegratorRole {
+    /// The integrator the role is held in
+    pub integrator: Pubkey,
+    /// Key holding the role
+    pub member: Pubkey,
+    pub role: OrgRole,
+    /// Key that granted it
+    pub granted_by: Pubkey,
+    /// When it was (last) granted
+    pub granted_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// An agent's membership of an integrator, listable by integrator
+#[account]
+#[derive(InitSpace)]
+pub struct IntegratorMember {
+    /// The integrator joined
+    pub integrator: Pubkey,
+    /// The member agent profile
+    pub agent: Pubkey,
+    /// When the agent joined
+    pub joined_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A trace being streamed in for onchain verification
+#[account]
+#[derive(InitSpace)]
+pub struct ReasoningBuffer {
+    /// The commitment being verified
+    pub commitment: Pubkey,
+    /// Who is streaming the trace
+    pub verifier: Pubkey,
+    /// Running hash of the bytes appended so far
+    pub hasher: sha256_stream::Sha256Stream,
+    /// When verification began
+    pub started_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// One independent verifier's check of a reveal against its hash
+#[account]
+#[derive(InitSpace)]
+pub struct VerifierAttestation {
+    /// The commitment whose reveal was checked
+    pub commitment: Pubkey,
+    /// Verifier that checked it
+    pub verifier: Pubkey,
+    /// Whether the fetched content matched `commitment_hash`
+    pub passed: bool,
+    /// Hash of the content the verifier fetched
+    pub content_hash: [u8; 32],
+    /// When the verifier fetched the URI
+    pub checked_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A bonded dispute of a revealed commitment. Holds the bond on top of
+/// its own rent until resolved or lapsed.
+#[account]
+#[derive(InitSpace)]
+pub struct Challenge {
+    /// The disputed commitment
+    pub commitment: Pubkey,
+    /// The commitment's agent profile
+    pub agent: Pubkey,
+    /// Who opened the challenge and posted the bond
+    pub challenger: Pubkey,
+    /// What the challenger claims is wrong
+    pub reason: ChallengeReason,
+    /// Hash of the challenger's off-chain evidence
+    pub evidence_hash: [u8; 32],
+    /// Bonded lamports
+    pub bond: u64,
+    /// When the challenge was opened
+    pub opened_at: i64,
+    /// The council must rule before this, or the challenge lapses
+    pub deadline: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A paid request for an independent review of a revealed commitment.
+/// Holds the fee, and the reviewer's bond once claimed, on top of its
+/// own rent.
+#[account]
+#[derive(InitSpace)]
+pub struct ReviewRequest {
+    /// The commitment to review
+    pub commitment: Pubkey,
+    /// Who asked and paid the fee: the commitment's authority or
+    /// counterparty
+    pub requester: Pubkey,
+    /// Who claimed the review, if anyone has
+    pub reviewer: Option<Pubkey>,
+    /// Lamports paid to the reviewer on completion
+    pub fee: u64,
+    /// Lamports the reviewer bonded when claiming
+    pub bond: u64,
+    /// When the review was requested
+    pub requested_at: i64,
+    /// The reviewer must submit before this; set on claim
+    pub deadline: Option<i64>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Lamports offered for checking a commitment's reveal onchain. Holds
+/// them on top of its own rent.
+#[account]
+#[derive(InitSpace)]
+pub struct VerificationBounty {
+    /// The commitment to check
+    pub commitment: Pubkey,
+    /// Who posted the bounty and can reclaim it once expired
+    pub funder: Pubkey,
+    /// Lamports offered
+    pub amount: u64,
+    /// Unclaimed from this point, the funder can take it back
+    pub expires_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Protocol fees collected from commits. Holds them on top of its own
+/// rent until the admin withdraws them.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeTreasury {
+    /// Lamports paid in fees, ever
+    pub collected: u64,
+    /// Lamports withdrawn, ever
+    pub withdrawn: u64,
+    /// Lamports refunded as reveal rebates, ever
+    pub rebated: u64,
+    /// Lamports credited to integrators as their fee share, ever
+    pub integrator_credited: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl FeeTreasury {
+    /// Fees held that are neither withdrawn, rebated nor owed to
+    /// integrators.
+    pub fn available(&self) -> Result<u64> {
+        self.collected
+            .checked_sub(self.withdrawn)
+            .and_then(|left| left.checked_sub(self.rebated))
+            .and_then(|left| left.checked_sub(self.integrator_credited))
+            .ok_or(error!(AxiomError::Overflow))
+    }
+}
+
+/// An agent's staked collateral. Holds the staked lamports on top of
+/// its own rent.
+#[account]
+#[derive(InitSpace)]
+pub struct StakeVault {
+    /// The agent profile the stake backs
+    pub agent: Pubkey,
+    /// Lamports requested for withdrawal
+    pub pending_unstake: u64,
+    /// When the pending unstake can be withdrawn
+    pub unstake_available_at: Option<i64>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Lamports held for a commitment's counterparty, released one tranche
+/// per verified chunk of the revealed trace. Holds them on top of its
+/// own rent.
+#[account]
+#[derive(InitSpace)]
+pub struct ActionEscrow {
+    /// The chunked commitment whose reveal releases the tranches
+    pub commitment: Pubkey,
+    /// Who funded the escrow and gets back what isn't released
+    pub depositor: Pubkey,
+    /// Who the tranches are released to
+    pub counterparty: Pubkey,
+    /// Lamports escrowed
+    pub amount: u64,
+    /// Lamports released so far
+    pub released: u64,
+    /// Number of tranches: the commitment's chunk count
+    pub tranches: u32,
+    /// Bit `i` is set once chunk `i`'s tranche was released
+    pub released_tranches: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// One storage verifier's check of a revealed trace
+#[account]
+#[derive(InitSpace)]
+pub struct AvailabilityAttestation {
+    /// The commitment whose reveal was checked
+    pub commitment: Pubkey,
+    /// The verifier that checked it
+    pub verifier: Pubkey,
+    /// When the trace was fetched and found to match
+    pub checked_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Plans an agent considered for a commitment's action and rejected
+#[account]
+#[derive(InitSpace)]
+pub struct Counterfactuals {
+    /// The commitment whose action the alternatives were weighed for
+    pub commitment: Pubkey,
+    /// The rejected plans, in the order they were recorded
+    #[max_len(MAX_COUNTERFACTUALS)]
+    pub alternatives: Vec<Alternative>,
+    /// When they were recorded
+    pub recorded_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A key allowed to record outcomes for an agent's commitments
+#[account]
+#[derive(InitSpace)]
+pub struct OutcomeReporter {
+    /// The agent profile
+    pub agent: Pubkey,
+    /// The reporter key
+    pub reporter: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// What came of a commitment's action, from `record_outcome`
+#[account]
+#[derive(InitSpace)]
+pub struct Outcome {
+    /// The commitment whose action it was
+    pub commitment: Pubkey,
+    /// Whether the action achieved what it set out to
+    pub success: bool,
+    /// Why it failed, when it did
+    pub failure_reason: Option<FailureReason>,
+    /// Hash of the result (receipt, report, ...)
+    pub result_hash: [u8; 32],
+    /// When the action was executed
+    pub executed_at: i64,
+    /// Key that recorded it: the authority or the agent's reporter
+    pub reporter: Pubkey,
+    /// When it was recorded
+    pub recorded_at: i64,
+    /// Verifiers may dispute it until then
+    pub disputable_until: i64,
+    /// A verifier's dispute, if one was raised
+    pub dispute: Option<OutcomeDispute>,
+    /// Whether `finalize_outcome` has run
+    pub finalized: bool,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Who paid for a commitment, from `split_commit_costs`
+#[account]
+#[derive(InitSpace)]
+pub struct CostSplit {
+    /// The commitment paid for
+    pub commitment: Pubkey,
+    /// Authority that fronted the cost and was reimbursed
+    pub payer: Pubkey,
+    /// Lamports split (the commitment's rent)
+    pub cost: u64,
+    /// What each funder paid; `payer` covered the rest
+    #[max_len(MAX_COST_FUNDERS)]
+    pub shares: Vec<CostShare>,
+    /// When the split was made
+    pub split_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// The hidden metadata of a commitment made with `commit_embargoed`
+#[account]
+#[derive(InitSpace)]
+pub struct MetadataEmbargo {
+    /// The embargoed commitment
+    pub commitment: Pubkey,
+    /// Hash of the action type and tags (see [`metadata_hash`])
+    pub metadata_hash: [u8; 32],
+    /// The commitment's tags; empty until disclosed
+    #[max_len(MAX_COMMITMENT_TAGS, 32)]
+    pub tags: Vec<String>,
+    /// When the metadata was disclosed
+    pub disclosed_at: Option<i64>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Commitments an offline authority approved in advance, from
+/// `open_presigned_batch`
+#[account]
+#[derive(InitSpace)]
+pub struct PresignedBatch {
+    /// The agent profile the commitments are for
+    pub agent: Pubkey,
+    /// Authority that signed the permit
+    pub authority: Pubkey,
+    /// Id the authority gave the batch
+    pub batch_id: u64,
+    /// Hot key allowed to make the commitments
+    pub submitter: Pubkey,
+    /// Entries can't be redeemed before this time
+    pub valid_from: i64,
+    /// Nor after this one
+    pub valid_until: i64,
+    /// The approved commitments
+    #[max_len(MAX_PRESIGNED_COMMITMENTS)]
+    pub entries: Vec<PresignedEntry>,
+    /// Bit `i` set once entry `i` was made
+    pub redeemed: u8,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A secondary key allowed to commit and start reveals for an agent
+#[account]
+#[derive(InitSpace)]
+pub struct Delegate {
+    /// The agent profile the key signs for
+    pub agent: Pubkey,
+    /// The delegated key
+    pub delegate: Pubkey,
+    /// When the key stops working (`None` = until revoked)
+    pub expires_at: Option<i64>,
+    /// Action types the key may commit to (empty = any)
+    #[max_len(MAX_DELEGATE_ACTION_TYPES, 32)]
+    pub action_types: Vec<String>,
+    /// When the key was (last) authorized
+    pub created_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A pending handover of an agent profile to a new authority
+#[account]
+#[derive(InitSpace)]
+pub struct AuthorityTransfer {
+    /// The agent profile being handed over
+    pub agent: Pubkey,
+    /// Authority that made the offer; paid its rent
+    pub proposed_by: Pubkey,
+    /// Wallet that must accept to take control
+    pub new_authority: Pubkey,
+    /// When the offer was made
+    pub proposed_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Where an agent's off-chain metadata is published, for registries and
+/// dashboards that need more than the profile's name
+#[account]
+#[derive(InitSpace)]
+pub struct AgentMetadata {
+    /// The agent profile described
+    pub agent: Pubkey,
+    /// URI of a JSON document (model card, operator info, avatar)
+    #[max_len(256)]
+    pub metadata_uri: Option<String>,
+    /// When the URI was last set or cleared
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// One document-signing key an agent has registered
+#[account]
+#[derive(InitSpace)]
+pub struct SigningKeyRecord {
+    /// The agent profile the key signs for
+    pub agent: Pubkey,
+    /// The signing key
+    pub key: Pubkey,
+    /// Position in the agent's key history (0 = first key)
+    pub index: u32,
+    /// When the key was registered
+    pub activated_at: i64,
+    /// When the next key replaced it, if one has
+    pub retired_at: Option<i64>,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// An agent's activity on one UTC day
+#[account]
+#[derive(InitSpace)]
+pub struct ActivityBucket {
+    /// The agent profile this bucket belongs to
+    pub agent: Pubkey,
+    /// Days since the Unix epoch (see [`activity_day`])
+    pub day: i64,
+    /// Commitments made during the day
+    pub commits: u32,
+    /// Reveals started during the day
+    pub reveals: u32,
+    /// First commitment made during the day
+    pub first_commitment: Pubkey,
+    /// Latest commitment made during the day
+    pub last_commitment: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Exponentially weighted averages of an agent's recent behaviour,
+/// updated as each sample comes in (see `METRICS_SMOOTHING_BPS`)
+#[account]
+#[derive(InitSpace)]
+pub struct AgentMetrics {
+    /// The agent profile these metrics belong to
+    pub agent: Pubkey,
+    /// Share of resolved commitments revealed rather than expired (basis
+    /// points)
+    pub reveal_rate_bps: u16,
+    /// Commit-to-reveal latency (seconds)
+    pub reveal_latency: u64,
+    /// Share of reveals with a challenge upheld against them (basis
+    /// points)
+    pub dispute_rate_bps: u16,
+    /// When a sample last came in
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// An agent's record for one action type
+#[account]
+#[derive(InitSpace)]
+pub struct ActionTypeStats {
+    /// The agent profile these stats belong to
+    pub agent: Pubkey,
+    /// The action type they cover
+    #[max_len(32)]
+    pub action_type: String,
+    /// Commitments made with `commit_reasoning`
+    pub commitments: u64,
+    /// Of those, reveals started
+    pub reveals: u64,
+    /// Of those, commitments expired unrevealed
+    pub expiries: u64,
+    /// Sum of the commitments' confidence
+    pub confidence_total: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// How often policy has blocked an agent, as reported with
+/// `record_rejection`
+#[account]
+#[derive(InitSpace)]
+pub struct RejectionStats {
+    /// The agent profile these counts belong to
+    pub agent: Pubkey,
+    /// Reveals refused for coming after the reveal deadline
+    pub late_reveals: u64,
+    /// Commits refused for an action type the integrator's compliance
+    /// policy doesn't allow
+    pub disallowed_action_types: u64,
+    /// When the latest rejection was recorded
+    pub last_rejected_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Compact, fixed-size view of an agent for other programs' gating
+/// checks, so they needn't load the profile and its commitments
+#[account]
+#[derive(InitSpace)]
+pub struct AgentSummary {
+    /// The agent profile summarized
+    pub agent: Pubkey,
+    /// Accountability score (basis points)
+    pub accountability_score: u16,
+    /// Band the score falls in
+    pub tier: AgentTier,
+    /// Latest resolved commitments, newest in bit 0: 1 if revealed,
+    /// 0 if expired
+    pub recent_outcomes: u8,
+    /// How many bits of `recent_outcomes` are filled (at most 8)
+    pub recent_count: u8,
+    /// Commit-to-reveal latency, smoothed as in `AgentMetrics` (seconds)
+    pub reveal_latency: u64,
+    /// When it was last updated
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// An agent's standing at one epoch, from `snapshot_epoch`
+#[account]
+#[derive(InitSpace)]
+pub struct EpochSnapshot {
+    /// The agent profile
+    pub agent: Pubkey,
+    /// Epoch the snapshot was taken in
+    pub epoch: u64,
+    /// Accountability score (basis points)
+    pub accountability_score: u16,
+    /// Commitments made so far
+    pub total_commitments: u64,
+    /// Mean commit-to-reveal latency in seconds, once anything is revealed
+    pub average_reveal_latency: Option<u64>,
+    /// When it was taken
+    pub taken_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// What remains of an agent's closed commitments
+#[account]
+#[derive(InitSpace)]
+pub struct CommitmentHistory {
+    /// The agent profile whose commitments were closed
+    pub agent: Pubkey,
+    /// Commitments closed so far
+    pub closed_commitments: u64,
+    /// Hash chain over every closed commitment's address and hash, in
+    /// closing order (see [`CommitmentHistory::record`])
+    pub accumulator: [u8; 32],
+    /// When the latest commitment was closed
+    pub last_closed_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A wallet's reaction to a revealed commitment
+#[account]
+#[derive(InitSpace)]
+pub struct Reaction {
+    /// The commitment reacted to
+    pub commitment: Pubkey,
+    /// The wallet that reacted
+    pub reactor: Pubkey,
+    /// Upvote (true) or downvote (false)
+    pub upvote: bool,
+    /// When the reaction was recorded
+    pub reacted_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// A wallet following an agent
+#[account]
+#[derive(InitSpace)]
+pub struct Follower {
+    /// The agent profile followed
+    pub agent: Pubkey,
+    /// The wallet following it
+    pub follower: Pubkey,
+    /// When the follow was recorded
+    pub followed_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl AgentProfile {
+    /// A freshly registered profile.
+    pub fn new(
+        authority: Pubkey,
+        name: String,
+        operator: Option<Pubkey>,
+        created_at: i64,
+        bump: u8,
+    ) -> Result<Self> {
+        require!(name.len() <= 64, AxiomError::NameTooLong);
+        require!(!name.is_empty(), AxiomError::NameEmpty);
+        
+        Ok(Self {
+            authority,
+            name,
+            total_commitments: 0,
+            total_verified: 0,
+            accountability_score: 10000, // Start at 100.00% (basis points)
+            created_at,
+            probation: None,
+            verified_operator: false,
+            operator_attestation: None,
+            confidence_histogram: [0; CONFIDENCE_BUCKETS],
+            signing_key: None,
+            signing_key_count: 0,
+            total_reveal_latency: 0,
+            timed_reveals: 0,
+            max_reveal_latency: 0,
+            integrator: None,
+            operator,
+            agent_id: None,
+            framework: None,
+            staked_lamports: 0,
+            simulated_commitments: 0,
+            challenges_received: 0,
+            challenges_upheld: 0,
+            expired_commitments: 0,
+            score_adjustment: 0,
+            cancelled_commitments: 0,
+            event_sequence: 0,
+            deactivated_at: None,
+            frozen: false,
+            idempotent_commits: false,
+            imported_score: 0,
+            has_metrics: false,
+            has_summary: false,
+            follower_count: 0,
+            open_challenges: 0,
+            bump,
+            reserved: [0; AGENT_PROFILE_RESERVED],
+        })
+    }
+
+    /// Count a commitment's confidence in the histogram.
+    pub fn record_confidence(&mut self, confidence: u8) {
+        let bucket = (usize::from(confidence) / 10).min(CONFIDENCE_BUCKETS - 1);
+        self.confidence_histogram[bucket] = self.confidence_histogram[bucket].saturating_add(1);
+    }
+
+    /// Account for a reveal started `latency` seconds after its commit.
+    pub fn record_reveal_latency(&mut self, latency: i64) -> Result<()> {
+        let latency = u64::try_from(latency).unwrap_or(0);
+        self.total_reveal_latency = self.total_reveal_latency.checked_add(latency)
+            .ok_or(AxiomError::Overflow)?;
+        self.timed_reveals = self.timed_reveals.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        self.max_reveal_latency = self.max_reveal_latency.max(latency);
+        Ok(())
+    }
+
+    /// Mean commit-to-reveal latency in seconds, once anything is revealed.
+    pub fn average_reveal_latency(&self) -> Option<u64> {
+        self.total_reveal_latency.checked_div(self.timed_reveals)
+    }
+
+    /// The score the agent's record implies, before adjustments.
+    ///
+    /// Blends the share of resolved commitments revealed on time (an
+    /// expired commitment is the only way to miss) with the share of
+    /// reveals no challenge was upheld against, by the config's weights.
+    /// An agent with nothing resolved yet scores 10000.
+    pub fn base_score(&self, config: &ProtocolConfig) -> u16 {
+        let revealed = u128::from(self.total_verified);
+        let upheld = u128::from(self.challenges_upheld);
+        let resolved = revealed + u128::from(self.expired_commitments);
+        let reveal_rate = (revealed * 10000).checked_div(resolved).unwrap_or(10000);
+        let undisputed_rate = 10000 - (upheld * 10000).checked_div(revealed.max(upheld)).unwrap_or(0);
+        let score = (reveal_rate * u128::from(config.score_reveal_weight_bps)
+            + undisputed_rate * u128::from(config.score_dispute_weight_bps))
+            / 10000;
+        score.min(10000) as u16
+    }
+
+    /// Recompute `accountability_score` from the record and the standing
+    /// adjustment, within the config's bounds (and below the probation
+    /// cap while on probation).
+    pub fn recompute_score(&mut self, config: &ProtocolConfig) {
+        let score = (i32::from(self.base_score(config)) + self.score_adjustment).clamp(0, 10000) as u16;
+        let cap = self.probation.map_or(u16::MAX, |p| p.max_score);
+        self.accountability_score = config.clamp_score(score.min(cap));
+    }
+
+    /// Shift the score by `delta` basis points on top of what the record
+    /// implies, and recompute.
+    pub fn adjust_score(&mut self, config: &ProtocolConfig, delta: i32) -> Result<()> {
+        let adjustment = self.score_adjustment.saturating_add(delta).clamp(-10000, 10000);
+        self.score_adjustment = adjustment;
+        self.recompute_score(config);
+        Ok(())
+    }
+
+    /// Pin the score to `score`, keeping it there across recomputations
+    /// until the record changes.
+    pub fn set_score(&mut self, config: &ProtocolConfig, score: u16) -> Result<()> {
+        self.score_adjustment = i32::from(score) - i32::from(self.base_score(config));
+        self.accountability_score = score;
+        Ok(())
+    }
+
+    /// Normalized summary of the profile at `agent`.
+    pub fn stats(&self, agent: Pubkey) -> AgentStats {
+        let counted = self.total_commitments
+            .saturating_sub(u64::from(self.cancelled_commitments));
+        let rate = |count: u64| {
+            (u128::from(count) * 10000)
+                .checked_div(u128::from(counted))
+                .map_or(0, |bps| bps.min(10000) as u16)
+        };
+        AgentStats {
+            agent,
+            accountability_score: self.accountability_score,
+            total_commitments: self.total_commitments,
+            reveal_rate_bps: rate(self.total_verified),
+            average_reveal_latency: self.average_reveal_latency(),
+            dispute_rate_bps: rate(u64::from(self.challenges_upheld)),
+        }
+    }
+}
+
+impl ActionTypeStats {
+    /// Mean confidence of the counted commitments, once there are any.
+    pub fn average_confidence(&self) -> Option<u8> {
+        self.confidence_total.checked_div(self.commitments).map(|mean| mean as u8)
+    }
+}
+
+impl AgentSummary {
+    /// Copy the profile's current score and tier.
+    fn sync(&mut self, profile: &AgentProfile, now: i64) {
+        self.accountability_score = profile.accountability_score;
+        self.tier = AgentTier::of(profile);
+        self.updated_at = now;
+    }
+
+    fn push_outcome(&mut self, revealed: bool) {
+        self.recent_outcomes = self.recent_outcomes << 1 | u8::from(revealed);
+        self.recent_count = self.recent_count.saturating_add(1).min(8);
+    }
+
+    /// A reveal started `latency` seconds after its commitment.
+    pub fn record_reveal(&mut self, latency: u64) {
+        self.push_outcome(true);
+        self.reveal_latency = AgentMetrics::smooth(self.reveal_latency, latency);
+    }
+
+    /// A commitment expired unrevealed.
+    pub fn record_expiry(&mut self) {
+        self.push_outcome(false);
+    }
+}
+
+impl AgentMetrics {
+    /// Move `average` towards `sample` by `METRICS_SMOOTHING_BPS`.
+    fn smooth(average: u64, sample: u64) -> u64 {
+        let blended = u128::from(average) * u128::from(10000 - METRICS_SMOOTHING_BPS)
+            + u128::from(sample) * u128::from(METRICS_SMOOTHING_BPS);
+        (blended / 10000) as u64
+    }
+
+    /// A reveal started `latency` seconds after its commitment.
+    pub fn record_reveal(&mut self, latency: u64) {
+        self.reveal_rate_bps = Self::smooth(self.reveal_rate_bps.into(), 10000) as u16;
+        self.reveal_latency = Self::smooth(self.reveal_latency, latency);
+        self.dispute_rate_bps = Self::smooth(self.dispute_rate_bps.into(), 0) as u16;
+    }
+
+    /// A commitment expired unrevealed.
+    pub fn record_expiry(&mut self) {
+        self.reveal_rate_bps = Self::smooth(self.reveal_rate_bps.into(), 0) as u16;
+    }
+
+    /// A challenge against a reveal was upheld.
+    pub fn record_upheld_challenge(&mut self) {
+        self.dispute_rate_bps = Self::smooth(self.dispute_rate_bps.into(), 10000) as u16;
+    }
+}
+
+impl ActivityBucket {
+    /// Fill in the identity of a bucket created by this instruction.
+    fn open(&mut self, agent: Pubkey, day: i64, bump: u8) {
+        if self.agent == Pubkey::default() {
+            self.agent = agent;
+            self.day = day;
+            self.bump = bump;
+        }
+    }
+}
+
+impl BatchCommitment {
+    pub fn is_revealed(&self, index: u32) -> bool {
+        self.revealed[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+
+    fn set_revealed(&mut self, index: u32) {
+        self.revealed[index as usize / 8] |= 1 << (index % 8);
+    }
+}
+
+impl ActionEscrow {
+    /// Lamports tranche `index` releases: an equal share of the amount,
+    /// with the remainder in the last tranche.
+    pub fn tranche_amount(&self, index: u32) -> u64 {
+        let tranches = u64::from(self.tranches.max(1));
+        let share = self.amount / tranches;
+        if index.saturating_add(1) >= self.tranches {
+            self.amount - share * (tranches - 1)
+        } else {
+            share
+        }
+    }
+}
+
+impl CommitmentHistory {
+    /// Fill in the identity of a history created by this instruction.
+    fn open(&mut self, agent: Pubkey, bump: u8) {
+        if self.agent == Pubkey::default() {
+            self.agent = agent;
+            self.bump = bump;
+        }
+    }
+
+    /// Fold a closed commitment into the accumulator:
+    /// `sha256(accumulator || commitment || commitment_hash)`.
+    pub fn record(&mut self, commitment: &Pubkey, commitment_hash: &[u8; 32], now: i64) -> Result<()> {
+        self.accumulator = solana_sha256_hasher::hashv(&[
+            &self.accumulator,
+            commitment.as_ref(),
+            commitment_hash,
+        ])
+        .to_bytes();
+        self.closed_commitments = self.closed_commitments.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        self.last_closed_at = now;
+        Ok(())
+    }
+}
+
+impl SigningKeyRecord {
+    /// Whether the key was the agent's signing key at `timestamp`.
+    pub fn was_active_at(&self, timestamp: i64) -> bool {
+        self.activated_at <= timestamp && self.retired_at.is_none_or(|at| timestamp < at)
+    }
+}
+
+impl CompliancePolicy {
+    /// Whether members may commit to `action_type`; a `hidden` one (of
+    /// a private or embargoed commitment) only passes when any type is
+    /// allowed.
+    pub fn allows_action_type(&self, action_type: &str, hidden: bool) -> bool {
+        self.allowed_action_types.is_empty()
+            || !hidden && self.allowed_action_types.iter().any(|t| t == action_type)
+    }
+}
+
+impl Delegate {
+    /// Whether the key may act on an `action_type` commitment at `now`.
+    pub fn allows(&self, action_type: &str, now: i64) -> bool {
+        self.expires_at.is_none_or(|at| now < at)
+            && (self.action_types.is_empty() || self.action_types.iter().any(|t| t == action_type))
+    }
+}
+
+impl ProtocolConfig {
+    /// Whether every bit of `feature` is enabled.
+    pub fn has_feature(&self, feature: u64) -> bool {
+        self.features & feature == feature
+    }
+
+    /// Check a reasoning URI is non-empty and within `max_uri_len`.
+    pub fn check_uri(&self, uri: &str) -> Result<()> {
+        require!(!uri.is_empty(), AxiomError::UriEmpty);
+        require!(uri.len() <= self.max_uri_len as usize, AxiomError::UriTooLong);
+        Ok(())
+    }
+
+    /// Clamp a computed score into the configured bounds.
+    pub fn clamp_score(&self, score: u16) -> u16 {
+        score.clamp(self.score_floor, self.score_ceiling)
+    }
+
+    /// Whether `signers` include at least `council_threshold` distinct
+    /// council members who signed the transaction.
+    pub fn council_approves(&self, signers: &[AccountInfo]) -> bool {
+        let approvals = self.council.iter()
+            .filter(|member| signers.iter().any(|s| s.is_signer && s.key == *member))
+            .count();
+        self.council_threshold > 0 && approvals >= self.council_threshold as usize
+    }
+}
+
+impl AgentProfile {
+    /// Advance the event sequence, returning the number for the event
+    /// about to be emitted.
+    pub fn next_event_sequence(&mut self) -> Result<u64> {
+        self.event_sequence = self.event_sequence.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        Ok(self.event_sequence)
+    }
+
+    /// Whether the agent may make new commitments.
+    pub fn is_active(&self) -> bool {
+        self.deactivated_at.is_none()
+    }
+}
+
+// ─── Types ─────────────────────────────────────────────────────────────────
+
+/// Lifecycle of a reasoning commitment.
+///
+/// Encoded where commitments once kept a `revealed: bool`, so the first
+/// two variants keep that flag's values: `false` reads as `Committed`
+/// and `true` as `Revealed`.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CommitmentStatus {
+    /// Hash committed, reasoning not yet published
+    Committed,
+    /// Reveal complete and counted towards the agent's record
+    Revealed,
+    /// URI posted, awaiting verification or the end of the grace period
+    Revealing,
+}
+
+/// Layout of a trace committed as a Merkle root over fixed-size chunks.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ChunkManifest {
+    /// Bytes per chunk (the last chunk may be shorter)
+    pub chunk_size: u32,
+    /// Number of chunks (Merkle leaves)
+    pub chunk_count: u32,
+}
+
+impl ChunkManifest {
+    /// Whether a revealed trace of `trace_len` bytes in `chunk_count`
+    /// chunks fits this manifest.
+    pub fn matches(&self, trace_len: u64, chunk_count: u32) -> bool {
+        let full = u64::from(self.chunk_size) * u64::from(self.chunk_count - 1);
+        chunk_count == self.chunk_count
+            && trace_len > full
+            && trace_len <= full + u64::from(self.chunk_size)
+    }
+}
+
+/// An attestor's claim of the agent framework an agent runs.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq, Debug)]
+pub struct FrameworkAttestation {
+    /// The attestor (usually a framework program's PDA) that issued it
+    pub attestor: Pubkey,
+    /// Framework version, e.g. "1.4.2"
+    #[max_len(MAX_FRAMEWORK_VERSION_LEN)]
+    pub version: String,
+    /// When it was issued
+    pub attested_at: i64,
+}
+
+/// When a commitment's reveal is due, as passed to `commit_reasoning`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RevealDeadline {
+    /// At this Unix timestamp
+    At(i64),
+    /// This many seconds after committing
+    After(i64),
+}
+
+impl RevealDeadline {
+    /// The absolute deadline for a commitment made at `now`.
+    pub fn resolve(self, now: i64) -> Result<i64> {
+        let deadline = match self {
+            RevealDeadline::At(at) => at,
+            RevealDeadline::After(seconds) => {
+                now.checked_add(seconds).ok_or(AxiomError::Overflow)?
+            }
+        };
+        require!(deadline > now, AxiomError::InvalidRevealDeadline);
+        Ok(deadline)
+    }
+}
+
+/// Grounds for challenging a revealed commitment.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChallengeReason {
+    /// The reasoning URI can't be fetched
+    UriUnreachable,
+    /// The fetched content doesn't hash to `commitment_hash`
+    ContentMismatch,
+    /// The reasoning doesn't support the action taken
+    ReasoningContradictsAction,
+}
+
+/// How a commitment's confidence number was produced.
+///
+/// Zero bytes decode as `Unspecified`, so accounts committed before the
+/// field existed read as that.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ConfidenceSource {
+    #[default]
+    Unspecified,
+    /// Derived from model token log-probabilities
+    ModelLogprob,
+    /// Share of an ensemble that voted for the action
+    EnsembleVote,
+    /// Set by a human operator
+    HumanSet,
+    /// Any rule-based estimate
+    Heuristic,
+}
+
+/// One funder's part of a `CostSplit`.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CostShare {
+    pub funder: Pubkey,
+    pub lamports: u64,
+}
+
+/// What `commit_reasoning` commits to.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CommitArgs {
+    /// Hash of the reasoning trace, made with `hash_algorithm`
+    pub commitment_hash: [u8; 32],
+    /// Type of action (max 32 chars)
+    pub action_type: String,
+    /// Confidence score (0-100)
+    pub confidence: u8,
+    /// Picks the commitment's address among the agent's
+    pub nonce: u64,
+    pub category: CommitmentCategory,
+    /// Layout of a chunked trace, if `commitment_hash` is a Merkle root
+    pub chunk_manifest: Option<ChunkManifest>,
+    /// Party the action affects
+    pub counterparty: Option<Pubkey>,
+    /// Value the action puts at risk
+    pub risk: Option<RiskDisclosure>,
+    /// Where `confidence` came from
+    pub confidence_source: ConfidenceSource,
+    /// Paper trade or backtest, kept out of the accountability stats
+    pub simulation: bool,
+    /// When the reveal must start by
+    pub reveal_deadline: Option<RevealDeadline>,
+    pub hash_algorithm: HashAlgorithm,
+}
+
+/// One commitment of a `PresignPermit`.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq, Debug)]
+pub struct PresignedEntry {
+    pub commitment_hash: [u8; 32],
+    #[max_len(32)]
+    pub action_type: String,
+    pub category: CommitmentCategory,
+    pub confidence: u8,
+}
+
+/// What an offline authority signs to approve future commitments (see
+/// [`presign_message`]).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PresignPermit {
+    pub batch_id: u64,
+    pub submitter: Pubkey,
+    pub valid_from: i64,
+    pub valid_until: i64,
+    pub entries: Vec<PresignedEntry>,
+}
+
+/// Hash function behind a commitment's `commitment_hash`.
+///
+/// Zero bytes decode as `Sha256`, so accounts committed before the field
+/// existed read as that.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    /// Keccak-256 as used by the EVM
+    Keccak256,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// Hash the concatenation of `parts`, through the runtime's syscall
+    /// for the algorithm.
+    pub fn hashv(self, parts: &[&[u8]]) -> [u8; 32] {
+        match self {
+            HashAlgorithm::Sha256 => solana_sha256_hasher::hashv(parts).to_bytes(),
+            HashAlgorithm::Keccak256 => solana_keccak_hasher::hashv(parts).to_bytes(),
+            HashAlgorithm::Blake3 => solana_blake3_hasher::hashv(parts).to_bytes(),
+        }
+    }
+}
+
+/// Band of accountability an agent is in, for coarse gating.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum AgentTier {
+    /// On probation, whatever the score
+    Probation,
+    /// Below `TIER_MEDIUM_SCORE`
+    #[default]
+    Low,
+    /// From `TIER_MEDIUM_SCORE`
+    Medium,
+    /// From `TIER_HIGH_SCORE`
+    High,
+}
+
+impl AgentTier {
+    pub fn of(profile: &AgentProfile) -> Self {
+        match profile.accountability_score {
+            _ if profile.probation.is_some() => AgentTier::Probation,
+            score if score >= TIER_HIGH_SCORE => AgentTier::High,
+            score if score >= TIER_MEDIUM_SCORE => AgentTier::Medium,
+            _ => AgentTier::Low,
+        }
+    }
+}
+
+/// Why a committed action failed, as recorded by `record_outcome`
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FailureReason {
+    /// The agent's model reasoned or acted wrongly
+    ModelError,
+    /// Conditions changed between decision and execution
+    MarketMoved,
+    /// RPC, network or other infrastructure failed
+    InfraFailure,
+    /// A policy stopped the action before it completed
+    AbortedByPolicy,
+    /// A third party exploited the action
+    Exploited,
+}
+
+/// A key's role within an integrator. See `grant_role` for what each
+/// may do.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OrgRole {
+    Admin,
+    Operator,
+    /// View-only; recognized off-chain
+    Auditor,
+}
+
+/// An integrator action gated by role.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OrgPermission {
+    ManageRoles,
+    ManagePolicy,
+    Admit,
+    Freeze,
+    RotateKeys,
+}
+
+impl OrgRole {
+    pub fn permits(self, permission: OrgPermission) -> bool {
+        match self {
+            OrgRole::Admin => true,
+            OrgRole::Operator => matches!(
+                permission,
+                OrgPermission::Admit | OrgPermission::Freeze | OrgPermission::RotateKeys
+            ),
+            OrgRole::Auditor => false,
+        }
+    }
+}
+
+/// One rejected plan of a commitment's `Counterfactuals`.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq, Debug)]
+pub struct Alternative {
+    /// SHA-256 of the plan
+    pub plan_hash: [u8; 32],
+    /// Where the plan was published; empty until revealed
+    #[max_len(256)]
+    pub plan_uri: String,
+}
+
+/// Stakes an agent declares for an action when committing to it.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RiskDisclosure {
+    /// Estimated worst-case loss, in lamports
+    pub max_loss_lamports: u64,
+    /// Value the action puts at risk, in lamports
+    pub affected_lamports: u64,
+    /// The same value in US cents, if the agent priced it (0 otherwise)
+    pub affected_usd_cents: u64,
+    /// Whether the action can't be undone once executed
+    pub irreversible: bool,
+}
+
+/// One side of `compare_agents`. Rates are basis points of
+/// `total_commitments`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AgentStats {
+    pub agent: Pubkey,
+    pub accountability_score: u16,
+    pub total_commitments: u64,
+    /// Commitments revealed
+    pub reveal_rate_bps: u16,
+    /// Mean commit-to-reveal latency in seconds
+    pub average_reveal_latency: Option<u64>,
+    /// Commitments with an upheld challenge
+    pub dispute_rate_bps: u16,
+}
+
+impl AgentStats {
+    /// Whether this agent is more accountable than `other`: higher
+    /// score, then fewer upheld disputes, then more reveals.
+    pub fn outranks(&self, other: &AgentStats) -> bool {
+        (self.accountability_score, other.dispute_rate_bps, self.reveal_rate_bps)
+            > (other.accountability_score, self.dispute_rate_bps, other.reveal_rate_bps)
+    }
+}
+
+/// Result of `get_features`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ProtocolFeatures {
+    /// `PROTOCOL_VERSION` of the deployed program
+    pub version: u16,
+    /// Enabled `FEATURE_*` bits
+    pub features: u64,
+    /// Whether the protocol is paused
+    pub paused: bool,
+}
+
+/// Result of `compare_agents`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AgentComparison {
+    pub first: AgentStats,
+    pub second: AgentStats,
+    /// The more accountable agent (`first` on a tie)
+    pub preferred: Pubkey,
+}
+
+/// A rejection counted by `record_rejection`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum Rejection {
+    /// `start_reveal` refused a reveal after the commitment's deadline
+    RevealDeadlinePassed,
+    /// A commit refused because the integrator's compliance policy
+    /// doesn't allow this action type
+    ActionTypeNotAllowed(String),
+}
+
+/// Integrator-level rules for member agents' commitments.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct IntegratorPolicy {
+    /// Every commitment must name a counterparty
+    pub require_counterparty: bool,
+    /// Members may use `commit_private`
+    pub allow_private: bool,
+}
+
+/// Probation terms, fixed at entry so later config changes don't move
+/// the goalposts for an agent already recovering.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Probation {
+    /// When probation started
+    pub started_at: i64,
+    /// Reveals needed to complete probation
+    pub required_reveals: u32,
+    /// Reveals made since probation started
+    pub completed_reveals: u32,
+    /// Score recovered per reveal (basis points)
+    pub recovery_bps: u16,
+    /// Recovery never lifts the score above this
+    pub max_score: u16,
+    /// Value at risk earning full recovery (lamports; 0 = unweighted)
+    pub reference_lamports: u64,
+}
+
+/// A verifier's objection to a recorded outcome
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OutcomeDispute {
+    /// Verifier that raised it
+    pub verifier: Pubkey,
+    /// Hash of the verifier's off-chain evidence
+    pub evidence_hash: [u8; 32],
+    /// When it was raised
+    pub disputed_at: i64,
+}
+
+impl Probation {
+    /// Score recovered by revealing a commitment with declared `risk`.
+    pub fn recovery_for(&self, risk: Option<RiskDisclosure>) -> u16 {
+        if self.reference_lamports == 0 {
+            return self.recovery_bps;
+        }
+        let at_risk = risk.map_or(0, |r| r.affected_lamports).min(self.reference_lamports);
+        (self.recovery_bps as u128 * at_risk as u128 / self.reference_lamports as u128) as u16
+    }
+}
+
+/// Protocol-wide commitment category.
+///
+/// `action_type` stays free-form; the category gives dashboards a fixed
+/// taxonomy to aggregate on.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CommitmentCategory {
+    Financial,
+    Governance,
+    ContentModeration,
+    Research,
+    Infrastructure,
+    Other,
+}
+
+/// Off-chain operator verification recorded on a profile.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OperatorAttestation {
+    /// Attestor that issued the attestation
+    pub attestor: Pubkey,
+    /// Hash of (or pointer to) the attestation report
+    pub reference: [u8; 32],
+    /// When the attestation was recorded
+    pub attested_at: i64,
+}
+
+// ─── Instruction Contexts ──────────────────────────────────────────────────
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct RegisterAgent<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgentProfile::INIT_SPACE,
+        seeds = [b"agent", authority.key().as_ref()],
+        bump
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent_id: u64)]
+pub struct RegisterAgentWithId<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgentProfile::INIT_SPACE,
+        seeds = [b"agent", authority.key().as_ref(), &agent_id.to_le_bytes()],
+        bump
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterAgentsBatch<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAgentMetadata<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AgentMetadata::INIT_SPACE,
+        seeds = [b"metadata", agent_profile.key().as_ref()],
+        bump
+    )]
+    pub agent_metadata: Account<'info, AgentMetadata>,
+    
+    #[account(has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetAgentStatus<'info> {
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetIdempotentCommits<'info> {
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct OpenMetrics<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgentMetrics::INIT_SPACE,
+        seeds = [b"metrics", agent_profile.key().as_ref()],
+        bump
+    )]
+    pub metrics: Account<'info, AgentMetrics>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenSummary<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgentSummary::INIT_SPACE,
+        seeds = [b"summary", agent_profile.key().as_ref()],
+        bump
+    )]
+    pub summary: Account<'info, AgentSummary>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshSummary<'info> {
+    #[account(
+        mut,
+        seeds = [b"summary", agent_profile.key().as_ref()],
+        bump = summary.bump
+    )]
+    pub summary: Account<'info, AgentSummary>,
+    
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthorityTransfer<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AuthorityTransfer::INIT_SPACE,
+        seeds = [b"authority_transfer", agent_profile.key().as_ref()],
+        bump
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+    
+    #[account(has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        close = proposed_by,
+        seeds = [b"authority_transfer", agent_profile.key().as_ref()],
+        bump = authority_transfer.bump,
+        has_one = new_authority @ AxiomError::Unauthorized,
+        has_one = proposed_by
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+    
+    #[account(
+        mut,
+        constraint = agent_profile.authority == authority_transfer.proposed_by
+            @ AxiomError::Unauthorized
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    /// CHECK: only receives the offer's rent; checked against the offer
+    #[account(mut)]
+    pub proposed_by: UncheckedAccount<'info>,
+    
+    pub new_authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"authority_transfer", agent_profile.key().as_ref()],
+        bump = authority_transfer.bump
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+    
+    #[account(has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct SetDelegate<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Delegate::INIT_SPACE,
+        seeds = [b"delegate", agent_profile.key().as_ref(), delegate.as_ref()],
+        bump
+    )]
+    pub delegate_record: Account<'info, Delegate>,
+    
+    #[account(has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"delegate",
+            agent_profile.key().as_ref(),
+            delegate_record.delegate.as_ref()
+        ],
+        bump = delegate_record.bump
+    )]
+    pub delegate_record: Account<'info, Delegate>,
+    
+    #[account(has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SyncCommitmentAuthority<'info> {
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(address = commitment.agent @ AxiomError::AgentMismatch)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetSigningKey<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SigningKeyRecord::INIT_SPACE,
+        seeds = [
+            b"signing_key",
+            agent_profile.key().as_ref(),
+            &agent_profile.signing_key_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub signing_key_record: Account<'info, SigningKeyRecord>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// The record being retired; required once a key is registered
+    #[account(
+        mut,
+        seeds = [
+            b"signing_key",
+            agent_profile.key().as_ref(),
+            &agent_profile.signing_key_count.saturating_sub(1).to_le_bytes()
+        ],
+        bump = previous_key.bump
+    )]
+    pub previous_key: Option<Account<'info, SigningKeyRecord>>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CommitArgs)]
 pub struct CommitReasoning<'info> {
+    /// Already initialized only on a retry; see `commit_reasoning`
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ReasoningCommitment::INIT_SPACE,
+        seeds = [
+            b"commitment",
+            agent_profile.key().as_ref(),
+            &args.nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    /// Checked against `authority` in the handler, which may also be a
+    /// delegate
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ActivityBucket::INIT_SPACE,
+        seeds = [
+            b"activity",
+            agent_profile.key().as_ref(),
+            &activity_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub activity_bucket: Account<'info, ActivityBucket>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ActionTypeStats::INIT_SPACE,
+        seeds = [b"action_stats", agent_profile.key().as_ref(), args.action_type.as_bytes()],
+        bump
+    )]
+    pub action_stats: Account<'info, ActionTypeStats>,
+    
+    /// The profile's authority, or a delegate key signing for it
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// The agent's integrator; required if it has one
+    #[account(mut)]
+    pub integrator: Option<Account<'info, Integrator>>,
+    
+    /// CHECK: the integrator's `CompliancePolicy` address, required with
+    /// the integrator whether or not it set one; checked and loaded by
+    /// `compliance_policy`
+    pub compliance_policy: Option<UncheckedAccount<'info>>,
+    
+    /// The signer's `Delegate` record; required if it isn't the authority
+    pub delegate: Option<Account<'info, Delegate>>,
+    
+    /// Earlier commitment of the same agent this one follows from
+    #[account(
+        constraint = parent_commitment.agent == agent_profile.key() @ AxiomError::AgentMismatch
+    )]
+    pub parent_commitment: Option<Account<'info, ReasoningCommitment>>,
+    
+    /// Required while the protocol charges a commit fee
+    #[account(mut, seeds = [b"fee_treasury"], bump = fee_treasury.bump)]
+    pub fee_treasury: Option<Account<'info, FeeTreasury>>,
+}
+
+#[derive(Accounts)]
+#[instruction(commitment_hash: [u8; 32], details_hash: [u8; 32], nonce: u64)]
+pub struct CommitPrivate<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ReasoningCommitment::INIT_SPACE,
+        seeds = [
+            b"commitment",
+            agent_profile.key().as_ref(),
+            &nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ActivityBucket::INIT_SPACE,
+        seeds = [
+            b"activity",
+            agent_profile.key().as_ref(),
+            &activity_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub activity_bucket: Account<'info, ActivityBucket>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// The agent's integrator; required if it has one
+    #[account(mut)]
+    pub integrator: Option<Account<'info, Integrator>>,
+    
+    /// CHECK: the integrator's `CompliancePolicy` address, required with
+    /// the integrator whether or not it set one; checked and loaded by
+    /// `compliance_policy`
+    pub compliance_policy: Option<UncheckedAccount<'info>>,
+    
+    /// Required while the protocol charges a commit fee
+    #[account(mut, seeds = [b"fee_treasury"], bump = fee_treasury.bump)]
+    pub fee_treasury: Option<Account<'info, FeeTreasury>>,
+}
+
+#[derive(Accounts)]
+pub struct SetOutcomeReporter<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + OutcomeReporter::INIT_SPACE,
+        seeds = [b"outcome_reporter", agent_profile.key().as_ref()],
+        bump
+    )]
+    pub reporter_record: Account<'info, OutcomeReporter>,
+    
+    #[account(has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClearOutcomeReporter<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"outcome_reporter", agent_profile.key().as_ref()],
+        bump = reporter_record.bump
+    )]
+    pub reporter_record: Account<'info, OutcomeReporter>,
+    
+    #[account(has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct RecordOutcome<'info> {
+    #[account(
+        init,
+        payer = reporter,
+        space = 8 + Outcome::INIT_SPACE,
+        seeds = [b"outcome", commitment.key().as_ref()],
+        bump
+    )]
+    pub outcome: Account<'info, Outcome>,
+    
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    /// The commitment's authority, or the agent's outcome reporter
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// The agent's `OutcomeReporter`; required unless `reporter` is the
+    /// authority
+    #[account(seeds = [b"outcome_reporter", commitment.agent.as_ref()], bump = reporter_record.bump)]
+    pub reporter_record: Option<Account<'info, OutcomeReporter>>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [b"outcome", commitment.key().as_ref()],
+        bump = outcome.bump
+    )]
+    pub outcome: Account<'info, Outcome>,
+    
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    /// A member of the storage verifier committee
+    pub verifier: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [b"outcome", commitment.key().as_ref()],
+        bump = outcome.bump
+    )]
+    pub outcome: Account<'info, Outcome>,
+    
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut, address = commitment.agent @ AxiomError::AgentMismatch)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SplitCommitCosts<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CostSplit::INIT_SPACE,
+        seeds = [b"cost_split", commitment.key().as_ref()],
+        bump
+    )]
+    pub cost_split: Account<'info, CostSplit>,
+    
+    #[account(has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(commitment_hash: [u8; 32], metadata_hash: [u8; 32], confidence: u8, nonce: u64)]
+pub struct CommitEmbargoed<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ReasoningCommitment::INIT_SPACE,
+        seeds = [
+            b"commitment",
+            agent_profile.key().as_ref(),
+            &nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MetadataEmbargo::INIT_SPACE,
+        seeds = [b"embargo", commitment.key().as_ref()],
+        bump
+    )]
+    pub embargo: Account<'info, MetadataEmbargo>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ActivityBucket::INIT_SPACE,
+        seeds = [
+            b"activity",
+            agent_profile.key().as_ref(),
+            &activity_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub activity_bucket: Account<'info, ActivityBucket>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// The agent's integrator; required if it has one
+    #[account(mut)]
+    pub integrator: Option<Account<'info, Integrator>>,
+    
+    /// CHECK: the integrator's `CompliancePolicy` address, required with
+    /// the integrator whether or not it set one; checked and loaded by
+    /// `compliance_policy`
+    pub compliance_policy: Option<UncheckedAccount<'info>>,
+    
+    /// Required while the protocol charges a commit fee
+    #[account(mut, seeds = [b"fee_treasury"], bump = fee_treasury.bump)]
+    pub fee_treasury: Option<Account<'info, FeeTreasury>>,
+}
+
+#[derive(Accounts)]
+pub struct DiscloseMetadata<'info> {
+    #[account(
+        mut,
+        has_one = commitment,
+        seeds = [b"embargo", commitment.key().as_ref()],
+        bump = embargo.bump
+    )]
+    pub embargo: Account<'info, MetadataEmbargo>,
+    
+    #[account(mut, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(batch_id: u64)]
+pub struct OpenPresignedBatch<'info> {
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + PresignedBatch::INIT_SPACE,
+        seeds = [b"presigned", agent_profile.key().as_ref(), &batch_id.to_le_bytes()],
+        bump
+    )]
+    pub batch: Account<'info, PresignedBatch>,
+    
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// CHECK: the instructions sysvar, checked by address
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u8, nonce: u64)]
+pub struct CommitPresigned<'info> {
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + ReasoningCommitment::INIT_SPACE,
+        seeds = [
+            b"commitment",
+            agent_profile.key().as_ref(),
+            &nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(
+        mut,
+        has_one = submitter,
+        constraint = batch.agent == agent_profile.key() @ AxiomError::AgentMismatch
+    )]
+    pub batch: Account<'info, PresignedBatch>,
+    
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = 8 + ActivityBucket::INIT_SPACE,
+        seeds = [
+            b"activity",
+            agent_profile.key().as_ref(),
+            &activity_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub activity_bucket: Account<'info, ActivityBucket>,
+    
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// The agent's integrator; required if it has one
+    #[account(mut)]
+    pub integrator: Option<Account<'info, Integrator>>,
+    
+    /// CHECK: the integrator's `CompliancePolicy` address, required with
+    /// the integrator whether or not it set one; checked and loaded by
+    /// `compliance_policy`
+    pub compliance_policy: Option<UncheckedAccount<'info>>,
+    
+    /// Required while the protocol charges a commit fee
+    #[account(mut, seeds = [b"fee_treasury"], bump = fee_treasury.bump)]
+    pub fee_treasury: Option<Account<'info, FeeTreasury>>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePresignedBatch<'info> {
+    #[account(mut, close = submitter, has_one = submitter)]
+    pub batch: Account<'info, PresignedBatch>,
+    
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct RecordCounterfactuals<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Counterfactuals::INIT_SPACE,
+        seeds = [b"counterfactuals", commitment.key().as_ref()],
+        bump
+    )]
+    pub counterfactuals: Account<'info, Counterfactuals>,
+    
+    #[account(mut, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealCounterfactual<'info> {
+    #[account(
+        mut,
+        seeds = [b"counterfactuals", commitment.key().as_ref()],
+        bump = counterfactuals.bump
+    )]
+    pub counterfactuals: Account<'info, Counterfactuals>,
+    
+    #[account(has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct PublishDetails<'info> {
+    #[account(mut, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut, address = commitment.agent)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(commitment_hash: [u8; 32], confidence: u8, nonce: u64)]
+pub struct CommitFromTemplate<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ReasoningCommitment::INIT_SPACE,
+        seeds = [
+            b"commitment",
+            agent_profile.key().as_ref(),
+            &nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ActivityBucket::INIT_SPACE,
+        seeds = [
+            b"activity",
+            agent_profile.key().as_ref(),
+            &activity_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub activity_bucket: Account<'info, ActivityBucket>,
+    
+    #[account(
+        mut,
+        constraint = template.owner == authority.key() @ AxiomError::Unauthorized
+    )]
+    pub template: Account<'info, Template>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// The agent's integrator; required if it has one
+    #[account(mut)]
+    pub integrator: Option<Account<'info, Integrator>>,
+    
+    /// CHECK: the integrator's `CompliancePolicy` address, required with
+    /// the integrator whether or not it set one; checked and loaded by
+    /// `compliance_policy`
+    pub compliance_policy: Option<UncheckedAccount<'info>>,
+    
+    /// Required while the protocol charges a commit fee
+    #[account(mut, seeds = [b"fee_treasury"], bump = fee_treasury.bump)]
+    pub fee_treasury: Option<Account<'info, FeeTreasury>>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateTemplate<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Template::INIT_SPACE,
+        seeds = [b"template", owner.key().as_ref(), name.as_bytes()],
+        bump
+    )]
+    pub template: Account<'info, Template>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterIntegrator<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Integrator::INIT_SPACE,
+        seeds = [b"integrator", authority.key().as_ref()],
+        bump
+    )]
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetIntegratorPolicy<'info> {
+    #[account(mut)]
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// The signer's role, unless it is the integrator's authority
+    pub role: Option<Account<'info, IntegratorRole>>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCompliancePolicy<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CompliancePolicy::INIT_SPACE,
+        seeds = [b"compliance", integrator.key().as_ref()],
+        bump
+    )]
+    pub compliance_policy: Account<'info, CompliancePolicy>,
+    
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// The signer's role, unless it is the integrator's authority
+    pub role: Option<Account<'info, IntegratorRole>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClearCompliancePolicy<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"compliance", integrator.key().as_ref()],
+        bump = compliance_policy.bump
+    )]
+    pub compliance_policy: Account<'info, CompliancePolicy>,
+    
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// The signer's role, unless it is the integrator's authority
+    pub role: Option<Account<'info, IntegratorRole>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordRejection<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RejectionStats::INIT_SPACE,
+        seeds = [b"rejections", agent_profile.key().as_ref()],
+        bump
+    )]
+    pub rejection_stats: Account<'info, RejectionStats>,
+    
+    #[account(has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    /// The commitment whose reveal was refused, for `RevealDeadlinePassed`
+    pub commitment: Option<Account<'info, ReasoningCommitment>>,
+    
+    /// CHECK: the agent's integrator's `CompliancePolicy` address, for
+    /// `ActionTypeNotAllowed`; checked in the handler
+    pub compliance_policy: Option<UncheckedAccount<'info>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinIntegrator<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + IntegratorMember::INIT_SPACE,
+        seeds = [b"integrator_member", integrator.key().as_ref(), agent_profile.key().as_ref()],
+        bump
+    )]
+    pub member: Account<'info, IntegratorMember>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub integrator: Account<'info, Integrator>,
+    
+    /// The integrator's authority, or a key with a role admitting members
+    pub integrator_authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// `integrator_authority`'s role, unless it is the integrator's authority
+    pub role: Option<Account<'info, IntegratorRole>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + IntegratorRole::INIT_SPACE,
+        seeds = [b"role", integrator.key().as_ref(), member.as_ref()],
+        bump
+    )]
+    pub role: Account<'info, IntegratorRole>,
+    
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// The signer's role, unless it is the integrator's authority
+    pub granter_role: Option<Account<'info, IntegratorRole>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"role", integrator.key().as_ref(), role.member.as_ref()],
+        bump = role.bump
+    )]
+    pub role: Account<'info, IntegratorRole>,
+    
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// The signer's role, unless it is the integrator's authority
+    pub revoker_role: Option<Account<'info, IntegratorRole>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMemberFrozen<'info> {
+    #[account(
+        mut,
+        constraint = agent_profile.integrator == Some(integrator.key()) @ AxiomError::IntegratorMismatch
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// The signer's role, unless it is the integrator's authority
+    pub role: Option<Account<'info, IntegratorRole>>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMemberSigningKey<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SigningKeyRecord::INIT_SPACE,
+        seeds = [
+            b"signing_key",
+            agent_profile.key().as_ref(),
+            &agent_profile.signing_key_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub signing_key_record: Account<'info, SigningKeyRecord>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// The record being retired; required once a key is registered
+    #[account(
+        mut,
+        seeds = [
+            b"signing_key",
+            agent_profile.key().as_ref(),
+            &agent_profile.signing_key_count.saturating_sub(1).to_le_bytes()
+        ],
+        bump = previous_key.bump
+    )]
+    pub previous_key: Option<Account<'info, SigningKeyRecord>>,
+    
+    #[account(
+        mut,
+        constraint = agent_profile.integrator == Some(integrator.key()) @ AxiomError::IntegratorMismatch
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    pub integrator: Account<'info, Integrator>,
+    
+    /// The signer's role, unless it is the integrator's authority
+    pub role: Option<Account<'info, IntegratorRole>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveIntegrator<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"integrator_member", integrator.key().as_ref(), agent_profile.key().as_ref()],
+        bump = member.bump
+    )]
+    pub member: Account<'info, IntegratorMember>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTemplate<'info> {
+    #[account(mut, close = owner, has_one = owner @ AxiomError::Unauthorized)]
+    pub template: Account<'info, Template>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32], leaf_count: u32, action_type: String, category: CommitmentCategory, nonce: u64)]
+pub struct CommitReasoningBatch<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BatchCommitment::INIT_SPACE,
+        seeds = [
+            b"batch",
+            agent_profile.key().as_ref(),
+            &nonce.to_le_bytes()
+        ],
+        bump
+    )]
+    pub batch: Account<'info, BatchCommitment>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ActivityBucket::INIT_SPACE,
+        seeds = [
+            b"activity",
+            agent_profile.key().as_ref(),
+            &activity_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub activity_bucket: Account<'info, ActivityBucket>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused,
+        constraint = config.has_feature(FEATURE_COMPRESSION) @ AxiomError::FeatureDisabled
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// The agent's integrator; required if it has one
+    #[account(mut)]
+    pub integrator: Option<Account<'info, Integrator>>,
+    
+    /// CHECK: the integrator's `CompliancePolicy` address, required with
+    /// the integrator whether or not it set one; checked and loaded by
+    /// `compliance_policy`
+    pub compliance_policy: Option<UncheckedAccount<'info>>,
+    
+    /// Required while the protocol charges a commit fee
+    #[account(mut, seeds = [b"fee_treasury"], bump = fee_treasury.bump)]
+    pub fee_treasury: Option<Account<'info, FeeTreasury>>,
+}
+
+#[derive(Accounts)]
+pub struct RevealFromBatch<'info> {
+    #[account(mut)]
+    pub batch: Account<'info, BatchCommitment>,
+    
+    // The profile's authority rather than the batch's, which stays the
+    // one that committed it after an authority transfer
+    #[account(
+        mut,
+        address = batch.agent @ AxiomError::AgentMismatch,
+        has_one = authority
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartReveal<'info> {
+    /// Checked against `authority` in the handler, which may also be a
+    /// delegate
+    #[account(
+        mut,
+        constraint = commitment.agent == agent_profile.key() @ AxiomError::AgentMismatch
+    )]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ActivityBucket::INIT_SPACE,
+        seeds = [
+            b"activity",
+            agent_profile.key().as_ref(),
+            &activity_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub activity_bucket: Account<'info, ActivityBucket>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// The signer's `Delegate` record; required if it isn't the authority
+    pub delegate: Option<Account<'info, Delegate>>,
+    
+    /// Required once the agent has opened metrics
+    #[account(
+        mut,
+        seeds = [b"metrics", agent_profile.key().as_ref()],
+        bump = metrics.bump
+    )]
+    pub metrics: Option<Account<'info, AgentMetrics>>,
+    
+    /// Required once the agent has opened a summary
+    #[account(
+        mut,
+        seeds = [b"summary", agent_profile.key().as_ref()],
+        bump = summary.bump
+    )]
+    pub summary: Option<Account<'info, AgentSummary>>,
+    
+    /// The commitment's `ActionTypeStats`; required if it counts there
+    #[account(
+        mut,
+        seeds = [
+            b"action_stats",
+            agent_profile.key().as_ref(),
+            commitment.action_type.as_bytes()
+        ],
+        bump = action_stats.bump
+    )]
+    pub action_stats: Option<Account<'info, ActionTypeStats>>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeReveal<'info> {
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut, address = commitment.agent @ AxiomError::AgentMismatch)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// Required once the agent has opened a summary
+    #[account(
+        mut,
+        seeds = [b"summary", agent_profile.key().as_ref()],
+        bump = summary.bump
+    )]
+    pub summary: Option<Account<'info, AgentSummary>>,
+    
+    /// Required, with `authority`, when the reveal earns a fee rebate
+    #[account(mut, seeds = [b"fee_treasury"], bump = fee_treasury.bump)]
+    pub fee_treasury: Option<Account<'info, FeeTreasury>>,
+    
+    /// The commitment's authority, which receives any fee rebate
+    #[account(mut, address = commitment.authority @ AxiomError::Unauthorized)]
+    pub authority: Option<SystemAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireCommitment<'info> {
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut, address = commitment.agent @ AxiomError::AgentMismatch)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// Required once the agent has opened metrics
+    #[account(
+        mut,
+        seeds = [b"metrics", agent_profile.key().as_ref()],
+        bump = metrics.bump
+    )]
+    pub metrics: Option<Account<'info, AgentMetrics>>,
+    
+    /// Required once the agent has opened a summary
+    #[account(
+        mut,
+        seeds = [b"summary", agent_profile.key().as_ref()],
+        bump = summary.bump
+    )]
+    pub summary: Option<Account<'info, AgentSummary>>,
+    
+    /// The commitment's `ActionTypeStats`; required if it counts there
+    #[account(
+        mut,
+        seeds = [
+            b"action_stats",
+            agent_profile.key().as_ref(),
+            commitment.action_type.as_bytes()
+        ],
+        bump = action_stats.bump
+    )]
+    pub action_stats: Option<Account<'info, ActionTypeStats>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelCommitment<'info> {
+    #[account(mut, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut, address = commitment.agent @ AxiomError::AgentMismatch)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct RequestDeadlineExtension<'info> {
+    #[account(mut, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Required while extensions charge a fee
+    #[account(mut, seeds = [b"fee_treasury"], bump = fee_treasury.bump)]
+    pub fee_treasury: Option<Account<'info, FeeTreasury>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCommitment<'info> {
+    #[account(mut, close = authority, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut, address = commitment.agent @ AxiomError::AgentMismatch)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CommitmentHistory::INIT_SPACE,
+        seeds = [b"history", commitment.agent.as_ref()],
+        bump
+    )]
+    pub history: Account<'info, CommitmentHistory>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyChunk<'info> {
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct OpenEscrow<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ActionEscrow::INIT_SPACE,
+        seeds = [b"escrow", commitment.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, ActionEscrow>,
+    
+    #[account(mut, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused,
+        constraint = config.has_feature(FEATURE_ESCROW) @ AxiomError::FeatureDisabled
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseTranche<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow", commitment.key().as_ref()],
+        bump = escrow.bump,
+        has_one = counterparty
+    )]
+    pub escrow: Account<'info, ActionEscrow>,
+    
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    /// CHECK: the escrow's counterparty, enforced by `has_one`; only
+    /// receives lamports
+    #[account(mut)]
+    pub counterparty: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct CloseEscrow<'info> {
+    #[account(
+        mut,
+        close = depositor,
+        seeds = [b"escrow", commitment.key().as_ref()],
+        bump = escrow.bump,
+        has_one = depositor
+    )]
+    pub escrow: Account<'info, ActionEscrow>,
+    
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyTraceSignature<'info> {
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(address = commitment.agent @ AxiomError::AgentMismatch)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// A past signing key of the agent, when the trace was signed by one
+    #[account(
+        seeds = [
+            b"signing_key",
+            agent_profile.key().as_ref(),
+            &signing_key_record.index.to_le_bytes()
+        ],
+        bump = signing_key_record.bump
+    )]
+    pub signing_key_record: Option<Account<'info, SigningKeyRecord>>,
+    
+    /// CHECK: the instructions sysvar, checked by address
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AttestAvailability<'info> {
+    #[account(
+        init,
+        payer = verifier,
+        space = 8 + AvailabilityAttestation::INIT_SPACE,
+        seeds = [b"availability", commitment.key().as_ref(), verifier.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, AvailabilityAttestation>,
+    
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttestCommitment<'info> {
+    #[account(
+        init,
+        payer = verifier,
+        space = 8 + VerifierAttestation::INIT_SPACE,
+        seeds = [b"verifier_attestation", commitment.key().as_ref(), verifier.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, VerifierAttestation>,
+    
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestReview<'info> {
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + ReviewRequest::INIT_SPACE,
+        seeds = [b"review", commitment.key().as_ref(), requester.key().as_ref()],
+        bump
+    )]
+    pub review: Account<'info, ReviewRequest>,
+    
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReview<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut, has_one = commitment)]
+    pub review: Account<'info, ReviewRequest>,
+    
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitReview<'info> {
+    #[account(mut, close = requester, has_one = commitment, has_one = requester)]
+    pub review: Account<'info, ReviewRequest>,
+    
+    #[account(
+        init,
+        payer = reviewer,
+        space = 8 + VerifierAttestation::INIT_SPACE,
+        seeds = [b"verifier_attestation", commitment.key().as_ref(), reviewer.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, VerifierAttestation>,
+    
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    /// CHECK: only receives the request's rent; checked against the
+    /// request
+    #[account(mut)]
+    pub requester: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelReview<'info> {
+    #[account(mut, close = requester, has_one = requester)]
+    pub review: Account<'info, ReviewRequest>,
+    
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct LapseReview<'info> {
+    #[account(mut, close = requester, has_one = requester)]
+    pub review: Account<'info, ReviewRequest>,
+    
+    /// CHECK: only receives lamports; checked against the request
+    #[account(mut)]
+    pub requester: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + StakeVault::INIT_SPACE,
+        seeds = [b"stake_vault", agent_profile.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, StakeVault>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake_vault", agent_profile.key().as_ref()],
+        bump = stake_vault.bump
+    )]
+    pub stake_vault: Account<'info, StakeVault>,
+    
+    #[account(has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake_vault", agent_profile.key().as_ref()],
+        bump = stake_vault.bump
+    )]
+    pub stake_vault: Account<'info, StakeVault>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SlashMismatch<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut, has_one = agent)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(
+        has_one = commitment,
+        constraint = config.storage_verifiers.contains(&attestation.verifier)
+            @ AxiomError::NotStorageVerifier
+    )]
+    pub attestation: Account<'info, VerifierAttestation>,
+    
+    #[account(mut)]
+    pub agent: Account<'info, AgentProfile>,
+    
+    #[account(
+        mut,
+        seeds = [b"stake_vault", agent.key().as_ref()],
+        bump = stake_vault.bump
+    )]
+    pub stake_vault: Account<'info, StakeVault>,
+    
+    /// CHECK: only receives lamports; must be the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    
+    #[account(mut, has_one = commitment)]
+    pub bounty: Option<Account<'info, VerificationBounty>>,
+}
+
+impl SlashMismatch<'_> {
+    /// Check that `digest` proves the attested mismatch and slash.
+    fn slash(&mut self, digest: Option<[u8; 32]>) -> Result<()> {
+        let config = &self.config;
+        require!(config.slash_bps > 0, AxiomError::SlashingDisabled);
+        let commitment = &mut self.commitment;
+        require!(!commitment.slashed, AxiomError::AlreadySlashed);
+        require!(
+            digest == Some(self.attestation.content_hash)
+                && digest != Some(commitment.commitment_hash),
+            AxiomError::MismatchNotProven
+        );
+        slash_stake(
+            config,
+            commitment,
+            &mut self.agent,
+            &mut self.stake_vault,
+            &self.challenger,
+            &self.treasury,
+        )?;
+        pay_bounty(
+            self.bounty.as_ref(),
+            self.challenger.to_account_info(),
+            Clock::get()?.unix_timestamp,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct SlashMismatchStreamed<'info> {
+    pub slash: SlashMismatch<'info>,
+    
+    #[account(
+        mut,
+        constraint = buffer.verifier == slash.challenger.key() @ AxiomError::Unauthorized,
+        constraint = buffer.commitment == slash.commitment.key() @ AxiomError::Unauthorized
+    )]
+    pub buffer: Account<'info, ReasoningBuffer>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct OpenChallenge<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused,
+        constraint = config.has_feature(FEATURE_DISPUTES) @ AxiomError::FeatureDisabled
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        init,
+        payer = challenger,
+        space = 8 + Challenge::INIT_SPACE,
+        seeds = [b"challenge", commitment.key().as_ref(), challenger.key().as_ref()],
+        bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+    
+    #[account(mut, has_one = agent)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub agent: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveChallenge<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        close = challenger,
+        has_one = challenger,
+        has_one = agent,
+        has_one = commitment
+    )]
+    pub challenge: Account<'info, Challenge>,
+    
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub agent: Account<'info, AgentProfile>,
+    
+    #[account(
+        mut,
+        seeds = [b"stake_vault", agent.key().as_ref()],
+        bump = stake_vault.bump
+    )]
+    pub stake_vault: Option<Account<'info, StakeVault>>,
+    
+    /// CHECK: only receives lamports; checked against the challenge
+    #[account(mut)]
+    pub challenger: UncheckedAccount<'info>,
+    
+    /// CHECK: only receives lamports; must be the configured treasury
+    #[account(mut, address = config.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Required once the agent has opened metrics
+    #[account(
+        mut,
+        seeds = [b"metrics", agent.key().as_ref()],
+        bump = metrics.bump
+    )]
+    pub metrics: Option<Account<'info, AgentMetrics>>,
+    
+    /// Required once the agent has opened a summary
+    #[account(
+        mut,
+        seeds = [b"summary", agent.key().as_ref()],
+        bump = summary.bump
+    )]
+    pub summary: Option<Account<'info, AgentSummary>>,
+}
+
+#[derive(Accounts)]
+pub struct LapseChallenge<'info> {
+    #[account(
+        mut,
+        close = challenger,
+        has_one = challenger,
+        has_one = agent,
+        has_one = commitment
+    )]
+    pub challenge: Account<'info, Challenge>,
+    
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub agent: Account<'info, AgentProfile>,
+    
+    /// CHECK: only receives lamports; checked against the challenge
+    #[account(mut)]
+    pub challenger: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAction<'info> {
+    #[account(mut, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyRevealInline<'info> {
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut, has_one = commitment)]
+    pub bounty: Option<Account<'info, VerificationBounty>>,
+}
+
+#[derive(Accounts)]
+pub struct BeginVerification<'info> {
+    #[account(
+        init,
+        payer = verifier,
+        space = 8 + ReasoningBuffer::INIT_SPACE,
+        seeds = [b"reasoning_buffer", commitment.key().as_ref(), verifier.key().as_ref()],
+        bump
+    )]
+    pub buffer: Account<'info, ReasoningBuffer>,
+    
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AppendChunk<'info> {
+    #[account(mut, has_one = verifier @ AxiomError::Unauthorized)]
+    pub buffer: Account<'info, ReasoningBuffer>,
+    
+    pub verifier: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeVerification<'info> {
+    #[account(
+        mut,
+        close = verifier,
+        has_one = verifier @ AxiomError::Unauthorized,
+        has_one = commitment
+    )]
+    pub buffer: Account<'info, ReasoningBuffer>,
+    
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut, has_one = commitment)]
+    pub bounty: Option<Account<'info, VerificationBounty>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelVerification<'info> {
+    #[account(mut, close = verifier, has_one = verifier @ AxiomError::Unauthorized)]
+    pub buffer: Account<'info, ReasoningBuffer>,
+    
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct PostBounty<'info> {
+    #[account(
+        init,
+        payer = funder,
+        space = 8 + VerificationBounty::INIT_SPACE,
+        seeds = [b"bounty", commitment.key().as_ref(), funder.key().as_ref()],
+        bump
+    )]
+    pub bounty: Account<'info, VerificationBounty>,
+    
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimBounty<'info> {
+    #[account(mut, close = funder, has_one = funder)]
+    pub bounty: Account<'info, VerificationBounty>,
+    
+    #[account(mut)]
+    pub funder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LockCommitment<'info> {
+    #[account(mut, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetRevealRetention<'info> {
+    #[account(mut, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireReveal<'info> {
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct DeclareRedactions<'info> {
+    #[account(mut, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct AcknowledgeCommitment<'info> {
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    pub counterparty: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct Resequence<'info> {
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SnapshotEpoch<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + EpochSnapshot::INIT_SPACE,
+        seeds = [
+            b"epoch_snapshot",
+            agent_profile.key().as_ref(),
+            &Clock::get()?.epoch.to_le_bytes()
+        ],
+        bump
+    )]
+    pub snapshot: Account<'info, EpochSnapshot>,
+    
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecomputeScore<'info> {
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct GetFeatures<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct CompareAgents<'info> {
+    pub first: Account<'info, AgentProfile>,
+    pub second: Account<'info, AgentProfile>,
+}
+
+#[derive(Accounts)]
+pub struct React<'info> {
+    #[account(
+        init,
+        payer = reactor,
+        space = 8 + Reaction::INIT_SPACE,
+        seeds = [b"reaction", commitment.key().as_ref(), reactor.key().as_ref()],
+        bump
+    )]
+    pub reaction: Account<'info, Reaction>,
+    
+    #[account(mut)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    #[account(mut)]
+    pub reactor: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FollowAgent<'info> {
+    #[account(
+        init,
+        payer = wallet,
+        space = 8 + Follower::INIT_SPACE,
+        seeds = [b"follower", agent_profile.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub follower: Account<'info, Follower>,
+    
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnfollowAgent<'info> {
+    #[account(
+        mut,
+        close = wallet,
+        seeds = [b"follower", agent_profile.key().as_ref(), wallet.key().as_ref()],
+        bump = follower.bump
+    )]
+    pub follower: Account<'info, Follower>,
+    
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
     #[account(
         init,
-        payer = authority,
-        space = 8 + ReasoningCommitment::INIT_SPACE,
-        seeds = [
-            b"commitment",
-            agent_profile.key().as_ref(),
-            &nonce.to_le_bytes()
-        ],
+        payer = admin,
+        space = 8 + ProtocolConfig::INIT_SPACE,
+        seeds = [b"config"],
         bump
     )]
-    pub commitment: Account<'info, ReasoningCommitment>,
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Axiom>,
     
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ AxiomError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
     #[account(
         mut,
-        seeds = [b"agent", authority.key().as_ref()],
-        bump = agent_profile.bump,
-        has_one = authority
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AxiomError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenFeeTreasury<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ AxiomError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + FeeTreasury::INIT_SPACE,
+        seeds = [b"fee_treasury"],
+        bump
+    )]
+    pub fee_treasury: Account<'info, FeeTreasury>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCommitFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AxiomError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// Must exist before fees can be charged into it
+    #[account(seeds = [b"fee_treasury"], bump = fee_treasury.bump)]
+    pub fee_treasury: Account<'info, FeeTreasury>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ AxiomError::Unauthorized)]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut, seeds = [b"fee_treasury"], bump = fee_treasury.bump)]
+    pub fee_treasury: Account<'info, FeeTreasury>,
+    
+    pub admin: Signer<'info>,
+    
+    /// CHECK: only receives lamports; chosen by the admin
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimIntegratorFees<'info> {
+    #[account(mut, has_one = authority @ AxiomError::Unauthorized)]
+    pub integrator: Account<'info, Integrator>,
+    
+    #[account(mut, seeds = [b"fee_treasury"], bump = fee_treasury.bump)]
+    pub fee_treasury: Account<'info, FeeTreasury>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        constraint = signer.key() == config.guardian
+            || signer.key() == config.admin @ AxiomError::Unauthorized
+    )]
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnterProbation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(attestor: Pubkey)]
+pub struct RegisterAttestor<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AttestorRecord::INIT_SPACE,
+        seeds = [b"attestor", attestor.as_ref()],
+        bump
+    )]
+    pub attestor_record: Account<'info, AttestorRecord>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AxiomError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttestor<'info> {
+    #[account(
+        mut,
+        seeds = [b"attestor", attestor_record.attestor.as_ref()],
+        bump = attestor_record.bump
+    )]
+    pub attestor_record: Account<'info, AttestorRecord>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AxiomError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(source: Pubkey)]
+pub struct RegisterReputationSource<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ReputationSource::INIT_SPACE,
+        seeds = [b"reputation_source", source.as_ref()],
+        bump
+    )]
+    pub source_record: Account<'info, ReputationSource>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AxiomError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeReputationSource<'info> {
+    #[account(
+        mut,
+        seeds = [b"reputation_source", source_record.source.as_ref()],
+        bump = source_record.bump
+    )]
+    pub source_record: Account<'info, ReputationSource>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AxiomError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ImportReputation<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ReputationImport::INIT_SPACE,
+        seeds = [b"reputation_import", agent_profile.key().as_ref()],
+        bump
     )]
+    pub import: Account<'info, ReputationImport>,
+    
+    #[account(mut, has_one = authority)]
     pub agent_profile: Account<'info, AgentProfile>,
     
+    #[account(
+        seeds = [b"reputation_source", source_record.source.as_ref()],
+        bump = source_record.bump
+    )]
+    pub source_record: Account<'info, ReputationSource>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    /// CHECK: the instructions sysvar, checked by address
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttestOperator<'info> {
+    #[account(
+        mut,
+        seeds = [b"attestor", attestor.key().as_ref()],
+        bump = attestor_record.bump
+    )]
+    pub attestor_record: Account<'info, AttestorRecord>,
+    
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    pub attestor: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeOperatorAttestation<'info> {
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    pub attestor: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ AxiomError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct OverrideScore<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AxiomError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCommitment<'info> {
+    /// CHECK: a commitment in any earlier layout, so it can't be
+    /// deserialized yet; the handler checks it decodes once grown
+    #[account(mut, owner = crate::ID)]
+    pub commitment: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAgentProfile<'info> {
+    /// CHECK: a profile in any earlier layout, so it can't be
+    /// deserialized yet; the handler checks it decodes once grown
+    #[account(mut, owner = crate::ID)]
+    pub agent_profile: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
-#[derive(Accounts)]
-pub struct RevealReasoning<'info> {
-    #[account(
-        mut,
-        has_one = authority,
-        constraint = commitment.agent == agent_profile.key() @ AxiomError::AgentMismatch
-    )]
-    pub commitment: Account<'info, ReasoningCommitment>,
-    
-    #[account(
-        mut,
-        seeds = [b"agent", authority.key().as_ref()],
-        bump = agent_profile.bump,
-        has_one = authority
-    )]
-    pub agent_profile: Account<'info, AgentProfile>,
-    
-    pub authority: Signer<'info>,
+#[cfg(feature = "test-instructions")]
+#[derive(Accounts)]
+pub struct TestSetScore<'info> {
+    #[account(mut, has_one = authority)]
+    pub agent_profile: Account<'info, AgentProfile>,
+    
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, ProtocolConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[cfg(feature = "test-instructions")]
+#[derive(Accounts)]
+pub struct TestBackdateCommitment<'info> {
+    #[account(mut, has_one = authority)]
+    pub commitment: Account<'info, ReasoningCommitment>,
+    
+    pub authority: Signer<'info>,
+}
+
+// ─── Events ────────────────────────────────────────────────────────────────
+
+#[event]
+pub struct ReasoningCommitted {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub action_type: String,
+    pub category: CommitmentCategory,
+    pub confidence: u8,
+    pub simulation: bool,
+    pub parent_commitment: Option<Pubkey>,
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchCommitted {
+    pub agent: Pubkey,
+    pub batch: Pubkey,
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+    pub action_type: String,
+    pub category: CommitmentCategory,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchLeafRevealed {
+    pub agent: Pubkey,
+    pub batch: Pubkey,
+    pub index: u32,
+    pub reasoning_hash: [u8; 32],
+    pub reasoning_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CounterfactualsRecorded {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub plan_hashes: Vec<[u8; 32]>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CounterfactualRevealed {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub index: u8,
+    pub plan_hash: [u8; 32],
+    pub plan_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OutcomeRecorded {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub success: bool,
+    pub failure_reason: Option<FailureReason>,
+    pub result_hash: [u8; 32],
+    pub executed_at: i64,
+    pub reporter: Pubkey,
+    pub disputable_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OutcomeDisputed {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub verifier: Pubkey,
+    pub evidence_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OutcomeFinalized {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub success: bool,
+    pub counted: bool,
+    pub accountability_score: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitCostsSplit {
+    pub commitment: Pubkey,
+    pub payer: Pubkey,
+    pub cost: u64,
+    pub shares: Vec<CostShare>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataDisclosed {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub action_type: String,
+    pub tags: Vec<String>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PresignedBatchOpened {
+    pub agent: Pubkey,
+    pub batch: Pubkey,
+    pub submitter: Pubkey,
+    pub entries: u8,
+    pub valid_from: i64,
+    pub valid_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DetailsPublished {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub action_type: String,
+    pub category: CommitmentCategory,
+    pub confidence: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IntegratorRegistered {
+    pub integrator: Pubkey,
+    pub authority: Pubkey,
+    pub name: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IntegratorJoined {
+    pub integrator: Pubkey,
+    pub agent: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FrameworkAttested {
+    pub agent: Pubkey,
+    pub attestor: Pubkey,
+    pub version: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TraceVerifiedOnchain {
+    pub commitment: Pubkey,
+    pub verifier: Pubkey,
+    pub trace_len: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TemplateCreated {
+    pub owner: Pubkey,
+    pub template: Pubkey,
+    pub name: String,
+}
+
+#[event]
+pub struct ReasoningRevealed {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub reasoning_uri: String,
+    pub trace_len: u64,
+    pub chunk_count: u32,
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevealFinalized {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChunkVerified {
+    pub commitment: Pubkey,
+    pub index: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TraceSignatureVerified {
+    pub commitment: Pubkey,
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AvailabilityAttested {
+    pub commitment: Pubkey,
+    pub verifier: Pubkey,
+    pub checked_at: i64,
+    pub attestations: u8,
+    pub available_at: Option<i64>,
+}
+
+#[event]
+pub struct ActionFoundInTrace {
+    pub commitment: Pubkey,
+    pub action_signature: [u8; 64],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ActionClaimed {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub action_signature: [u8; 64],
+    pub action_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowOpened {
+    pub commitment: Pubkey,
+    pub counterparty: Pubkey,
+    pub amount: u64,
+    pub tranches: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TrancheReleased {
+    pub commitment: Pubkey,
+    pub counterparty: Pubkey,
+    pub index: u32,
+    pub amount: u64,
+    pub released: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowClosed {
+    pub commitment: Pubkey,
+    pub depositor: Pubkey,
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitmentClosed {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub commitment_hash: [u8; 32],
+    pub closed_commitments: u64,
+    pub accumulator: [u8; 32],
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitmentCancelled {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DeadlineExtended {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub previous_deadline: i64,
+    pub reveal_deadline: i64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitmentExpired {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub accountability_score: u16,
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitmentLocked {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevealExpired {
+    pub commitment: Pubkey,
+    pub epoch: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitmentAttested {
+    pub commitment: Pubkey,
+    pub verifier: Pubkey,
+    pub passed: bool,
+    pub passes: u32,
+    pub failures: u32,
+    pub checked_at: i64,
+}
+
+#[event]
+pub struct ReviewRequested {
+    pub review: Pubkey,
+    pub commitment: Pubkey,
+    pub requester: Pubkey,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReviewClaimed {
+    pub review: Pubkey,
+    pub commitment: Pubkey,
+    pub reviewer: Pubkey,
+    pub bond: u64,
+    pub deadline: i64,
+}
+
+#[event]
+pub struct ReviewCompleted {
+    pub review: Pubkey,
+    pub commitment: Pubkey,
+    pub reviewer: Pubkey,
+    pub passed: bool,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReviewCancelled {
+    pub review: Pubkey,
+    pub commitment: Pubkey,
+    pub requester: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReviewLapsed {
+    pub review: Pubkey,
+    pub commitment: Pubkey,
+    pub reviewer: Pubkey,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyPosted {
+    pub bounty: Pubkey,
+    pub commitment: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct BountyPaid {
+    pub bounty: Pubkey,
+    pub commitment: Pubkey,
+    pub verifier: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyReclaimed {
+    pub bounty: Pubkey,
+    pub commitment: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IntegratorFeesClaimed {
+    pub integrator: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevealRebated {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeChanged {
+    pub agent: Pubkey,
+    pub staked_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeSlashed {
+    pub agent: Pubkey,
+    pub commitment: Pubkey,
+    pub challenger: Pubkey,
+    pub to_challenger: u64,
+    pub to_treasury: u64,
+    pub staked_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChallengeOpened {
+    pub challenge: Pubkey,
+    pub commitment: Pubkey,
+    pub agent: Pubkey,
+    pub challenger: Pubkey,
+    pub reason: ChallengeReason,
+    pub bond: u64,
+    pub deadline: i64,
+}
+
+#[event]
+pub struct ChallengeResolved {
+    pub challenge: Pubkey,
+    pub commitment: Pubkey,
+    pub agent: Pubkey,
+    pub upheld: bool,
+    pub accountability_score: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChallengeLapsed {
+    pub challenge: Pubkey,
+    pub commitment: Pubkey,
+    pub agent: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedactionsDeclared {
+    pub commitment: Pubkey,
+    pub redacted_chunks: Vec<u32>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitmentAcknowledged {
+    pub commitment: Pubkey,
+    pub counterparty: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReactionAdded {
+    pub commitment: Pubkey,
+    pub reactor: Pubkey,
+    pub upvote: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentFollowed {
+    pub agent: Pubkey,
+    pub follower: Pubkey,
+    pub follower_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentUnfollowed {
+    pub agent: Pubkey,
+    pub follower: Pubkey,
+    pub follower_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentResequenced {
+    pub agent: Pubkey,
+    pub sequence: u64,
+    pub total_commitments: u64,
+    pub total_verified: u64,
+    pub accountability_score: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpochSnapshotTaken {
+    pub agent: Pubkey,
+    pub epoch: u64,
+    pub accountability_score: u16,
+    pub total_commitments: u64,
+    pub average_reveal_latency: Option<u64>,
+    pub timestamp: i64,
 }
 
-// ─── Events ────────────────────────────────────────────────────────────────
+#[event]
+pub struct ScoreRecomputed {
+    pub agent: Pubkey,
+    pub previous_score: u16,
+    pub accountability_score: u16,
+    pub timestamp: i64,
+}
 
 #[event]
-pub struct ReasoningCommitted {
+pub struct ScoreOverridden {
     pub agent: Pubkey,
-    pub commitment: Pubkey,
-    pub action_type: String,
-    pub confidence: u8,
+    pub admin: Pubkey,
+    pub previous_score: u16,
+    pub new_score: u16,
+    pub justification_hash: [u8; 32],
     pub timestamp: i64,
 }
 
 #[event]
-pub struct ReasoningRevealed {
+pub struct ProbationStarted {
     pub agent: Pubkey,
-    pub commitment: Pubkey,
-    pub reasoning_uri: String,
+    pub accountability_score: u16,
+    pub required_reveals: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProbationCompleted {
+    pub agent: Pubkey,
+    pub accountability_score: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReputationSourceRegistered {
+    pub source: Pubkey,
+    pub name: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReputationSourceRevoked {
+    pub source: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReputationImported {
+    pub agent: Pubkey,
+    pub source: Pubkey,
+    pub score: u16,
+    pub snapshot_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestorRegistered {
+    pub attestor: Pubkey,
+    pub name: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestorRevoked {
+    pub attestor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OperatorAttested {
+    pub agent: Pubkey,
+    pub attestor: Pubkey,
+    pub reference: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentStatusChanged {
+    pub agent: Pubkey,
+    pub active: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolPauseChanged {
+    pub paused: bool,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub integrator: Pubkey,
+    pub member: Pubkey,
+    pub role: OrgRole,
+    pub granted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub integrator: Pubkey,
+    pub member: Pubkey,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MemberFrozen {
+    pub integrator: Pubkey,
+    pub agent: Pubkey,
+    pub frozen: bool,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentMetadataUpdated {
+    pub agent: Pubkey,
+    pub metadata_uri: Option<String>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompliancePolicySet {
+    pub integrator: Pubkey,
+    pub allowed_action_types: Vec<String>,
+    pub min_confidence: u8,
+    pub require_reveal_deadline: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RejectionRecorded {
+    pub agent: Pubkey,
+    pub late_reveals: u64,
+    pub disallowed_action_types: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateSet {
+    pub agent: Pubkey,
+    pub delegate: Pubkey,
+    pub expires_at: Option<i64>,
+    pub action_types: Vec<String>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateRevoked {
+    pub agent: Pubkey,
+    pub delegate: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub agent: Pubkey,
+    pub authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentAuthorityTransferred {
+    pub agent: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SigningKeySet {
+    pub agent: Pubkey,
+    pub signing_key: Pubkey,
+    pub index: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OperatorAttestationRevoked {
+    pub agent: Pubkey,
+    pub attestor: Pubkey,
     pub timestamp: i64,
 }
 
+// ─── Helpers ───────────────────────────────────────────────────────────────
+
+/// The activity bucket (days since the Unix epoch) `timestamp` falls in.
+pub fn activity_day(timestamp: i64) -> i64 {
+    timestamp.div_euclid(SECONDS_PER_DAY)
+}
+
+/// Slash `slash_bps` of the agent's stake over `commitment`, paying the
+/// challenger's share to `challenger` and the rest to `treasury`.
+fn slash_stake<'info>(
+    config: &ProtocolConfig,
+    commitment: &mut Account<'info, ReasoningCommitment>,
+    profile: &mut Account<'info, AgentProfile>,
+    vault: &mut Account<'info, StakeVault>,
+    challenger: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+) -> Result<()> {
+    commitment.slashed = true;
+    
+    let staked = profile.staked_lamports;
+    let slashed = bps_of(staked, config.slash_bps);
+    let to_challenger = bps_of(slashed, config.slash_challenger_bps);
+    let to_treasury = slashed - to_challenger;
+    vault.sub_lamports(slashed)?;
+    challenger.add_lamports(to_challenger)?;
+    treasury.add_lamports(to_treasury)?;
+    
+    let remaining = staked - slashed;
+    profile.staked_lamports = remaining;
+    vault.pending_unstake = vault.pending_unstake.min(remaining);
+    
+    emit!(StakeSlashed {
+        agent: profile.key(),
+        commitment: commitment.key(),
+        challenger: challenger.key(),
+        to_challenger,
+        to_treasury,
+        staked_lamports: remaining,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Pay `bounty`, if one was passed, to `verifier`, closing it. An expired
+/// bounty is left for its funder to reclaim.
+fn pay_bounty<'info>(
+    bounty: Option<&Account<'info, VerificationBounty>>,
+    verifier: AccountInfo<'info>,
+    now: i64,
+) -> Result<()> {
+    let Some(bounty) = bounty else {
+        return Ok(());
+    };
+    require!(now < bounty.expires_at, AxiomError::BountyExpired);
+    
+    emit!(BountyPaid {
+        bounty: bounty.key(),
+        commitment: bounty.commitment,
+        verifier: verifier.key(),
+        amount: bounty.amount,
+        timestamp: now,
+    });
+    
+    bounty.close(verifier)
+}
+
+/// `bps` basis points of `lamports`, rounded down.
+pub fn bps_of(lamports: u64, bps: u16) -> u64 {
+    (lamports as u128 * bps as u128 / 10_000) as u64
+}
+
+/// Hash committed by `commit_private`:
+/// `sha256(action_type || category || confidence || salt)`, with the
+/// category as its variant index. The salt keeps the few plausible
+/// action types and confidences from being guessed off the hash.
+pub fn details_hash(
+    action_type: &str,
+    category: CommitmentCategory,
+    confidence: u8,
+    salt: &[u8; 32],
+) -> [u8; 32] {
+    solana_sha256_hasher::hashv(&[
+        action_type.as_bytes(),
+        &[category as u8, confidence],
+        salt,
+    ])
+    .to_bytes()
+}
+
+/// Hash committed by `commit_embargoed`:
+/// `sha256(len || action_type || (len || tag)* || salt)`, each length a
+/// single byte so the action type and tags can't be regrouped.
+pub fn metadata_hash(action_type: &str, tags: &[String], salt: &[u8; 32]) -> [u8; 32] {
+    let fields: Vec<&str> = std::iter::once(action_type)
+        .chain(tags.iter().map(String::as_str))
+        .collect();
+    let lengths: Vec<[u8; 1]> = fields.iter().map(|field| [field.len() as u8]).collect();
+    let mut parts: Vec<&[u8]> = Vec::with_capacity(2 * fields.len() + 1);
+    for (length, field) in lengths.iter().zip(&fields) {
+        parts.push(length);
+        parts.push(field.as_bytes());
+    }
+    parts.push(salt);
+    solana_sha256_hasher::hashv(&parts).to_bytes()
+}
+
+/// Message a reputation source signs for `import_reputation`:
+/// `"solprism:reputation" || agent || score (u16 LE) || snapshot_at
+/// (i64 LE)`.
+pub fn reputation_snapshot_message(agent: &Pubkey, score: u16, snapshot_at: i64) -> Vec<u8> {
+    [
+        b"solprism:reputation".as_ref(),
+        agent.as_ref(),
+        &score.to_le_bytes(),
+        &snapshot_at.to_le_bytes(),
+    ]
+    .concat()
+}
+
+/// Message an offline authority signs for `open_presigned_batch`:
+/// `PRESIGN_DOMAIN || agent || permit` (Borsh).
+pub fn presign_message(agent: &Pubkey, permit: &PresignPermit) -> Result<Vec<u8>> {
+    Ok([PRESIGN_DOMAIN, agent.as_ref(), &permit.try_to_vec()?].concat())
+}
+
+/// Signer and message of the Ed25519 program instruction directly
+/// before the current one, which the runtime has already verified.
+fn preceding_signature(instructions: &AccountInfo) -> Result<(Pubkey, Vec<u8>)> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, AxiomError::MissingSignatureVerification);
+    let verify = load_instruction_at_checked(current as usize - 1, instructions)?;
+    require!(
+        verify.program_id == solana_sdk_ids::ed25519_program::ID,
+        AxiomError::MissingSignatureVerification
+    );
+    let (signer, message) = ed25519::single_signature(&verify.data)
+        .ok_or(AxiomError::MissingSignatureVerification)?;
+    let signer = Pubkey::try_from(signer)
+        .map_err(|_| AxiomError::MissingSignatureVerification)?;
+    Ok((signer, message.to_vec()))
+}
+
+/// `bytes` in base58 (Bitcoin alphabet), as transaction signatures are
+/// written in traces.
+pub fn base58(bytes: &[u8]) -> Vec<u8> {
+    const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    // Little-endian base-58 digits of the big-endian input
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in bytes {
+        let mut carry = u32::from(byte);
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    std::iter::repeat_n(b'1', zeros)
+        .chain(digits.iter().rev().map(|&digit| ALPHABET[digit as usize]))
+        .collect()
+}
+
+/// What `commitment_hash` must equal for `trace` to match: its hash
+/// under `algorithm` (of `salt || trace` when salted), or its Merkle root
+/// when committed with `manifest`. `None` if the trace doesn't fit the
+/// manifest.
+pub fn trace_digest(
+    trace: &[u8],
+    manifest: Option<ChunkManifest>,
+    algorithm: HashAlgorithm,
+    salt: Option<[u8; 32]>,
+) -> Option<[u8; 32]> {
+    match manifest {
+        None => Some(match salt {
+            Some(salt) => algorithm.hashv(&[&salt, trace]),
+            None => algorithm.hashv(&[trace]),
+        }),
+        Some(manifest) => {
+            let chunk_count = trace.len().div_ceil(manifest.chunk_size as usize) as u32;
+            manifest
+                .matches(trace.len() as u64, chunk_count)
+                .then(|| merkle::root(trace, manifest.chunk_size as usize))
+        }
+    }
+}
+
+/// Fields of a new commitment, as passed to `commit_reasoning` or
+/// resolved from a template.
+struct NewCommitment {
+    commitment_hash: [u8; 32],
+    action_type: String,
+    category: CommitmentCategory,
+    confidence: u8,
+    confidence_source: ConfidenceSource,
+    nonce: u64,
+    chunk_manifest: Option<ChunkManifest>,
+    template: Option<Pubkey>,
+    counterparty: Option<Pubkey>,
+    risk: Option<RiskDisclosure>,
+    details_hash: Option<[u8; 32]>,
+    simulation: bool,
+    /// Absolute reveal deadline
+    reveal_deadline: Option<i64>,
+    /// Action type and tags hidden until `disclose_metadata`
+    embargoed: bool,
+    hash_algorithm: HashAlgorithm,
+    /// Earlier commitment of the same agent this one follows from
+    parent: Option<Pubkey>,
+}
+
+/// Update the `ActionTypeStats` a commitment counts towards, which must
+/// be passed if it does.
+fn record_action_stats(
+    commitment: &ReasoningCommitment,
+    stats: Option<&mut Account<ActionTypeStats>>,
+    update: impl FnOnce(&mut ActionTypeStats),
+) -> Result<()> {
+    if !commitment.in_action_stats {
+        return Ok(());
+    }
+    update(stats.ok_or(AxiomError::ActionStatsRequired)?);
+    Ok(())
+}
+
+/// Apply `update` to the agent's `AgentSummary` and bring its score up to
+/// date; the summary must be passed once the agent has opened one.
+fn record_summary(
+    profile: &AgentProfile,
+    summary: Option<&mut Account<AgentSummary>>,
+    now: i64,
+    update: impl FnOnce(&mut AgentSummary),
+) -> Result<()> {
+    let Some(summary) = summary else {
+        require!(!profile.has_summary, AxiomError::SummaryRequired);
+        return Ok(());
+    };
+    update(summary);
+    summary.sync(profile, now);
+    Ok(())
+}
+
+/// Feed a sample into the agent's `AgentMetrics`, which must be passed
+/// once the agent has opened one.
+fn record_metrics(
+    profile: &AgentProfile,
+    metrics: Option<&mut Account<AgentMetrics>>,
+    now: i64,
+    sample: impl FnOnce(&mut AgentMetrics),
+) -> Result<()> {
+    let Some(metrics) = metrics else {
+        require!(!profile.has_metrics, AxiomError::MetricsRequired);
+        return Ok(());
+    };
+    sample(metrics);
+    metrics.updated_at = now;
+    Ok(())
+}
+
+/// Hold a new commitment to the agent's integrator policy, if it has an
+/// integrator, and count it there, crediting the integrator its share of
+/// the commit fee. Agents outside any integrator must not pass one.
+fn count_for_integrator(
+    config: &ProtocolConfig,
+    integrator: Option<&mut Account<Integrator>>,
+    treasury: Option<&mut Account<FeeTreasury>>,
+    compliance: Option<&AccountInfo>,
+    profile: &AgentProfile,
+    new: &NewCommitment,
+) -> Result<()> {
+    require!(
+        integrator.as_ref().map(|i| i.key()) == profile.integrator,
+        AxiomError::IntegratorMismatch
+    );
+    let Some(integrator) = integrator else {
+        return Ok(());
+    };
+    let policy = integrator.policy;
+    require!(
+        new.counterparty.is_some() || !policy.require_counterparty,
+        AxiomError::IntegratorPolicyViolation
+    );
+    require!(
+        new.details_hash.is_none() || policy.allow_private,
+        AxiomError::IntegratorPolicyViolation
+    );
+    if let Some(compliance) = compliance_policy(&integrator.key(), compliance)? {
+        // Private commitments hide action type and confidence, embargoed
+        // ones the action type
+        let private = new.details_hash.is_some();
+        let confident = compliance.min_confidence == 0
+            || !private && new.confidence >= compliance.min_confidence;
+        require!(
+            compliance.allows_action_type(&new.action_type, private || new.embargoed)
+                && confident
+                && (new.reveal_deadline.is_some() || !compliance.require_reveal_deadline),
+            AxiomError::CompliancePolicyViolation
+        );
+    }
+    integrator.commitments = integrator.commitments.checked_add(1)
+        .ok_or(AxiomError::Overflow)?;
+    credit_integrator(config, integrator, treasury, 1)
+}
+
+/// Set aside `integrator_fee_share_bps` of the commit fee on `count`
+/// commitments for `integrator` to claim. The lamports stay in the
+/// treasury, which is required unless there is nothing to credit.
+fn credit_integrator(
+    config: &ProtocolConfig,
+    integrator: &mut Integrator,
+    treasury: Option<&mut Account<FeeTreasury>>,
+    count: u64,
+) -> Result<()> {
+    let fees = config.commit_fee.checked_mul(count)
+        .ok_or(AxiomError::Overflow)?;
+    let share = bps_of(fees, config.integrator_fee_share_bps);
+    if share == 0 {
+        return Ok(());
+    }
+    let treasury = treasury.ok_or(AxiomError::FeeTreasuryRequired)?;
+    treasury.integrator_credited = treasury.integrator_credited.checked_add(share)
+        .ok_or(AxiomError::Overflow)?;
+    integrator.claimable_fees = integrator.claimable_fees.checked_add(share)
+        .ok_or(AxiomError::Overflow)?;
+    Ok(())
+}
+
+/// Check that `signer` holds a live delegation from `agent` covering
+/// `action_type`, in place of the agent's authority.
+fn check_delegate(
+    delegate: Option<&Delegate>,
+    agent: Pubkey,
+    signer: Pubkey,
+    action_type: &str,
+    now: i64,
+) -> Result<()> {
+    let delegate = delegate.ok_or(AxiomError::Unauthorized)?;
+    require!(
+        delegate.agent == agent && delegate.delegate == signer,
+        AxiomError::Unauthorized
+    );
+    require!(delegate.allows(action_type, now), AxiomError::DelegateNotAllowed);
+    Ok(())
+}
+
+/// The integrator's compliance policy, if it set one. `account` must be
+/// at the policy's address even when it didn't, so members can't commit
+/// around a policy by leaving it out.
+fn compliance_policy(
+    integrator: &Pubkey,
+    account: Option<&AccountInfo>,
+) -> Result<Option<CompliancePolicy>> {
+    let account = account.ok_or(AxiomError::CompliancePolicyMismatch)?;
+    let (address, _) = Pubkey::find_program_address(
+        &[b"compliance", integrator.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(account.key(), address, AxiomError::CompliancePolicyMismatch);
+    if account.owner != &crate::ID || account.data_is_empty() {
+        return Ok(None);
+    }
+    let data = account.try_borrow_data()?;
+    Ok(Some(CompliancePolicy::try_deserialize(&mut &data[..])?))
+}
+
+/// Make `signing_key` the agent's document-signing key in a new
+/// `record`, retiring the `previous` one.
+fn rotate_signing_key(
+    profile: &mut Account<AgentProfile>,
+    previous: Option<&mut Account<SigningKeyRecord>>,
+    record: &mut Account<SigningKeyRecord>,
+    signing_key: Pubkey,
+    bump: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    match previous {
+        Some(previous) if profile.signing_key_count > 0 => previous.retired_at = Some(now),
+        None if profile.signing_key_count == 0 => {}
+        _ => return err!(AxiomError::PreviousSigningKeyMismatch),
+    }
+    
+    record.agent = profile.key();
+    record.key = signing_key;
+    record.index = profile.signing_key_count;
+    record.activated_at = now;
+    record.retired_at = None;
+    record.bump = bump;
+    
+    profile.signing_key = Some(signing_key);
+    profile.signing_key_count = profile.signing_key_count.checked_add(1)
+        .ok_or(AxiomError::Overflow)?;
+    
+    emit!(SigningKeySet {
+        agent: record.agent,
+        signing_key,
+        index: record.index,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+/// Check that `signer` may act for `integrator` with `permission`: it is
+/// the integrator's authority, or holds a role there that grants it.
+fn check_org_permission(
+    integrator: &Account<Integrator>,
+    signer: Pubkey,
+    role: Option<&IntegratorRole>,
+    permission: OrgPermission,
+) -> Result<()> {
+    if signer == integrator.authority {
+        return Ok(());
+    }
+    let role = role.ok_or(AxiomError::Unauthorized)?;
+    require!(
+        role.integrator == integrator.key() && role.member == signer,
+        AxiomError::Unauthorized
+    );
+    require!(role.role.permits(permission), AxiomError::RoleNotPermitted);
+    Ok(())
+}
+
+/// Validate and initialize a commitment, crediting the agent's profile
+/// and today's activity bucket.
+fn record_commitment(
+    commitment: &mut Account<ReasoningCommitment>,
+    profile: &mut Account<AgentProfile>,
+    bucket: &mut Account<ActivityBucket>,
+    authority: Pubkey,
+    commitment_bump: u8,
+    bucket_bump: u8,
+    new: NewCommitment,
+) -> Result<()> {
+    require!(profile.is_active(), AxiomError::AgentInactive);
+    require!(!profile.frozen, AxiomError::AgentFrozen);
+    require!(new.action_type.len() <= 32, AxiomError::ActionTypeTooLong);
+    require!(new.confidence <= 100, AxiomError::InvalidConfidence);
+    require!(
+        new.chunk_manifest.is_none() || new.hash_algorithm == HashAlgorithm::Sha256,
+        AxiomError::UnsupportedHashAlgorithm
+    );
+    if let Some(manifest) = new.chunk_manifest {
+        require!(
+            manifest.chunk_size > 0 && manifest.chunk_count > 0,
+            AxiomError::InvalidChunkManifest
+        );
+    }
+    
+    let agent_key = profile.key();
+    let commitment_key = commitment.key();
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    
+    commitment.agent = agent_key;
+    commitment.authority = authority;
+    commitment.commitment_hash = new.commitment_hash;
+    commitment.action_type = new.action_type.clone();
+    commitment.category = new.category;
+    commitment.confidence = new.confidence;
+    commitment.timestamp = now;
+    commitment.status = CommitmentStatus::Committed;
+    commitment.revealed_at = 0;
+    commitment.reasoning_uri = String::new();
+    commitment.nonce = new.nonce;
+    commitment.trace_len = 0;
+    commitment.chunk_count = 0;
+    commitment.chunk_manifest = new.chunk_manifest;
+    commitment.trace_signer = None;
+    commitment.availability_attestations = 0;
+    commitment.available_at = None;
+    commitment.template = new.template;
+    commitment.counterparty = new.counterparty;
+    commitment.acknowledged_at = None;
+    commitment.details_hash = new.details_hash;
+    commitment.details_published_at = None;
+    commitment.risk = new.risk;
+    commitment.slot = clock.slot;
+    commitment.action_signature = None;
+    commitment.action_slot = None;
+    commitment.salt = None;
+    commitment.parent = new.parent;
+    commitment.bump = commitment_bump;
+    commitment.confidence_source = new.confidence_source;
+    commitment.verified_onchain = false;
+    commitment.locked = false;
+    commitment.uri_expiry_epoch = None;
+    commitment.verifier_confirmations = 0;
+    commitment.verifier_refutations = 0;
+    commitment.redacted_chunks = 0;
+    commitment.simulation = new.simulation;
+    commitment.slashed = false;
+    commitment.reveal_deadline = new.reveal_deadline;
+    commitment.expired = false;
+    commitment.cancelled = false;
+    commitment.open_challenges = 0;
+    commitment.sequence = 0;
+    commitment.escrowed = false;
+    commitment.counterfactuals = 0;
+    commitment.embargoed = new.embargoed;
+    commitment.hash_algorithm = new.hash_algorithm;
+    commitment.in_action_stats = false;
+    commitment.fee_paid = 0;
+    commitment.deadline_extended = false;
+    commitment.action_in_trace = false;
+    commitment.reserved = [0; COMMITMENT_RESERVED];
+    
+    if new.simulation {
+        let simulated = profile.simulated_commitments.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        profile.simulated_commitments = simulated;
+    } else {
+        // Increment agent's commitment count
+        profile.total_commitments = profile.total_commitments.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        // A private commitment's confidence counts once it is published
+        if new.details_hash.is_none() {
+            profile.record_confidence(new.confidence);
+        }
+    }
+    
+    bucket.open(agent_key, activity_day(now), bucket_bump);
+    bucket.commits = bucket.commits.checked_add(1)
+        .ok_or(AxiomError::Overflow)?;
+    if bucket.first_commitment == Pubkey::default() {
+        bucket.first_commitment = commitment_key;
+    }
+    bucket.last_commitment = commitment_key;
+    
+    let sequence = profile.next_event_sequence()?;
+    commitment.sequence = sequence;
+    
+    emit!(ReasoningCommitted {
+        agent: agent_key,
+        commitment: commitment_key,
+        action_type: new.action_type,
+        category: new.category,
+        confidence: new.confidence,
+        simulation: new.simulation,
+        parent_commitment: new.parent,
+        sequence,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+/// Charge the protocol's `commit_fee` for `count` new commitments:
+/// `payer` pays it into the `FeeTreasury`, which is required while a fee
+/// is set. Returns the fee per commitment.
+fn charge_commit_fee<'info>(
+    config: &ProtocolConfig,
+    treasury: Option<&mut Account<'info, FeeTreasury>>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    count: u64,
+) -> Result<u64> {
+    let fee = config.commit_fee.checked_mul(count)
+        .ok_or(AxiomError::Overflow)?;
+    collect_fee(treasury, payer, system_program, fee)?;
+    Ok(config.commit_fee)
+}
+
+/// Grow `account` to `space` bytes, zero-filling the new tail, with
+/// `payer` topping up its rent. Accounts already that large are left
+/// alone.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+    let shortfall = Rent::get()?.minimum_balance(space).saturating_sub(account.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    account.resize(space)?;
+    Ok(())
+}
+
+/// Create the program account at the PDA `account` (derived from
+/// `seeds`) with `space` bytes, `payer` funding its rent. As with
+/// Anchor's `init`, an address someone already sent lamports to, which
+/// `create_account` refuses, is topped up, allocated and assigned
+/// instead, so a stray transfer can't block the address.
+fn create_pda<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let balance = account.lamports();
+    if balance == 0 {
+        return anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                anchor_lang::system_program::CreateAccount {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+                &[seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+    let shortfall = rent.saturating_sub(balance);
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    anchor_lang::system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    anchor_lang::system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            &[seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+/// Have `payer` pay `fee` lamports into the `FeeTreasury`, which is
+/// required unless the fee is 0.
+fn collect_fee<'info>(
+    treasury: Option<&mut Account<'info, FeeTreasury>>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    fee: u64,
+) -> Result<()> {
+    if fee == 0 {
+        return Ok(());
+    }
+    let treasury = treasury.ok_or(AxiomError::FeeTreasuryRequired)?;
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: payer.to_account_info(),
+                to: treasury.to_account_info(),
+            },
+        ),
+        fee,
+    )?;
+    treasury.collected = treasury.collected.checked_add(fee)
+        .ok_or(AxiomError::Overflow)?;
+    Ok(())
+}
+
+/// Check a verifier's attestation of a reveal, add it to the
+/// commitment's tallies, and record it in `attestation`.
+fn record_attestation(
+    commitment: &mut Account<ReasoningCommitment>,
+    attestation: &mut Account<VerifierAttestation>,
+    verifier: Pubkey,
+    passed: bool,
+    content_hash: [u8; 32],
+    checked_at: i64,
+    bump: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        commitment.status != CommitmentStatus::Committed,
+        AxiomError::NotRevealed
+    );
+    require!(
+        commitment.revealed_at <= checked_at && checked_at <= now,
+        AxiomError::InvalidCheckTime
+    );
+    require!(
+        passed == (content_hash == commitment.commitment_hash),
+        AxiomError::InconsistentAttestation
+    );
+    let tally = if passed {
+        &mut commitment.verifier_confirmations
+    } else {
+        &mut commitment.verifier_refutations
+    };
+    *tally = tally.checked_add(1).ok_or(AxiomError::Overflow)?;
+    
+    attestation.commitment = commitment.key();
+    attestation.verifier = verifier;
+    attestation.passed = passed;
+    attestation.content_hash = content_hash;
+    attestation.checked_at = checked_at;
+    attestation.bump = bump;
+    
+    emit!(CommitmentAttested {
+        commitment: commitment.key(),
+        verifier,
+        passed,
+        passes: commitment.verifier_confirmations,
+        failures: commitment.verifier_refutations,
+        checked_at,
+    });
+    
+    Ok(())
+}
+
+/// Fail unless `chunk` is chunk `index` of the commitment's Merkle root.
+fn check_chunk(
+    commitment: &ReasoningCommitment,
+    index: u32,
+    chunk: &[u8],
+    proof: &[[u8; 32]],
+) -> Result<()> {
+    let manifest = commitment.chunk_manifest.ok_or(AxiomError::NotChunked)?;
+    require!(chunk.len() <= manifest.chunk_size as usize, AxiomError::InvalidChunk);
+    require!(
+        merkle::verify(
+            &commitment.commitment_hash,
+            &merkle::leaf(chunk),
+            index,
+            manifest.chunk_count,
+            proof,
+        ),
+        AxiomError::InvalidChunk
+    );
+    Ok(())
+}
+
+/// Move a `Revealing` commitment to `Revealed` and credit the agent.
+#[allow(clippy::too_many_arguments)]
+fn complete_reveal<'info>(
+    config: &ProtocolConfig,
+    commitment: &mut ReasoningCommitment,
+    profile: &mut AgentProfile,
+    fee_treasury: Option<&mut Account<'info, FeeTreasury>>,
+    authority: Option<&SystemAccount<'info>>,
+    agent_key: Pubkey,
+    commitment_key: Pubkey,
+    now: i64,
+) -> Result<()> {
+    commitment.status = CommitmentStatus::Revealed;
+    
+    // A reveal made by its deadline and verified onchain gets part of its
+    // commit fee back, as far as the treasury still holds it
+    let rebate = bps_of(commitment.fee_paid, config.reveal_rebate_bps);
+    let timely = commitment.reveal_deadline
+        .is_some_and(|deadline| commitment.revealed_at <= deadline);
+    if rebate > 0 && timely && commitment.verified_onchain {
+        let (Some(treasury), Some(authority)) = (fee_treasury, authority) else {
+            return err!(AxiomError::RebateAccountsRequired);
+        };
+        let amount = rebate.min(treasury.available()?);
+        treasury.rebated = treasury.rebated.checked_add(amount)
+            .ok_or(AxiomError::Overflow)?;
+        treasury.sub_lamports(amount)?;
+        authority.add_lamports(amount)?;
+        
+        emit!(RevealRebated {
+            agent: agent_key,
+            commitment: commitment_key,
+            recipient: authority.key(),
+            amount,
+            timestamp: now,
+        });
+    }
+    
+    if !commitment.simulation {
+        profile.total_verified = profile.total_verified.checked_add(1)
+            .ok_or(AxiomError::Overflow)?;
+        profile.recompute_score(config);
+    }
+    
+    // Each reveal during probation earns back part of the score,
+    // weighted by what the agent declared was at stake
+    if let Some(mut probation) = profile.probation.filter(|_| !commitment.simulation) {
+        probation.completed_reveals = probation.completed_reveals.saturating_add(1);
+        profile.adjust_score(config, i32::from(probation.recovery_for(commitment.risk)))?;
+        
+        if probation.completed_reveals >= probation.required_reveals {
+            profile.probation = None;
+            emit!(ProbationCompleted {
+                agent: agent_key,
+                accountability_score: profile.accountability_score,
+                timestamp: now,
+            });
+        } else {
+            profile.probation = Some(probation);
+        }
+    }
+    
+    let sequence = profile.next_event_sequence()?;
+    commitment.sequence = sequence;
+    
+    emit!(RevealFinalized {
+        agent: agent_key,
+        commitment: commitment_key,
+        sequence,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+fn validate_score_bounds(score_floor: u16, score_ceiling: u16) -> Result<()> {
+    require!(score_ceiling <= 10000, AxiomError::InvalidScore);
+    require!(score_floor <= score_ceiling, AxiomError::InvalidScoreBounds);
+    Ok(())
+}
+
 // ─── Errors ────────────────────────────────────────────────────────────────
 
 #[error_code]
@@ -275,4 +10860,275 @@ pub enum AxiomError {
     AgentMismatch,
     #[msg("Arithmetic overflow")]
     Overflow,
+    #[msg("Score must be between 0 and 10000 basis points")]
+    InvalidScore,
+    #[msg("Duration must not be negative")]
+    InvalidDuration,
+    #[msg("Signer is not authorized for this action")]
+    Unauthorized,
+    #[msg("Score floor must not exceed the score ceiling")]
+    InvalidScoreBounds,
+    #[msg("Score is outside the configured floor and ceiling")]
+    ScoreOutOfBounds,
+    #[msg("A non-zero justification hash is required")]
+    JustificationRequired,
+    #[msg("Probation terms are invalid")]
+    InvalidProbationTerms,
+    #[msg("Agent is already on probation")]
+    AlreadyOnProbation,
+    #[msg("Agent score is not below the probation threshold")]
+    ScoreAboveProbationThreshold,
+    #[msg("Agent operator is not attested by this attestor")]
+    NotAttested,
+    #[msg("Attestor has been revoked")]
+    AttestorInactive,
+    #[msg("Trace length and chunk count must be non-zero and consistent")]
+    InvalidTraceSize,
+    #[msg("Chunk size and chunk count must be non-zero")]
+    InvalidChunkManifest,
+    #[msg("Commitment was not made over chunks")]
+    NotChunked,
+    #[msg("Chunk does not match the committed Merkle root")]
+    InvalidChunk,
+    #[msg("Commitment is not awaiting reveal finalization")]
+    NotRevealing,
+    #[msg("Reveal grace period has not ended")]
+    GracePeriodActive,
+    #[msg("Commitment has not been revealed")]
+    NotRevealed,
+    #[msg("Expected a single inline Ed25519 signature check just before this instruction")]
+    MissingSignatureVerification,
+    #[msg("Signature is not the agent's over the commitment hash")]
+    TraceSignatureMismatch,
+    #[msg("Pass the current signing key record exactly when rotating")]
+    PreviousSigningKeyMismatch,
+    #[msg("Storage verifier committee or quorum is invalid")]
+    InvalidStorageQuorum,
+    #[msg("Signer is not a configured storage verifier")]
+    NotStorageVerifier,
+    #[msg("Check time must fall between the reveal and now")]
+    InvalidCheckTime,
+    #[msg("Template name or required fields are empty or too long")]
+    InvalidTemplate,
+    #[msg("Commitment has already been acknowledged")]
+    AlreadyAcknowledged,
+    #[msg("Commitment is not private")]
+    NotPrivate,
+    #[msg("Commitment details have already been published")]
+    DetailsAlreadyPublished,
+    #[msg("Details do not match the committed details hash")]
+    DetailsMismatch,
+    #[msg("An action has already been claimed for this commitment")]
+    ActionAlreadyClaimed,
+    #[msg("Action slot is not after the commitment")]
+    ActionPredatesCommitment,
+    #[msg("Action slot is in the future")]
+    InvalidActionSlot,
+    #[msg("Trace does not hash to the commitment hash")]
+    TraceHashMismatch,
+    #[msg("Commitment is locked")]
+    CommitmentLocked,
+    #[msg("Redacted chunks must be increasing, in range and non-empty")]
+    InvalidRedactions,
+    #[msg("Redactions have already been declared")]
+    RedactionsAlreadyDeclared,
+    #[msg("Stake amount is zero or exceeds the stake")]
+    InvalidStakeAmount,
+    #[msg("No unstake is pending")]
+    NoPendingUnstake,
+    #[msg("Unstake delay has not passed")]
+    UnstakeDelayActive,
+    #[msg("Slashing is disabled")]
+    SlashingDisabled,
+    #[msg("Slashing shares must be at most 10000 basis points")]
+    InvalidSlashingTerms,
+    #[msg("Commitment has already been slashed")]
+    AlreadySlashed,
+    #[msg("Content does not prove the attested hash mismatch")]
+    MismatchNotProven,
+    #[msg("The challenge deadline has passed")]
+    ChallengeDeadlinePassed,
+    #[msg("The challenge is still open for a ruling")]
+    ChallengePeriodActive,
+    #[msg("Council must be non-empty, without duplicates, with a threshold it can meet")]
+    InvalidCouncil,
+    #[msg("Not enough council members signed")]
+    CouncilQuorumNotMet,
+    #[msg("Reveal deadline must be in the future")]
+    InvalidRevealDeadline,
+    #[msg("The reveal deadline has passed")]
+    RevealDeadlinePassed,
+    #[msg("The reveal deadline has not passed yet")]
+    RevealDeadlineNotPassed,
+    #[msg("Commitment has no reveal deadline")]
+    NoRevealDeadline,
+    #[msg("Score weights must sum to 10000")]
+    InvalidScoreWeights,
+    #[msg("Commitment was cancelled")]
+    CommitmentCancelled,
+    #[msg("The reveal's challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("The reveal's challenge window is open or a challenge is pending")]
+    ChallengeWindowOpen,
+    #[msg("Commitment names no counterparty")]
+    NoCounterparty,
+    #[msg("A chunked commitment can escrow for at most 64 chunks")]
+    TooManyTranches,
+    #[msg("Escrow must be non-zero and at most the action value the commitment disclosed")]
+    EscrowExceedsActionValue,
+    #[msg("Tranche has already been released")]
+    TrancheReleased,
+    #[msg("Escrow still has tranches to release for a live commitment")]
+    EscrowActive,
+    #[msg("A batch covers between 1 and 1024 reasoning hashes")]
+    InvalidBatchSize,
+    #[msg("Reasoning hash does not match the batch's Merkle root")]
+    InvalidBatchLeaf,
+    #[msg("Metadata URI must be 1 to 256 characters")]
+    InvalidMetadataUri,
+    #[msg("Counterfactuals must be 1 to 4 distinct plan hashes, revealed by a valid index")]
+    InvalidCounterfactuals,
+    #[msg("Review fee must be greater than zero")]
+    InvalidReviewFee,
+    #[msg("Review request has already been claimed")]
+    ReviewAlreadyClaimed,
+    #[msg("Review deadline has passed, or the request isn't claimed")]
+    ReviewDeadlinePassed,
+    #[msg("Review is unclaimed or its deadline hasn't passed")]
+    ReviewPeriodActive,
+    #[msg("Authority is already the one given")]
+    InvalidAuthority,
+    #[msg("Delegates can be limited to at most 4 action types of 1 to 32 characters")]
+    InvalidDelegate,
+    #[msg("Delegate key has expired or isn't allowed this action type")]
+    DelegateNotAllowed,
+    #[msg("Compliance policies allow at most 8 action types of 1 to 32 characters")]
+    InvalidCompliancePolicy,
+    #[msg("Compliance policy account missing or not the integrator's")]
+    CompliancePolicyMismatch,
+    #[msg("Commitment violates the integrator's compliance policy")]
+    CompliancePolicyViolation,
+    #[msg("Agent is inactive and can't make new commitments")]
+    AgentInactive,
+    #[msg("Agent already has that status")]
+    AgentStatusUnchanged,
+    #[msg("Signer's role doesn't permit this action")]
+    RoleNotPermitted,
+    #[msg("Agent is frozen by its integrator")]
+    AgentFrozen,
+    #[msg("Commitments carry at most 8 tags of 1 to 32 characters")]
+    InvalidTags,
+    #[msg("Commitment's metadata isn't under embargo")]
+    NotEmbargoed,
+    #[msg("Action type and tags don't match the committed metadata hash")]
+    MetadataMismatch,
+    #[msg("Disclose the embargoed metadata before revealing")]
+    MetadataEmbargoed,
+    #[msg("URI limit must be between 1 and 256")]
+    InvalidUriLimit,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("That feature is disabled on this deployment")]
+    FeatureDisabled,
+    #[msg("Unknown feature bit")]
+    UnknownFeature,
+    #[msg("Commitment already exists at this nonce")]
+    CommitmentExists,
+    #[msg("Only SHA-256 commitments support this")]
+    UnsupportedHashAlgorithm,
+    #[msg("Cost splits need one signing funder per share, at most 4, totalling at most 100%")]
+    InvalidCostSplit,
+    #[msg("Outcome must be executed between the commit and now")]
+    InvalidOutcomeTime,
+    #[msg("Reputation source has been revoked")]
+    ReputationSourceInactive,
+    #[msg("Snapshot is in the future or not newer than the last import")]
+    InvalidSnapshot,
+    #[msg("Snapshot isn't signed by the source for this agent and score")]
+    SnapshotSignatureMismatch,
+    #[msg("The agent keeps metrics; pass its AgentMetrics account")]
+    MetricsRequired,
+    #[msg("Failed outcomes need a failure reason; successful ones take none")]
+    InvalidFailureReason,
+    #[msg("Pre-signed permit is malformed or not signed by the agent's authority")]
+    InvalidPresignPermit,
+    #[msg("Outside the pre-signed batch's validity window")]
+    PresignWindowClosed,
+    #[msg("Pre-signed entry was already committed")]
+    PresignedEntryRedeemed,
+    #[msg("The commitment counts towards action type stats; pass its ActionTypeStats account")]
+    ActionStatsRequired,
+    #[msg("The agent keeps a summary; pass its AgentSummary account")]
+    SummaryRequired,
+    #[msg("A bounty needs lamports, a future expiry and a commitment not yet verified or slashed")]
+    InvalidBounty,
+    #[msg("The bounty has expired")]
+    BountyExpired,
+    #[msg("The bounty has not expired yet")]
+    BountyActive,
+    #[msg("A commit fee is charged; pass the FeeTreasury account")]
+    FeeTreasuryRequired,
+    #[msg("Withdrawal must be positive and within the fees not yet withdrawn")]
+    InsufficientTreasury,
+    #[msg("Rebate plus integrator fee share must be at most 10000 basis points")]
+    InvalidRebate,
+    #[msg("A fee rebate is due; pass the FeeTreasury and the commitment's authority")]
+    RebateAccountsRequired,
+    #[msg("Extension must be positive and within the configured maximum")]
+    InvalidDeadlineExtension,
+    #[msg("The reveal deadline has already been extended")]
+    DeadlineAlreadyExtended,
+    #[msg("The outcome's dispute window has closed")]
+    OutcomeDisputeWindowClosed,
+    #[msg("The outcome's dispute window is still open")]
+    OutcomeDisputeWindowOpen,
+    #[msg("The outcome has already been disputed")]
+    OutcomeAlreadyDisputed,
+    #[msg("The outcome has already been finalized")]
+    OutcomeAlreadyFinalized,
+    #[msg("Integrator fee share plus rebate must be at most 10000 basis points")]
+    InvalidFeeShare,
+    #[msg("The integrator has no fees to claim")]
+    NothingToClaim,
+    #[msg("The agent has open challenges")]
+    ChallengesOpen,
+    #[msg("The rule behind the rejection would not reject it")]
+    NotRejected,
+    #[msg("At most 8 outcome verifiers")]
+    TooManyOutcomeVerifiers,
+    #[msg("Signer is not a configured outcome verifier")]
+    NotOutcomeVerifier,
+    #[msg("Commitment expired unrevealed")]
+    CommitmentExpired,
+    #[msg("Attestation result contradicts the content hash")]
+    InconsistentAttestation,
+    #[msg("Reveal retention is not enabled")]
+    RetentionDisabled,
+    #[msg("Retention is shorter than the configured minimum")]
+    RetentionTooShort,
+    #[msg("Reveal already has a retention period")]
+    RetentionAlreadySet,
+    #[msg("Locked commitments can't expire, and expiring ones can't be locked")]
+    RetentionConflict,
+    #[msg("Reveal has no expired retention period")]
+    RetentionNotExpired,
+    #[msg("Chunked commitments are verified chunk by chunk against their Merkle root")]
+    ChunkedCommitment,
+    #[msg("Framework version is empty or too long")]
+    InvalidFrameworkVersion,
+    #[msg("Value does not fit in the account's reserved space")]
+    ReservedOutOfBounds,
+    #[msg("Batch is empty, too large, or its accounts don't match its names")]
+    InvalidBatch,
+    #[msg("Integrator name is empty or too long")]
+    InvalidIntegrator,
+    #[msg("Agent already belongs to an integrator")]
+    AlreadyInIntegrator,
+    #[msg("Integrator account does not match the agent's integrator")]
+    IntegratorMismatch,
+    #[msg("Commitment violates the integrator's policy")]
+    IntegratorPolicyViolation,
 }
+
+#[cfg(test)]
+mod tests;
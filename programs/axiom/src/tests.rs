@@ -0,0 +1,487 @@
+use super::*;
+
+/// `data` padded with zeros to `space` bytes, as `grow_account` leaves
+/// an account.
+fn grown(mut data: Vec<u8>, space: usize) -> Vec<u8> {
+    assert!(data.len() <= space);
+    data.resize(space, 0);
+    data
+}
+
+/// A commitment as the original layout serialized it, up to its bump.
+fn original_commitment(revealed: bool) -> Vec<u8> {
+    let mut data = ReasoningCommitment::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&[1; 32]);
+    data.extend_from_slice(&[2; 32]);
+    data.extend_from_slice(&[3; 32]);
+    "trade".to_string().serialize(&mut data).unwrap();
+    data.push(90);
+    data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+    data.push(u8::from(revealed));
+    "ipfs://trace".to_string().serialize(&mut data).unwrap();
+    data.extend_from_slice(&5u64.to_le_bytes());
+    data.push(254);
+    data
+}
+
+#[test]
+fn original_commitments_decode_once_grown() {
+    for (revealed, status) in [
+        (false, CommitmentStatus::Committed),
+        (true, CommitmentStatus::Revealed),
+    ] {
+        let data = grown(
+            original_commitment(revealed),
+            8 + ReasoningCommitment::INIT_SPACE,
+        );
+        let commitment = ReasoningCommitment::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(commitment.agent, Pubkey::new_from_array([1; 32]));
+        assert_eq!(commitment.commitment_hash, [3; 32]);
+        assert_eq!(commitment.action_type, "trade");
+        assert_eq!(commitment.confidence, 90);
+        assert_eq!(commitment.status, status);
+        assert_eq!(commitment.reasoning_uri, "ipfs://trace");
+        assert_eq!(commitment.nonce, 5);
+        assert_eq!(commitment.bump, 254);
+        assert_eq!(commitment.category, CommitmentCategory::Financial);
+        assert_eq!(commitment.reveal_deadline, None);
+        assert_eq!(commitment.hash_algorithm, HashAlgorithm::Sha256);
+    }
+}
+
+#[test]
+fn original_commitments_do_not_decode_before_growing() {
+    let data = original_commitment(true);
+    assert!(ReasoningCommitment::try_deserialize(&mut &data[..]).is_err());
+}
+
+#[test]
+fn original_profiles_decode_once_grown() {
+    let mut data = AgentProfile::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&[1; 32]);
+    "agent".to_string().serialize(&mut data).unwrap();
+    data.extend_from_slice(&12u64.to_le_bytes());
+    data.extend_from_slice(&9u64.to_le_bytes());
+    data.extend_from_slice(&7500u16.to_le_bytes());
+    data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+    data.push(253);
+    assert!(AgentProfile::try_deserialize(&mut &data[..]).is_err());
+
+    let data = grown(data, 8 + AgentProfile::INIT_SPACE);
+    let profile = AgentProfile::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(profile.authority, Pubkey::new_from_array([1; 32]));
+    assert_eq!(profile.name, "agent");
+    assert_eq!(profile.total_commitments, 12);
+    assert_eq!(profile.total_verified, 9);
+    assert_eq!(profile.accountability_score, 7500);
+    assert_eq!(profile.created_at, 1_700_000_000);
+    assert_eq!(profile.bump, 253);
+    assert_eq!(profile.probation, None);
+    assert_eq!(profile.score_adjustment, 0);
+    assert!(profile.is_active());
+}
+
+/// 32 bytes from 64 hex digits.
+fn hex32(digits: &str) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (byte, pair) in out.iter_mut().zip(digits.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap();
+    }
+    out
+}
+
+/// FIPS 180-2 example messages and their SHA-256 digests.
+const SHA256_VECTORS: [(&[u8], &str); 4] = [
+    (b"", "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+    (b"abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+    (
+        b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+    ),
+    (
+        b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+        "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1",
+    ),
+];
+
+#[test]
+fn sha256_stream_matches_nist_vectors_whatever_the_split() {
+    for (message, digest) in SHA256_VECTORS {
+        for split in [1, 3, 55, 56, 63, 64, 65, 128] {
+            let mut stream = sha256_stream::Sha256Stream::default();
+            for chunk in message.chunks(split) {
+                stream.update(chunk);
+            }
+            assert_eq!(stream.finalize(), hex32(digest), "split {split}");
+        }
+    }
+}
+
+#[test]
+fn sha256_stream_matches_nist_million_a() {
+    let mut stream = sha256_stream::Sha256Stream::default();
+    let block = [b'a'; 1000];
+    for _ in 0..1000 {
+        stream.update(&block);
+    }
+    assert_eq!(
+        stream.finalize(),
+        hex32("cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0")
+    );
+}
+
+#[test]
+fn sha256_stream_matches_the_syscall_around_block_boundaries() {
+    let data: Vec<u8> = (0..=255u8).cycle().take(300).collect();
+    for len in [
+        0, 1, 54, 55, 56, 57, 63, 64, 65, 119, 120, 127, 128, 129, 300,
+    ] {
+        let message = &data[..len];
+        let mut stream = sha256_stream::Sha256Stream::default();
+        let (head, tail) = message.split_at(len / 3);
+        stream.update(head);
+        stream.update(&[]);
+        stream.update(tail);
+        assert_eq!(
+            stream.finalize(),
+            solana_sha256_hasher::hash(message).to_bytes(),
+            "len {len}"
+        );
+    }
+}
+
+fn leaves(count: u8) -> Vec<[u8; 32]> {
+    (0..count).map(|i| merkle::leaf(&[i])).collect()
+}
+
+/// Siblings from leaf `index` up to the root, skipping promoted nodes.
+fn merkle_proof(mut level: Vec<[u8; 32]>, mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => merkle::node(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        index /= 2;
+    }
+    proof
+}
+
+#[test]
+fn merkle_roots_promote_unpaired_nodes() {
+    let l = leaves(7);
+    assert_eq!(merkle::root_from_leaves(vec![]), merkle::leaf(&[]));
+    assert_eq!(merkle::root_from_leaves(l[..1].to_vec()), l[0]);
+    assert_eq!(
+        merkle::root_from_leaves(l[..3].to_vec()),
+        merkle::node(&merkle::node(&l[0], &l[1]), &l[2])
+    );
+    let left = merkle::node(&merkle::node(&l[0], &l[1]), &merkle::node(&l[2], &l[3]));
+    assert_eq!(
+        merkle::root_from_leaves(l[..5].to_vec()),
+        merkle::node(&left, &l[4])
+    );
+    let right = merkle::node(&merkle::node(&l[4], &l[5]), &l[6]);
+    assert_eq!(merkle::root_from_leaves(l), merkle::node(&left, &right));
+}
+
+#[test]
+fn merkle_root_of_data_hashes_its_chunks() {
+    let data = b"abcdefghij";
+    let chunks: Vec<[u8; 32]> = data.chunks(4).map(merkle::leaf).collect();
+    assert_eq!(merkle::root(data, 4), merkle::root_from_leaves(chunks));
+}
+
+#[test]
+fn merkle_verify_accepts_every_leaf_of_odd_trees() {
+    for count in [1u8, 2, 3, 5, 6, 7, 9] {
+        let l = leaves(count);
+        let root = merkle::root_from_leaves(l.clone());
+        for (index, leaf) in l.iter().enumerate() {
+            let proof = merkle_proof(l.clone(), index);
+            assert!(
+                merkle::verify(&root, leaf, index as u32, count.into(), &proof),
+                "leaf {index} of {count}"
+            );
+        }
+    }
+}
+
+#[test]
+fn merkle_verify_rejects_bad_proofs() {
+    let l = leaves(5);
+    let root = merkle::root_from_leaves(l.clone());
+    let proof = merkle_proof(l.clone(), 2);
+    assert!(merkle::verify(&root, &l[2], 2, 5, &proof));
+    // Wrong leaf, wrong position, wrong tree size
+    assert!(!merkle::verify(&root, &l[3], 2, 5, &proof));
+    assert!(!merkle::verify(&root, &l[2], 3, 5, &proof));
+    assert!(!merkle::verify(&root, &l[2], 2, 4, &proof));
+    // Missing, extra and tampered siblings
+    assert!(!merkle::verify(&root, &l[2], 2, 5, &proof[..1]));
+    let mut longer = proof.clone();
+    longer.push(l[0]);
+    assert!(!merkle::verify(&root, &l[2], 2, 5, &longer));
+    let mut tampered = proof;
+    tampered[0][0] ^= 1;
+    assert!(!merkle::verify(&root, &l[2], 2, 5, &tampered));
+    // Out of range
+    assert!(!merkle::verify(&root, &l[4], 5, 5, &[]));
+    assert!(!merkle::verify(&root, &l[0], 0, 0, &[]));
+}
+
+/// Ed25519 program data verifying one signature, everything inline.
+fn ed25519_data(pubkey: &[u8; 32], message: &[u8]) -> Vec<u8> {
+    let inline = ed25519::INLINE;
+    let fields = [16, inline, 80, inline, 112, message.len() as u16, inline];
+    let mut data = vec![1, 0];
+    for field in fields {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(&[5; 64]);
+    data.extend_from_slice(pubkey);
+    data.extend_from_slice(message);
+    data
+}
+
+/// `data` with offsets-record field `i` replaced.
+fn with_field(mut data: Vec<u8>, i: usize, value: u16) -> Vec<u8> {
+    data[2 + 2 * i..4 + 2 * i].copy_from_slice(&value.to_le_bytes());
+    data
+}
+
+#[test]
+fn ed25519_single_signature_reads_inline_data() {
+    let data = ed25519_data(&[8; 32], b"hello");
+    let (pubkey, message) = ed25519::single_signature(&data).unwrap();
+    assert_eq!(pubkey, &[8; 32]);
+    assert_eq!(message, b"hello");
+}
+
+#[test]
+fn ed25519_single_signature_rejects_malformed_data() {
+    let data = ed25519_data(&[8; 32], b"hello");
+    assert!(ed25519::single_signature(&[]).is_none());
+    assert!(ed25519::single_signature(&data[..15]).is_none());
+    // Signature count other than one
+    for count in [0, 2] {
+        let mut other = data.clone();
+        other[0] = count;
+        assert!(ed25519::single_signature(&other).is_none());
+    }
+    // Anything held in another instruction
+    for field in [1, 3, 6] {
+        assert!(ed25519::single_signature(&with_field(data.clone(), field, 0)).is_none());
+    }
+    // Offsets or sizes running past the data
+    assert!(ed25519::single_signature(&data[..data.len() - 1]).is_none());
+    assert!(ed25519::single_signature(&with_field(data.clone(), 0, 60)).is_none());
+    assert!(ed25519::single_signature(&with_field(data.clone(), 2, 90)).is_none());
+    assert!(ed25519::single_signature(&with_field(data.clone(), 5, u16::MAX)).is_none());
+    assert!(ed25519::single_signature(&with_field(data, 4, u16::MAX)).is_none());
+}
+
+#[test]
+fn trace_digest_hashes_under_each_algorithm() {
+    assert_eq!(
+        trace_digest(b"abc", None, HashAlgorithm::Sha256, None),
+        Some(hex32(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        ))
+    );
+    assert_eq!(
+        trace_digest(b"", None, HashAlgorithm::Keccak256, None),
+        Some(hex32(
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        ))
+    );
+    assert_eq!(
+        trace_digest(b"", None, HashAlgorithm::Blake3, None),
+        Some(hex32(
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        ))
+    );
+    // Salted: sha256([9; 32] || "abc")
+    assert_eq!(
+        trace_digest(b"abc", None, HashAlgorithm::Sha256, Some([9; 32])),
+        Some(hex32(
+            "5d79ea65a238a2dc4a69d1a337506ed34e36229a5bee9fb3f2f0eabd34efde38"
+        ))
+    );
+}
+
+#[test]
+fn trace_digest_takes_the_merkle_root_of_manifest_traces() {
+    let trace = b"abcdefghij";
+    let manifest = ChunkManifest {
+        chunk_size: 4,
+        chunk_count: 3,
+    };
+    assert_eq!(
+        trace_digest(trace, Some(manifest), HashAlgorithm::Sha256, None),
+        Some(merkle::root(trace, 4))
+    );
+    // Too short or too long for three 4-byte chunks
+    assert_eq!(
+        trace_digest(&trace[..8], Some(manifest), HashAlgorithm::Sha256, None),
+        None
+    );
+    assert_eq!(
+        trace_digest(
+            b"abcdefghijklm",
+            Some(manifest),
+            HashAlgorithm::Sha256,
+            None
+        ),
+        None
+    );
+}
+
+#[test]
+fn details_hash_matches_known_answer() {
+    // sha256("trade" || [0, 90] || [7; 32])
+    let hash = details_hash("trade", CommitmentCategory::Financial, 90, &[7; 32]);
+    assert_eq!(
+        hash,
+        hex32("cca54c0433cdb6e6b2a2d820a7c410e8deec1875ab81938d192d1f40469a5d42")
+    );
+    assert_ne!(
+        hash,
+        details_hash("trade", CommitmentCategory::Governance, 90, &[7; 32])
+    );
+    assert_ne!(
+        hash,
+        details_hash("trade", CommitmentCategory::Financial, 91, &[7; 32])
+    );
+    assert_ne!(
+        hash,
+        details_hash("trade", CommitmentCategory::Financial, 90, &[8; 32])
+    );
+}
+
+/// A commitment to `trace` as a Merkle root over `chunk_size`-byte chunks.
+fn chunked_commitment(trace: &[u8], chunk_size: u32) -> ReasoningCommitment {
+    let data = grown(
+        ReasoningCommitment::DISCRIMINATOR.to_vec(),
+        8 + ReasoningCommitment::INIT_SPACE,
+    );
+    let mut commitment = ReasoningCommitment::try_deserialize(&mut &data[..]).unwrap();
+    commitment.commitment_hash = merkle::root(trace, chunk_size as usize);
+    commitment.chunk_manifest = Some(ChunkManifest {
+        chunk_size,
+        chunk_count: trace.len().div_ceil(chunk_size as usize) as u32,
+    });
+    commitment
+}
+
+#[test]
+fn check_chunk_accepts_every_chunk_with_its_proof() {
+    let trace = b"the quick brown fox jumps";
+    let commitment = chunked_commitment(trace, 4);
+    let l: Vec<[u8; 32]> = trace.chunks(4).map(merkle::leaf).collect();
+    for (index, chunk) in trace.chunks(4).enumerate() {
+        let proof = merkle_proof(l.clone(), index);
+        assert!(check_chunk(&commitment, index as u32, chunk, &proof).is_ok());
+    }
+}
+
+#[test]
+fn check_chunk_rejects_bad_chunks_and_proofs() {
+    let trace = b"the quick brown fox jumps";
+    let commitment = chunked_commitment(trace, 4);
+    let l: Vec<[u8; 32]> = trace.chunks(4).map(merkle::leaf).collect();
+    let proof = merkle_proof(l.clone(), 2);
+    let invalid: Error = AxiomError::InvalidChunk.into();
+    // Corrupted chunk, chunk at the wrong index, tampered proof
+    assert_eq!(
+        check_chunk(&commitment, 2, b"QUIC", &proof).unwrap_err(),
+        invalid
+    );
+    assert_eq!(
+        check_chunk(&commitment, 3, b"uick", &proof).unwrap_err(),
+        invalid
+    );
+    let mut tampered = proof.clone();
+    tampered[1][31] ^= 1;
+    assert_eq!(
+        check_chunk(&commitment, 2, b"uick", &tampered).unwrap_err(),
+        invalid
+    );
+    // Longer than a chunk
+    assert_eq!(
+        check_chunk(&commitment, 2, b"uick ", &proof).unwrap_err(),
+        invalid
+    );
+    // Out of range: past the last chunk, and far past it
+    let last = merkle_proof(l, 6);
+    assert!(check_chunk(&commitment, 6, b"s", &last).is_ok());
+    assert_eq!(
+        check_chunk(&commitment, 7, b"s", &last).unwrap_err(),
+        invalid
+    );
+    assert_eq!(
+        check_chunk(&commitment, u32::MAX, b"s", &last).unwrap_err(),
+        invalid
+    );
+}
+
+#[test]
+fn check_chunk_needs_a_chunked_commitment() {
+    let mut commitment = chunked_commitment(b"abcd", 4);
+    commitment.chunk_manifest = None;
+    assert_eq!(
+        check_chunk(&commitment, 0, b"abcd", &[]).unwrap_err(),
+        AxiomError::NotChunked.into()
+    );
+    assert_ne!(
+        check_chunk(&commitment, 0, b"abcd", &[]).unwrap_err(),
+        AxiomError::InvalidChunk.into()
+    );
+}
+
+#[test]
+fn base58_matches_known_encodings() {
+    assert_eq!(base58(&[]), b"");
+    assert_eq!(base58(&[0]), b"1");
+    assert_eq!(base58(b"Hello World!"), b"2NEpo7TZRRrLZSi2U");
+    assert_eq!(base58(&[0, 0, 0x28, 0x7f, 0xb4, 0xcd]), b"11233QC4");
+    assert_eq!(
+        base58(b"The quick brown fox jumps over the lazy dog"),
+        b"7DdiPPYtxLjCD3wA1po2rvZHTDYjkZYiEtazrfiwJcwnKCizhGFhBGHeRdx"
+    );
+    // A signature's worth of 0xff
+    assert_eq!(
+        base58(&[0xff; 64]),
+        b"67rpwLCuS5DGA8KGZXKsVQ7dnPb9goRLoKfgGbLfQg9WoLUgNY77E2jT11fem3coV9nAkguBACzrU1iyZM4B8roQ"
+    );
+}
+
+#[test]
+fn fee_treasury_keeps_integrator_credits_out_of_available() {
+    let mut treasury = FeeTreasury {
+        collected: 10_000,
+        withdrawn: 2_000,
+        rebated: 1_000,
+        integrator_credited: 0,
+        bump: 255,
+    };
+    assert_eq!(treasury.available().unwrap(), 7_000);
+    // Credits stay reserved after the integrator claims them: the claim
+    // moves lamports out but the running total is never reduced
+    treasury.integrator_credited = 1_500;
+    assert_eq!(treasury.available().unwrap(), 5_500);
+    treasury.integrator_credited = 7_000;
+    assert_eq!(treasury.available().unwrap(), 0);
+    treasury.integrator_credited = 7_001;
+    assert_eq!(
+        treasury.available().unwrap_err(),
+        AxiomError::Overflow.into()
+    );
+}
@@ -7,7 +7,7 @@
  * @see https://github.com/STCisGOOD/autovault
  */
 
-import { SolprismClient, createReasoningTrace } from "../src";
+import { SolprismClient, createReasoningTrace, ReasoningTrace } from "../src";
 import { Keypair, Connection } from "@solana/web3.js";
 
 // ─── AutoVault Decision Cycle with SOLPRISM ─────────────────────────────
@@ -99,9 +99,10 @@ async function revealAfterExecution(
   client: SolprismClient,
   wallet: Keypair,
   commitmentAddress: string,
-  storageUri: string
+  storageUri: string,
+  trace: ReasoningTrace
 ) {
-  const reveal = await client.revealReasoning(wallet, commitmentAddress, storageUri);
+  const reveal = await client.revealReasoning(wallet, commitmentAddress, storageUri, trace);
   console.log(`[SOLPRISM] Reasoning revealed: ${reveal.signature}`);
   return reveal;
 }
@@ -141,5 +142,5 @@ async function autovaultDecisionCycle() {
   // SOLPRISM: Reveal reasoning AFTER execution
   // Store the trace wherever you want (IPFS, your API, GitHub, etc.)
   const storageUri = "https://autovault-delta.vercel.app/api/traces/" + result.commitmentAddress;
-  await revealAfterExecution(client, wallet, result.commitmentAddress, storageUri);
+  await revealAfterExecution(client, wallet, result.commitmentAddress, storageUri, trace);
 }
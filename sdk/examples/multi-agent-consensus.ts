@@ -103,9 +103,9 @@ async function revealPhase(
 ) {
   console.log("\n=== Phase 2: Reveal (verify independence) ===\n");
 
-  for (const { opinion, result } of commitments) {
+  for (const { opinion, trace, result } of commitments) {
     const uri = `https://solana-yield.vercel.app/api/consensus/${result.commitmentAddress}`;
-    await client.revealReasoning(opinion.wallet, result.commitmentAddress, uri);
+    await client.revealReasoning(opinion.wallet, result.commitmentAddress, uri, trace);
     console.log(`  ${opinion.agentName}: revealed — ${opinion.recommendation} (${opinion.confidence}%)`);
   }
 
@@ -96,7 +96,8 @@ async function main() {
   const reveal = await client.revealReasoning(
     AGENT_KEYPAIR,
     commitment.commitmentAddress,
-    reasoningUri
+    reasoningUri,
+    reasoning
   );
   console.log('Reasoning revealed:', reveal.signature);
 
@@ -10,13 +10,16 @@ import {
   PublicKey,
   Keypair,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Ed25519Program,
   TransactionInstruction,
   Transaction,
   sendAndConfirmTransaction,
 } from "@solana/web3.js";
-import { Program, AnchorProvider, Wallet, BN, Idl } from "@coral-xyz/anchor";
+import { Program, AnchorProvider, Wallet, BN, Idl, utils } from "@coral-xyz/anchor";
 
 import {
+  Cluster,
   ReasoningTrace,
   SolprismConfig,
   CommitResult,
@@ -24,23 +27,78 @@ import {
   VerifyResult,
   OnChainCommitment,
   OnChainAgentProfile,
+  CommitmentStatus,
 } from "./types";
-import { hashTrace, hashTraceHex, verifyHash } from "./hash";
+import {
+  hashTrace,
+  hashTraceHex,
+  verifyHash,
+  canonicalJson,
+  COMMITMENT_CATEGORIES,
+  CommitmentCategory,
+} from "./hash";
 import { createReasoningTrace } from "./schema";
 
 // ─── Constants ────────────────────────────────────────────────────────────
 
+/**
+ * Version of the program interface this SDK builds instructions for; the
+ * program reports its own through `get_features`.
+ */
+export const PROTOCOL_VERSION = 2;
+
 /** Default program ID (deployed on devnet) */
 export const SOLPRISM_PROGRAM_ID = new PublicKey(
   "CZcvoryaQNrtZ3qb3gC1h9opcYpzEP1D9Mu1RVwFQeBu"
 );
 
+/**
+ * Program ID per cluster. Mirrors the `declare_id!` per cluster feature in
+ * the Anchor program; clusters without a deployment are absent.
+ */
+export const SOLPRISM_PROGRAM_IDS: Partial<Record<Cluster, PublicKey>> = {
+  "mainnet-beta": SOLPRISM_PROGRAM_ID,
+  devnet: SOLPRISM_PROGRAM_ID,
+  localnet: SOLPRISM_PROGRAM_ID,
+};
+
+/** Public RPC endpoint per cluster */
+export const DEFAULT_RPC_URLS: Record<Cluster, string> = {
+  "mainnet-beta": "https://api.mainnet-beta.solana.com",
+  devnet: "https://api.devnet.solana.com",
+  testnet: "https://api.testnet.solana.com",
+  localnet: "http://127.0.0.1:8899",
+};
+
 /** Default Solana devnet RPC */
-const DEFAULT_RPC = "https://api.devnet.solana.com";
+const DEFAULT_RPC = DEFAULT_RPC_URLS.devnet;
+
+/**
+ * Resolve the SOLPRISM program ID for a cluster.
+ *
+ * @throws Error if SOLPRISM is not deployed on that cluster
+ */
+export function getProgramId(cluster: Cluster): PublicKey {
+  const id = SOLPRISM_PROGRAM_IDS[cluster];
+  if (!id) {
+    throw new Error(`SOLPRISM is not deployed on ${cluster}`);
+  }
+  return id;
+}
 
 /** PDA seed prefixes (must match the Anchor program) */
 const SEED_AGENT = Buffer.from("agent");
 const SEED_COMMITMENT = Buffer.from("commitment");
+const SEED_SIGNING_KEY = Buffer.from("signing_key");
+const SEED_CONFIG = Buffer.from("config");
+const SEED_ACTIVITY = Buffer.from("activity");
+const SEED_ACTION_STATS = Buffer.from("action_stats");
+const SEED_FEE_TREASURY = Buffer.from("fee_treasury");
+const SEED_METRICS = Buffer.from("metrics");
+const SEED_SUMMARY = Buffer.from("summary");
+
+/** Length of an activity bucket, matching the program's `activity_day` */
+const SECONDS_PER_DAY = 86_400;
 
 // ─── IDL (embedded for zero-dependency usage) ─────────────────────────────
 
@@ -51,9 +109,28 @@ const SEED_COMMITMENT = Buffer.from("commitment");
 const DISCRIMINATORS = {
   registerAgent: Buffer.from([135, 157, 66, 195, 2, 113, 175, 30]),
   commitReasoning: Buffer.from([163, 80, 25, 135, 94, 49, 218, 44]),
-  revealReasoning: Buffer.from([76, 215, 6, 241, 209, 207, 84, 96]),
+  startReveal: Buffer.from([53, 233, 172, 216, 107, 91, 161, 239]),
+  finalizeReveal: Buffer.from([50, 41, 16, 48, 114, 165, 131, 206]),
+  verifyTraceSignature: Buffer.from([0, 216, 70, 123, 94, 214, 158, 45]),
+  setSigningKey: Buffer.from([131, 231, 152, 76, 182, 145, 86, 167]),
+  acknowledgeCommitment: Buffer.from([144, 26, 153, 115, 97, 152, 149, 190]),
 } as const;
 
+/** `CommitmentStatus` variants in the program's declaration order */
+const COMMITMENT_STATUSES: readonly CommitmentStatus[] = [
+  "committed",
+  "revealed",
+  "revealing",
+];
+
+/**
+ * Offsets of ReasoningCommitment's fixed-size leading fields, from its
+ * layout: discriminator, `agent`, `authority`, `commitment_hash`.
+ */
+const COMMITMENT_AGENT_OFFSET = 8;
+const COMMITMENT_AUTHORITY_OFFSET = COMMITMENT_AGENT_OFFSET + 32;
+const COMMITMENT_HASH_OFFSET = COMMITMENT_AUTHORITY_OFFSET + 32;
+
 // Account discriminators for deserialization
 const ACCOUNT_DISCRIMINATORS = {
   AgentProfile: Buffer.from([60, 227, 42, 24, 0, 87, 86, 205]),
@@ -91,6 +168,97 @@ export function deriveCommitmentPDA(
   );
 }
 
+/**
+ * Derive the PDA recording an agent's `index`-th document-signing key.
+ */
+export function deriveSigningKeyPDA(
+  agentProfile: PublicKey,
+  index: number,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): [PublicKey, number] {
+  const indexBuf = Buffer.alloc(4);
+  indexBuf.writeUInt32LE(index);
+  return PublicKey.findProgramAddressSync(
+    [SEED_SIGNING_KEY, agentProfile.toBuffer(), indexBuf],
+    programId
+  );
+}
+
+/**
+ * Derive the protocol config PDA.
+ */
+export function deriveConfigPDA(
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([SEED_CONFIG], programId);
+}
+
+/**
+ * Derive the PDA of an agent's activity bucket for the day containing
+ * `timestamp` (Unix seconds).
+ */
+export function deriveActivityPDA(
+  agentProfile: PublicKey,
+  timestamp: number,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): [PublicKey, number] {
+  const dayBuf = Buffer.alloc(8);
+  dayBuf.writeBigInt64LE(BigInt(Math.floor(timestamp / SECONDS_PER_DAY)));
+  return PublicKey.findProgramAddressSync(
+    [SEED_ACTIVITY, agentProfile.toBuffer(), dayBuf],
+    programId
+  );
+}
+
+/**
+ * Derive the PDA of an agent's stats for one action type.
+ */
+export function deriveActionStatsPDA(
+  agentProfile: PublicKey,
+  actionType: string,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [SEED_ACTION_STATS, agentProfile.toBuffer(), Buffer.from(actionType, "utf-8")],
+    programId
+  );
+}
+
+/**
+ * Derive the PDA of an agent's metrics.
+ */
+export function deriveMetricsPDA(
+  agentProfile: PublicKey,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [SEED_METRICS, agentProfile.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive the PDA of an agent's summary.
+ */
+export function deriveSummaryPDA(
+  agentProfile: PublicKey,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [SEED_SUMMARY, agentProfile.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive the fee treasury PDA.
+ */
+export function deriveFeeTreasuryPDA(
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([SEED_FEE_TREASURY], programId);
+}
+
 // ─── Serialization Helpers ────────────────────────────────────────────────
 
 /** Encode a string as [u32 length][utf8 bytes] (Borsh format) */
@@ -109,11 +277,52 @@ function encodeU64(n: bigint | number): Buffer {
   return buf;
 }
 
+/** Encode an i64 as 8 bytes LE */
+function encodeI64(n: bigint | number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigInt64LE(BigInt(n));
+  return buf;
+}
+
+/** Encode a u32 as 4 bytes LE */
+function encodeU32(n: number): Buffer {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32LE(n);
+  return buf;
+}
+
 /** Encode a u8 */
 function encodeU8(n: number): Buffer {
   return Buffer.from([n]);
 }
 
+/** Encode an Option<T> (Borsh format) */
+function encodeOption<T>(value: T | undefined, encode: (v: T) => Buffer): Buffer {
+  return value === undefined
+    ? Buffer.from([0])
+    : Buffer.concat([Buffer.from([1]), encode(value)]);
+}
+
+/** Encode a `RevealDeadline` enum value */
+function encodeRevealDeadline(deadline: { at: number } | { after: number }): Buffer {
+  return "at" in deadline
+    ? Buffer.concat([encodeU8(0), encodeI64(deadline.at)])
+    : Buffer.concat([encodeU8(1), encodeI64(deadline.after)]);
+}
+
+/** Account meta for an optional account, `present` or not */
+function optionalAccount(
+  pubkey: PublicKey,
+  present: boolean,
+  isWritable: boolean,
+  programId: PublicKey
+): { pubkey: PublicKey; isSigner: boolean; isWritable: boolean } {
+  // Anchor reads the program ID in an optional account's slot as "none"
+  return present
+    ? { pubkey, isSigner: false, isWritable }
+    : { pubkey: programId, isSigner: false, isWritable: false };
+}
+
 // ─── Instruction Builders ─────────────────────────────────────────────────
 
 /**
@@ -135,6 +344,7 @@ export function buildRegisterAgentIx(
     keys: [
       { pubkey: agentProfile, isSigner: false, isWritable: true },
       { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: deriveConfigPDA(programId)[0], isSigner: false, isWritable: false },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
     programId,
@@ -142,8 +352,30 @@ export function buildRegisterAgentIx(
   });
 }
 
+/** Optional `commit_reasoning` arguments; the defaults match the old instruction */
+export interface CommitOptions {
+  /** Commitment category (default: "financial") */
+  category?: CommitmentCategory;
+  /** Party the action affects */
+  counterparty?: PublicKey;
+  /** Paper trade or backtest, kept out of the accountability stats */
+  simulation?: boolean;
+  /**
+   * When the reveal must start by: a Unix timestamp, or a number of
+   * seconds after committing
+   */
+  revealDeadline?: { at: number } | { after: number };
+}
+
 /**
  * Build a commit_reasoning instruction.
+ *
+ * Leaves out the integrator, delegate and parent commitment accounts, so
+ * it is for agents without an integrator committing as their authority.
+ *
+ * @param withFeeTreasury - Pass the fee treasury, required while the
+ *   protocol charges a commit fee
+ * @param now - Unix time used to pick the activity bucket (default: now)
  */
 export function buildCommitReasoningIx(
   authority: PublicKey,
@@ -151,58 +383,242 @@ export function buildCommitReasoningIx(
   actionType: string,
   confidence: number,
   nonce: bigint | number,
+  options: CommitOptions = {},
+  withFeeTreasury = false,
+  now: number = Math.floor(Date.now() / 1000),
   programId: PublicKey = SOLPRISM_PROGRAM_ID
 ): TransactionInstruction {
   const [agentProfile] = deriveAgentPDA(authority, programId);
   const [commitment] = deriveCommitmentPDA(agentProfile, nonce, programId);
+  const category = COMMITMENT_CATEGORIES.indexOf(options.category ?? "financial");
+  if (category < 0) {
+    throw new Error(`Unknown commitment category: ${options.category}`);
+  }
 
+  // CommitArgs, in field order
   const data = Buffer.concat([
     DISCRIMINATORS.commitReasoning,
-    Buffer.from(commitmentHash),     // [u8; 32]
-    encodeString(actionType),         // String
-    encodeU8(confidence),             // u8
-    encodeU64(nonce),                 // u64
+    Buffer.from(commitmentHash),          // commitment_hash: [u8; 32]
+    encodeString(actionType),             // action_type: String
+    encodeU8(confidence),                 // confidence: u8
+    encodeU64(nonce),                     // nonce: u64
+    encodeU8(category),                   // category: CommitmentCategory
+    encodeOption(undefined, encodeU8),    // chunk_manifest: None
+    encodeOption(options.counterparty, (key) => key.toBuffer()),
+    encodeOption(undefined, encodeU8),    // risk: None
+    encodeU8(0),                          // confidence_source: Unspecified
+    encodeU8(options.simulation ? 1 : 0), // simulation: bool
+    encodeOption(options.revealDeadline, encodeRevealDeadline),
+    encodeU8(0),                          // hash_algorithm: Sha256
   ]);
 
   return new TransactionInstruction({
     keys: [
       { pubkey: commitment, isSigner: false, isWritable: true },
       { pubkey: agentProfile, isSigner: false, isWritable: true },
+      { pubkey: deriveActivityPDA(agentProfile, now, programId)[0], isSigner: false, isWritable: true },
+      { pubkey: deriveActionStatsPDA(agentProfile, actionType, programId)[0], isSigner: false, isWritable: true },
       { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: deriveConfigPDA(programId)[0], isSigner: false, isWritable: false },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      optionalAccount(programId, false, false, programId),   // integrator
+      optionalAccount(programId, false, false, programId),   // compliance_policy
+      optionalAccount(programId, false, false, programId),   // delegate
+      optionalAccount(programId, false, false, programId),   // parent_commitment
+      optionalAccount(deriveFeeTreasuryPDA(programId)[0], withFeeTreasury, true, programId),
     ],
     programId,
     data,
   });
 }
 
+/** Accounts an agent opened that reveals must update */
+export interface RevealAccounts {
+  /** The agent has an `AgentMetrics` account */
+  metrics?: boolean;
+  /** The agent has an `AgentSummary` account */
+  summary?: boolean;
+  /** The commitment counts in its action type's stats */
+  actionStats?: boolean;
+}
+
 /**
- * Build a reveal_reasoning instruction.
+ * Build a start_reveal instruction, publishing where the trace lives.
+ * The reveal completes with `finalize_reveal` once the protocol's grace
+ * period has passed.
+ *
+ * @param traceLen - Byte length of the published trace
+ * @param now - Unix time used to pick the activity bucket (default: now)
  */
-export function buildRevealReasoningIx(
+export function buildStartRevealIx(
   authority: PublicKey,
   commitmentAddress: PublicKey,
+  actionType: string,
   reasoningUri: string,
+  traceLen: number,
+  accounts: RevealAccounts = {},
+  now: number = Math.floor(Date.now() / 1000),
   programId: PublicKey = SOLPRISM_PROGRAM_ID
 ): TransactionInstruction {
   const [agentProfile] = deriveAgentPDA(authority, programId);
+  const [metrics] = deriveMetricsPDA(agentProfile, programId);
+  const [summary] = deriveSummaryPDA(agentProfile, programId);
+  const [actionStats] = deriveActionStatsPDA(agentProfile, actionType, programId);
 
   const data = Buffer.concat([
-    DISCRIMINATORS.revealReasoning,
-    encodeString(reasoningUri),
+    DISCRIMINATORS.startReveal,
+    encodeString(reasoningUri),        // reasoning_uri: String
+    encodeU64(traceLen),               // trace_len: u64
+    encodeU32(1),                      // chunk_count: u32 (unchunked)
+    encodeOption(undefined, encodeU8), // salt: none
   ]);
 
   return new TransactionInstruction({
     keys: [
       { pubkey: commitmentAddress, isSigner: false, isWritable: true },
       { pubkey: agentProfile, isSigner: false, isWritable: true },
-      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: deriveActivityPDA(agentProfile, now, programId)[0], isSigner: false, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: deriveConfigPDA(programId)[0], isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      optionalAccount(programId, false, false, programId), // delegate
+      optionalAccount(metrics, accounts.metrics ?? false, true, programId),
+      optionalAccount(summary, accounts.summary ?? false, true, programId),
+      optionalAccount(actionStats, accounts.actionStats ?? false, true, programId),
     ],
     programId,
     data,
   });
 }
 
+/**
+ * Build a finalize_reveal instruction. Anyone can send it once the
+ * reveal's grace period has passed.
+ *
+ * @param withRebate - Pass the fee treasury and authority so a reveal
+ *   that earned a fee rebate is paid it
+ */
+export function buildFinalizeRevealIx(
+  authority: PublicKey,
+  commitmentAddress: PublicKey,
+  accounts: Pick<RevealAccounts, "summary"> = {},
+  withRebate = false,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): TransactionInstruction {
+  const [agentProfile] = deriveAgentPDA(authority, programId);
+  const [summary] = deriveSummaryPDA(agentProfile, programId);
+
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: commitmentAddress, isSigner: false, isWritable: true },
+      { pubkey: agentProfile, isSigner: false, isWritable: true },
+      { pubkey: deriveConfigPDA(programId)[0], isSigner: false, isWritable: false },
+      optionalAccount(summary, accounts.summary ?? false, true, programId),
+      optionalAccount(deriveFeeTreasuryPDA(programId)[0], withRebate, true, programId),
+      optionalAccount(authority, withRebate, true, programId),
+    ],
+    programId,
+    data: DISCRIMINATORS.finalizeReveal,
+  });
+}
+
+/**
+ * Build a set_signing_key instruction, registering `signingKey` as the
+ * agent's document-signing key (retiring the current one, if any).
+ *
+ * @param keyCount - The profile's current `signingKeyCount`
+ */
+export function buildSetSigningKeyIx(
+  authority: PublicKey,
+  signingKey: PublicKey,
+  keyCount: number,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): TransactionInstruction {
+  const [agentProfile] = deriveAgentPDA(authority, programId);
+  const [record] = deriveSigningKeyPDA(agentProfile, keyCount, programId);
+  // Anchor reads the program ID in an optional account's slot as "none"
+  const previous = keyCount > 0
+    ? deriveSigningKeyPDA(agentProfile, keyCount - 1, programId)[0]
+    : programId;
+
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: record, isSigner: false, isWritable: true },
+      { pubkey: previous, isSigner: false, isWritable: keyCount > 0 },
+      { pubkey: agentProfile, isSigner: false, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: Buffer.concat([DISCRIMINATORS.setSigningKey, signingKey.toBuffer()]),
+  });
+}
+
+/**
+ * Build an acknowledge_commitment instruction, signed by the party the
+ * agent named as the commitment's counterparty.
+ */
+export function buildAcknowledgeCommitmentIx(
+  counterparty: PublicKey,
+  commitmentAddress: PublicKey,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): TransactionInstruction {
+  return new TransactionInstruction({
+    keys: [
+      { pubkey: commitmentAddress, isSigner: false, isWritable: true },
+      { pubkey: counterparty, isSigner: true, isWritable: false },
+    ],
+    programId,
+    data: DISCRIMINATORS.acknowledgeCommitment,
+  });
+}
+
+/**
+ * Build the instruction pair that proves a signed trace's signature
+ * onchain: the Ed25519 program check, then verify_trace_signature.
+ * They must be sent in this order in one transaction; anyone can send it.
+ *
+ * @param authority - The agent's authority (identifies its profile)
+ * @param signingKeyIndex - For traces signed by a since-rotated signing
+ *   key: that key's index in the agent's key history
+ * @throws Error if the trace is unsigned
+ */
+export function buildVerifyTraceSignatureIxs(
+  trace: ReasoningTrace,
+  commitmentAddress: PublicKey,
+  authority: PublicKey,
+  signingKeyIndex?: number,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): [TransactionInstruction, TransactionInstruction] {
+  if (!trace.signature) {
+    throw new Error("Trace is not signed");
+  }
+  const signer = new PublicKey(trace.signature.publicKey);
+  const [agentProfile] = deriveAgentPDA(authority, programId);
+  const record = signingKeyIndex === undefined
+    ? programId
+    : deriveSigningKeyPDA(agentProfile, signingKeyIndex, programId)[0];
+
+  const check = Ed25519Program.createInstructionWithPublicKey({
+    publicKey: signer.toBytes(),
+    message: hashTrace(trace),
+    signature: utils.bytes.bs58.decode(trace.signature.signature),
+  });
+
+  const verify = new TransactionInstruction({
+    keys: [
+      { pubkey: commitmentAddress, isSigner: false, isWritable: true },
+      { pubkey: agentProfile, isSigner: false, isWritable: false },
+      { pubkey: record, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: DISCRIMINATORS.verifyTraceSignature,
+  });
+
+  return [check, verify];
+}
+
 // ─── Account Deserialization ──────────────────────────────────────────────
 
 function readString(buf: Buffer, offset: number): [string, number] {
@@ -260,7 +676,7 @@ export function deserializeAgentProfile(data: Buffer): OnChainAgentProfile {
  */
 export function deserializeCommitment(data: Buffer): OnChainCommitment {
   // Skip 8-byte discriminator
-  let offset = 8;
+  let offset = COMMITMENT_AGENT_OFFSET;
 
   // agent: Pubkey
   const agent = new PublicKey(data.slice(offset, offset + 32)).toBase58();
@@ -286,8 +702,8 @@ export function deserializeCommitment(data: Buffer): OnChainCommitment {
   const timestamp = Number(data.readBigInt64LE(offset));
   offset += 8;
 
-  // revealed: bool
-  const revealed = data[offset] === 1;
+  // status: CommitmentStatus (where the old `revealed: bool` was)
+  const status = COMMITMENT_STATUSES[data[offset]];
   offset += 1;
 
   // reasoning_uri: String
@@ -295,7 +711,7 @@ export function deserializeCommitment(data: Buffer): OnChainCommitment {
   offset = ruEnd;
 
   // nonce: u64
-  // const nonce = Number(data.readBigUInt64LE(offset));
+  const nonce = Number(data.readBigUInt64LE(offset));
   offset += 8;
 
   // bump: u8
@@ -307,8 +723,10 @@ export function deserializeCommitment(data: Buffer): OnChainCommitment {
     actionType,
     confidence,
     timestamp,
-    revealed,
+    status,
+    revealed: status === "revealed",
     reasoningUri: reasoningUri || null,
+    nonce,
     bump,
   };
 }
@@ -335,8 +753,9 @@ export function deserializeCommitment(data: Buffer): OnChainCommitment {
  * // Commit reasoning
  * const result = await client.commitReasoning(wallet, trace);
  *
- * // Reveal
- * await client.revealReasoning(wallet, result.commitmentAddress, 'ipfs://...');
+ * // Reveal, then finalize once the grace period has passed
+ * await client.revealReasoning(wallet, result.commitmentAddress, 'ipfs://...', trace);
+ * await client.finalizeReveal(wallet, result.commitmentAddress);
  *
  * // Verify
  * const verified = await client.verifyReasoning(result.commitmentAddress, trace);
@@ -365,6 +784,32 @@ export class SolprismClient {
       : SOLPRISM_PROGRAM_ID;
   }
 
+  /**
+   * Create a client for a named cluster, using its program ID and
+   * (unless overridden) its public RPC endpoint.
+   *
+   * @param cluster - Target cluster
+   * @param rpcUrl - Optional RPC endpoint override
+   */
+  static forCluster(cluster: Cluster, rpcUrl?: string): SolprismClient {
+    return new SolprismClient(
+      rpcUrl ?? DEFAULT_RPC_URLS[cluster],
+      getProgramId(cluster)
+    );
+  }
+
+  /**
+   * Create a client from a SolprismConfig. An explicit `programId` wins
+   * over the one implied by `cluster`.
+   */
+  static fromConfig(config: SolprismConfig): SolprismClient {
+    const cluster = config.cluster ?? "devnet";
+    return new SolprismClient(
+      config.rpcUrl ?? DEFAULT_RPC_URLS[cluster],
+      config.programId ?? getProgramId(cluster)
+    );
+  }
+
   // ─── Agent Registration ──────────────────────────────────────────────
 
   /**
@@ -396,12 +841,14 @@ export class SolprismClient {
    * @param wallet - The agent's keypair
    * @param trace - The reasoning trace to commit
    * @param nonceOverride - Optional: specify nonce (otherwise auto-incremented)
+   * @param options - Optional commitment arguments
    * @returns CommitResult with signature, address, and hash
    */
   async commitReasoning(
     wallet: Keypair,
     trace: ReasoningTrace,
-    nonceOverride?: number
+    nonceOverride?: number,
+    options: CommitOptions = {}
   ): Promise<CommitResult> {
     // Hash the trace
     const hash = hashTrace(trace);
@@ -425,6 +872,9 @@ export class SolprismClient {
       actionType,
       confidence,
       nonce,
+      options,
+      await this.accountExists(deriveFeeTreasuryPDA(this.programId)[0]),
+      undefined,
       this.programId
     );
 
@@ -457,27 +907,46 @@ export class SolprismClient {
   // ─── Reveal ──────────────────────────────────────────────────────────
 
   /**
-   * Reveal the full reasoning for a commitment.
+   * Start revealing the full reasoning for a commitment. The reveal
+   * counts once `finalizeReveal` is sent after the grace period.
    *
    * @param wallet - The agent's keypair
    * @param commitmentAddress - The commitment PDA address
    * @param reasoningUri - URI where the full reasoning is stored
+   * @param trace - The committed trace, published at `reasoningUri`
    * @returns RevealResult with signature and URI
    */
   async revealReasoning(
     wallet: Keypair,
     commitmentAddress: string | PublicKey,
-    reasoningUri: string
+    reasoningUri: string,
+    trace: ReasoningTrace
   ): Promise<RevealResult> {
     const commitPubkey =
       typeof commitmentAddress === "string"
         ? new PublicKey(commitmentAddress)
         : commitmentAddress;
+    const [agentProfile] = deriveAgentPDA(wallet.publicKey, this.programId);
+    const [metrics] = deriveMetricsPDA(agentProfile, this.programId);
+    const [summary] = deriveSummaryPDA(agentProfile, this.programId);
+    const [actionStats] = deriveActionStatsPDA(
+      agentProfile,
+      trace.action.type,
+      this.programId
+    );
 
-    const ix = buildRevealReasoningIx(
+    const ix = buildStartRevealIx(
       wallet.publicKey,
       commitPubkey,
+      trace.action.type,
       reasoningUri,
+      Buffer.byteLength(canonicalJson(trace), "utf-8"),
+      {
+        metrics: await this.accountExists(metrics),
+        summary: await this.accountExists(summary),
+        actionStats: await this.accountExists(actionStats),
+      },
+      undefined,
       this.programId
     );
 
@@ -493,6 +962,50 @@ export class SolprismClient {
     };
   }
 
+  /**
+   * Complete a started reveal once its grace period has passed. Anyone
+   * can pay for this; a fee rebate, if earned, goes to the authority.
+   *
+   * @param payer - Pays the transaction fee
+   * @param commitmentAddress - The commitment PDA address
+   * @returns Transaction signature
+   */
+  async finalizeReveal(
+    payer: Keypair,
+    commitmentAddress: string | PublicKey
+  ): Promise<string> {
+    const commitPubkey =
+      typeof commitmentAddress === "string"
+        ? new PublicKey(commitmentAddress)
+        : commitmentAddress;
+    const info = await this.connection.getAccountInfo(commitPubkey);
+    if (!info) {
+      throw new Error("Commitment account not found onchain");
+    }
+    const authority = new PublicKey(
+      Buffer.from(info.data).slice(
+        COMMITMENT_AUTHORITY_OFFSET,
+        COMMITMENT_AUTHORITY_OFFSET + 32
+      )
+    );
+    const [agentProfile] = deriveAgentPDA(authority, this.programId);
+    const [summary] = deriveSummaryPDA(agentProfile, this.programId);
+
+    const ix = buildFinalizeRevealIx(
+      authority,
+      commitPubkey,
+      { summary: await this.accountExists(summary) },
+      await this.accountExists(deriveFeeTreasuryPDA(this.programId)[0]),
+      this.programId
+    );
+
+    const tx = new Transaction().add(ix);
+
+    return sendAndConfirmTransaction(this.connection, tx, [payer], {
+      commitment: "confirmed",
+    });
+  }
+
   // ─── Verify ──────────────────────────────────────────────────────────
 
   /**
@@ -547,6 +1060,11 @@ export class SolprismClient {
 
   // ─── Account Fetching ────────────────────────────────────────────────
 
+  /** Whether an account exists at `address` */
+  private async accountExists(address: PublicKey): Promise<boolean> {
+    return (await this.connection.getAccountInfo(address)) !== null;
+  }
+
   /**
    * Fetch an agent profile from chain.
    */
@@ -619,7 +1137,7 @@ export class SolprismClient {
         },
         {
           memcmp: {
-            offset: 8, // after discriminator
+            offset: COMMITMENT_AGENT_OFFSET,
             bytes: agentPda.toBase58(),
           },
         },
@@ -631,6 +1149,51 @@ export class SolprismClient {
       .map((a) => deserializeCommitment(Buffer.from(a.account.data)));
   }
 
+  /**
+   * Find the onchain commitments of a reasoning hash, e.g. to trace a
+   * leaked reasoning document back to its record. Any agent may commit
+   * any hash, so there can be several; check each one's agent.
+   *
+   * @param commitmentHash - The trace hash (bytes or hex); use
+   *   `hashTrace` to get it from a trace
+   * @returns Matching commitments with their addresses; check each
+   *   one's `status` to tell revealed records from pending ones
+   */
+  async findCommitmentsByHash(
+    commitmentHash: Uint8Array | string
+  ): Promise<Array<{ address: string; commitment: OnChainCommitment }>> {
+    const hash =
+      typeof commitmentHash === "string"
+        ? Buffer.from(commitmentHash, "hex")
+        : Buffer.from(commitmentHash);
+    if (hash.length !== 32) {
+      throw new Error("Commitment hash must be 32 bytes");
+    }
+
+    const accounts = await this.connection.getProgramAccounts(this.programId, {
+      filters: [
+        {
+          memcmp: {
+            offset: 0,
+            bytes: Buffer.from(ACCOUNT_DISCRIMINATORS.ReasoningCommitment).toString("base64"),
+            encoding: "base64" as any,
+          },
+        },
+        {
+          memcmp: {
+            offset: COMMITMENT_HASH_OFFSET,
+            bytes: utils.bytes.bs58.encode(hash),
+          },
+        },
+      ],
+    });
+
+    return accounts.map((a) => ({
+      address: a.pubkey.toBase58(),
+      commitment: deserializeCommitment(Buffer.from(a.account.data)),
+    }));
+  }
+
   // ─── Convenience ────────────────────────────────────────────────────
 
   /**
@@ -638,7 +1201,8 @@ export class SolprismClient {
    *
    * 1. Hashes the trace
    * 2. Commits the hash onchain
-   * 3. Reveals with the provided URI
+   * 3. Starts the reveal with the provided URI (finish it with
+   *    `finalizeReveal` after the grace period)
    *
    * @returns Both commit and reveal results
    */
@@ -651,7 +1215,8 @@ export class SolprismClient {
     const reveal = await this.revealReasoning(
       wallet,
       commit.commitmentAddress,
-      reasoningUri
+      reasoningUri,
+      trace
     );
     return { commit, reveal };
   }
@@ -0,0 +1,213 @@
+/**
+ * SOLPRISM Protocol — Deterministic Fixtures
+ *
+ * Reproducible agents, commitments and reveal payloads for integrators'
+ * test suites and demos. Everything is derived from a seed string, so the
+ * same seed always yields the same keypairs, traces and hashes.
+ */
+
+import { createHash } from "crypto";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+import { ActionType, ReasoningTrace } from "./types";
+import { canonicalJson, hashTrace, hashTraceHex } from "./hash";
+import { createReasoningTrace } from "./schema";
+import {
+  SOLPRISM_PROGRAM_ID,
+  deriveAgentPDA,
+  deriveCommitmentPDA,
+} from "./client";
+
+/** Fixed epoch for fixture timestamps (2025-01-01T00:00:00Z, ms) */
+export const FIXTURE_EPOCH_MS = 1_735_689_600_000;
+
+const ACTION_TYPES: ActionType[] = [
+  "trade",
+  "audit",
+  "rebalance",
+  "decision",
+  "governance",
+];
+
+// ─── Seeded RNG ───────────────────────────────────────────────────────────
+
+/**
+ * Deterministic byte stream: SHA-256 in counter mode over
+ * `seed || label || counter`. Not for secrets — fixtures only.
+ */
+export class FixtureRng {
+  private counter = 0;
+
+  constructor(
+    private readonly seed: string,
+    private readonly label: string = ""
+  ) {}
+
+  /** Next 32 pseudo-random bytes */
+  nextBytes(): Buffer {
+    const out = createHash("sha256")
+      .update(`solprism-fixture:${this.seed}:${this.label}:${this.counter}`)
+      .digest();
+    this.counter += 1;
+    return out;
+  }
+
+  /** Uniform integer in [0, max) */
+  nextInt(max: number): number {
+    return this.nextBytes().readUInt32LE(0) % max;
+  }
+
+  /** Pick an element of a non-empty array */
+  pick<T>(items: readonly T[]): T {
+    return items[this.nextInt(items.length)];
+  }
+
+  /** Derive an independent stream for a sub-label */
+  fork(label: string): FixtureRng {
+    return new FixtureRng(this.seed, `${this.label}/${label}`);
+  }
+}
+
+// ─── Fixtures ─────────────────────────────────────────────────────────────
+
+/** A deterministic test agent */
+export interface FixtureAgent {
+  index: number;
+  name: string;
+  keypair: Keypair;
+  profileAddress: PublicKey;
+}
+
+/** A deterministic commitment with its reveal payload */
+export interface FixtureCommitment {
+  agent: FixtureAgent;
+  nonce: number;
+  trace: ReasoningTrace;
+  /** Exact bytes that hash to `commitmentHash` */
+  revealBody: string;
+  commitmentHash: Uint8Array;
+  commitmentHashHex: string;
+  commitmentAddress: PublicKey;
+  /** Placeholder URI derived from the hash */
+  reasoningUri: string;
+}
+
+/**
+ * Create a deterministic agent (keypair, name and profile PDA).
+ *
+ * @param seed - Fixture seed shared by a test suite
+ * @param index - Agent index within the seed
+ */
+export function createFixtureAgent(
+  seed: string,
+  index: number,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): FixtureAgent {
+  const rng = new FixtureRng(seed, `agent/${index}`);
+  const keypair = Keypair.fromSeed(rng.nextBytes());
+  const [profileAddress] = deriveAgentPDA(keypair.publicKey, programId);
+  return {
+    index,
+    name: `fixture-agent-${index}`,
+    keypair,
+    profileAddress,
+  };
+}
+
+/**
+ * Create a canonical reasoning trace for an agent's `nonce`-th commitment.
+ * Timestamps advance one minute per nonce from FIXTURE_EPOCH_MS.
+ */
+export function createFixtureTrace(
+  seed: string,
+  agent: FixtureAgent,
+  nonce: number
+): ReasoningTrace {
+  const rng = new FixtureRng(seed, `trace/${agent.index}/${nonce}`);
+  const actionType = rng.pick(ACTION_TYPES);
+  const confidence = rng.nextInt(101);
+  const alternatives = rng.nextInt(3);
+
+  return createReasoningTrace({
+    agent: agent.name,
+    timestamp: FIXTURE_EPOCH_MS + nonce * 60_000,
+    action: {
+      type: actionType,
+      description: `Fixture ${actionType} #${nonce}`,
+    },
+    inputs: {
+      dataSources: [
+        {
+          name: "fixture-feed",
+          type: "api",
+          summary: `sample ${rng.nextInt(1_000_000)}`,
+        },
+      ],
+      context: `Deterministic fixture for seed "${seed}"`,
+    },
+    analysis: {
+      observations: [`observation ${rng.nextInt(1000)}`],
+      logic: `Fixture logic for ${actionType} at nonce ${nonce}`,
+      alternativesConsidered: Array.from({ length: alternatives }, (_, i) => ({
+        action: `alternative ${i}`,
+        reasonRejected: "fixture",
+      })),
+    },
+    decision: {
+      actionChosen: `execute ${actionType}`,
+      confidence,
+      riskAssessment: confidence >= 80 ? "low" : confidence >= 50 ? "moderate" : "high",
+      expectedOutcome: "fixture outcome",
+    },
+  });
+}
+
+/**
+ * Create a deterministic commitment (trace, hash, PDA and reveal payload).
+ */
+export function createFixtureCommitment(
+  seed: string,
+  agent: FixtureAgent,
+  nonce: number,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): FixtureCommitment {
+  const trace = createFixtureTrace(seed, agent, nonce);
+  const commitmentHashHex = hashTraceHex(trace);
+  const [commitmentAddress] = deriveCommitmentPDA(
+    agent.profileAddress,
+    nonce,
+    programId
+  );
+
+  return {
+    agent,
+    nonce,
+    trace,
+    revealBody: canonicalJson(trace),
+    commitmentHash: hashTrace(trace),
+    commitmentHashHex,
+    commitmentAddress,
+    reasoningUri: `ipfs://fixture/${commitmentHashHex}`,
+  };
+}
+
+/**
+ * Create a full fixture set: `agents` agents with `commitmentsPerAgent`
+ * commitments each (nonces 0..n-1).
+ */
+export function createFixtureSet(
+  seed: string,
+  agents: number,
+  commitmentsPerAgent: number,
+  programId: PublicKey = SOLPRISM_PROGRAM_ID
+): { agents: FixtureAgent[]; commitments: FixtureCommitment[] } {
+  const fixtureAgents = Array.from({ length: agents }, (_, i) =>
+    createFixtureAgent(seed, i, programId)
+  );
+  const commitments = fixtureAgents.flatMap((agent) =>
+    Array.from({ length: commitmentsPerAgent }, (_, nonce) =>
+      createFixtureCommitment(seed, agent, nonce, programId)
+    )
+  );
+  return { agents: fixtureAgents, commitments };
+}
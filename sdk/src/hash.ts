@@ -36,6 +36,15 @@ function sortKeys(obj: unknown): unknown {
   return obj;
 }
 
+/**
+ * Serialize a reasoning trace exactly as it is hashed (keys deep-sorted,
+ * `signature` left out). Publish the whole trace, signature included.
+ */
+export function canonicalJson(trace: ReasoningTrace): string {
+  const { signature: _signature, ...body } = trace;
+  return JSON.stringify(sortKeys(body));
+}
+
 /**
  * Compute the SHA-256 hash of a reasoning trace.
  * 
@@ -47,7 +56,7 @@ function sortKeys(obj: unknown): unknown {
  * @returns 32-byte SHA-256 hash as Uint8Array
  */
 export function hashTrace(trace: ReasoningTrace): Uint8Array {
-  const canonical = JSON.stringify(sortKeys(trace));
+  const canonical = canonicalJson(trace);
   const hash = createHash("sha256").update(canonical, "utf-8").digest();
   return new Uint8Array(hash);
 }
@@ -177,3 +186,129 @@ export function validateTrace(trace: unknown): string[] {
   
   return errors;
 }
+
+/**
+ * List the metadata fields a commitment template requires that a trace
+ * lacks. Template fields name keys of `metadata` (e.g. `model`) or of
+ * `metadata.custom`; the program only publishes the list, so verifiers
+ * check traces against it on reveal.
+ *
+ * @param trace - The revealed reasoning trace
+ * @param requiredFields - The template's `requiredFields`
+ * @returns The missing field names (empty if the trace conforms)
+ */
+export function missingTemplateFields(
+  trace: ReasoningTrace,
+  requiredFields: string[]
+): string[] {
+  const metadata = (trace.metadata ?? {}) as Record<string, unknown>;
+  const custom = trace.metadata?.custom ?? {};
+  return requiredFields.filter(
+    (field) => metadata[field] === undefined && custom[field] === undefined
+  );
+}
+
+/**
+ * Onchain commitment categories, in program variant order.
+ */
+export const COMMITMENT_CATEGORIES = [
+  "financial",
+  "governance",
+  "contentModeration",
+  "research",
+  "infrastructure",
+  "other",
+] as const;
+
+export type CommitmentCategory = (typeof COMMITMENT_CATEGORIES)[number];
+
+/**
+ * Compute the details hash for `commit_private`, which hides a
+ * commitment's action type, category and confidence until
+ * `publish_details` is called with the same values and salt.
+ *
+ * @param salt - 32 random bytes; keep them to publish later
+ */
+export function hashCommitmentDetails(
+  actionType: string,
+  category: CommitmentCategory,
+  confidence: number,
+  salt: Uint8Array
+): Uint8Array {
+  if (salt.length !== 32) {
+    throw new Error("Salt must be 32 bytes");
+  }
+  return new Uint8Array(
+    createHash("sha256")
+      .update(Buffer.from(actionType, "utf-8"))
+      .update(Buffer.from([COMMITMENT_CATEGORIES.indexOf(category), confidence]))
+      .update(salt)
+      .digest()
+  );
+}
+
+/** Merkle leaf hash of one chunk, as used by chunk-manifest commitments. */
+export function merkleLeaf(chunk: Uint8Array): Uint8Array {
+  return new Uint8Array(
+    createHash("sha256").update(Buffer.from([0])).update(chunk).digest()
+  );
+}
+
+function merkleNode(left: Uint8Array, right: Uint8Array): Uint8Array {
+  return new Uint8Array(
+    createHash("sha256")
+      .update(Buffer.from([1]))
+      .update(left)
+      .update(right)
+      .digest()
+  );
+}
+
+/**
+ * A chunk of a redacted reveal: either its data, or — for a withheld
+ * chunk — its Merkle leaf hash.
+ */
+export type RevealChunk =
+  | { data: Uint8Array }
+  | { redacted: Uint8Array };
+
+/**
+ * Split a trace into chunks, replacing the chunks at `redact` with
+ * their leaf hashes. Publish the result together with a
+ * `declare_redactions` call listing the same indices.
+ *
+ * Sensitive chunks should be padded with random bytes at commit time,
+ * otherwise a short chunk can be recovered from its leaf hash.
+ */
+export function redactTrace(
+  data: Uint8Array,
+  chunkSize: number,
+  redact: number[]
+): RevealChunk[] {
+  const chunks: RevealChunk[] = [];
+  for (let i = 0, offset = 0; offset < data.length; i++, offset += chunkSize) {
+    const chunk = data.subarray(offset, offset + chunkSize);
+    chunks.push(redact.includes(i) ? { redacted: merkleLeaf(chunk) } : { data: chunk });
+  }
+  return chunks;
+}
+
+/**
+ * Merkle root of a (possibly redacted) reveal. Matches the onchain
+ * `commitment_hash` of a chunk-manifest commitment exactly when the
+ * unredacted chunks are unchanged.
+ */
+export function merkleRoot(chunks: RevealChunk[]): Uint8Array {
+  let level = chunks.map((c) => ("redacted" in c ? c.redacted : merkleLeaf(c.data)));
+  if (level.length === 0) {
+    return merkleLeaf(new Uint8Array());
+  }
+  while (level.length > 1) {
+    const next: Uint8Array[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(i + 1 < level.length ? merkleNode(level[i], level[i + 1]) : level[i]);
+    }
+    level = next;
+  }
+  return level[0];
+}
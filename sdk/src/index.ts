@@ -24,7 +24,7 @@
  * // Execute your action...
  * 
  * // Reveal full reasoning
- * const reveal = await client.revealReasoning(wallet, commitment.commitmentAddress, 'ipfs://...');
+ * const reveal = await client.revealReasoning(wallet, commitment.commitmentAddress, 'ipfs://...', trace);
  * 
  * // Anyone can verify
  * const result = await client.verifyReasoning(commitment.commitmentAddress, trace);
@@ -37,12 +37,15 @@
 // Re-export types
 export type {
   ReasoningTrace,
+  TraceSignature,
   ActionType,
   DataSource,
   Alternative,
   OnChainCommitment,
   OnChainAgentProfile,
+  CommitmentStatus,
   SolprismConfig,
+  Cluster,
   CommitResult,
   RevealResult,
   VerifyResult,
@@ -57,7 +60,18 @@ export {
   verifyHash,
   validateTrace,
   canonicalize,
+  canonicalJson,
+  missingTemplateFields,
+  hashCommitmentDetails,
+  COMMITMENT_CATEGORIES,
+  merkleLeaf,
+  merkleRoot,
+  redactTrace,
 } from "./hash";
+export type { CommitmentCategory, RevealChunk } from "./hash";
+
+// Re-export trace signing
+export { signTrace, verifyTraceSignature } from "./signature";
 
 // Re-export schema helpers
 export { createReasoningTrace, createSimpleTrace } from "./schema";
@@ -67,11 +81,53 @@ export type { CreateTraceInput } from "./schema";
 export {
   SolprismClient,
   SOLPRISM_PROGRAM_ID,
+  SOLPRISM_PROGRAM_IDS,
+  PROTOCOL_VERSION,
+  DEFAULT_RPC_URLS,
+  getProgramId,
   deriveAgentPDA,
   deriveCommitmentPDA,
+  deriveSigningKeyPDA,
+  deriveConfigPDA,
+  deriveActivityPDA,
+  deriveActionStatsPDA,
+  deriveMetricsPDA,
+  deriveSummaryPDA,
+  deriveFeeTreasuryPDA,
   buildRegisterAgentIx,
   buildCommitReasoningIx,
-  buildRevealReasoningIx,
+  buildStartRevealIx,
+  buildFinalizeRevealIx,
+  buildVerifyTraceSignatureIxs,
+  buildSetSigningKeyIx,
+  buildAcknowledgeCommitmentIx,
   deserializeAgentProfile,
   deserializeCommitment,
 } from "./client";
+export type { CommitOptions, RevealAccounts } from "./client";
+
+// Re-export priority-fee transaction building
+export {
+  estimateComputeUnits,
+  estimatePriorityFee,
+  buildPriorityTransaction,
+  sendWithPriority,
+  buildJitoTipIx,
+  sendJitoBundle,
+  DEFAULT_COMPUTE_UNITS,
+  MAX_COMPUTE_UNITS,
+  JITO_BUNDLE_URL,
+  JITO_TIP_ACCOUNTS,
+} from "./tx";
+export type { PriorityOptions, PriorityTransaction } from "./tx";
+
+// Re-export deterministic fixtures
+export {
+  FixtureRng,
+  FIXTURE_EPOCH_MS,
+  createFixtureAgent,
+  createFixtureTrace,
+  createFixtureCommitment,
+  createFixtureSet,
+} from "./fixtures";
+export type { FixtureAgent, FixtureCommitment } from "./fixtures";
@@ -0,0 +1,84 @@
+/**
+ * SOLPRISM Protocol — Signed Reasoning Traces
+ *
+ * An agent signs the hash of its trace body with its authority key, or
+ * with a document-signing key registered through `set_signing_key`, and
+ * embeds the signature in the trace. Anyone holding the trace, wherever
+ * it is hosted, can then check who produced it, offline with
+ * `verifyTraceSignature` or onchain with `verify_trace_signature`.
+ */
+
+import { createPrivateKey, createPublicKey, sign, verify } from "crypto";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { utils } from "@coral-xyz/anchor";
+
+import { ReasoningTrace, TraceSignature } from "./types";
+import { hashTrace } from "./hash";
+
+const { bs58 } = utils.bytes;
+
+/** Base64url without padding, as JWK expects */
+function b64url(bytes: Uint8Array): string {
+  return Buffer.from(bytes).toString("base64url");
+}
+
+/**
+ * Sign a reasoning trace with the agent's keypair.
+ *
+ * Any existing signature is replaced. The trace hash, and therefore the
+ * commitment, is the same before and after signing.
+ *
+ * @param trace - The reasoning trace to sign
+ * @param signer - The agent's authority or registered signing keypair
+ * @returns A copy of the trace with `signature` set
+ */
+export function signTrace(trace: ReasoningTrace, signer: Keypair): ReasoningTrace {
+  const key = createPrivateKey({
+    key: {
+      kty: "OKP",
+      crv: "Ed25519",
+      d: b64url(signer.secretKey.slice(0, 32)),
+      x: b64url(signer.publicKey.toBytes()),
+    },
+    format: "jwk",
+  });
+  const signature: TraceSignature = {
+    algorithm: "ed25519",
+    publicKey: signer.publicKey.toBase58(),
+    signature: bs58.encode(sign(null, hashTrace(trace), key)),
+  };
+  return { ...trace, signature };
+}
+
+/**
+ * Check a trace's embedded signature.
+ *
+ * @param trace - A signed reasoning trace
+ * @param expectedSigner - Optional: also require this signer (e.g. the
+ *   agent's onchain authority)
+ * @returns true if the signature is valid (and from `expectedSigner`)
+ */
+export function verifyTraceSignature(
+  trace: ReasoningTrace,
+  expectedSigner?: PublicKey | string
+): boolean {
+  const sig = trace.signature;
+  if (!sig || sig.algorithm !== "ed25519") return false;
+  if (expectedSigner && new PublicKey(expectedSigner).toBase58() !== sig.publicKey) {
+    return false;
+  }
+
+  try {
+    const key = createPublicKey({
+      key: {
+        kty: "OKP",
+        crv: "Ed25519",
+        x: b64url(new PublicKey(sig.publicKey).toBytes()),
+      },
+      format: "jwk",
+    });
+    return verify(null, hashTrace(trace), key, bs58.decode(sig.signature));
+  } catch {
+    return false;
+  }
+}
@@ -0,0 +1,270 @@
+/**
+ * SOLPRISM Protocol — Priority-Fee-Aware Transactions
+ *
+ * Commit-before-act only works if the commitment lands before the
+ * action. This module sizes each transaction's compute budget by
+ * simulating it, prices it from recent priority fees on the accounts it
+ * writes, and can submit a commit and the action it covers as one Jito
+ * bundle, so they land together and in order or not at all.
+ */
+
+import {
+  ComputeBudgetProgram,
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import { utils } from "@coral-xyz/anchor";
+
+const { bs58 } = utils.bytes;
+
+// ─── Constants ────────────────────────────────────────────────────────────
+
+/** Compute units requested when simulation fails */
+export const DEFAULT_COMPUTE_UNITS = 200_000;
+
+/** Most compute units a single transaction may request */
+export const MAX_COMPUTE_UNITS = 1_400_000;
+
+/** Jito block engine bundle endpoint (mainnet) */
+export const JITO_BUNDLE_URL =
+  "https://mainnet.block-engine.jito.wtf/api/v1/bundles";
+
+/** Jito tip accounts; a bundle must pay one of them */
+export const JITO_TIP_ACCOUNTS = [
+  "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+  "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+  "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+  "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+  "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+  "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+  "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+  "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+].map((key) => new PublicKey(key));
+
+// ─── Options ──────────────────────────────────────────────────────────────
+
+/** How a transaction's compute budget and priority fee are chosen */
+export interface PriorityOptions {
+  /** Fixed compute unit limit (default: simulate and add `computeMargin`) */
+  computeUnits?: number;
+  /** Headroom over simulated usage, as a fraction (default: 0.1) */
+  computeMargin?: number;
+  /** Fixed price in micro-lamports per compute unit (default: estimate) */
+  microLamports?: number;
+  /** Percentile of recent fees to pay, 0-100 (default: 75) */
+  feePercentile?: number;
+  /** Highest price paid, in micro-lamports per compute unit (default: 100000) */
+  maxMicroLamports?: number;
+}
+
+/** A transaction ready to sign, with the budget it was built with */
+export interface PriorityTransaction {
+  transaction: Transaction;
+  /** Compute unit limit set on the transaction */
+  computeUnits: number;
+  /** Compute unit price set on the transaction, in micro-lamports */
+  microLamports: number;
+}
+
+// ─── Estimation ───────────────────────────────────────────────────────────
+
+/**
+ * Compute units `instructions` consume together, by simulating them.
+ *
+ * @returns Units consumed, or undefined if the simulation failed
+ */
+export async function estimateComputeUnits(
+  connection: Connection,
+  payer: PublicKey,
+  instructions: TransactionInstruction[]
+): Promise<number | undefined> {
+  const message = new TransactionMessage({
+    payerKey: payer,
+    // Replaced by the node; simulation needs no real blockhash
+    recentBlockhash: PublicKey.default.toBase58(),
+    instructions: [
+      ComputeBudgetProgram.setComputeUnitLimit({ units: MAX_COMPUTE_UNITS }),
+      ...instructions,
+    ],
+  }).compileToV0Message();
+
+  const { value } = await connection.simulateTransaction(
+    new VersionedTransaction(message),
+    { sigVerify: false, replaceRecentBlockhash: true }
+  );
+  if (value.err || value.unitsConsumed === undefined) {
+    return undefined;
+  }
+  return value.unitsConsumed;
+}
+
+/**
+ * A priority fee for a transaction writing `writable`: the given
+ * percentile of recent fees paid on those accounts.
+ *
+ * @returns Price in micro-lamports per compute unit
+ */
+export async function estimatePriorityFee(
+  connection: Connection,
+  writable: PublicKey[],
+  percentile = 75
+): Promise<number> {
+  const recent = await connection.getRecentPrioritizationFees({
+    lockedWritableAccounts: writable,
+  });
+  const fees = recent.map((f) => f.prioritizationFee).sort((a, b) => a - b);
+  if (fees.length === 0) {
+    return 0;
+  }
+  const index = Math.min(
+    fees.length - 1,
+    Math.floor((fees.length * Math.max(0, Math.min(100, percentile))) / 100)
+  );
+  return fees[index];
+}
+
+// ─── Building & Sending ───────────────────────────────────────────────────
+
+/**
+ * Build a transaction running `instructions` behind compute-budget
+ * instructions sized and priced for them.
+ *
+ * @param connection - RPC connection used for simulation and fee history
+ * @param payer - Fee payer
+ * @param instructions - The instructions to run
+ * @param options - Fixed values or estimation parameters
+ */
+export async function buildPriorityTransaction(
+  connection: Connection,
+  payer: PublicKey,
+  instructions: TransactionInstruction[],
+  options: PriorityOptions = {}
+): Promise<PriorityTransaction> {
+  let computeUnits = options.computeUnits;
+  if (computeUnits === undefined) {
+    const used = await estimateComputeUnits(connection, payer, instructions);
+    computeUnits = used === undefined
+      ? DEFAULT_COMPUTE_UNITS
+      : Math.ceil(used * (1 + (options.computeMargin ?? 0.1)));
+  }
+  computeUnits = Math.min(computeUnits, MAX_COMPUTE_UNITS);
+
+  let microLamports = options.microLamports;
+  if (microLamports === undefined) {
+    const writable = instructions.flatMap((ix) =>
+      ix.keys.filter((k) => k.isWritable).map((k) => k.pubkey)
+    );
+    microLamports = Math.min(
+      await estimatePriorityFee(connection, writable, options.feePercentile),
+      options.maxMicroLamports ?? 100_000
+    );
+  }
+
+  const { blockhash, lastValidBlockHeight } =
+    await connection.getLatestBlockhash("confirmed");
+  const transaction = new Transaction({
+    feePayer: payer,
+    blockhash,
+    lastValidBlockHeight,
+  }).add(
+    ComputeBudgetProgram.setComputeUnitLimit({ units: computeUnits }),
+    ComputeBudgetProgram.setComputeUnitPrice({ microLamports }),
+    ...instructions
+  );
+
+  return { transaction, computeUnits, microLamports };
+}
+
+/**
+ * Build, sign and send `instructions` with an estimated priority fee,
+ * waiting for confirmation.
+ *
+ * @param signers - Signers; the first pays the fee
+ * @returns Transaction signature
+ */
+export async function sendWithPriority(
+  connection: Connection,
+  instructions: TransactionInstruction[],
+  signers: Keypair[],
+  options: PriorityOptions = {}
+): Promise<string> {
+  const { transaction } = await buildPriorityTransaction(
+    connection,
+    signers[0].publicKey,
+    instructions,
+    options
+  );
+  return sendAndConfirmTransaction(connection, transaction, signers, {
+    commitment: "confirmed",
+  });
+}
+
+// ─── Jito Bundles ─────────────────────────────────────────────────────────
+
+/**
+ * Transfer `lamports` to a Jito tip account. Put it in the last
+ * transaction of a bundle so the tip is only paid if everything before
+ * it landed.
+ */
+export function buildJitoTipIx(
+  payer: PublicKey,
+  lamports: number,
+  tipAccount: PublicKey = JITO_TIP_ACCOUNTS[
+    Math.floor(Math.random() * JITO_TIP_ACCOUNTS.length)
+  ]
+): TransactionInstruction {
+  return SystemProgram.transfer({
+    fromPubkey: payer,
+    toPubkey: tipAccount,
+    lamports,
+  });
+}
+
+/**
+ * Submit signed transactions as one Jito bundle: they execute in order,
+ * in the same block, all or none. At most five per bundle.
+ *
+ * Typical use is a commitment followed by the action it covers, so the
+ * action can never land without its commitment in front of it.
+ *
+ * @param transactions - Fully signed transactions, in execution order
+ * @param url - Block engine bundle endpoint (default: mainnet)
+ * @returns The bundle ID
+ */
+export async function sendJitoBundle(
+  transactions: Transaction[],
+  url: string = JITO_BUNDLE_URL
+): Promise<string> {
+  if (transactions.length === 0 || transactions.length > 5) {
+    throw new Error("A Jito bundle holds 1 to 5 transactions");
+  }
+  const encoded = transactions.map((tx) => bs58.encode(tx.serialize()));
+
+  const response = await fetch(url, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({
+      jsonrpc: "2.0",
+      id: 1,
+      method: "sendBundle",
+      params: [encoded],
+    }),
+  });
+  const body = (await response.json()) as {
+    result?: string;
+    error?: { message: string };
+  };
+  if (!response.ok || body.error || !body.result) {
+    throw new Error(
+      `Jito bundle rejected: ${body.error?.message ?? response.statusText}`
+    );
+  }
+  return body.result;
+}
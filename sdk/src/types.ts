@@ -103,10 +103,39 @@ export interface ReasoningTrace {
     /** Custom key-value pairs */
     custom?: Record<string, string | number | boolean>;
   };
+
+  /**
+   * Optional: the agent's signature over the trace body (see `signTrace`).
+   * Excluded from the trace hash, so signing never changes the commitment.
+   */
+  signature?: TraceSignature;
+}
+
+/**
+ * An ed25519 signature binding a trace to the key that produced it.
+ *
+ * The signed message is the 32-byte trace hash (`hashTrace`), i.e. the
+ * onchain `commitmentHash`, so the same signature can be checked offline
+ * and proven onchain with `verify_trace_signature`.
+ */
+export interface TraceSignature {
+  /** Signature scheme */
+  algorithm: "ed25519";
+  /** Base58 public key of the signer (authority or signing key) */
+  publicKey: string;
+  /** Base58 signature over the trace hash */
+  signature: string;
 }
 
 // ─── Onchain Types ────────────────────────────────────────────────────────
 
+/**
+ * Where a commitment is in the reveal flow: `start_reveal` moves it from
+ * `committed` to `revealing`, and `finalize_reveal` to `revealed` once
+ * the grace period has passed.
+ */
+export type CommitmentStatus = "committed" | "revealing" | "revealed";
+
 /** Onchain commitment data (mirrors the Anchor account struct) */
 export interface OnChainCommitment {
   /** The agent's public key */
@@ -119,10 +148,14 @@ export interface OnChainCommitment {
   confidence: number;
   /** Unix timestamp */
   timestamp: number;
-  /** Whether the full reasoning has been revealed */
+  /** Reveal progress */
+  status: CommitmentStatus;
+  /** Whether the reveal has completed (`status === "revealed"`) */
   revealed: boolean;
   /** URI to the full reasoning (set on reveal) */
   reasoningUri: string | null;
+  /** Nonce the commitment's address was derived from */
+  nonce: number;
   /** PDA bump */
   bump: number;
 }
@@ -145,11 +178,16 @@ export interface OnChainAgentProfile {
 
 // ─── SDK Types ─────────────────────────────────────────────────────────────
 
+/** Solana cluster a SOLPRISM deployment lives on */
+export type Cluster = "mainnet-beta" | "devnet" | "testnet" | "localnet";
+
 /** Configuration for SOLPRISM SDK client */
 export interface SolprismConfig {
-  /** Solana RPC endpoint URL */
+  /** Target cluster (default: devnet); selects program ID and RPC */
+  cluster?: Cluster;
+  /** Solana RPC endpoint URL (overrides the cluster default) */
   rpcUrl?: string;
-  /** SOLPRISM program ID onchain */
+  /** SOLPRISM program ID onchain (overrides the cluster default) */
   programId?: string;
   /** IPFS gateway URL for storing/retrieving reasoning traces */
   ipfsGateway?: string;
@@ -157,7 +157,8 @@ async function main() {
   const revealResult = await client.revealReasoning(
     wallet,
     commitResult.commitmentAddress,
-    reasoningUri.slice(0, 200) // URI max is 256 chars
+    reasoningUri.slice(0, 200), // URI max is 256 chars
+    trace
   );
   console.log(`   ✅ Revealed! Tx: ${revealResult.signature}`);
 